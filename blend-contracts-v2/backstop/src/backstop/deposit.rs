@@ -32,6 +32,24 @@ pub fn execute_deposit(e: &Env, from: &Address, pool_address: &Address, amount:
     to_mint
 }
 
+/// Perform a deposit into the backstop module, requiring at least `min_shares` shares to be minted
+///
+/// ### Panics
+/// If the number of shares minted is less than `min_shares`
+pub fn execute_deposit_min(
+    e: &Env,
+    from: &Address,
+    pool_address: &Address,
+    amount: i128,
+    min_shares: i128,
+) -> i128 {
+    let to_mint = execute_deposit(e, from, pool_address, amount);
+    if to_mint < min_shares {
+        panic_with_error!(e, &BackstopError::BadRequest);
+    }
+    to_mint
+}
+
 #[cfg(test)]
 mod tests {
     use soroban_sdk::{testutils::Address as _, Address};
@@ -257,6 +275,83 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_deposit_min() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool_0_id = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+        backstop_token_client.mint(&frodo, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &frodo, &pool_0_id, 25_0000000);
+        });
+
+        // rate is unchanged since the quote, so the minimum is met
+        e.as_contract(&backstop_address, || {
+            let shares = execute_deposit_min(&e, &samwise, &pool_0_id, 10_0000000, 10_0000000);
+            assert_eq!(shares, 10_0000000);
+
+            let user_balance = storage::get_user_balance(&e, &pool_0_id, &samwise);
+            assert_eq!(user_balance.shares, 10_0000000);
+            let pool_balance = storage::get_pool_balance(&e, &pool_0_id);
+            assert_eq!(pool_balance.shares, 35_0000000);
+            assert_eq!(pool_balance.tokens, 35_0000000);
+        });
+        assert_eq!(backstop_token_client.balance(&samwise), 90_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_execute_deposit_min_under_min_shares() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool_0_id = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+        backstop_token_client.mint(&frodo, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        backstop_token_client.approve(
+            &frodo,
+            &backstop_address,
+            &25_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &frodo, &pool_0_id, 25_0000000);
+        });
+
+        // share price doubles after the deposit was quoted at a 1:1 rate
+        e.as_contract(&backstop_address, || {
+            execute_donate(&e, &frodo, &pool_0_id, 25_0000000);
+        });
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit_min(&e, &samwise, &pool_0_id, 10_0000000, 10_0000000);
+        });
+    }
+
     // #[test]
     // #[should_panic(expected = "Error(Contract, #1005)")]
     // fn test_execute_deposit_small_initial_mint() {
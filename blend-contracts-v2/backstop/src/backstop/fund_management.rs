@@ -1,4 +1,4 @@
-use crate::{contract::require_nonnegative, storage, BackstopError};
+use crate::{contract::require_nonnegative, emissions, storage, BackstopError};
 use sep_41_token::TokenClient;
 use soroban_sdk::{panic_with_error, Address, Env};
 
@@ -12,6 +12,9 @@ pub fn execute_draw(e: &Env, pool_address: &Address, amount: i128, to: &Address)
 
     let mut pool_balance = storage::get_pool_balance(e, pool_address);
 
+    // accrue reward zone emissions against the balance before the draw
+    emissions::update_rz_emis_data(e, pool_address, false);
+
     pool_balance.withdraw(e, amount, 0);
     storage::set_pool_balance(e, pool_address, &pool_balance);
 
@@ -29,6 +32,9 @@ pub fn execute_donate(e: &Env, from: &Address, pool_address: &Address, amount: i
     let mut pool_balance = storage::get_pool_balance(e, pool_address);
     require_is_from_pool_factory(e, pool_address, pool_balance.shares);
 
+    // accrue reward zone emissions against the balance before the donation
+    emissions::update_rz_emis_data(e, pool_address, false);
+
     let backstop_token = TokenClient::new(e, &storage::get_backstop_token(e));
     backstop_token.transfer_from(
         &e.current_contract_address(),
@@ -41,12 +47,25 @@ pub fn execute_donate(e: &Env, from: &Address, pool_address: &Address, amount: i
     storage::set_pool_balance(e, pool_address, &pool_balance);
 }
 
+/// Perform a top up of a pool's backstop by a third party. The pool must have existing
+/// depositors to share the donated tokens.
+pub fn execute_top_up(e: &Env, from: &Address, pool_address: &Address, amount: i128) {
+    let pool_balance = storage::get_pool_balance(e, pool_address);
+    require_is_from_pool_factory(e, pool_address, pool_balance.shares);
+    if pool_balance.shares == 0 {
+        panic_with_error!(e, &BackstopError::BadRequest);
+    }
+
+    execute_donate(e, from, pool_address, amount);
+}
+
 #[cfg(test)]
 mod tests {
     use soroban_sdk::{testutils::Address as _, Address};
 
     use crate::{
         backstop::execute_deposit,
+        storage::RzEmissionData,
         testutils::{create_backstop, create_backstop_token, create_mock_pool_factory},
     };
 
@@ -86,6 +105,46 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_donate_accrues_rz_emissions() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_id = create_backstop(&e);
+        let pool_0_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_id, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+        backstop_token_client.mint(&frodo, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_id);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        backstop_token_client.approve(&samwise, &backstop_id, &30_0000000, &e.ledger().sequence());
+        e.as_contract(&backstop_id, || {
+            execute_deposit(&e, &frodo, &pool_0_id, 25_0000000);
+            storage::set_rz_emis_data(
+                &e,
+                &pool_0_id,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 1_0000000,
+                },
+            );
+            storage::set_rz_emission_index(&e, &0_10000000000000);
+
+            execute_donate(&e, &samwise, &pool_0_id, 30_0000000);
+
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_0_id).unwrap();
+            assert_eq!(rz_emis_data.index, 0_10000000000000);
+            assert_eq!(rz_emis_data.accrued, 3_5000000);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #8)")]
     fn test_execute_donate_negative_amount() {
@@ -200,6 +259,94 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_top_up() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_id = create_backstop(&e);
+        let pool_0_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_id, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+        backstop_token_client.mint(&frodo, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_id);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        // initialize pool 0 with funds
+        e.as_contract(&backstop_id, || {
+            execute_deposit(&e, &frodo, &pool_0_id, 25_0000000);
+        });
+
+        backstop_token_client.approve(&samwise, &backstop_id, &25_0000000, &e.ledger().sequence());
+        e.as_contract(&backstop_id, || {
+            let pre_share_value =
+                storage::get_pool_balance(&e, &pool_0_id).convert_to_tokens(1_0000000);
+
+            execute_top_up(&e, &samwise, &pool_0_id, 25_0000000);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_0_id);
+            assert_eq!(new_pool_balance.shares, 25_0000000);
+            assert_eq!(new_pool_balance.tokens, 50_0000000);
+            assert_eq!(new_pool_balance.q4w, 0);
+            assert_eq!(pre_share_value, 1_0000000);
+            assert_eq!(new_pool_balance.convert_to_tokens(1_0000000), 2_0000000);
+            assert_eq!(backstop_token_client.balance(&samwise), 75_0000000);
+            assert_eq!(backstop_token_client.balance(&backstop_id), 50_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_execute_top_up_no_depositors() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_id = create_backstop(&e);
+        let pool_0_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_id, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_id);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        backstop_token_client.approve(&samwise, &backstop_id, &25_0000000, &e.ledger().sequence());
+        e.as_contract(&backstop_id, || {
+            execute_top_up(&e, &samwise, &pool_0_id, 25_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1004)")]
+    fn test_execute_top_up_not_pool() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_id = create_backstop(&e);
+        let pool_0_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_id, &bombadil);
+        backstop_token_client.mint(&samwise, &100_0000000);
+
+        create_mock_pool_factory(&e, &backstop_id);
+
+        e.as_contract(&backstop_id, || {
+            execute_top_up(&e, &samwise, &pool_0_id, 25_0000000);
+        });
+    }
+
     #[test]
     fn test_execute_draw() {
         let e = Env::default();
@@ -234,6 +381,53 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_draw_accrues_rz_emissions() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_address = create_backstop(&e);
+        let pool_0_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&frodo, &100_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_0_id);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &frodo, &pool_0_id, 50_0000000);
+            storage::set_rz_emis_data(
+                &e,
+                &pool_0_id,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 0,
+                },
+            );
+            // a distribution occurs mid-cycle
+            storage::set_rz_emission_index(&e, &0_10000000000000);
+
+            execute_draw(&e, &pool_0_id, 20_0000000, &samwise);
+
+            // accrual up to the draw uses the old balance
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_0_id).unwrap();
+            assert_eq!(rz_emis_data.index, 0_10000000000000);
+            assert_eq!(rz_emis_data.accrued, 5_0000000);
+
+            // accrual after the draw uses the new balance
+            storage::set_rz_emission_index(&e, &0_20000000000000);
+            emissions::update_rz_emis_data(&e, &pool_0_id, false);
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_0_id).unwrap();
+            assert_eq!(rz_emis_data.index, 0_20000000000000);
+            assert_eq!(rz_emis_data.accrued, 8_0000000);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1003)")]
     fn test_execute_draw_only_can_take_from_pool() {
@@ -1,15 +1,17 @@
 mod deposit;
-pub use deposit::execute_deposit;
+pub use deposit::{execute_deposit, execute_deposit_min};
 
 mod fund_management;
-pub use fund_management::{execute_donate, execute_draw};
+pub use fund_management::{execute_donate, execute_draw, execute_top_up};
 
 mod withdrawal;
 pub use withdrawal::{execute_dequeue_withdrawal, execute_queue_withdrawal, execute_withdraw};
 
 mod pool;
 pub use pool::{
-    load_pool_backstop_data, require_is_from_pool_factory, require_pool_above_threshold,
+    calc_pool_threshold_pct, load_backstop_dashboard, load_backstop_threshold,
+    load_pool_backstop_data, load_pool_balance_detailed, load_pool_balances_export,
+    require_is_from_pool_factory, require_pool_above_threshold, BackstopDashboard,
     PoolBackstopData, PoolBalance,
 };
 
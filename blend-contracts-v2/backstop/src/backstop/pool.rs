@@ -1,11 +1,13 @@
 use soroban_fixed_point_math::FixedPoint;
-use soroban_sdk::{contracttype, panic_with_error, unwrap::UnwrapOptimized, Address, Env};
+use soroban_sdk::{
+    contracttype, panic_with_error, unwrap::UnwrapOptimized, vec, Address, Env, Vec,
+};
 
 use crate::{
     constants::SCALAR_7,
     dependencies::{CometClient, PoolFactoryClient},
     errors::BackstopError,
-    storage,
+    storage::{self, BackstopEmissionData, RzEmissionData},
 };
 
 /// The pool's backstop data
@@ -18,6 +20,18 @@ pub struct PoolBackstopData {
     pub usdc: i128,    // the amount of usdc held in the pool's backstop via backstop tokens
 }
 
+/// The full state of a pool's backstop
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopDashboard {
+    pub in_reward_zone: bool, // whether the pool is in the reward zone
+    pub eps: u64,             // the backstop emissions per second (14 decimals)
+    pub expiration: u64,      // the expiration of the backstop emissions
+    pub q4w_pct: i128,        // the percentage of shares queued for withdrawal (7 decimals)
+    pub coverage: i128, // the pool's backstop balance as a percentage of the threshold (7 decimals)
+    pub share_price: i128, // the number of backstop tokens per backstop share (7 decimals)
+}
+
 pub fn load_pool_backstop_data(e: &Env, address: &Address) -> PoolBackstopData {
     let pool_balance = storage::get_pool_balance(e, address);
     let q4w_pct = if pool_balance.shares > 0 {
@@ -93,22 +107,110 @@ pub fn require_is_from_pool_factory(e: &Env, address: &Address, balance: i128) {
 ///
 /// Returns true if the pool's backstop balance is above the threshold
 pub fn require_pool_above_threshold(pool_backstop_data: &PoolBackstopData) -> bool {
+    calc_saturating_pool_pc(pool_backstop_data) >= THRESHOLD_PC
+}
+
+/// Load the pool's backstop token balance and whether it meets the threshold
+///
+/// Returns (tokens, above_threshold)
+///
+/// ### Arguments
+/// * `address` - The pool address
+pub fn load_backstop_threshold(e: &Env, address: &Address) -> (i128, bool) {
+    let pool_backstop_data = load_pool_backstop_data(e, address);
+    (
+        pool_backstop_data.tokens,
+        require_pool_above_threshold(&pool_backstop_data),
+    )
+}
+
+/// Calculate the pool's backstop balance as a percentage of the threshold
+///
+/// Returns the percentage of the threshold met, scaled to 7 decimals
+pub fn calc_pool_threshold_pct(pool_backstop_data: &PoolBackstopData) -> i128 {
+    calc_saturating_pool_pc(pool_backstop_data) / (THRESHOLD_PC / SCALAR_7)
+}
+
+/// The threshold product constant - 1e25 (100k^5)
+const THRESHOLD_PC: i128 = 10_000_000_000_000_000_000_000_000i128;
+
+fn calc_saturating_pool_pc(pool_backstop_data: &PoolBackstopData) -> i128 {
     // @dev: Calculation for pools product constant of underlying will often overflow i128
     //       so saturating mul is used. This is safe because the threshold is below i128::MAX and the
     //       protocol does not need to differentiate between pools over the threshold product constant.
     //       The calculation is:
     //        - Threshold % = (bal_blnd^4 * bal_usdc) / PC^5 such that PC is 100k
-    let threshold_pc = 10_000_000_000_000_000_000_000_000i128; // 1e25 (100k^5)
 
     // floor balances to nearest full unit and calculate saturated pool product constant
     let bal_blnd = pool_backstop_data.blnd / SCALAR_7;
     let bal_usdc = pool_backstop_data.usdc / SCALAR_7;
-    let saturating_pool_pc = bal_blnd
+    bal_blnd
         .saturating_mul(bal_blnd)
         .saturating_mul(bal_blnd)
         .saturating_mul(bal_blnd)
-        .saturating_mul(bal_usdc);
-    saturating_pool_pc >= threshold_pc
+        .saturating_mul(bal_usdc)
+}
+
+/// Load the full state of a pool's backstop
+///
+/// ### Arguments
+/// * `address` - The pool address
+pub fn load_backstop_dashboard(e: &Env, address: &Address) -> BackstopDashboard {
+    let pool_backstop_data = load_pool_backstop_data(e, address);
+    let pool_balance = storage::get_pool_balance(e, address);
+    let (eps, expiration) = match storage::get_backstop_emis_data(e, address) {
+        Some(emis_data) => (emis_data.eps, emis_data.expiration),
+        None => (0, 0),
+    };
+    BackstopDashboard {
+        in_reward_zone: storage::get_reward_zone(e).contains(address),
+        eps,
+        expiration,
+        q4w_pct: pool_backstop_data.q4w_pct,
+        coverage: calc_pool_threshold_pct(&pool_backstop_data),
+        share_price: pool_balance.convert_to_tokens(SCALAR_7),
+    }
+}
+
+/// Load the pool's backstop balance with the amount of tokens not queued for withdrawal
+///
+/// Returns (tokens, shares, q4w, non_queued_tokens)
+///
+/// ### Arguments
+/// * `address` - The pool address
+pub fn load_pool_balance_detailed(e: &Env, address: &Address) -> (i128, i128, i128, i128) {
+    let pool_balance = storage::get_pool_balance(e, address);
+    (
+        pool_balance.tokens,
+        pool_balance.shares,
+        pool_balance.q4w,
+        pool_balance.non_queued_tokens(),
+    )
+}
+
+/// Load a snapshot of the backstop state held for each pool, for use by migration tooling
+///
+/// Returns a vec of (pool, pool_balance, backstop_emis_data, rz_emis_data)
+///
+/// ### Arguments
+/// * `pools` - The pool addresses
+pub fn load_pool_balances_export(
+    e: &Env,
+    pools: &Vec<Address>,
+) -> Vec<(
+    Address,
+    PoolBalance,
+    Option<BackstopEmissionData>,
+    Option<RzEmissionData>,
+)> {
+    let mut export = vec![e];
+    for pool in pools.iter() {
+        let pool_balance = storage::get_pool_balance(e, &pool);
+        let backstop_emis_data = storage::get_backstop_emis_data(e, &pool);
+        let rz_emis_data = storage::get_rz_emis_data(e, &pool);
+        export.push_back((pool, pool_balance, backstop_emis_data, rz_emis_data));
+    }
+    export
 }
 
 /// The pool's backstop balances
@@ -200,11 +302,14 @@ impl PoolBalance {
 
 #[cfg(test)]
 mod tests {
-    use soroban_sdk::testutils::Address as _;
-
-    use crate::testutils::{
-        create_backstop, create_blnd_token, create_comet_lp_pool_with_tokens_per_share,
-        create_mock_pool_factory, create_usdc_token,
+    use soroban_sdk::{testutils::Address as _, vec};
+
+    use crate::{
+        storage::BackstopEmissionData,
+        testutils::{
+            create_backstop, create_blnd_token, create_comet_lp_pool_with_tokens_per_share,
+            create_mock_pool_factory, create_usdc_token,
+        },
     };
 
     use super::*;
@@ -449,6 +554,316 @@ mod tests {
         assert!(result);
     }
 
+    /********** calc_pool_threshold_pct **********/
+
+    #[test]
+    fn test_calc_pool_threshold_pct() {
+        let pool_backstop_data = PoolBackstopData {
+            blnd: 200000_0000000,
+            q4w_pct: 0,
+            tokens: 20_000_0000000,
+            usdc: 6_249_0000000,
+        };
+
+        let result = calc_pool_threshold_pct(&pool_backstop_data);
+        assert_eq!(result, 0_9998400);
+        assert!(!require_pool_above_threshold(&pool_backstop_data));
+    }
+
+    #[test]
+    fn test_calc_pool_threshold_pct_over() {
+        let pool_backstop_data = PoolBackstopData {
+            blnd: 200001_0000000,
+            q4w_pct: 0,
+            tokens: 15_000_0000000,
+            usdc: 6_250_0000000,
+        };
+
+        let result = calc_pool_threshold_pct(&pool_backstop_data);
+        assert_eq!(result, 1_0000200);
+        assert!(require_pool_above_threshold(&pool_backstop_data));
+    }
+
+    /********** load_backstop_threshold **********/
+
+    #[test]
+    fn test_load_backstop_threshold_under() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0500000,
+        );
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 50_239_0000000,
+                    tokens: 50_239_0000000,
+                    q4w: 0,
+                },
+            );
+
+            // 251,195 BLND and 2,511 USDC is just under the threshold
+            let (tokens, above_threshold) = load_backstop_threshold(&e, &pool);
+            assert_eq!(tokens, 50_239_0000000);
+            assert_eq!(above_threshold, false);
+            assert_eq!(
+                above_threshold,
+                require_pool_above_threshold(&load_pool_backstop_data(&e, &pool))
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_backstop_threshold_over() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0500000,
+        );
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 50_240_0000000,
+                    tokens: 50_240_0000000,
+                    q4w: 0,
+                },
+            );
+
+            // 251,200 BLND and 2,512 USDC is just over the threshold
+            let (tokens, above_threshold) = load_backstop_threshold(&e, &pool);
+            assert_eq!(tokens, 50_240_0000000);
+            assert_eq!(above_threshold, true);
+            assert_eq!(
+                above_threshold,
+                require_pool_above_threshold(&load_pool_backstop_data(&e, &pool))
+            );
+        });
+    }
+
+    /********** load_backstop_dashboard **********/
+
+    #[test]
+    fn test_load_backstop_dashboard() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_address,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_0500000,
+        );
+
+        e.as_contract(&backstop_address, || {
+            storage::set_reward_zone(&e, &vec![&e, Address::generate(&e), pool.clone()]);
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 30_000_0000000,
+                    tokens: 40_000_0000000,
+                    q4w: 10_000_0000000,
+                },
+            );
+            storage::set_backstop_emis_data(
+                &e,
+                &pool,
+                &BackstopEmissionData {
+                    expiration: 1713139200 + 1000,
+                    eps: 0_10000000000000,
+                    index: 8877660000000,
+                    last_time: 1713139200 - 12345,
+                },
+            );
+
+            let dashboard = load_backstop_dashboard(&e, &pool);
+
+            let pool_data = load_pool_backstop_data(&e, &pool);
+            let pool_balance = storage::get_pool_balance(&e, &pool);
+            let emis_data = storage::get_backstop_emis_data(&e, &pool).unwrap_optimized();
+            assert_eq!(
+                dashboard.in_reward_zone,
+                storage::get_reward_zone(&e).contains(&pool)
+            );
+            assert_eq!(dashboard.eps, emis_data.eps);
+            assert_eq!(dashboard.expiration, emis_data.expiration);
+            assert_eq!(dashboard.q4w_pct, pool_data.q4w_pct);
+            assert_eq!(dashboard.coverage, calc_pool_threshold_pct(&pool_data));
+            assert_eq!(
+                dashboard.share_price,
+                pool_balance.convert_to_tokens(SCALAR_7)
+            );
+
+            assert!(dashboard.in_reward_zone);
+            assert_eq!(dashboard.q4w_pct, 0_3333334);
+            assert_eq!(dashboard.coverage, 0_3200000);
+            assert_eq!(dashboard.share_price, 1_3333333);
+        });
+    }
+
+    #[test]
+    fn test_load_backstop_dashboard_empty() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        e.as_contract(&backstop_address, || {
+            let dashboard = load_backstop_dashboard(&e, &pool);
+
+            assert!(!dashboard.in_reward_zone);
+            assert_eq!(dashboard.eps, 0);
+            assert_eq!(dashboard.expiration, 0);
+            assert_eq!(dashboard.q4w_pct, 0);
+            assert_eq!(dashboard.coverage, 0);
+            assert_eq!(dashboard.share_price, SCALAR_7);
+        });
+    }
+
+    #[test]
+    fn test_load_pool_balance_detailed() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let backstop_address = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool,
+                &PoolBalance {
+                    shares: 150_0000000,
+                    tokens: 200_0000000,
+                    q4w: 30_0000000,
+                },
+            );
+
+            let (tokens, shares, q4w, non_queued_tokens) = load_pool_balance_detailed(&e, &pool);
+            assert_eq!(tokens, 200_0000000);
+            assert_eq!(shares, 150_0000000);
+            assert_eq!(q4w, 30_0000000);
+            // 30 shares @ 1.3333333 tokens per share are queued
+            assert_eq!(non_queued_tokens, 160_0000000);
+        });
+    }
+
+    #[test]
+    fn test_load_pool_balances_export() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let backstop_address = create_backstop(&e);
+        let pool_0 = Address::generate(&e);
+        let pool_1 = Address::generate(&e);
+
+        e.as_contract(&backstop_address, || {
+            storage::set_pool_balance(
+                &e,
+                &pool_0,
+                &PoolBalance {
+                    shares: 150_0000000,
+                    tokens: 200_0000000,
+                    q4w: 30_0000000,
+                },
+            );
+            storage::set_backstop_emis_data(
+                &e,
+                &pool_0,
+                &BackstopEmissionData {
+                    expiration: 1000,
+                    eps: 0_10000000000000,
+                    index: 12_34560000000000,
+                    last_time: 500,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &pool_0,
+                &RzEmissionData {
+                    index: 5_00000000000000,
+                    accrued: 10_0000000,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    shares: 50_0000000,
+                    tokens: 55_0000000,
+                    q4w: 0,
+                },
+            );
+
+            let export = load_pool_balances_export(&e, &vec![&e, pool_0.clone(), pool_1.clone()]);
+            assert_eq!(export.len(), 2);
+
+            let (address, pool_balance, backstop_emis_data, rz_emis_data) = export.get_unchecked(0);
+            assert_eq!(address, pool_0);
+            assert_eq!(pool_balance.shares, 150_0000000);
+            assert_eq!(pool_balance.tokens, 200_0000000);
+            assert_eq!(pool_balance.q4w, 30_0000000);
+            let backstop_emis_data = backstop_emis_data.unwrap();
+            assert_eq!(backstop_emis_data.expiration, 1000);
+            assert_eq!(backstop_emis_data.eps, 0_10000000000000);
+            assert_eq!(backstop_emis_data.index, 12_34560000000000);
+            assert_eq!(backstop_emis_data.last_time, 500);
+            let rz_emis_data = rz_emis_data.unwrap();
+            assert_eq!(rz_emis_data.index, 5_00000000000000);
+            assert_eq!(rz_emis_data.accrued, 10_0000000);
+
+            let (address, pool_balance, backstop_emis_data, rz_emis_data) = export.get_unchecked(1);
+            assert_eq!(address, pool_1);
+            assert_eq!(pool_balance.shares, 50_0000000);
+            assert_eq!(pool_balance.tokens, 55_0000000);
+            assert_eq!(pool_balance.q4w, 0);
+            assert!(backstop_emis_data.is_none());
+            assert!(rz_emis_data.is_none());
+        });
+    }
+
     /********** Logic **********/
 
     #[test]
@@ -53,6 +53,9 @@ pub fn execute_withdraw(e: &Env, from: &Address, pool_address: &Address, amount:
     let mut pool_balance = storage::get_pool_balance(e, pool_address);
     let mut user_balance = storage::get_user_balance(e, pool_address, from);
 
+    // accrue reward zone emissions against the balance before the withdrawal
+    emissions::update_rz_emis_data(e, pool_address, false);
+
     user_balance.withdraw_shares(e, amount);
 
     let to_return = pool_balance.convert_to_tokens(amount);
@@ -265,6 +268,68 @@ mod tests {
             assert_eq!(new_pool_balance.tokens, 75_0000000);
         });
     }
+
+    #[test]
+    fn test_execute_dequeue_withdrawal_partial_restores_non_queued_tokens() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &200_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 200_0000000);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 100_0000000);
+
+            let pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(pool_balance.non_queued_tokens(), 100_0000000);
+
+            execute_dequeue_withdrawal(&e, &samwise, &pool_address, 40_0000000);
+
+            let new_user_balance = storage::get_user_balance(&e, &pool_address, &samwise);
+            assert_eq!(new_user_balance.shares, 140_0000000);
+            assert_eq!(new_user_balance.q4w.len(), 1);
+            assert_eq!(new_user_balance.q4w.get_unchecked(0).amount, 60_0000000);
+
+            let new_pool_balance = storage::get_pool_balance(&e, &pool_address);
+            assert_eq!(new_pool_balance.q4w, 60_0000000);
+            assert_eq!(new_pool_balance.non_queued_tokens(), 140_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")]
+    fn test_execute_dequeue_withdrawal_more_than_queued() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let backstop_address = create_backstop(&e);
+        let pool_address = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (_, backstop_token_client) = create_backstop_token(&e, &backstop_address, &bombadil);
+        backstop_token_client.mint(&samwise, &200_0000000);
+
+        let (_, mock_pool_factory_client) = create_mock_pool_factory(&e, &backstop_address);
+        mock_pool_factory_client.set_pool(&pool_address);
+
+        e.as_contract(&backstop_address, || {
+            execute_deposit(&e, &samwise, &pool_address, 200_0000000);
+            execute_queue_withdrawal(&e, &samwise, &pool_address, 100_0000000);
+
+            execute_dequeue_withdrawal(&e, &samwise, &pool_address, 100_0000001);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #8)")]
     fn test_execute_dequeue_withdrawal_negative_amount() {
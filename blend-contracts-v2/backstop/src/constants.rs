@@ -7,6 +7,13 @@ pub const SCALAR_14: i128 = 1_0000000_0000000;
 /// The maximum reward zone size
 pub const MAX_RZ_SIZE: u32 = 50;
 
+/// The maximum time in seconds of emissions processed by a single distribution (1 year)
+pub const MAX_DISTRIBUTION_GAP: u64 = 365 * 24 * 60 * 60;
+
+/// The time in seconds a pool must have been in the reward zone before a pool with an equal
+/// backstop deposit can swap it out (30 days)
+pub const RZ_TIE_MIN_TENURE: u64 = 30 * 24 * 60 * 60;
+
 /// The maximum amount of active Q4W entries that a user can have against a single backstop.
 pub const MAX_Q4W_SIZE: u32 = 20;
 
@@ -1,11 +1,15 @@
 use crate::{
-    backstop::{self, load_pool_backstop_data, PoolBackstopData, UserBalance, Q4W},
+    backstop::{
+        self, load_backstop_dashboard, load_backstop_threshold, load_pool_backstop_data,
+        load_pool_balance_detailed, load_pool_balances_export, BackstopDashboard, PoolBackstopData,
+        PoolBalance, UserBalance, Q4W,
+    },
     constants::{MAX_BACKFILLED_EMISSIONS, SCALAR_7},
     dependencies::EmitterClient,
-    emissions,
+    emissions::{self, DistributionResult},
     errors::BackstopError,
     events::BackstopEvents,
-    storage,
+    storage::{self, BackstopConfig, BackstopEmissionData, RzEmissionData},
 };
 use soroban_sdk::{contract, contractclient, contractimpl, panic_with_error, Address, Env, Vec};
 
@@ -29,6 +33,27 @@ pub trait Backstop {
     /// * `amount` - The amount of tokens to deposit
     fn deposit(e: Env, from: Address, pool_address: Address, amount: i128) -> i128;
 
+    /// Deposit backstop tokens from "from" into the backstop of a pool, reverting if fewer than
+    /// `min_shares` shares would be minted
+    ///
+    /// Returns the number of backstop pool shares minted
+    ///
+    /// ### Arguments
+    /// * `from` - The address depositing into the backstop
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of tokens to deposit
+    /// * `min_shares` - The minimum number of shares to mint
+    ///
+    /// ### Panics
+    /// If the number of shares minted is less than `min_shares`
+    fn deposit_min(
+        e: Env,
+        from: Address,
+        pool_address: Address,
+        amount: i128,
+        min_shares: i128,
+    ) -> i128;
+
     /// Queue deposited pool shares from "from" for withdraw from a backstop of a pool
     ///
     /// Returns the created queue for withdrawal
@@ -72,15 +97,74 @@ pub trait Backstop {
     /// * `pool_address` - The address of the pool
     fn pool_data(e: Env, pool: Address) -> PoolBackstopData;
 
+    /// Fetch the full state of the pool's backstop for display
+    ///
+    /// Returns the reward zone membership, backstop emission eps and expiration, Q4W percentage,
+    /// threshold coverage ratio, and share price of the pool's backstop
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_backstop_dashboard(e: Env, pool: Address) -> BackstopDashboard;
+
+    /// Fetch the pool's backstop balance, including how much is active versus queued for withdrawal
+    ///
+    /// Returns (tokens, shares, q4w, non_queued_tokens), where q4w is in shares and
+    /// non_queued_tokens is the amount of tokens not queued for withdrawal
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_pool_balance_detailed(e: Env, pool: Address) -> (i128, i128, i128, i128);
+
+    /// Fetch the pool's backstop token balance and whether it meets the threshold required to
+    /// enter the reward zone
+    ///
+    /// Returns (tokens, above_threshold)
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_backstop_threshold(e: Env, pool: Address) -> (i128, bool);
+
+    /// Export the backstop state held for each pool in a single call, for use by migration tooling
+    ///
+    /// Returns a vec of (pool, pool_balance, backstop_emis_data, rz_emis_data), where the emission
+    /// data is None if it does not exist for the pool
+    ///
+    /// ### Arguments
+    /// * `pools` - The addresses of the pools to export
+    fn export_pool_balances(
+        e: Env,
+        pools: Vec<Address>,
+    ) -> Vec<(
+        Address,
+        PoolBalance,
+        Option<BackstopEmissionData>,
+        Option<RzEmissionData>,
+    )>;
+
     /// Fetch the backstop token for the backstop
     fn backstop_token(e: Env) -> Address;
 
+    /// Fetch the backstop's configuration, including the backstop token, emitter, BLND token,
+    /// USDC token, and pool factory
+    fn get_config(e: Env) -> BackstopConfig;
+
     /********** Emissions **********/
 
     /// Update the backstop with new emissions for all reward zone pools
     ///
-    /// Returns the amount of new emissions for all reward zone pools
-    fn distribute(e: Env) -> i128;
+    /// Returns a `DistributionResult` with the amount of new emissions for all reward zone pools,
+    /// whether the emissions were backfilled, whether the distribution time was reset instead of
+    /// distributing, and the new reward zone emission index
+    fn distribute(e: Env) -> DistributionResult;
+
+    /// Update the backstop with new emissions for all reward zone pools. If backfilling emissions
+    /// would exceed the maximum backfilled emissions, only the remaining backfill allotment is
+    /// distributed instead of reverting.
+    ///
+    /// Returns a `DistributionResult` with the amount of new emissions for all reward zone pools,
+    /// whether the emissions were backfilled, whether the distribution time was reset instead of
+    /// distributing, and the new reward zone emission index
+    fn distribute_clamped(e: Env) -> DistributionResult;
 
     /// Distribute emissions to a reward zone pool and its backstop
     ///
@@ -93,6 +177,84 @@ pub trait Backstop {
     /// If the pool is not in the reward zone or the pool does not authorize the call
     fn gulp_emissions(e: Env, pool: Address) -> i128;
 
+    /// (Only Pool) Set the maximum emissions a pool can gulp in a single `gulp_emissions` call.
+    /// Any emissions over the maximum are deferred to the next gulp.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `max_emissions` - The maximum emissions the pool can gulp at once, or 0 to remove the cap
+    ///
+    /// ### Errors
+    /// If `max_emissions` is negative or the pool does not authorize the call
+    fn set_max_gulp_emissions(e: Env, pool: Address, max_emissions: i128);
+
+    /// (Only Pool) Set the share of a pool's reward zone emissions that go to the backstop.
+    /// The remainder is given to the pool.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `share` - The backstop's share of the emissions (7 decimals)
+    ///
+    /// ### Errors
+    /// If `share` is greater than 1 or the pool does not authorize the call
+    fn set_backstop_emission_share(e: Env, pool: Address, share: u32);
+
+    /// Fetch the share of a pool's reward zone emissions that go to the backstop (7 decimals)
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_backstop_emission_share(e: Env, pool: Address) -> u32;
+
+    /// (Only Pool) Enable or disable reward zone emissions for a pool. A disabled pool keeps its
+    /// reward zone slot and emission index, but does not accrue new emissions.
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    /// * `enabled` - Whether the pool accrues reward zone emissions
+    ///
+    /// ### Errors
+    /// If the pool does not authorize the call
+    fn set_pool_emissions_enabled(e: Env, pool: Address, enabled: bool);
+
+    /// Fetch whether a pool accrues reward zone emissions
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_pool_emissions_enabled(e: Env, pool: Address) -> bool;
+
+    /// Fetch the current emissions of a pool's backstop
+    ///
+    /// Returns a tuple of (eps, expiration), where eps is the emissions per second scaled by 14
+    /// decimals, or (0, 0) if the pool's backstop has no emissions
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn get_backstop_eps(e: Env, pool: Address) -> (u64, u64);
+
+    /// Fetch the emissions a pool would receive if `gulp_emissions` were called now
+    ///
+    /// Returns a tuple of (new_backstop_emissions, new_pool_emissions)
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn pending_emissions(e: Env, pool: Address) -> (i128, i128);
+
+    /// Fetch the outstanding BLND allowance the backstop has granted a pool for pool emissions
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn pool_emissions_allowance(e: Env, pool: Address) -> i128;
+
+    /// Fetch the amount of emissions that can still be backfilled before `distribute` reverts
+    /// due to exceeding the maximum backfilled emissions
+    fn get_backfill_remaining(e: Env) -> i128;
+
+    /// Fetch the backfill status of the backstop
+    ///
+    /// Returns None if no distribution has occurred, true if emissions are currently being
+    /// backfilled, and false otherwise
+    fn get_backfill_status(e: Env) -> Option<bool>;
+
     /// Add a pool to the reward zone, and if the reward zone is full, a pool to remove
     ///
     /// ### Arguments
@@ -112,6 +274,35 @@ pub trait Backstop {
     /// If the pool is not below the threshold or if the pool is not in the reward zone
     fn remove_reward(e: Env, to_remove: Address);
 
+    /// Remove multiple pools from the reward zone. All pools must be in the reward zone
+    /// and below the backstop threshold, otherwise no pools are removed.
+    ///
+    /// ### Arguments
+    /// * `to_remove` - The addresses of the pools to remove
+    ///
+    /// ### Errors
+    /// If any pool is above the backstop threshold or is not in the reward zone
+    fn remove_reward_batch(e: Env, to_remove: Vec<Address>);
+
+    /// Remove every pool in the reward zone that is below the backstop threshold
+    ///
+    /// Returns the addresses of the removed pools
+    ///
+    /// ### Errors
+    /// If a pool needs to be removed and distribution has not occurred in the last 24 hours
+    fn prune_reward_zone(e: Env) -> Vec<Address>;
+
+    /// Fetch the reward zone and the emission state of each pool in it
+    ///
+    /// Returns a vector of `(pool, non_queued_tokens, rz_emis_index)` for each pool in the reward zone
+    fn get_reward_zone_info(e: Env) -> Vec<(Address, i128, i128)>;
+
+    /// Check if a pool is in the reward zone
+    ///
+    /// ### Arguments
+    /// * `pool` - The address of the pool
+    fn is_in_reward_zone(e: Env, pool: Address) -> bool;
+
     /// Claim backstop deposit emissions from a list of pools for `from`
     ///
     /// Returns the amount of BLND emissions claimed
@@ -156,6 +347,21 @@ pub trait Backstop {
     /// If the `pool_address` is not valid, backstop does not have sufficient allowance from `from`, or if the pool does not
     /// authorize the call
     fn donate(e: Env, from: Address, pool_address: Address, amount: i128);
+
+    /// Sends backstop tokens from "from" to a pools backstop without requiring the pool's authorization.
+    /// The tokens are shared by the pool's existing depositors.
+    ///
+    /// NOTE: This is not a deposit, and "from" will permanently lose access to the funds
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user sending backstop tokens
+    /// * `pool_address` - The address of the pool
+    /// * `amount` - The amount of backstop tokens to send
+    ///
+    /// ### Errors
+    /// If the pool is not a deployed pool, the pool's backstop has no depositors, or "from" does not
+    /// authorize the call
+    fn top_up(e: Env, from: Address, pool_address: Address, amount: i128);
 }
 
 #[contractimpl]
@@ -211,6 +417,22 @@ impl Backstop for BackstopContract {
         to_mint
     }
 
+    fn deposit_min(
+        e: Env,
+        from: Address,
+        pool_address: Address,
+        amount: i128,
+        min_shares: i128,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        let to_mint = backstop::execute_deposit_min(&e, &from, &pool_address, amount, min_shares);
+
+        BackstopEvents::deposit(&e, pool_address, from, amount, to_mint);
+        to_mint
+    }
+
     fn queue_withdrawal(e: Env, from: Address, pool_address: Address, amount: i128) -> Q4W {
         storage::extend_instance(&e);
         from.require_auth();
@@ -248,18 +470,60 @@ impl Backstop for BackstopContract {
         load_pool_backstop_data(&e, &pool)
     }
 
+    fn get_backstop_dashboard(e: Env, pool: Address) -> BackstopDashboard {
+        load_backstop_dashboard(&e, &pool)
+    }
+
+    fn get_pool_balance_detailed(e: Env, pool: Address) -> (i128, i128, i128, i128) {
+        load_pool_balance_detailed(&e, &pool)
+    }
+
+    fn get_backstop_threshold(e: Env, pool: Address) -> (i128, bool) {
+        load_backstop_threshold(&e, &pool)
+    }
+
+    fn export_pool_balances(
+        e: Env,
+        pools: Vec<Address>,
+    ) -> Vec<(
+        Address,
+        PoolBalance,
+        Option<BackstopEmissionData>,
+        Option<RzEmissionData>,
+    )> {
+        load_pool_balances_export(&e, &pools)
+    }
+
     fn backstop_token(e: Env) -> Address {
         storage::get_backstop_token(&e)
     }
 
+    fn get_config(e: Env) -> BackstopConfig {
+        BackstopConfig {
+            backstop_token: storage::get_backstop_token(&e),
+            emitter: storage::get_emitter(&e),
+            blnd_token: storage::get_blnd_token(&e),
+            usdc_token: storage::get_usdc_token(&e),
+            pool_factory: storage::get_pool_factory(&e),
+        }
+    }
+
     /********** Emissions **********/
 
-    fn distribute(e: Env) -> i128 {
+    fn distribute(e: Env) -> DistributionResult {
         storage::extend_instance(&e);
-        let new_emissions = emissions::distribute(&e);
+        let result = emissions::distribute(&e);
 
-        BackstopEvents::distribute(&e, new_emissions);
-        new_emissions
+        BackstopEvents::distribute(&e, result.new_emissions);
+        result
+    }
+
+    fn distribute_clamped(e: Env) -> DistributionResult {
+        storage::extend_instance(&e);
+        let result = emissions::distribute_clamped(&e);
+
+        BackstopEvents::distribute(&e, result.new_emissions);
+        result
     }
 
     fn gulp_emissions(e: Env, pool: Address) -> i128 {
@@ -271,18 +535,78 @@ impl Backstop for BackstopContract {
         pool_emissions
     }
 
-    fn add_reward(e: Env, to_add: Address, to_remove: Option<Address>) {
+    fn set_max_gulp_emissions(e: Env, pool: Address, max_emissions: i128) {
         storage::extend_instance(&e);
-        emissions::add_to_reward_zone(&e, to_add.clone(), to_remove.clone());
+        pool.require_auth();
+        emissions::set_max_gulp_emissions(&e, &pool, max_emissions);
+    }
 
-        BackstopEvents::rw_zone_add(&e, to_add, to_remove);
+    fn set_backstop_emission_share(e: Env, pool: Address, share: u32) {
+        storage::extend_instance(&e);
+        pool.require_auth();
+        emissions::set_backstop_emission_share(&e, &pool, share);
+    }
+
+    fn get_backstop_emission_share(e: Env, pool: Address) -> u32 {
+        storage::get_backstop_emission_share(&e, &pool)
+    }
+
+    fn set_pool_emissions_enabled(e: Env, pool: Address, enabled: bool) {
+        storage::extend_instance(&e);
+        pool.require_auth();
+        emissions::set_pool_emissions_enabled(&e, &pool, enabled);
+    }
+
+    fn get_pool_emissions_enabled(e: Env, pool: Address) -> bool {
+        storage::get_pool_emissions_enabled(&e, &pool)
+    }
+
+    fn get_backstop_eps(e: Env, pool: Address) -> (u64, u64) {
+        emissions::get_backstop_eps(&e, &pool)
+    }
+
+    fn pending_emissions(e: Env, pool: Address) -> (i128, i128) {
+        emissions::pending_emissions(&e, &pool)
+    }
+
+    fn pool_emissions_allowance(e: Env, pool: Address) -> i128 {
+        emissions::get_pool_emissions_allowance(&e, &pool)
+    }
+
+    fn get_backfill_remaining(e: Env) -> i128 {
+        emissions::get_backfill_remaining(&e)
+    }
+
+    fn get_backfill_status(e: Env) -> Option<bool> {
+        storage::get_backfill_status(&e)
+    }
+
+    fn add_reward(e: Env, to_add: Address, to_remove: Option<Address>) {
+        storage::extend_instance(&e);
+        emissions::add_to_reward_zone(&e, to_add, to_remove);
     }
 
     fn remove_reward(e: Env, to_remove: Address) {
         storage::extend_instance(&e);
-        emissions::remove_from_reward_zone(&e, to_remove.clone());
+        emissions::remove_from_reward_zone(&e, to_remove);
+    }
+
+    fn remove_reward_batch(e: Env, to_remove: Vec<Address>) {
+        storage::extend_instance(&e);
+        emissions::remove_from_reward_zone_batch(&e, to_remove);
+    }
+
+    fn prune_reward_zone(e: Env) -> Vec<Address> {
+        storage::extend_instance(&e);
+        emissions::prune_reward_zone(&e)
+    }
 
-        BackstopEvents::rw_zone_remove(&e, to_remove);
+    fn get_reward_zone_info(e: Env) -> Vec<(Address, i128, i128)> {
+        emissions::get_reward_zone_info(&e)
+    }
+
+    fn is_in_reward_zone(e: Env, pool: Address) -> bool {
+        emissions::is_in_reward_zone(&e, &pool)
     }
 
     fn claim(e: Env, from: Address, pool_addresses: Vec<Address>, to: Address) -> i128 {
@@ -323,6 +647,15 @@ impl Backstop for BackstopContract {
 
         BackstopEvents::donate(&e, pool_address, from, amount);
     }
+
+    fn top_up(e: Env, from: Address, pool_address: Address, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        backstop::execute_top_up(&e, &from, &pool_address, amount);
+
+        BackstopEvents::donate(&e, pool_address, from, amount);
+    }
 }
 
 /// Require that an incoming amount is not negative
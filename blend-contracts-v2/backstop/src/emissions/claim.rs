@@ -1,4 +1,7 @@
-use crate::{dependencies::CometClient, errors::BackstopError, events::BackstopEvents, storage};
+use crate::{
+    backstop::PoolBalance, dependencies::CometClient, errors::BackstopError,
+    events::BackstopEvents, storage,
+};
 use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{
     auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
@@ -8,20 +11,29 @@ use soroban_sdk::{
 use super::distributor::claim_emissions;
 
 /// Perform a claim for backstop deposit emissions by a user from the backstop module
+///
+/// The emissions of all pools are accrued in one pass, and the aggregate BLND is deposited into
+/// the backstop token once before the LP tokens are split across the pools
+///
+/// ### Panics
+/// If `pool_addresses` is empty or contains a duplicate pool
 pub fn execute_claim(e: &Env, from: &Address, pool_addresses: &Vec<Address>, to: &Address) -> i128 {
     if pool_addresses.is_empty() {
         panic_with_error!(e, BackstopError::BadRequest);
     }
 
     let mut claimed: i128 = 0;
-    let mut claims: Map<Address, i128> = Map::new(e);
+    let mut claims: Map<Address, (PoolBalance, i128)> = Map::new(e);
     for pool_id in pool_addresses.iter() {
+        if claims.contains_key(pool_id.clone()) {
+            panic_with_error!(e, BackstopError::BadRequest);
+        }
         let pool_balance = storage::get_pool_balance(e, &pool_id);
         let user_balance = storage::get_user_balance(e, &pool_id, from);
         let claim_amt = claim_emissions(e, &pool_id, &pool_balance, from, &user_balance);
 
         claimed += claim_amt;
-        claims.set(pool_id, claim_amt);
+        claims.set(pool_id, (pool_balance, claim_amt));
     }
 
     if claimed > 0 {
@@ -52,12 +64,13 @@ pub fn execute_claim(e: &Env, from: &Address, pool_addresses: &Vec<Address>, to:
             &0,
             &e.current_contract_address(),
         );
-        for pool_id in pool_addresses.iter() {
-            let claim_amount = claims.get(pool_id.clone()).unwrap();
+        for (pool_id, (mut pool_balance, claim_amount)) in claims.iter() {
+            if claim_amount == 0 {
+                continue;
+            }
             let deposit_amount = lp_tokens_out
                 .fixed_mul_floor(claim_amount, claimed)
                 .unwrap();
-            let mut pool_balance = storage::get_pool_balance(e, &pool_id);
             let mut user_balance = storage::get_user_balance(e, &pool_id, to);
 
             // Deposit LP tokens into pool backstop
@@ -582,4 +595,137 @@ mod tests {
             assert_eq!(new_user_2_data.index, 67000000000000);
         });
     }
+
+    #[test]
+    fn test_claim_many_pools_matches_individual_claims() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let block_timestamp = 1500000000 + 12345;
+        e.ledger().set(LedgerInfo {
+            timestamp: block_timestamp,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_address = create_backstop(&e);
+        let pool_1_id = Address::generate(&e);
+        let pool_2_id = Address::generate(&e);
+        let pool_3_id = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let (blnd_address, blnd_token_client) = create_blnd_token(&e, &backstop_address, &bombadil);
+        let (usdc_address, _) = create_usdc_token(&e, &backstop_address, &bombadil);
+        blnd_token_client.mint(&backstop_address, &500_0000000);
+        let (lp_address, _) = create_comet_lp_pool(&e, &bombadil, &blnd_address, &usdc_address);
+
+        let pools = vec![&e, pool_1_id.clone(), pool_2_id.clone(), pool_3_id.clone()];
+        let eps = [0_10000000000000, 0_02000000000000, 0_05000000000000];
+        e.as_contract(&backstop_address, || {
+            storage::set_backstop_token(&e, &lp_address);
+            storage::set_blnd_token(&e, &blnd_address);
+            storage::set_rz_emission_index(&e, &1_00000000000000);
+            for (i, pool_id) in pools.iter().enumerate() {
+                storage::set_backstop_emis_data(
+                    &e,
+                    &pool_id,
+                    &BackstopEmissionData {
+                        expiration: 1500000000 + 7 * 24 * 60 * 60,
+                        eps: eps[i],
+                        index: 0,
+                        last_time: 1500000000,
+                    },
+                );
+                storage::set_rz_emis_data(
+                    &e,
+                    &pool_id,
+                    &RzEmissionData {
+                        index: 0,
+                        accrued: 0,
+                    },
+                );
+                storage::set_pool_balance(
+                    &e,
+                    &pool_id,
+                    &PoolBalance {
+                        shares: 100_0000000,
+                        tokens: 120_0000000,
+                        q4w: 0,
+                    },
+                );
+                // samwise and frodo hold identical positions in each pool
+                for user in [&samwise, &frodo] {
+                    storage::set_user_emis_data(
+                        &e,
+                        &pool_id,
+                        user,
+                        &UserEmissionData {
+                            index: 0,
+                            accrued: 0,
+                        },
+                    );
+                    storage::set_user_balance(
+                        &e,
+                        &pool_id,
+                        user,
+                        &UserBalance {
+                            shares: 10_0000000,
+                            q4w: vec![&e],
+                        },
+                    );
+                }
+            }
+
+            // samwise claims all pools in one call
+            let total_claim = execute_claim(&e, &samwise, &pools, &samwise);
+
+            // frodo claims each pool individually
+            let mut individual_claims: i128 = 0;
+            for pool_id in pools.iter() {
+                individual_claims += execute_claim(&e, &frodo, &vec![&e, pool_id.clone()], &frodo);
+            }
+
+            assert!(total_claim > 0);
+            assert_eq!(total_claim, individual_claims);
+            assert_eq!(
+                blnd_token_client.balance(&backstop_address),
+                500_0000000 - total_claim - individual_claims
+            );
+            for pool_id in pools.iter() {
+                let sam_balance = storage::get_user_balance(&e, &pool_id, &samwise);
+                assert!(sam_balance.shares > 10_0000000);
+                let new_user_data =
+                    storage::get_user_emis_data(&e, &pool_id, &samwise).unwrap_optimized();
+                assert_eq!(new_user_data.accrued, 0);
+            }
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_claim_duplicate_pool() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let backstop_address = create_backstop(&e);
+        let pool_1_id = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&backstop_address, || {
+            execute_claim(
+                &e,
+                &samwise,
+                &vec![&e, pool_1_id.clone(), pool_1_id.clone()],
+                &samwise,
+            );
+        });
+    }
 }
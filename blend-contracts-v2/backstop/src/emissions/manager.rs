@@ -1,19 +1,33 @@
 use cast::{i128, u64};
 use sep_41_token::TokenClient;
 use soroban_fixed_point_math::FixedPoint;
-use soroban_sdk::{panic_with_error, unwrap::UnwrapOptimized, Address, Env, Vec};
+use soroban_sdk::{contracttype, panic_with_error, unwrap::UnwrapOptimized, Address, Env, Vec};
 
 use crate::{
     backstop::{load_pool_backstop_data, require_pool_above_threshold},
-    constants::{MAX_BACKFILLED_EMISSIONS, MAX_RZ_SIZE, SCALAR_14, SCALAR_7},
+    constants::{
+        MAX_BACKFILLED_EMISSIONS, MAX_DISTRIBUTION_GAP, MAX_RZ_SIZE, RZ_TIE_MIN_TENURE, SCALAR_14,
+        SCALAR_7,
+    },
     dependencies::EmitterClient,
     errors::BackstopError,
+    events::BackstopEvents,
     storage::{self, BackstopEmissionData, RzEmissionData},
     PoolBalance,
 };
 
 use super::distributor::update_emission_data;
 
+/// The result of a reward zone emissions distribution
+#[derive(Clone)]
+#[contracttype]
+pub struct DistributionResult {
+    pub new_emissions: i128, // the amount of new emissions distributed to the reward zone
+    pub is_backfill: bool,   // whether the emissions are backfilled by the backstop
+    pub was_reset: bool, // whether the last distribution time was reset to the emitter's without distributing
+    pub new_index: i128, // the reward zone emission index after the distribution
+}
+
 /// Add a pool to the reward zone. If the reward zone is full, attempt to swap it with the pool to remove.
 pub fn add_to_reward_zone(e: &Env, to_add: Address, to_remove: Option<Address>) {
     let mut reward_zone = storage::get_reward_zone(e);
@@ -34,16 +48,33 @@ pub fn add_to_reward_zone(e: &Env, to_add: Address, to_remove: Option<Address>)
     if MAX_RZ_SIZE > reward_zone.len() {
         // there is room in the reward zone. Add "to_add".
         reward_zone.push_front(to_add.clone());
+        BackstopEvents::rw_zone_add(e, to_add.clone(), None, pool_data.tokens, 0);
     } else {
         match to_remove {
             None => panic_with_error!(e, BackstopError::RewardZoneFull),
             Some(to_remove) => {
-                // Verify "to_add" has a higher backstop deposit that "to_remove"
-                if pool_data.tokens <= storage::get_pool_balance(e, &to_remove).tokens {
+                // verify distribute was run recently before swapping pools, so "to_remove" does not lose
+                // excess emissions and "to_add" starts accruing from an up-to-date index
+                require_recent_distribution(e);
+
+                // Verify "to_add" has a higher backstop deposit that "to_remove". If the deposits are equal,
+                // "to_remove" can only be swapped out if it is below the threshold excluding queued withdrawals,
+                // or if it has held its spot for long enough, to prevent a pool pinning its spot by matching deposits
+                let to_remove_tokens = storage::get_pool_balance(e, &to_remove).tokens;
+                if pool_data.tokens < to_remove_tokens
+                    || (pool_data.tokens == to_remove_tokens && !can_swap_on_tie(e, &to_remove))
+                {
                     panic_with_error!(e, BackstopError::InvalidRewardZoneEntry);
                 }
                 remove_pool(e, &mut reward_zone, &to_remove);
                 reward_zone.push_front(to_add.clone());
+                BackstopEvents::rw_zone_add(
+                    e,
+                    to_add.clone(),
+                    Some(to_remove),
+                    pool_data.tokens,
+                    to_remove_tokens,
+                );
             }
         }
     }
@@ -59,9 +90,36 @@ pub fn add_to_reward_zone(e: &Env, to_add: Address, to_remove: Option<Address>)
     } else {
         set_rz_emissions(e, &to_add, rz_emission_index, 0, false);
     }
+    storage::set_rz_entry_time(e, &to_add, &e.ledger().timestamp());
     storage::set_reward_zone(e, &reward_zone);
 }
 
+/// Check if a reward zone pool can be swapped out by a pool with an equal backstop deposit
+///
+/// Returns true if the pool is below the threshold when excluding shares queued for withdrawal,
+/// or if the pool has been in the reward zone for at least `RZ_TIE_MIN_TENURE`
+fn can_swap_on_tie(e: &Env, pool: &Address) -> bool {
+    let entry_time = storage::get_rz_entry_time(e, pool);
+    if entry_time > 0 && e.ledger().timestamp() - entry_time >= RZ_TIE_MIN_TENURE {
+        return true;
+    }
+
+    let pool_balance = storage::get_pool_balance(e, pool);
+    let mut pool_data = load_pool_backstop_data(e, pool);
+    if pool_balance.tokens > 0 {
+        let non_queued_tokens = pool_balance.non_queued_tokens();
+        pool_data.blnd = pool_data
+            .blnd
+            .fixed_mul_floor(non_queued_tokens, pool_balance.tokens)
+            .unwrap_optimized();
+        pool_data.usdc = pool_data
+            .usdc
+            .fixed_mul_floor(non_queued_tokens, pool_balance.tokens)
+            .unwrap_optimized();
+    }
+    !require_pool_above_threshold(&pool_data)
+}
+
 /// remove a pool to the reward zone if below the minimum backstop deposit threshold
 pub fn remove_from_reward_zone(e: &Env, to_remove: Address) {
     let mut reward_zone = storage::get_reward_zone(e);
@@ -74,7 +132,61 @@ pub fn remove_from_reward_zone(e: &Env, to_remove: Address) {
     } else {
         remove_pool(e, &mut reward_zone, &to_remove);
         storage::set_reward_zone(e, &reward_zone);
+        BackstopEvents::rw_zone_remove(e, to_remove, pool_data.tokens);
+    }
+}
+
+/// Require that distribute was run within the last 24 hours
+///
+/// ### Panics
+/// If the last distribution occurred more than 24 hours ago
+fn require_recent_distribution(e: &Env) {
+    let last_distribution = storage::get_last_distribution_time(e);
+    if last_distribution < e.ledger().timestamp() - 24 * 60 * 60 {
+        panic_with_error!(e, BackstopError::StaleDistribution);
+    }
+}
+
+/// Remove multiple pools from the reward zone if they are all below the minimum backstop deposit threshold
+///
+/// ### Panics
+/// If any pool is above the threshold or not in the reward zone
+pub fn remove_from_reward_zone_batch(e: &Env, pools: Vec<Address>) {
+    let mut reward_zone = storage::get_reward_zone(e);
+
+    for pool in pools.iter() {
+        let pool_data = load_pool_backstop_data(e, &pool);
+        if require_pool_above_threshold(&pool_data) || !reward_zone.contains(&pool) {
+            panic_with_error!(e, BackstopError::BadRequest);
+        }
+        remove_pool(e, &mut reward_zone, &pool);
+        BackstopEvents::rw_zone_remove(e, pool, pool_data.tokens);
+    }
+    storage::set_reward_zone(e, &reward_zone);
+}
+
+/// Remove every pool in the reward zone that is below the minimum backstop deposit threshold
+///
+/// Returns the addresses of the removed pools
+///
+/// ### Panics
+/// If a pool needs to be removed and distribute was not run within the last 24 hours
+pub fn prune_reward_zone(e: &Env) -> Vec<Address> {
+    let mut reward_zone = storage::get_reward_zone(e);
+    let mut removed: Vec<Address> = Vec::new(e);
+
+    for pool in reward_zone.clone().iter() {
+        let pool_data = load_pool_backstop_data(e, &pool);
+        if !require_pool_above_threshold(&pool_data) {
+            remove_pool(e, &mut reward_zone, &pool);
+            BackstopEvents::rw_zone_remove(e, pool.clone(), pool_data.tokens);
+            removed.push_back(pool);
+        }
+    }
+    if !removed.is_empty() {
+        storage::set_reward_zone(e, &reward_zone);
     }
+    removed
 }
 
 /// Remove a pool from the reward zone and set the backstop emissions index to i128::MAX
@@ -89,7 +201,10 @@ fn remove_pool(e: &Env, reward_zone: &mut Vec<Address>, to_remove: &Address) {
                 panic_with_error!(e, BackstopError::BadRequest);
             }
 
-            // update backstop emissions for the pool before removing it from the reward zone
+            // checkpoint every reward zone pool at the current index before the reward zone
+            // changes, so accrual up to the removal is unaffected by the change in reward zone tokens
+            checkpoint_reward_zone(e, reward_zone);
+
             // set emission index to i128::MAX to prevent further emissions
             let to_remove_emis_data = storage::get_rz_emis_data(e, &to_remove).unwrap_optimized();
             set_rz_emissions(e, &to_remove, i128::MAX, to_remove_emis_data.accrued, false);
@@ -100,7 +215,56 @@ fn remove_pool(e: &Env, reward_zone: &mut Vec<Address>, to_remove: &Address) {
     }
 }
 
-pub fn distribute(e: &Env) -> i128 {
+/// Accrue reward zone emissions for every pool in the reward zone up to the current reward zone
+/// emission index
+fn checkpoint_reward_zone(e: &Env, reward_zone: &Vec<Address>) {
+    for pool in reward_zone.iter() {
+        update_rz_emis_data(e, &pool, false);
+    }
+}
+
+/// Fetch the reward zone pools with their non-queued backstop tokens and reward zone emission index
+///
+/// If a pool has no reward zone emission data, the current reward zone emission index is reported
+pub fn get_reward_zone_info(e: &Env) -> Vec<(Address, i128, i128)> {
+    let reward_zone = storage::get_reward_zone(e);
+    let rz_emission_index = storage::get_rz_emission_index(e);
+    let mut rz_info: Vec<(Address, i128, i128)> = Vec::new(e);
+    for pool in reward_zone.iter() {
+        let non_queued_tokens = storage::get_pool_balance(e, &pool).non_queued_tokens();
+        let index = match storage::get_rz_emis_data(e, &pool) {
+            Some(emis_data) => emis_data.index,
+            None => rz_emission_index,
+        };
+        rz_info.push_back((pool, non_queued_tokens, index));
+    }
+    rz_info
+}
+
+/// Check if a pool is in the reward zone
+pub fn is_in_reward_zone(e: &Env, pool: &Address) -> bool {
+    storage::get_reward_zone(e).contains(pool)
+}
+
+/// Distribute new emissions from the emitter to the reward zone
+///
+/// Returns the result of the distribution
+///
+/// ### Panics
+/// If backfilling emissions would exceed `MAX_BACKFILLED_EMISSIONS`
+pub fn distribute(e: &Env) -> DistributionResult {
+    do_distribute(e, false)
+}
+
+/// Distribute new emissions from the emitter to the reward zone. If backfilling emissions would exceed
+/// `MAX_BACKFILLED_EMISSIONS`, only the remaining backfill allotment is distributed instead of reverting.
+///
+/// Returns the result of the distribution
+pub fn distribute_clamped(e: &Env) -> DistributionResult {
+    do_distribute(e, true)
+}
+
+fn do_distribute(e: &Env, clamp_backfill: bool) -> DistributionResult {
     let is_backfill: bool;
     let mut needs_reset: bool = false;
     let last_backfill_status = storage::get_backfill_status(e);
@@ -130,7 +294,12 @@ pub fn distribute(e: &Env) -> i128 {
     // start emissions from that time
     if last_distribution == 0 {
         storage::set_last_distribution_time(e, &emitter_last_distribution);
-        return 0;
+        return DistributionResult {
+            new_emissions: 0,
+            is_backfill,
+            was_reset: true,
+            new_index: storage::get_rz_emission_index(e),
+        };
     }
 
     // if this is the first distribution after a backstop swap, we need to stop the backfill emissions
@@ -140,7 +309,12 @@ pub fn distribute(e: &Env) -> i128 {
     // between those two timepoints.
     if needs_reset {
         storage::set_last_distribution_time(e, &emitter_last_distribution);
-        return 0;
+        return DistributionResult {
+            new_emissions: 0,
+            is_backfill,
+            was_reset: true,
+            new_index: storage::get_rz_emission_index(e),
+        };
     }
 
     let reward_zone = storage::get_reward_zone(e);
@@ -156,8 +330,12 @@ pub fn distribute(e: &Env) -> i128 {
         panic_with_error!(e, BackstopError::BadRequest);
     }
 
+    // process at most the maximum distribution gap of emissions. Any remaining emissions are
+    // carried forward to the next distribution.
+    let distribution_time = emitter_last_distribution.min(last_distribution + MAX_DISTRIBUTION_GAP);
+
     // emitter releases 1 token per second
-    let new_emissions = i128(emitter_last_distribution - last_distribution) * SCALAR_7;
+    let mut new_emissions = i128(distribution_time - last_distribution) * SCALAR_7;
 
     // if backfilling emissions, ensure we are not over the maximum backfilled emissions allotment.
     // backfilled emissions must fit within the maximum drop amount from the emitter.
@@ -165,11 +343,18 @@ pub fn distribute(e: &Env) -> i128 {
         let mut cur_backfill = storage::get_backfill_emissions(e);
         cur_backfill += new_emissions;
         if cur_backfill > MAX_BACKFILLED_EMISSIONS {
-            panic_with_error!(e, BackstopError::MaxBackfillEmissions);
+            if !clamp_backfill {
+                panic_with_error!(e, BackstopError::MaxBackfillEmissions);
+            }
+            new_emissions -= cur_backfill - MAX_BACKFILLED_EMISSIONS;
+            if new_emissions < 0 {
+                new_emissions = 0;
+            }
+            cur_backfill = MAX_BACKFILLED_EMISSIONS;
         }
         storage::set_backfill_emissions(e, &cur_backfill);
     }
-    storage::set_last_distribution_time(e, &emitter_last_distribution);
+    storage::set_last_distribution_time(e, &distribution_time);
     let prev_index = storage::get_rz_emission_index(e);
 
     // fetch total tokens of BLND in the reward zone
@@ -179,6 +364,10 @@ pub fn distribute(e: &Env) -> i128 {
         let pool_balance = storage::get_pool_balance(e, &rz_pool);
         total_non_queued_tokens += pool_balance.non_queued_tokens();
     }
+    // emissions cannot be distributed if every reward zone pool is fully queued for withdrawal
+    if total_non_queued_tokens <= 0 {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
 
     let additional_index = new_emissions
         .fixed_div_floor(total_non_queued_tokens, SCALAR_14)
@@ -186,23 +375,34 @@ pub fn distribute(e: &Env) -> i128 {
     let new_index = prev_index + additional_index;
     storage::set_rz_emission_index(e, &new_index);
 
-    return new_emissions;
+    DistributionResult {
+        new_emissions,
+        is_backfill,
+        was_reset: false,
+        new_index,
+    }
 }
 
 /// Assign backstop and pool emissions to `pool` based on the reward zone and the backstop emissions index
 /// Returns the amount of backstop and pool emissions assigned to the pool
 #[allow(clippy::zero_prefixed_literal)]
 pub fn gulp_emissions(e: &Env, pool: &Address) -> (i128, i128) {
+    if !storage::get_pool_emissions_enabled(e, pool) {
+        // emissions are paused - keep the pool's index current without consuming its accrued emissions
+        update_rz_emis_data(e, pool, false);
+        return (0, 0);
+    }
     let pool_balance = storage::get_pool_balance(e, pool);
 
-    let new_emissions = update_rz_emis_data(e, pool, true);
+    let accrued = update_rz_emis_data(e, pool, true);
+    let (new_emissions, deferred_emissions) = cap_gulp_emissions(e, pool, accrued);
+    if deferred_emissions > 0 {
+        // defer any emissions over the pool's cap to the next gulp
+        let gulp_index = storage::get_rz_emission_index(e);
+        set_rz_emissions(e, pool, gulp_index, deferred_emissions, false);
+    }
     if new_emissions > 0 {
-        let new_backstop_emissions = new_emissions
-            .fixed_mul_floor(0_7000000, SCALAR_7)
-            .unwrap_optimized();
-        let new_pool_emissions = new_emissions
-            .fixed_mul_floor(0_3000000, SCALAR_7)
-            .unwrap_optimized();
+        let (new_backstop_emissions, new_pool_emissions) = split_emissions(e, pool, new_emissions);
 
         // distribute pool emissions via allowance to pools
         let blnd_token_client = TokenClient::new(e, &storage::get_blnd_token(e));
@@ -220,27 +420,145 @@ pub fn gulp_emissions(e: &Env, pool: &Address) -> (i128, i128) {
     return (0, 0);
 }
 
+/// Fetch the amount of emissions that can still be backfilled before `distribute` reverts
+/// with `MaxBackfillEmissions`
+pub fn get_backfill_remaining(e: &Env) -> i128 {
+    let remaining = MAX_BACKFILLED_EMISSIONS - storage::get_backfill_emissions(e);
+    remaining.max(0)
+}
+
+/// Fetch the outstanding BLND allowance the backstop has granted `pool` for pool emissions
+pub fn get_pool_emissions_allowance(e: &Env, pool: &Address) -> i128 {
+    let blnd_token_client = TokenClient::new(e, &storage::get_blnd_token(e));
+    blnd_token_client.allowance(&e.current_contract_address(), pool)
+}
+
+/// Fetch the current backstop emissions for `pool`
+///
+/// Returns a tuple of (eps, expiration), where eps is scaled by 14 decimals, or (0, 0) if the
+/// pool's backstop has no emissions
+pub fn get_backstop_eps(e: &Env, pool: &Address) -> (u64, u64) {
+    match storage::get_backstop_emis_data(e, pool) {
+        Some(emis_data) => (emis_data.eps, emis_data.expiration),
+        None => (0, 0),
+    }
+}
+
 pub fn update_rz_emis_data(e: &Env, pool: &Address, to_gulp: bool) -> i128 {
     if let Some(emission_data) = storage::get_rz_emis_data(e, pool) {
         let pool_balance = storage::get_pool_balance(e, pool);
         let gulp_index = storage::get_rz_emission_index(e);
-        let mut accrued = emission_data.accrued;
         if emission_data.index < gulp_index || to_gulp {
-            if pool_balance.non_queued_tokens() > 0 {
-                let new_emissions = pool_balance
-                    .non_queued_tokens()
-                    .fixed_mul_floor(gulp_index - emission_data.index, SCALAR_14)
-                    .unwrap_optimized();
-                accrued += new_emissions;
-                return set_rz_emissions(e, pool, gulp_index, accrued, to_gulp);
+            // a pool with emissions disabled does not accrue new emissions
+            let accrued = if storage::get_pool_emissions_enabled(e, pool) {
+                calc_rz_accrued(&pool_balance, &emission_data, gulp_index)
             } else {
-                return set_rz_emissions(e, pool, gulp_index, accrued, to_gulp);
-            }
+                emission_data.accrued
+            };
+            return set_rz_emissions(e, pool, gulp_index, accrued, to_gulp);
         }
     }
     return 0;
 }
 
+/// Fetch the emissions a pool would receive if `gulp_emissions` were called now. Does not write to storage.
+///
+/// Returns a tuple of (new_backstop_emissions, new_pool_emissions)
+pub fn pending_emissions(e: &Env, pool: &Address) -> (i128, i128) {
+    if !storage::get_pool_emissions_enabled(e, pool) {
+        return (0, 0);
+    }
+    if let Some(emission_data) = storage::get_rz_emis_data(e, pool) {
+        let pool_balance = storage::get_pool_balance(e, pool);
+        let gulp_index = storage::get_rz_emission_index(e);
+        let accrued = calc_rz_accrued(&pool_balance, &emission_data, gulp_index);
+        let (new_emissions, _) = cap_gulp_emissions(e, pool, accrued);
+        if new_emissions > 0 {
+            return split_emissions(e, pool, new_emissions);
+        }
+    }
+    (0, 0)
+}
+
+/// Calculate the total emissions accrued by a reward zone pool at the given reward zone emission index
+fn calc_rz_accrued(
+    pool_balance: &PoolBalance,
+    emission_data: &RzEmissionData,
+    gulp_index: i128,
+) -> i128 {
+    let non_queued_tokens = pool_balance.non_queued_tokens();
+    if non_queued_tokens > 0 {
+        let new_emissions = non_queued_tokens
+            .fixed_mul_floor(gulp_index - emission_data.index, SCALAR_14)
+            .unwrap_optimized();
+        emission_data.accrued + new_emissions
+    } else {
+        emission_data.accrued
+    }
+}
+
+/// Cap the emissions a pool can gulp in a single `gulp_emissions` call
+///
+/// Returns a tuple of (emissions to gulp, emissions deferred to the next gulp)
+fn cap_gulp_emissions(e: &Env, pool: &Address, accrued: i128) -> (i128, i128) {
+    match storage::get_max_gulp_emissions(e, pool) {
+        Some(max_emissions) if accrued > max_emissions => (max_emissions, accrued - max_emissions),
+        _ => (accrued, 0),
+    }
+}
+
+/// Enable or disable reward zone emissions for a pool. A disabled pool keeps its reward zone slot
+/// and emission index, but does not accrue new emissions. Emissions accrued before the pool was
+/// disabled can be gulped once it is enabled again.
+///
+/// ### Arguments
+/// * `pool` - The address of the pool
+/// * `enabled` - Whether the pool accrues reward zone emissions
+pub fn set_pool_emissions_enabled(e: &Env, pool: &Address, enabled: bool) {
+    // checkpoint the pool's emissions under the current setting before changing it
+    update_rz_emis_data(e, pool, false);
+    storage::set_pool_emissions_enabled(e, pool, &enabled);
+}
+
+/// Set the maximum emissions a pool can gulp in a single `gulp_emissions` call. Any emissions
+/// over the maximum are deferred to the next gulp. A maximum of 0 removes the cap.
+///
+/// ### Panics
+/// If the max emissions is negative
+pub fn set_max_gulp_emissions(e: &Env, pool: &Address, max_emissions: i128) {
+    if max_emissions < 0 {
+        panic_with_error!(e, BackstopError::BadRequest);
+    } else if max_emissions == 0 {
+        storage::del_max_gulp_emissions(e, pool);
+    } else {
+        storage::set_max_gulp_emissions(e, pool, &max_emissions);
+    }
+}
+
+/// Set the share of a pool's reward zone emissions that go to the backstop. The remainder
+/// is given to the pool.
+///
+/// ### Panics
+/// If the share is greater than 1
+pub fn set_backstop_emission_share(e: &Env, pool: &Address, share: u32) {
+    if share > SCALAR_7 as u32 {
+        panic_with_error!(e, BackstopError::BadRequest);
+    }
+    storage::set_backstop_emission_share(e, pool, &share);
+}
+
+/// Split new emissions for a reward zone pool into (backstop emissions, pool emissions)
+fn split_emissions(e: &Env, pool: &Address, new_emissions: i128) -> (i128, i128) {
+    let backstop_share = i128(storage::get_backstop_emission_share(e, pool));
+    let new_backstop_emissions = new_emissions
+        .fixed_mul_floor(backstop_share, SCALAR_7)
+        .unwrap_optimized();
+    let new_pool_emissions = new_emissions
+        .fixed_mul_floor(SCALAR_7 - backstop_share, SCALAR_7)
+        .unwrap_optimized();
+    (new_backstop_emissions, new_pool_emissions)
+}
+
 fn set_rz_emissions(e: &Env, pool_id: &Address, index: i128, accrued: i128, to_gulp: bool) -> i128 {
     if to_gulp {
         storage::set_rz_emis_data(e, pool_id, &RzEmissionData { index, accrued: 0 });
@@ -275,16 +593,20 @@ pub fn set_backstop_emission_eps(
             let tokens_since_last_emission = i128(emission_data.eps)
                 .fixed_mul_floor(i128(time_since_last_emission), SCALAR_7)
                 .unwrap_optimized();
-            tokens_left_to_emit += tokens_since_last_emission;
+            tokens_left_to_emit = match tokens_left_to_emit.checked_add(tokens_since_last_emission)
+            {
+                Some(tokens) => tokens,
+                None => panic_with_error!(e, BackstopError::EmissionRateOverflow),
+            };
         }
-        // Scale eps by 14 decimal places to reduce rounding errors
-        let eps = u64(tokens_left_to_emit * SCALAR_7 / (7 * 24 * 60 * 60)).unwrap_optimized();
+        let eps = calc_backstop_eps(e, tokens_left_to_emit);
         emission_data.eps = eps;
         emission_data.expiration = expiration;
         storage::set_backstop_emis_data(e, pool_id, &emission_data);
+        BackstopEvents::backstop_eps_set(e, pool_id.clone(), eps, expiration);
     } else {
         // first time the pool's backstop is receiving emissions - ensure data is written
-        let eps = u64(tokens_left_to_emit * SCALAR_7 / (7 * 24 * 60 * 60)).unwrap_optimized();
+        let eps = calc_backstop_eps(e, tokens_left_to_emit);
         storage::set_backstop_emis_data(
             e,
             pool_id,
@@ -295,6 +617,22 @@ pub fn set_backstop_emission_eps(
                 last_time: e.ledger().timestamp(),
             },
         );
+        BackstopEvents::backstop_eps_set(e, pool_id.clone(), eps, expiration);
+    }
+}
+
+/// Calculate the eps required to emit `tokens` over a week. Eps is scaled by 14 decimal places
+/// to reduce rounding errors.
+///
+/// ### Panics
+/// If the eps does not fit in a u64
+fn calc_backstop_eps(e: &Env, tokens: i128) -> u64 {
+    match tokens
+        .checked_mul(SCALAR_7)
+        .and_then(|scaled_tokens| u64(scaled_tokens / (7 * 24 * 60 * 60)).ok())
+    {
+        Some(eps) => eps,
+        None => panic_with_error!(e, BackstopError::EmissionRateOverflow),
     }
 }
 
@@ -302,8 +640,8 @@ pub fn set_backstop_emission_eps(
 mod tests {
     use super::*;
     use soroban_sdk::{
-        testutils::{Address as _, Ledger, LedgerInfo},
-        vec, Vec,
+        testutils::{Address as _, Events, Ledger, LedgerInfo},
+        vec, IntoVal, Symbol, Vec,
     };
 
     use crate::{
@@ -314,6 +652,106 @@ mod tests {
         },
     };
 
+    /********** set_backstop_emission_eps **********/
+
+    #[test]
+    fn test_set_backstop_emission_eps_events() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let backstop = create_backstop(&e);
+        let pool_1 = Address::generate(&e);
+        let pool_balance = PoolBalance {
+            shares: 100_000_0000000,
+            tokens: 100_000_0000000,
+            q4w: 0,
+        };
+
+        // first time the pool's backstop receives emissions
+        e.as_contract(&backstop, || {
+            set_backstop_emission_eps(&e, &pool_1, &pool_balance, 604800_0000000);
+            assert_eq!(
+                e.events().all().last_unchecked(),
+                (
+                    backstop.clone(),
+                    (Symbol::new(&e, "backstop_eps_set"), pool_1.clone()).into_val(&e),
+                    (100000000000000u64, 1713139200u64 + 604800).into_val(&e)
+                )
+            );
+        });
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200 + 24 * 60 * 60,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        // unemitted tokens from the previous eps are rolled into the new eps
+        e.as_contract(&backstop, || {
+            set_backstop_emission_eps(&e, &pool_1, &pool_balance, 604800_0000000);
+            assert_eq!(
+                e.events().all().last_unchecked(),
+                (
+                    backstop.clone(),
+                    (Symbol::new(&e, "backstop_eps_set"), pool_1.clone()).into_val(&e),
+                    (185714285714285u64, 1713139200u64 + 24 * 60 * 60 + 604800).into_val(&e)
+                )
+            );
+            let emis_data = storage::get_backstop_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(emis_data.eps, 185714285714285);
+            assert_eq!(emis_data.expiration, 1713139200 + 24 * 60 * 60 + 604800);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1012)")]
+    fn test_set_backstop_emission_eps_overflows_u64() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+        let pool_1 = Address::generate(&e);
+        let pool_balance = PoolBalance {
+            shares: 100_000_0000000,
+            tokens: 100_000_0000000,
+            q4w: 0,
+        };
+
+        e.as_contract(&backstop, || {
+            // ~1.1e11 tokens over a week is the most a u64 eps can represent
+            set_backstop_emission_eps(&e, &pool_1, &pool_balance, 200_000_000_000_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1012)")]
+    fn test_set_backstop_emission_eps_overflows_i128() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+        let pool_1 = Address::generate(&e);
+        let pool_balance = PoolBalance {
+            shares: 100_000_0000000,
+            tokens: 100_000_0000000,
+            q4w: 0,
+        };
+
+        e.as_contract(&backstop, || {
+            set_backstop_emission_eps(&e, &pool_1, &pool_balance, i128::MAX / 2);
+        });
+    }
+
     /********** gulp_emissions **********/
 
     #[test]
@@ -471,13 +909,31 @@ mod tests {
             assert_eq!(new_pool_3_data.expiration, 1713139200 + 7 * 24 * 60 * 60);
             assert_eq!(new_pool_3_data.index, 0);
             assert_eq!(new_pool_3_data.last_time, 1713139200);
+
+            assert_eq!(
+                get_backstop_eps(&e, &pool_1),
+                (new_pool_1_data.eps, new_pool_1_data.expiration)
+            );
+            assert_eq!(
+                get_backstop_eps(&e, &pool_3),
+                (new_pool_3_data.eps, new_pool_3_data.expiration)
+            );
         });
     }
 
-    /********** distribute **********/
+    #[test]
+    fn test_get_backstop_eps_no_emissions() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        e.as_contract(&backstop, || {
+            assert_eq!(get_backstop_eps(&e, &pool), (0, 0));
+        });
+    }
 
     #[test]
-    fn test_distribute() {
+    fn test_gulp_emissions_custom_split() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
@@ -494,6 +950,7 @@ mod tests {
 
         let backstop = create_backstop(&e);
         let emitter_distro_time = 1713139200 - 10;
+        let blnd_token_client = create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
         create_emitter(
             &e,
             &backstop,
@@ -501,15 +958,56 @@ mod tests {
             &Address::generate(&e),
             emitter_distro_time,
         );
-
         let pool_1 = Address::generate(&e);
         let pool_2 = Address::generate(&e);
         let pool_3 = Address::generate(&e);
         let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
 
+        // setup pool 1 to have ongoing emissions
+        let pool_1_emissions_data = BackstopEmissionData {
+            expiration: 1713139200 + 1000,
+            eps: 0_10000000000000,
+            index: 8877660000000,
+            last_time: 1713139200 - 12345,
+        };
+
+        // setup pool 2 to have expired emissions
+        let pool_2_emissions_data = BackstopEmissionData {
+            expiration: 1713139200 - 12345,
+            eps: 0_05000000000000,
+            index: 4532340000000,
+            last_time: 1713139200 - 12345,
+        };
+        // setup pool 3 to have no emissions
         e.as_contract(&backstop, || {
-            storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - 7 * 24 * 60 * 60));
             storage::set_reward_zone(&e, &reward_zone);
+            storage::set_backstop_emis_data(&e, &pool_1, &pool_1_emissions_data);
+            storage::set_rz_emis_data(
+                &e,
+                &pool_1,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 0,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &pool_2,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 0,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &pool_3,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 0,
+                },
+            );
+            storage::set_backstop_emis_data(&e, &pool_2, &pool_2_emissions_data);
             storage::set_pool_balance(
                 &e,
                 &pool_1,
@@ -537,72 +1035,78 @@ mod tests {
                     q4w: 0,
                 },
             );
+            blnd_token_client.approve(&backstop, &pool_1, &100_123_0000000, &e.ledger().sequence());
+            // pool 1 uses the default 70/30 split, pool 2 a 90/10 split, and pool 3 a 50/50 split
+            set_backstop_emission_share(&e, &pool_2, 0_9000000);
+            set_backstop_emission_share(&e, &pool_3, 0_5000000);
 
             distribute(&e);
+            gulp_emissions(&e, &pool_1);
+            gulp_emissions(&e, &pool_2);
+            gulp_emissions(&e, &pool_3);
 
-            let gulp_index = storage::get_rz_emission_index(&e);
-            assert_eq!(gulp_index, 8640000000000);
-            let last_distro_time = storage::get_last_distribution_time(&e);
-            assert_eq!(last_distro_time, emitter_distro_time);
-            let backfilled_emissions = storage::get_backfill_emissions(&e);
-            assert_eq!(backfilled_emissions, 0);
-            // backfill status remains unchanged if not set
-            let backfill_status = storage::get_backfill_status(&e);
-            assert_eq!(backfill_status, None);
-        });
-    }
-
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1000)")]
-    fn test_distribute_empty_rz() {
-        let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
-
-        e.ledger().set(LedgerInfo {
-            timestamp: 1713139200,
-            protocol_version: 22,
-            sequence_number: 0,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
-
-        let backstop = create_backstop(&e);
-        let emitter_distro_time = 1713139200 - 10;
-        create_emitter(
-            &e,
-            &backstop,
-            &Address::generate(&e),
-            &Address::generate(&e),
-            emitter_distro_time,
-        );
+            assert_eq!(storage::get_last_distribution_time(&e), emitter_distro_time);
+            assert_eq!(
+                storage::get_pool_balance(&e, &pool_1).tokens,
+                300_000_0000000
+            );
+            assert_eq!(
+                storage::get_pool_balance(&e, &pool_2).tokens,
+                200_000_0000000
+            );
+            assert_eq!(
+                storage::get_pool_balance(&e, &pool_3).tokens,
+                500_000_0000000
+            );
+            assert_eq!(
+                blnd_token_client.allowance(&backstop, &pool_1),
+                154_555_0000000
+            );
+            assert_eq!(
+                blnd_token_client.allowance(&backstop, &pool_2),
+                12_096_0000000
+            );
+            assert_eq!(
+                blnd_token_client.allowance(&backstop, &pool_3),
+                151_200_0000000
+            );
 
-        let pool_1 = Address::generate(&e);
+            // validate backstop emissions
 
-        let reward_zone: Vec<Address> = vec![&e];
+            let new_pool_1_data = storage::get_backstop_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(new_pool_1_data.eps, 0_21016534391534);
+            assert_eq!(new_pool_1_data.expiration, 1713139200 + 7 * 24 * 60 * 60);
+            assert_eq!(new_pool_1_data.index, 9494910000000);
+            assert_eq!(new_pool_1_data.last_time, 1713139200);
 
-        e.as_contract(&backstop, || {
-            storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
-            storage::set_reward_zone(&e, &reward_zone);
-            storage::set_pool_balance(
-                &e,
-                &pool_1,
-                &PoolBalance {
-                    tokens: 300_000_0000000,
-                    shares: 200_000_0000000,
-                    q4w: 0,
-                },
-            );
+            let new_pool_2_data = storage::get_backstop_emis_data(&e, &pool_2).unwrap_optimized();
+            assert_eq!(new_pool_2_data.eps, 0_18000000000000);
+            assert_eq!(new_pool_2_data.expiration, 1713139200 + 7 * 24 * 60 * 60);
+            assert_eq!(new_pool_2_data.index, 4532340000000);
+            assert_eq!(new_pool_2_data.last_time, 1713139200);
 
-            distribute(&e);
+            let new_pool_3_data = storage::get_backstop_emis_data(&e, &pool_3).unwrap_optimized();
+            assert_eq!(new_pool_3_data.eps, 0_25000000000000);
+            assert_eq!(new_pool_3_data.expiration, 1713139200 + 7 * 24 * 60 * 60);
+            assert_eq!(new_pool_3_data.index, 0);
+            assert_eq!(new_pool_3_data.last_time, 1713139200);
         });
     }
 
     #[test]
     #[should_panic(expected = "Error(Contract, #1000)")]
-    fn test_distribute_too_soon() {
+    fn test_set_backstop_emission_share_over_one() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+        let pool = Address::generate(&e);
+
+        e.as_contract(&backstop, || {
+            set_backstop_emission_share(&e, &pool, 1_0000001);
+        });
+    }
+
+    #[test]
+    fn test_gulp_emissions_over_max_defers_remainder() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
@@ -618,8 +1122,8 @@ mod tests {
         });
 
         let backstop = create_backstop(&e);
-        create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
-        let emitter_distro_time = 1713139200 - 1000;
+        let emitter_distro_time = 1713139200 - 10;
+        let blnd_token_client = create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
         create_emitter(
             &e,
             &backstop,
@@ -627,77 +1131,65 @@ mod tests {
             &Address::generate(&e),
             emitter_distro_time,
         );
-        let blnd_token_client = create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
-
         let pool_1 = Address::generate(&e);
-        let pool_2 = Address::generate(&e);
-        let pool_3 = Address::generate(&e);
-        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
-
-        // setup pool 1 to have ongoing emissions
-
-        let pool_1_emissions_data = BackstopEmissionData {
-            expiration: 1713139200 + 1000,
-            eps: 0_1000000,
-            index: 887766,
-            last_time: 1713139200 - 12345,
-        };
-
-        // setup pool 2 to have expired emissions
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone()];
 
-        let pool_2_emissions_data = BackstopEmissionData {
-            expiration: 1713139200 - 12345,
-            eps: 0_0500000,
-            index: 453234,
-            last_time: 1713139200 - 12345,
-        };
-        // setup pool 3 to have no emissions
         e.as_contract(&backstop, || {
-            storage::set_last_distribution_time(&e, &(emitter_distro_time - 59 * 60));
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - 7 * 24 * 60 * 60));
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_backstop_emis_data(&e, &pool_1, &pool_1_emissions_data);
-            blnd_token_client.approve(&backstop, &pool_1, &100_123_0000000, &e.ledger().sequence());
-
-            storage::set_backstop_emis_data(&e, &pool_2, &pool_2_emissions_data);
-            storage::set_pool_balance(
+            storage::set_rz_emis_data(
                 &e,
                 &pool_1,
-                &PoolBalance {
-                    tokens: 300_000_0000000,
-                    shares: 200_000_0000000,
-                    q4w: 0,
-                },
-            );
-            storage::set_pool_balance(
-                &e,
-                &pool_2,
-                &PoolBalance {
-                    tokens: 200_000_0000000,
-                    shares: 150_000_0000000,
-                    q4w: 0,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 0,
                 },
             );
             storage::set_pool_balance(
                 &e,
-                &pool_3,
+                &pool_1,
                 &PoolBalance {
-                    tokens: 500_000_0000000,
-                    shares: 600_000_0000000,
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
                     q4w: 0,
                 },
             );
+            set_max_gulp_emissions(&e, &pool_1, 100_000_0000000);
 
             distribute(&e);
+            update_rz_emis_data(&e, &pool_1, false);
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(rz_emis_data.accrued, 604_800_0000000);
+
+            assert_eq!(
+                pending_emissions(&e, &pool_1),
+                (70_000_0000000, 30_000_0000000)
+            );
+            let result = gulp_emissions(&e, &pool_1);
+            assert_eq!(result, (70_000_0000000, 30_000_0000000));
+            assert_eq!(
+                blnd_token_client.allowance(&backstop, &pool_1),
+                30_000_0000000
+            );
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(rz_emis_data.index, storage::get_rz_emission_index(&e));
+            assert_eq!(rz_emis_data.accrued, 504_800_0000000);
+
+            // deferred emissions are gulped on the next call
+            let result = gulp_emissions(&e, &pool_1);
+            assert_eq!(result, (70_000_0000000, 30_000_0000000));
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(rz_emis_data.accrued, 404_800_0000000);
         });
     }
 
     #[test]
-    fn test_distribute_gulp_no_overflow() {
+    fn test_gulp_emissions_disabled_pauses_accrual() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
         e.ledger().set(LedgerInfo {
-            timestamp: 1713139200 + 10_000_000_000,
+            timestamp: 1713139200,
             protocol_version: 22,
             sequence_number: 0,
             network_id: Default::default(),
@@ -708,23 +1200,11 @@ mod tests {
         });
 
         let backstop = create_backstop(&e);
-        let (blnd_address, _) = create_blnd_token(&e, &backstop, &Address::generate(&e));
-
-        // Distribute 1 trillion tokens to 1 backstop token
-        let emitter_distro_time = 1713139200 + 10_000_000_000;
-        create_emitter(
-            &e,
-            &backstop,
-            &Address::generate(&e),
-            &blnd_address,
-            emitter_distro_time,
-        );
+        let blnd_token_client = create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
         let pool_1 = Address::generate(&e);
         let reward_zone: Vec<Address> = vec![&e, pool_1.clone()];
 
         e.as_contract(&backstop, || {
-            storage::set_backfill_status(&e, &false);
-            storage::set_last_distribution_time(&e, &(&1713139200));
             storage::set_reward_zone(&e, &reward_zone);
             storage::set_rz_emis_data(
                 &e,
@@ -738,50 +1218,83 @@ mod tests {
                 &e,
                 &pool_1,
                 &PoolBalance {
-                    tokens: 1_0000000,
-                    shares: 1_0000000,
+                    tokens: 100_000_0000000,
+                    shares: 100_000_0000000,
                     q4w: 0,
                 },
             );
-
-            let gulp_index = storage::get_rz_emission_index(&e);
-            assert_eq!(gulp_index, 0);
+            storage::set_rz_emission_index(&e, &0_10000000000000);
+
+            // disabling checkpoints the emissions accrued so far
+            set_pool_emissions_enabled(&e, &pool_1, false);
+            assert!(!storage::get_pool_emissions_enabled(&e, &pool_1));
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(rz_emis_data.index, 0_10000000000000);
+            assert_eq!(rz_emis_data.accrued, 10_000_0000000);
+
+            // a disabled pool does not accrue or gulp emissions, but keeps its index current
+            storage::set_rz_emission_index(&e, &0_20000000000000);
+            assert_eq!(pending_emissions(&e, &pool_1), (0, 0));
+            assert_eq!(gulp_emissions(&e, &pool_1), (0, 0));
+            assert_eq!(blnd_token_client.allowance(&backstop, &pool_1), 0);
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(rz_emis_data.index, 0_20000000000000);
+            assert_eq!(rz_emis_data.accrued, 10_000_0000000);
+            assert_eq!(storage::get_reward_zone(&e), reward_zone);
+
+            // re-enabling resumes accrual from the current index
+            set_pool_emissions_enabled(&e, &pool_1, true);
+            storage::set_rz_emission_index(&e, &0_30000000000000);
+            assert_eq!(
+                pending_emissions(&e, &pool_1),
+                (14_000_0000000, 6_000_0000000)
+            );
+            let result = gulp_emissions(&e, &pool_1);
+            assert_eq!(result, (14_000_0000000, 6_000_0000000));
+            assert_eq!(
+                blnd_token_client.allowance(&backstop, &pool_1),
+                6_000_0000000
+            );
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(rz_emis_data.index, 0_30000000000000);
+            assert_eq!(rz_emis_data.accrued, 0);
         });
-        // Distribute 1 trillion tokens to 1 backstop token
-        for _ in 0..100 {
-            e.as_contract(&backstop, || {
-                distribute(&e);
-                gulp_emissions(&e, &pool_1);
-            });
-            e.ledger().set(LedgerInfo {
-                timestamp: e.ledger().timestamp() + 10_000_000_000,
-                protocol_version: 22,
-                sequence_number: 0,
-                network_id: Default::default(),
-                base_reserve: 10,
-                min_temp_entry_ttl: 10,
-                min_persistent_entry_ttl: 10,
-                max_entry_ttl: 3110400,
-            });
-            create_emitter(
-                &e,
-                &backstop,
-                &Address::generate(&e),
-                &blnd_address,
-                e.ledger().timestamp() + 10_000_000_000,
+    }
+
+    #[test]
+    fn test_set_max_gulp_emissions_zero_removes_cap() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+        let pool_1 = Address::generate(&e);
+
+        e.as_contract(&backstop, || {
+            set_max_gulp_emissions(&e, &pool_1, 100_000_0000000);
+            assert_eq!(
+                storage::get_max_gulp_emissions(&e, &pool_1),
+                Some(100_000_0000000)
             );
-        }
+
+            set_max_gulp_emissions(&e, &pool_1, 0);
+            assert_eq!(storage::get_max_gulp_emissions(&e, &pool_1), None);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_set_max_gulp_emissions_negative() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+        let pool_1 = Address::generate(&e);
+
         e.as_contract(&backstop, || {
-            let gulp_index = storage::get_rz_emission_index(&e);
-            assert_eq!(gulp_index, 101000000000000000000000000);
-            // backfill status remains unchanged if false
-            let backfill_status = storage::get_backfill_status(&e);
-            assert_eq!(backfill_status, Some(false));
+            set_max_gulp_emissions(&e, &pool_1, -1);
         });
     }
 
+    /********** pending_emissions **********/
+
     #[test]
-    fn test_distribute_no_last_dist_time() {
+    fn test_pending_emissions_matches_gulp() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
@@ -798,6 +1311,7 @@ mod tests {
 
         let backstop = create_backstop(&e);
         let emitter_distro_time = 1713139200 - 10;
+        let blnd_token_client = create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
         create_emitter(
             &e,
             &backstop,
@@ -805,21 +1319,37 @@ mod tests {
             &Address::generate(&e),
             emitter_distro_time,
         );
-
         let pool_1 = Address::generate(&e);
         let pool_2 = Address::generate(&e);
         let pool_3 = Address::generate(&e);
-        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone()];
 
         e.as_contract(&backstop, || {
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - 7 * 24 * 60 * 60));
             storage::set_reward_zone(&e, &reward_zone);
+            storage::set_rz_emis_data(
+                &e,
+                &pool_1,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 1_0000000,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &pool_2,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 0,
+                },
+            );
             storage::set_pool_balance(
                 &e,
                 &pool_1,
                 &PoolBalance {
                     tokens: 300_000_0000000,
                     shares: 200_000_0000000,
-                    q4w: 0,
+                    q4w: 20_000_0000000,
                 },
             );
             storage::set_pool_balance(
@@ -831,26 +1361,37 @@ mod tests {
                     q4w: 0,
                 },
             );
-            storage::set_pool_balance(
-                &e,
-                &pool_3,
-                &PoolBalance {
-                    tokens: 500_000_0000000,
-                    shares: 600_000_0000000,
-                    q4w: 0,
-                },
-            );
 
-            let new_emissions = distribute(&e);
+            distribute(&e);
 
-            assert_eq!(new_emissions, 0);
-            let last_distro_time = storage::get_last_distribution_time(&e);
-            assert_eq!(last_distro_time, emitter_distro_time);
+            let pending_1 = pending_emissions(&e, &pool_1);
+            let pending_2 = pending_emissions(&e, &pool_2);
+            // pool 3 is not in the reward zone
+            assert_eq!(pending_emissions(&e, &pool_3), (0, 0));
+
+            // no state was changed
+            let pool_1_rz_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(pool_1_rz_data.index, 0);
+            assert_eq!(pool_1_rz_data.accrued, 1_0000000);
+            assert_eq!(blnd_token_client.allowance(&backstop, &pool_1), 0);
+            assert!(storage::get_backstop_emis_data(&e, &pool_1).is_none());
+
+            assert_eq!(gulp_emissions(&e, &pool_1), pending_1);
+            assert_eq!(gulp_emissions(&e, &pool_2), pending_2);
+            assert_eq!(blnd_token_client.allowance(&backstop, &pool_1), pending_1.1);
+            assert_eq!(blnd_token_client.allowance(&backstop, &pool_2), pending_2.1);
+            assert!(pending_1.0 > 0);
+            assert!(pending_2.0 > 0);
+
+            // nothing is pending after a gulp
+            assert_eq!(pending_emissions(&e, &pool_1), (0, 0));
         });
     }
 
+    /********** distribute **********/
+
     #[test]
-    fn test_distribute_backfill_emissions() {
+    fn test_distribute() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
@@ -865,12 +1406,11 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let v1_backstop = create_backstop(&e);
         let backstop = create_backstop(&e);
         let emitter_distro_time = 1713139200 - 10;
         create_emitter(
             &e,
-            &v1_backstop,
+            &backstop,
             &Address::generate(&e),
             &Address::generate(&e),
             emitter_distro_time,
@@ -880,13 +1420,8 @@ mod tests {
         let pool_2 = Address::generate(&e);
         let pool_3 = Address::generate(&e);
         let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
-        let start_backfilled_emissions = 1_000_000 * SCALAR_7;
-        let rz_emis_index: i128 = 1_0000000_0000000;
 
         e.as_contract(&backstop, || {
-            storage::set_backfill_status(&e, &true);
-            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
-            storage::set_rz_emission_index(&e, &rz_emis_index);
             storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
             storage::set_reward_zone(&e, &reward_zone);
             storage::set_pool_balance(
@@ -917,24 +1452,27 @@ mod tests {
                 },
             );
 
-            distribute(&e);
+            let result = distribute(&e);
+            assert_eq!(result.new_emissions, 60 * 60 * 24 * SCALAR_7);
+            assert_eq!(result.is_backfill, false);
+            assert_eq!(result.was_reset, false);
+            assert_eq!(result.new_index, 8640000000000);
 
             let gulp_index = storage::get_rz_emission_index(&e);
-            assert_eq!(gulp_index, rz_emis_index + 8641000000000);
+            assert_eq!(gulp_index, 8640000000000);
             let last_distro_time = storage::get_last_distribution_time(&e);
-            assert_eq!(last_distro_time, e.ledger().timestamp());
+            assert_eq!(last_distro_time, emitter_distro_time);
             let backfilled_emissions = storage::get_backfill_emissions(&e);
-            assert_eq!(
-                backfilled_emissions,
-                start_backfilled_emissions + (60 * 60 * 24 + 10) * SCALAR_7
-            );
-            let is_backfill = storage::get_backfill_status(&e);
-            assert_eq!(is_backfill, Some(true));
+            assert_eq!(backfilled_emissions, 0);
+            // backfill status remains unchanged if not set
+            let backfill_status = storage::get_backfill_status(&e);
+            assert_eq!(backfill_status, None);
         });
     }
 
     #[test]
-    fn test_distribute_backfill_emissions_first_call() {
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_distribute_rz_fully_queued() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
@@ -949,12 +1487,11 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let v1_backstop = create_backstop(&e);
         let backstop = create_backstop(&e);
         let emitter_distro_time = 1713139200 - 10;
         create_emitter(
             &e,
-            &v1_backstop,
+            &backstop,
             &Address::generate(&e),
             &Address::generate(&e),
             emitter_distro_time,
@@ -962,10 +1499,10 @@ mod tests {
 
         let pool_1 = Address::generate(&e);
         let pool_2 = Address::generate(&e);
-        let pool_3 = Address::generate(&e);
-        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone()];
 
         e.as_contract(&backstop, || {
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
             storage::set_reward_zone(&e, &reward_zone);
             storage::set_pool_balance(
                 &e,
@@ -973,7 +1510,7 @@ mod tests {
                 &PoolBalance {
                     tokens: 300_000_0000000,
                     shares: 200_000_0000000,
-                    q4w: 0,
+                    q4w: 200_000_0000000,
                 },
             );
             storage::set_pool_balance(
@@ -982,35 +1519,17 @@ mod tests {
                 &PoolBalance {
                     tokens: 200_000_0000000,
                     shares: 150_000_0000000,
-                    q4w: 0,
-                },
-            );
-            storage::set_pool_balance(
-                &e,
-                &pool_3,
-                &PoolBalance {
-                    tokens: 500_000_0000000,
-                    shares: 600_000_0000000,
-                    q4w: 0,
+                    q4w: 150_000_0000000,
                 },
             );
 
             distribute(&e);
-
-            let gulp_index = storage::get_rz_emission_index(&e);
-            assert_eq!(gulp_index, 0);
-            let last_distro_time = storage::get_last_distribution_time(&e);
-            assert_eq!(last_distro_time, e.ledger().timestamp());
-            let backfilled_emissions = storage::get_backfill_emissions(&e);
-            assert_eq!(backfilled_emissions, 0);
-            let is_backfill = storage::get_backfill_status(&e);
-            assert_eq!(is_backfill, Some(true));
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1010)")]
-    fn test_distribute_backfill_emissions_over_max() {
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_distribute_empty_rz() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
@@ -1025,27 +1544,21 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let v1_backstop = create_backstop(&e);
         let backstop = create_backstop(&e);
         let emitter_distro_time = 1713139200 - 10;
         create_emitter(
             &e,
-            &v1_backstop,
+            &backstop,
             &Address::generate(&e),
             &Address::generate(&e),
             emitter_distro_time,
         );
 
         let pool_1 = Address::generate(&e);
-        let pool_2 = Address::generate(&e);
-        let pool_3 = Address::generate(&e);
-        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
-        let start_backfilled_emissions = MAX_BACKFILLED_EMISSIONS - (60 * 60 * 24 + 9) * SCALAR_7;
-        let rz_emis_index: i128 = 100_0000000_0000000;
+
+        let reward_zone: Vec<Address> = vec![&e];
 
         e.as_contract(&backstop, || {
-            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
-            storage::set_rz_emission_index(&e, &rz_emis_index);
             storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
             storage::set_reward_zone(&e, &reward_zone);
             storage::set_pool_balance(
@@ -1057,31 +1570,14 @@ mod tests {
                     q4w: 0,
                 },
             );
-            storage::set_pool_balance(
-                &e,
-                &pool_2,
-                &PoolBalance {
-                    tokens: 200_000_0000000,
-                    shares: 150_000_0000000,
-                    q4w: 0,
-                },
-            );
-            storage::set_pool_balance(
-                &e,
-                &pool_3,
-                &PoolBalance {
-                    tokens: 500_000_0000000,
-                    shares: 600_000_0000000,
-                    q4w: 0,
-                },
-            );
 
             distribute(&e);
         });
     }
 
     #[test]
-    fn test_distribute_backfill_emissions_over_needs_reset() {
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_distribute_too_soon() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
 
@@ -1097,7 +1593,8 @@ mod tests {
         });
 
         let backstop = create_backstop(&e);
-        let emitter_distro_time = 1713139200 - 10;
+        create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
+        let emitter_distro_time = 1713139200 - 1000;
         create_emitter(
             &e,
             &backstop,
@@ -1105,21 +1602,38 @@ mod tests {
             &Address::generate(&e),
             emitter_distro_time,
         );
+        let blnd_token_client = create_blnd_token(&e, &backstop, &Address::generate(&e)).1;
 
         let pool_1 = Address::generate(&e);
         let pool_2 = Address::generate(&e);
         let pool_3 = Address::generate(&e);
         let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
-        let start_backfilled_emissions = 1_000_000 * SCALAR_7;
-        let rz_emis_index: i128 = 1_0000000_0000000;
-        let last_distro_time = 1713139200 - 10000;
 
+        // setup pool 1 to have ongoing emissions
+
+        let pool_1_emissions_data = BackstopEmissionData {
+            expiration: 1713139200 + 1000,
+            eps: 0_1000000,
+            index: 887766,
+            last_time: 1713139200 - 12345,
+        };
+
+        // setup pool 2 to have expired emissions
+
+        let pool_2_emissions_data = BackstopEmissionData {
+            expiration: 1713139200 - 12345,
+            eps: 0_0500000,
+            index: 453234,
+            last_time: 1713139200 - 12345,
+        };
+        // setup pool 3 to have no emissions
         e.as_contract(&backstop, || {
-            storage::set_backfill_status(&e, &true);
-            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
-            storage::set_rz_emission_index(&e, &rz_emis_index);
-            storage::set_last_distribution_time(&e, &last_distro_time);
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - 59 * 60));
             storage::set_reward_zone(&e, &reward_zone);
+            storage::set_backstop_emis_data(&e, &pool_1, &pool_1_emissions_data);
+            blnd_token_client.approve(&backstop, &pool_1, &100_123_0000000, &e.ledger().sequence());
+
+            storage::set_backstop_emis_data(&e, &pool_2, &pool_2_emissions_data);
             storage::set_pool_balance(
                 &e,
                 &pool_1,
@@ -1149,74 +1663,106 @@ mod tests {
             );
 
             distribute(&e);
-
-            let gulp_index = storage::get_rz_emission_index(&e);
-            assert_eq!(gulp_index, rz_emis_index);
-            let last_distro_time = storage::get_last_distribution_time(&e);
-            assert_eq!(last_distro_time, emitter_distro_time);
-            let backfilled_emissions = storage::get_backfill_emissions(&e);
-            assert_eq!(backfilled_emissions, start_backfilled_emissions);
-            let is_backfill = storage::get_backfill_status(&e);
-            assert_eq!(is_backfill, Some(false));
         });
     }
 
-    /********** add_to_reward_zone **********/
-
     #[test]
-    fn test_add_to_rz_empty_adds_pool() {
+    fn test_distribute_gulp_no_overflow() {
         let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
         e.ledger().set(LedgerInfo {
-            timestamp: 1713139200,
+            timestamp: 1713139200 + 10_000_000_000,
             protocol_version: 22,
             sequence_number: 0,
-            base_reserve: 10,
             network_id: Default::default(),
+            base_reserve: 10,
             min_temp_entry_ttl: 10,
             min_persistent_entry_ttl: 10,
             max_entry_ttl: 3110400,
         });
-        e.mock_all_auths();
 
-        let bombadil = Address::generate(&e);
-        let backstop_id = create_backstop(&e);
-        let to_add = Address::generate(&e);
+        let backstop = create_backstop(&e);
+        let (blnd_address, _) = create_blnd_token(&e, &backstop, &Address::generate(&e));
 
-        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
-        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
-        create_comet_lp_pool_with_tokens_per_share(
+        // Distribute 1 trillion tokens to 1 backstop token
+        let emitter_distro_time = 1713139200 + 10_000_000_000;
+        create_emitter(
             &e,
-            &backstop_id,
-            &bombadil,
-            &blnd_id,
-            5_0000000,
-            &usdc_id,
-            0_1000000,
+            &backstop,
+            &Address::generate(&e),
+            &blnd_address,
+            emitter_distro_time,
         );
+        let pool_1 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone()];
 
-        e.as_contract(&backstop_id, || {
+        e.as_contract(&backstop, || {
+            storage::set_backfill_status(&e, &false);
+            storage::set_last_distribution_time(&e, &(&1713139200));
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_rz_emis_data(
+                &e,
+                &pool_1,
+                &RzEmissionData {
+                    index: 0,
+                    accrued: 0,
+                },
+            );
             storage::set_pool_balance(
                 &e,
-                &to_add,
+                &pool_1,
                 &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_000_0000000,
-                    q4w: 1_000_0000000,
+                    tokens: 1_0000000,
+                    shares: 1_0000000,
+                    q4w: 0,
                 },
             );
 
-            add_to_reward_zone(&e, to_add.clone(), None);
-            let actual_rz = storage::get_reward_zone(&e);
-            let expected_rz: Vec<Address> = vec![&e, to_add];
-            assert_eq!(actual_rz, expected_rz);
+            // seed a large reward zone index so the first gulp accrues ~1 trillion tokens
+            storage::set_rz_emission_index(&e, &100000000000000000000000000);
+        });
+        for _ in 0..100 {
+            e.as_contract(&backstop, || {
+                distribute(&e);
+                gulp_emissions(&e, &pool_1);
+            });
+            e.ledger().set(LedgerInfo {
+                timestamp: e.ledger().timestamp() + 10_000_000_000,
+                protocol_version: 22,
+                sequence_number: 0,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 10,
+                min_persistent_entry_ttl: 10,
+                max_entry_ttl: 3110400,
+            });
+            create_emitter(
+                &e,
+                &backstop,
+                &Address::generate(&e),
+                &blnd_address,
+                e.ledger().timestamp() + 10_000_000_000,
+            );
+        }
+        e.as_contract(&backstop, || {
+            let gulp_index = storage::get_rz_emission_index(&e);
+            assert_eq!(gulp_index, 100315360000000000000000000);
+            let rz_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(rz_emis_data.index, gulp_index);
+            // backfill status remains unchanged if false
+            let backfill_status = storage::get_backfill_status(&e);
+            assert_eq!(backfill_status, Some(false));
         });
     }
 
     #[test]
-    fn test_add_to_rz_before_max_50() {
+    fn test_distribute_max_distribution_gap() {
         let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
         e.ledger().set(LedgerInfo {
-            timestamp: 1713139200 - 100000,
+            timestamp: 1713139200,
             protocol_version: 22,
             sequence_number: 0,
             network_id: Default::default(),
@@ -1225,65 +1771,1116 @@ mod tests {
             min_persistent_entry_ttl: 10,
             max_entry_ttl: 3110400,
         });
-        e.mock_all_auths();
-
-        let bombadil = Address::generate(&e);
-        let backstop_id = create_backstop(&e);
-        let to_add = Address::generate(&e);
 
-        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
-        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
-        create_comet_lp_pool_with_tokens_per_share(
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
             &e,
-            &backstop_id,
-            &bombadil,
-            &blnd_id,
-            5_0000000,
-            &usdc_id,
-            0_1000000,
+            &backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
         );
-        let mut reward_zone: Vec<Address> = vec![
-            &e,
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-        ];
 
-        e.as_contract(&backstop_id, || {
+        let pool_1 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone()];
+        let year: u64 = 365 * 24 * 60 * 60;
+        // 3 years and 1 day since the last distribution
+        let last_distro_time = emitter_distro_time - 3 * year - 24 * 60 * 60;
+
+        e.as_contract(&backstop, || {
+            storage::set_last_distribution_time(&e, &last_distro_time);
             storage::set_reward_zone(&e, &reward_zone);
             storage::set_pool_balance(
                 &e,
-                &to_add,
+                &pool_1,
                 &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_000_0000000,
-                    q4w: 1_000_0000000,
+                    tokens: 1_000_000_0000000,
+                    shares: 1_000_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            let mut total_emissions: i128 = 0;
+            for i in 1..=3 {
+                let result = distribute(&e);
+                assert_eq!(result.new_emissions, i128(year) * SCALAR_7);
+                assert_eq!(result.new_index, 3153600000000000 * i);
+                assert_eq!(
+                    storage::get_last_distribution_time(&e),
+                    last_distro_time + year * (i as u64)
+                );
+                total_emissions += result.new_emissions;
+            }
+
+            let result = distribute(&e);
+            assert_eq!(result.new_emissions, 24 * 60 * 60 * SCALAR_7);
+            assert_eq!(result.new_index, 9469440000000000);
+            total_emissions += result.new_emissions;
+
+            // all emissions were distributed
+            assert_eq!(
+                total_emissions,
+                i128(emitter_distro_time - last_distro_time) * SCALAR_7
+            );
+            assert_eq!(storage::get_last_distribution_time(&e), emitter_distro_time);
+            assert_eq!(storage::get_rz_emission_index(&e), 9469440000000000);
+        });
+    }
+
+    #[test]
+    fn test_distribute_no_last_dist_time() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
+            &e,
+            &backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
+        );
+
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let pool_3 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+
+        e.as_contract(&backstop, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    tokens: 200_000_0000000,
+                    shares: 150_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_3,
+                &PoolBalance {
+                    tokens: 500_000_0000000,
+                    shares: 600_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            let result = distribute(&e);
+
+            assert_eq!(result.new_emissions, 0);
+            assert_eq!(result.is_backfill, false);
+            assert_eq!(result.was_reset, true);
+            assert_eq!(result.new_index, 0);
+            let last_distro_time = storage::get_last_distribution_time(&e);
+            assert_eq!(last_distro_time, emitter_distro_time);
+        });
+    }
+
+    #[test]
+    fn test_distribute_backfill_emissions() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let v1_backstop = create_backstop(&e);
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
+            &e,
+            &v1_backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
+        );
+
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let pool_3 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+        let start_backfilled_emissions = 1_000_000 * SCALAR_7;
+        let rz_emis_index: i128 = 1_0000000_0000000;
+
+        e.as_contract(&backstop, || {
+            storage::set_backfill_status(&e, &true);
+            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
+            storage::set_rz_emission_index(&e, &rz_emis_index);
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    tokens: 200_000_0000000,
+                    shares: 150_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_3,
+                &PoolBalance {
+                    tokens: 500_000_0000000,
+                    shares: 600_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            let result = distribute(&e);
+            assert_eq!(result.new_emissions, (60 * 60 * 24 + 10) * SCALAR_7);
+            assert_eq!(result.is_backfill, true);
+            assert_eq!(result.was_reset, false);
+            assert_eq!(result.new_index, rz_emis_index + 8641000000000);
+
+            let gulp_index = storage::get_rz_emission_index(&e);
+            assert_eq!(gulp_index, rz_emis_index + 8641000000000);
+            let last_distro_time = storage::get_last_distribution_time(&e);
+            assert_eq!(last_distro_time, e.ledger().timestamp());
+            let backfilled_emissions = storage::get_backfill_emissions(&e);
+            assert_eq!(
+                backfilled_emissions,
+                start_backfilled_emissions + (60 * 60 * 24 + 10) * SCALAR_7
+            );
+            let is_backfill = storage::get_backfill_status(&e);
+            assert_eq!(is_backfill, Some(true));
+        });
+    }
+
+    #[test]
+    fn test_distribute_backfill_emissions_first_call() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let v1_backstop = create_backstop(&e);
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
+            &e,
+            &v1_backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
+        );
+
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let pool_3 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+
+        e.as_contract(&backstop, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    tokens: 200_000_0000000,
+                    shares: 150_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_3,
+                &PoolBalance {
+                    tokens: 500_000_0000000,
+                    shares: 600_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            let result = distribute(&e);
+            assert_eq!(result.new_emissions, 0);
+            assert_eq!(result.is_backfill, true);
+            assert_eq!(result.was_reset, true);
+            assert_eq!(result.new_index, 0);
+
+            let gulp_index = storage::get_rz_emission_index(&e);
+            assert_eq!(gulp_index, 0);
+            let last_distro_time = storage::get_last_distribution_time(&e);
+            assert_eq!(last_distro_time, e.ledger().timestamp());
+            let backfilled_emissions = storage::get_backfill_emissions(&e);
+            assert_eq!(backfilled_emissions, 0);
+            let is_backfill = storage::get_backfill_status(&e);
+            assert_eq!(is_backfill, Some(true));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1010)")]
+    fn test_distribute_backfill_emissions_over_max() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let v1_backstop = create_backstop(&e);
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
+            &e,
+            &v1_backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
+        );
+
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let pool_3 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+        let start_backfilled_emissions = MAX_BACKFILLED_EMISSIONS - (60 * 60 * 24 + 9) * SCALAR_7;
+        let rz_emis_index: i128 = 100_0000000_0000000;
+
+        e.as_contract(&backstop, || {
+            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
+            storage::set_rz_emission_index(&e, &rz_emis_index);
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    tokens: 200_000_0000000,
+                    shares: 150_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_3,
+                &PoolBalance {
+                    tokens: 500_000_0000000,
+                    shares: 600_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            distribute(&e);
+        });
+    }
+
+    #[test]
+    fn test_distribute_backfill_emissions_to_max() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let v1_backstop = create_backstop(&e);
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
+            &e,
+            &v1_backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
+        );
+
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let pool_3 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+        let start_backfilled_emissions = MAX_BACKFILLED_EMISSIONS - (60 * 60 * 24 + 10) * SCALAR_7;
+        let rz_emis_index: i128 = 100_0000000_0000000;
+
+        e.as_contract(&backstop, || {
+            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
+            storage::set_rz_emission_index(&e, &rz_emis_index);
+            storage::set_last_distribution_time(&e, &(emitter_distro_time - (60 * 60 * 24)));
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    tokens: 200_000_0000000,
+                    shares: 150_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_3,
+                &PoolBalance {
+                    tokens: 500_000_0000000,
+                    shares: 600_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            assert_eq!(get_backfill_remaining(&e), (60 * 60 * 24 + 10) * SCALAR_7);
+
+            distribute(&e);
+
+            assert_eq!(
+                storage::get_backfill_emissions(&e),
+                MAX_BACKFILLED_EMISSIONS
+            );
+            assert_eq!(get_backfill_remaining(&e), 0);
+        });
+    }
+
+    #[test]
+    fn test_distribute_clamped_backfill_emissions_over_max() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let v1_backstop = create_backstop(&e);
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
+            &e,
+            &v1_backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
+        );
+
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone()];
+        let start_backfilled_emissions = MAX_BACKFILLED_EMISSIONS - 60 * 60 * 12 * SCALAR_7;
+        let rz_emis_index: i128 = 100_0000000_0000000;
+
+        e.as_contract(&backstop, || {
+            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
+            storage::set_rz_emission_index(&e, &rz_emis_index);
+            storage::set_last_distribution_time(&e, &(1713139200 - (60 * 60 * 24)));
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    tokens: 132_000_0000000,
+                    shares: 150_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            // only half of the day's emissions fit within the backfill allotment
+            let result = distribute_clamped(&e);
+            assert_eq!(result.new_emissions, 60 * 60 * 12 * SCALAR_7);
+            assert_eq!(result.is_backfill, true);
+            assert_eq!(result.was_reset, false);
+            assert_eq!(result.new_index, rz_emis_index + 0_1000000_0000000);
+            assert_eq!(
+                storage::get_backfill_emissions(&e),
+                MAX_BACKFILLED_EMISSIONS
+            );
+            assert_eq!(storage::get_last_distribution_time(&e), 1713139200);
+
+            // any further distribution emits nothing
+            e.ledger().set_timestamp(1713139200 + 60 * 60 * 2);
+            let result = distribute_clamped(&e);
+            assert_eq!(result.new_emissions, 0);
+            assert_eq!(result.new_index, rz_emis_index + 0_1000000_0000000);
+            assert_eq!(
+                storage::get_backfill_emissions(&e),
+                MAX_BACKFILLED_EMISSIONS
+            );
+            assert_eq!(
+                storage::get_last_distribution_time(&e),
+                1713139200 + 60 * 60 * 2
+            );
+        });
+    }
+
+    /********** get_backfill_remaining **********/
+
+    #[test]
+    fn test_get_backfill_remaining() {
+        let e = Env::default();
+        let backstop = create_backstop(&e);
+
+        e.as_contract(&backstop, || {
+            assert_eq!(get_backfill_remaining(&e), MAX_BACKFILLED_EMISSIONS);
+
+            storage::set_backfill_emissions(&e, &(MAX_BACKFILLED_EMISSIONS - 1));
+            assert_eq!(get_backfill_remaining(&e), 1);
+
+            storage::set_backfill_emissions(&e, &MAX_BACKFILLED_EMISSIONS);
+            assert_eq!(get_backfill_remaining(&e), 0);
+
+            storage::set_backfill_emissions(&e, &(MAX_BACKFILLED_EMISSIONS + 1));
+            assert_eq!(get_backfill_remaining(&e), 0);
+        });
+    }
+
+    #[test]
+    fn test_distribute_backfill_emissions_over_needs_reset() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let backstop = create_backstop(&e);
+        let emitter_distro_time = 1713139200 - 10;
+        create_emitter(
+            &e,
+            &backstop,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            emitter_distro_time,
+        );
+
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let pool_3 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone(), pool_3.clone()];
+        let start_backfilled_emissions = 1_000_000 * SCALAR_7;
+        let rz_emis_index: i128 = 1_0000000_0000000;
+        let last_distro_time = 1713139200 - 10000;
+
+        e.as_contract(&backstop, || {
+            storage::set_backfill_status(&e, &true);
+            storage::set_backfill_emissions(&e, &start_backfilled_emissions);
+            storage::set_rz_emission_index(&e, &rz_emis_index);
+            storage::set_last_distribution_time(&e, &last_distro_time);
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    tokens: 300_000_0000000,
+                    shares: 200_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    tokens: 200_000_0000000,
+                    shares: 150_000_0000000,
+                    q4w: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &pool_3,
+                &PoolBalance {
+                    tokens: 500_000_0000000,
+                    shares: 600_000_0000000,
+                    q4w: 0,
+                },
+            );
+
+            let result = distribute(&e);
+            assert_eq!(result.new_emissions, 0);
+            assert_eq!(result.is_backfill, false);
+            assert_eq!(result.was_reset, true);
+            assert_eq!(result.new_index, rz_emis_index);
+
+            let gulp_index = storage::get_rz_emission_index(&e);
+            assert_eq!(gulp_index, rz_emis_index);
+            let last_distro_time = storage::get_last_distribution_time(&e);
+            assert_eq!(last_distro_time, emitter_distro_time);
+            let backfilled_emissions = storage::get_backfill_emissions(&e);
+            assert_eq!(backfilled_emissions, start_backfilled_emissions);
+            let is_backfill = storage::get_backfill_status(&e);
+            assert_eq!(is_backfill, Some(false));
+        });
+    }
+
+    /********** add_to_reward_zone **********/
+
+    #[test]
+    fn test_add_to_rz_empty_adds_pool() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            base_reserve: 10,
+            network_id: Default::default(),
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+
+        e.as_contract(&backstop_id, || {
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+
+            add_to_reward_zone(&e, to_add.clone(), None);
+            let actual_rz = storage::get_reward_zone(&e);
+            let expected_rz: Vec<Address> = vec![&e, to_add.clone()];
+            assert_eq!(actual_rz, expected_rz);
+
+            assert_eq!(
+                e.events().all().last_unchecked(),
+                (
+                    backstop_id.clone(),
+                    (Symbol::new(&e, "rw_zone_add"),).into_val(&e),
+                    (to_add, None::<Address>, 100_000_0000000i128, 0i128).into_val(&e)
+                )
+            );
+        });
+    }
+
+    #[test]
+    fn test_add_to_rz_before_max_50() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200 - 100000,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let mut reward_zone: Vec<Address> = vec![
+            &e,
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+        ];
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+
+            add_to_reward_zone(&e, to_add.clone(), None);
+            let actual_rz = storage::get_reward_zone(&e);
+            reward_zone.push_front(to_add);
+            assert_eq!(actual_rz, reward_zone);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1002)")]
+    fn test_add_to_rz_empty_pool_under_backstop_threshold() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            base_reserve: 10,
+            network_id: Default::default(),
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+
+        e.as_contract(&backstop_id, || {
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 30_000_0000000,
+                    tokens: 40_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            // storage::set_lp_token_val(&e, &(5_0000000, 0_1000000));
+
+            add_to_reward_zone(&e, to_add.clone(), None);
+            let actual_rz = storage::get_reward_zone(&e);
+            let expected_rz: Vec<Address> = vec![&e, to_add];
+            assert_eq!(actual_rz, expected_rz);
+        });
+    }
+
+    #[test]
+    fn test_add_to_rz_increases_size_over_time() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200 + (1 << 23),
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let mut reward_zone: Vec<Address> = vec![
+            &e,
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+        ];
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+
+            add_to_reward_zone(&e, to_add.clone(), None);
+            let actual_rz = storage::get_reward_zone(&e);
+            reward_zone.push_front(to_add);
+            assert_eq!(actual_rz, reward_zone);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1009)")]
+    fn test_add_to_rz_respects_max_size() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            // Allow enough time for 100 pools to be added
+            timestamp: 1713139200 + (1 << 23) * 100,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let mut reward_zone: Vec<Address> = vec![&e];
+        for _ in 0..50 {
+            reward_zone.push_back(Address::generate(&e));
+        }
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+
+            // This should fail due to the reward zone being full and not having a pool to remove
+            add_to_reward_zone(&e, to_add.clone(), None);
+        });
+    }
+
+    #[test]
+    fn test_add_to_rz_swap_happy_path() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let backstop_id = create_backstop(&e);
+        create_blnd_token(&e, &backstop_id, &Address::generate(&e));
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+        let to_remove = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let mut reward_zone: Vec<Address> = vec![&e];
+        for _ in 0..50 {
+            reward_zone.push_back(Address::generate(&e));
+        }
+        reward_zone.set(7, to_remove.clone());
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_001_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &to_remove,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_backstop_emis_data(
+                &e,
+                &to_remove,
+                &BackstopEmissionData {
+                    eps: 0_10000000000000,
+                    expiration: 1713139200 + 1000,
+                    index: 0,
+                    last_time: 1713139200 - 12345,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &to_remove,
+                &RzEmissionData {
+                    index: (1234 * SCALAR_7),
+                    accrued: 0,
                 },
             );
-
-            add_to_reward_zone(&e, to_add.clone(), None);
+            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove.clone()));
             let actual_rz = storage::get_reward_zone(&e);
-            reward_zone.push_front(to_add);
+            assert_eq!(actual_rz.len(), 50);
+            reward_zone.remove(7);
+            reward_zone.push_front(to_add.clone());
             assert_eq!(actual_rz, reward_zone);
+
+            let to_remove_emis_data = storage::get_rz_emis_data(&e, &to_remove).unwrap_optimized();
+            let to_add_emis_data = storage::get_rz_emis_data(&e, &to_add).unwrap_optimized();
+            assert_eq!(to_add_emis_data.index, 5678 * SCALAR_7);
+            assert_eq!(to_remove_emis_data.index, i128::MAX);
+            assert_eq!(storage::get_rz_entry_time(&e, &to_add), 1713139200);
+
+            assert_eq!(
+                e.events().all().last_unchecked(),
+                (
+                    backstop_id.clone(),
+                    (Symbol::new(&e, "rw_zone_add"),).into_val(&e),
+                    (
+                        to_add.clone(),
+                        Some(to_remove.clone()),
+                        100_001_0000000i128,
+                        100_000_0000000i128
+                    )
+                        .into_val(&e)
+                )
+            );
         });
     }
 
     #[test]
     #[should_panic(expected = "Error(Contract, #1002)")]
-    fn test_add_to_rz_empty_pool_under_backstop_threshold() {
+    fn test_add_to_rz_swap_not_enough_tokens() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
             protocol_version: 22,
             sequence_number: 0,
+            network_id: Default::default(),
             base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+        let to_remove = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let mut reward_zone: Vec<Address> = vec![&e];
+        for _ in 0..50 {
+            reward_zone.push_back(Address::generate(&e));
+        }
+        reward_zone.set(7, to_remove.clone());
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &to_remove,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove));
+        });
+    }
+
+    #[test]
+    fn test_add_to_rz_swap_tie_to_remove_below_threshold() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
             network_id: Default::default(),
+            base_reserve: 10,
             min_temp_entry_ttl: 10,
             min_persistent_entry_ttl: 10,
             max_entry_ttl: 3110400,
@@ -1293,6 +2890,7 @@ mod tests {
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
         let to_add = Address::generate(&e);
+        let to_remove = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
         let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
@@ -1305,31 +2903,207 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
+        let mut reward_zone: Vec<Address> = vec![&e];
+        for _ in 0..50 {
+            reward_zone.push_back(Address::generate(&e));
+        }
+        reward_zone.set(7, to_remove.clone());
 
         e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
             storage::set_pool_balance(
                 &e,
                 &to_add,
                 &PoolBalance {
-                    shares: 30_000_0000000,
-                    tokens: 40_000_0000000,
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
                     q4w: 1_000_0000000,
                 },
             );
-            // storage::set_lp_token_val(&e, &(5_0000000, 0_1000000));
+            storage::set_rz_emis_data(
+                &e,
+                &to_remove,
+                &RzEmissionData {
+                    index: (1234 * SCALAR_7),
+                    accrued: 0,
+                },
+            );
+            // most of "to_remove"'s backstop is queued for withdrawal, so it is below the threshold
+            storage::set_pool_balance(
+                &e,
+                &to_remove,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 89_000_0000000,
+                },
+            );
+            storage::set_rz_entry_time(&e, &to_remove, &(1713139200 - 1000));
 
-            add_to_reward_zone(&e, to_add.clone(), None);
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove.clone()));
             let actual_rz = storage::get_reward_zone(&e);
-            let expected_rz: Vec<Address> = vec![&e, to_add];
-            assert_eq!(actual_rz, expected_rz);
+            assert_eq!(actual_rz.len(), 50);
+            assert!(actual_rz.contains(&to_add));
+            assert!(!actual_rz.contains(&to_remove));
+        });
+    }
+
+    #[test]
+    fn test_add_to_rz_swap_tie_to_remove_over_tenure() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+        let to_remove = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let mut reward_zone: Vec<Address> = vec![&e];
+        for _ in 0..50 {
+            reward_zone.push_back(Address::generate(&e));
+        }
+        reward_zone.set(7, to_remove.clone());
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &to_remove,
+                &RzEmissionData {
+                    index: (1234 * SCALAR_7),
+                    accrued: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &to_remove,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_rz_entry_time(&e, &to_remove, &(1713139200 - RZ_TIE_MIN_TENURE));
+
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove.clone()));
+            let actual_rz = storage::get_reward_zone(&e);
+            assert!(actual_rz.contains(&to_add));
+            assert!(!actual_rz.contains(&to_remove));
+            assert_eq!(storage::get_rz_entry_time(&e, &to_add), 1713139200);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1002)")]
+    fn test_add_to_rz_swap_tie_to_remove_under_tenure() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_add = Address::generate(&e);
+        let to_remove = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let mut reward_zone: Vec<Address> = vec![&e];
+        for _ in 0..50 {
+            reward_zone.push_back(Address::generate(&e));
+        }
+        reward_zone.set(7, to_remove.clone());
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_pool_balance(
+                &e,
+                &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &to_remove,
+                &RzEmissionData {
+                    index: (1234 * SCALAR_7),
+                    accrued: 0,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &to_remove,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_000_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_rz_entry_time(&e, &to_remove, &(1713139200 - RZ_TIE_MIN_TENURE + 1));
+
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove.clone()));
         });
     }
 
     #[test]
-    fn test_add_to_rz_increases_size_over_time() {
+    #[should_panic(expected = "Error(Contract, #1011)")]
+    fn test_add_to_rz_swap_distribution_too_long_ago() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
-            timestamp: 1713139200 + (1 << 23),
+            timestamp: 1713139200,
             protocol_version: 22,
             sequence_number: 0,
             network_id: Default::default(),
@@ -1343,6 +3117,7 @@ mod tests {
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
         let to_add = Address::generate(&e);
+        let to_remove = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
         let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
@@ -1355,25 +3130,27 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
-        let mut reward_zone: Vec<Address> = vec![
-            &e,
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-        ];
+        let mut reward_zone: Vec<Address> = vec![&e];
+        for _ in 0..50 {
+            reward_zone.push_back(Address::generate(&e));
+        }
+        reward_zone.set(7, to_remove.clone());
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60 - 1));
             storage::set_pool_balance(
                 &e,
                 &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 100_001_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &to_remove,
                 &PoolBalance {
                     shares: 90_000_0000000,
                     tokens: 100_000_0000000,
@@ -1381,20 +3158,16 @@ mod tests {
                 },
             );
 
-            add_to_reward_zone(&e, to_add.clone(), None);
-            let actual_rz = storage::get_reward_zone(&e);
-            reward_zone.push_front(to_add);
-            assert_eq!(actual_rz, reward_zone);
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove));
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1009)")]
-    fn test_add_to_rz_respects_max_size() {
+    #[should_panic(expected = "Error(Contract, #1011)")]
+    fn test_add_to_rz_swap_distribution_too_long_ago_checked_first() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
-            // Allow enough time for 100 pools to be added
-            timestamp: 1713139200 + (1 << 23) * 100,
+            timestamp: 1713139200,
             protocol_version: 22,
             sequence_number: 0,
             network_id: Default::default(),
@@ -1408,6 +3181,7 @@ mod tests {
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
         let to_add = Address::generate(&e);
+        let to_remove = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
         let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
@@ -1424,11 +3198,22 @@ mod tests {
         for _ in 0..50 {
             reward_zone.push_back(Address::generate(&e));
         }
+
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60 - 1));
             storage::set_pool_balance(
                 &e,
                 &to_add,
+                &PoolBalance {
+                    shares: 90_000_0000000,
+                    tokens: 99_999_0000000,
+                    q4w: 1_000_0000000,
+                },
+            );
+            storage::set_pool_balance(
+                &e,
+                &to_remove,
                 &PoolBalance {
                     shares: 90_000_0000000,
                     tokens: 100_000_0000000,
@@ -1436,13 +3221,15 @@ mod tests {
                 },
             );
 
-            // This should fail due to the reward zone being full and not having a pool to remove
-            add_to_reward_zone(&e, to_add.clone(), None);
+            // "to_remove" is not in the reward zone and has more tokens than "to_add", but the
+            // stale distribution is rejected before either is validated
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove));
         });
     }
 
     #[test]
-    fn test_add_to_rz_swap_happy_path() {
+    #[should_panic(expected = "Error(Contract, #1002)")]
+    fn test_add_to_rz_to_remove_not_in_rz() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1454,9 +3241,6 @@ mod tests {
             min_persistent_entry_ttl: 10,
             max_entry_ttl: 3110400,
         });
-
-        let backstop_id = create_backstop(&e);
-        create_blnd_token(&e, &backstop_id, &Address::generate(&e));
         e.mock_all_auths();
 
         let bombadil = Address::generate(&e);
@@ -1479,11 +3263,10 @@ mod tests {
         for _ in 0..50 {
             reward_zone.push_back(Address::generate(&e));
         }
-        reward_zone.set(7, to_remove.clone());
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_last_distribution_time(&e, &(1713139200 - 24 * 60 * 60));
             storage::set_pool_balance(
                 &e,
                 &to_add,
@@ -1502,42 +3285,15 @@ mod tests {
                     q4w: 1_000_0000000,
                 },
             );
-            storage::set_backstop_emis_data(
-                &e,
-                &to_remove,
-                &BackstopEmissionData {
-                    eps: 0_10000000000000,
-                    expiration: 1713139200 + 1000,
-                    index: 0,
-                    last_time: 1713139200 - 12345,
-                },
-            );
-            storage::set_rz_emis_data(
-                &e,
-                &to_remove,
-                &RzEmissionData {
-                    index: (1234 * SCALAR_7),
-                    accrued: 0,
-                },
-            );
-            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
-            add_to_reward_zone(&e, to_add.clone(), Some(to_remove.clone()));
-            let actual_rz = storage::get_reward_zone(&e);
-            assert_eq!(actual_rz.len(), 50);
-            reward_zone.remove(7);
-            reward_zone.push_front(to_add.clone());
-            assert_eq!(actual_rz, reward_zone);
+            // storage::set_lp_token_val(&e, &(5_0000000, 0_1000000));
 
-            let to_remove_emis_data = storage::get_rz_emis_data(&e, &to_remove).unwrap_optimized();
-            let to_add_emis_data = storage::get_rz_emis_data(&e, &to_add).unwrap_optimized();
-            assert_eq!(to_add_emis_data.index, 5678 * SCALAR_7);
-            assert_eq!(to_remove_emis_data.index, i128::MAX);
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove));
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1002)")]
-    fn test_add_to_rz_swap_not_enough_tokens() {
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_add_to_rz_already_exists_panics() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1567,21 +3323,29 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
-        let mut reward_zone: Vec<Address> = vec![&e];
-        for _ in 0..50 {
-            reward_zone.push_back(Address::generate(&e));
-        }
-        reward_zone.set(7, to_remove.clone());
+        let reward_zone: Vec<Address> = vec![
+            &e,
+            Address::generate(&e),
+            to_remove.clone(),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            Address::generate(&e),
+            to_add.clone(),
+            Address::generate(&e),
+            Address::generate(&e),
+        ];
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_last_distribution_time(&e, &(1713139200 - 24 * 60 * 60));
             storage::set_pool_balance(
                 &e,
                 &to_add,
                 &PoolBalance {
                     shares: 90_000_0000000,
-                    tokens: 100_000_0000000,
+                    tokens: 100_001_0000000,
                     q4w: 1_000_0000000,
                 },
             );
@@ -1594,14 +3358,16 @@ mod tests {
                     q4w: 1_000_0000000,
                 },
             );
+            // storage::set_lp_token_val(&e, &(5_0000000, 0_1000000));
 
-            add_to_reward_zone(&e, to_add.clone(), Some(to_remove));
+            add_to_reward_zone(&e, to_add.clone(), Some(to_remove.clone()));
         });
     }
 
+    /********** remove_from_reward_zone **********/
+
     #[test]
-    #[should_panic(expected = "Error(Contract, #1000)")]
-    fn test_add_to_rz_swap_distribution_too_long_ago() {
+    fn test_remove_from_rz() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1617,7 +3383,6 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
-        let to_add = Address::generate(&e);
         let to_remove = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
@@ -1631,18 +3396,18 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
-        let mut reward_zone: Vec<Address> = vec![&e];
-        for _ in 0..50 {
-            reward_zone.push_back(Address::generate(&e));
-        }
-        reward_zone.set(7, to_remove.clone());
+        let mut reward_zone: Vec<Address> = vec![
+            &e,
+            Address::generate(&e),
+            to_remove.clone(), // index 7
+        ];
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60 - 1));
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
             storage::set_pool_balance(
                 &e,
-                &to_add,
+                &to_remove,
                 &PoolBalance {
                     shares: 90_000_0000000,
                     tokens: 100_001_0000000,
@@ -1653,19 +3418,51 @@ mod tests {
                 &e,
                 &to_remove,
                 &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_000_0000000,
+                    shares: 35_000_0000000,
+                    tokens: 40_000_0000000,
                     q4w: 1_000_0000000,
                 },
             );
+            storage::set_backstop_emis_data(
+                &e,
+                &to_remove,
+                &BackstopEmissionData {
+                    eps: 0_10000000000000,
+                    expiration: 1713139200 + 1000,
+                    index: 0,
+                    last_time: 1713139200 - 12345,
+                },
+            );
+            storage::set_rz_emis_data(&e, &to_remove, {
+                &RzEmissionData {
+                    index: 1234 * SCALAR_7,
+                    accrued: 0,
+                }
+            });
+            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
+            remove_from_reward_zone(&e, to_remove.clone());
+            let actual_rz = storage::get_reward_zone(&e);
+            reward_zone.remove(1);
+            assert_eq!(actual_rz.len(), 1);
+            assert_eq!(actual_rz, reward_zone);
 
-            add_to_reward_zone(&e, to_add.clone(), Some(to_remove));
+            let to_remove_rz_emis_data =
+                storage::get_rz_emis_data(&e, &to_remove).unwrap_optimized();
+            assert_eq!(to_remove_rz_emis_data.index, i128::MAX);
+
+            assert_eq!(
+                e.events().all().last_unchecked(),
+                (
+                    backstop_id.clone(),
+                    (Symbol::new(&e, "rw_zone_remove"),).into_val(&e),
+                    (to_remove.clone(), 40_000_0000000i128).into_val(&e)
+                )
+            );
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1002)")]
-    fn test_add_to_rz_to_remove_not_in_rz() {
+    fn test_remove_from_rz_checkpoints_reward_zone() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1681,7 +3478,8 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
-        let to_add = Address::generate(&e);
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
         let to_remove = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
@@ -1695,41 +3493,61 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
-        let mut reward_zone: Vec<Address> = vec![&e];
-        for _ in 0..50 {
-            reward_zone.push_back(Address::generate(&e));
-        }
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), to_remove.clone(), pool_2.clone()];
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_last_distribution_time(&e, &(1713139200 - 24 * 60 * 60));
-            storage::set_pool_balance(
-                &e,
-                &to_add,
-                &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_001_0000000,
-                    q4w: 1_000_0000000,
-                },
-            );
-            storage::set_pool_balance(
-                &e,
-                &to_remove,
-                &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_000_0000000,
-                    q4w: 1_000_0000000,
-                },
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            let pool_balances = [
+                (pool_1.clone(), 100_000_0000000),
+                (pool_2.clone(), 50_000_0000000),
+                (to_remove.clone(), 40_000_0000000),
+            ];
+            for (pool, tokens) in pool_balances.iter() {
+                storage::set_pool_balance(
+                    &e,
+                    pool,
+                    &PoolBalance {
+                        shares: *tokens,
+                        tokens: *tokens,
+                        q4w: 0,
+                    },
+                );
+                storage::set_rz_emis_data(
+                    &e,
+                    pool,
+                    &RzEmissionData {
+                        index: 0,
+                        accrued: 0,
+                    },
+                );
+            }
+            storage::set_rz_emission_index(&e, &0_10000000000000);
+
+            remove_from_reward_zone(&e, to_remove.clone());
+            assert_eq!(
+                storage::get_reward_zone(&e),
+                vec![&e, pool_1.clone(), pool_2.clone()]
             );
-            // storage::set_lp_token_val(&e, &(5_0000000, 0_1000000));
 
-            add_to_reward_zone(&e, to_add.clone(), Some(to_remove));
+            // remaining pools accrued against the reward zone as it was before the removal
+            let pool_1_emis_data = storage::get_rz_emis_data(&e, &pool_1).unwrap_optimized();
+            assert_eq!(pool_1_emis_data.index, 0_10000000000000);
+            assert_eq!(pool_1_emis_data.accrued, 10_000_0000000);
+            let pool_2_emis_data = storage::get_rz_emis_data(&e, &pool_2).unwrap_optimized();
+            assert_eq!(pool_2_emis_data.index, 0_10000000000000);
+            assert_eq!(pool_2_emis_data.accrued, 5_000_0000000);
+
+            // the removed pool keeps the emissions it accrued before the removal
+            let to_remove_emis_data = storage::get_rz_emis_data(&e, &to_remove).unwrap_optimized();
+            assert_eq!(to_remove_emis_data.index, i128::MAX);
+            assert_eq!(to_remove_emis_data.accrued, 4_000_0000000);
         });
     }
 
     #[test]
     #[should_panic(expected = "Error(Contract, #1000)")]
-    fn test_add_to_rz_already_exists_panics() {
+    fn test_remove_from_rz_above_threshold() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1745,7 +3563,6 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
-        let to_add = Address::generate(&e);
         let to_remove = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
@@ -1762,48 +3579,45 @@ mod tests {
         let reward_zone: Vec<Address> = vec![
             &e,
             Address::generate(&e),
-            to_remove.clone(),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            Address::generate(&e),
-            to_add.clone(),
-            Address::generate(&e),
-            Address::generate(&e),
+            to_remove.clone(), // index 7
         ];
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_last_distribution_time(&e, &(1713139200 - 24 * 60 * 60));
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
             storage::set_pool_balance(
                 &e,
-                &to_add,
+                &to_remove,
                 &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_001_0000000,
+                    shares: 80_000_0000000,
+                    tokens: 90_000_0000000,
                     q4w: 1_000_0000000,
                 },
             );
-            storage::set_pool_balance(
+            storage::set_backstop_emis_data(
                 &e,
                 &to_remove,
-                &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_000_0000000,
-                    q4w: 1_000_0000000,
+                &BackstopEmissionData {
+                    eps: 0_10000000000000,
+                    expiration: 1713139200 + 1000,
+                    index: 0,
+                    last_time: 1713139200 - 12345,
                 },
             );
-            // storage::set_lp_token_val(&e, &(5_0000000, 0_1000000));
-
-            add_to_reward_zone(&e, to_add.clone(), Some(to_remove.clone()));
+            storage::set_rz_emis_data(&e, &to_remove, {
+                &RzEmissionData {
+                    index: 1234 * SCALAR_7,
+                    accrued: 0,
+                }
+            });
+            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
+            remove_from_reward_zone(&e, to_remove.clone());
         });
     }
 
-    /********** remove_from_reward_zone **********/
-
     #[test]
-    fn test_remove_from_rz() {
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_remove_from_rz_last_distribution_too_long_ago() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1832,7 +3646,7 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
-        let mut reward_zone: Vec<Address> = vec![
+        let reward_zone: Vec<Address> = vec![
             &e,
             Address::generate(&e),
             to_remove.clone(), // index 7
@@ -1840,22 +3654,13 @@ mod tests {
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
-            storage::set_pool_balance(
-                &e,
-                &to_remove,
-                &PoolBalance {
-                    shares: 90_000_0000000,
-                    tokens: 100_001_0000000,
-                    q4w: 1_000_0000000,
-                },
-            );
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60 - 1));
             storage::set_pool_balance(
                 &e,
                 &to_remove,
                 &PoolBalance {
-                    shares: 35_000_0000000,
-                    tokens: 40_000_0000000,
+                    shares: 80_000_0000000,
+                    tokens: 90_000_0000000,
                     q4w: 1_000_0000000,
                 },
             );
@@ -1877,20 +3682,12 @@ mod tests {
             });
             storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
             remove_from_reward_zone(&e, to_remove.clone());
-            let actual_rz = storage::get_reward_zone(&e);
-            reward_zone.remove(1);
-            assert_eq!(actual_rz.len(), 1);
-            assert_eq!(actual_rz, reward_zone);
-
-            let to_remove_rz_emis_data =
-                storage::get_rz_emis_data(&e, &to_remove).unwrap_optimized();
-            assert_eq!(to_remove_rz_emis_data.index, i128::MAX);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1000)")]
-    fn test_remove_from_rz_above_threshold() {
+    #[should_panic(expected = "Error(Contract, #1002)")]
+    fn test_remove_from_rz_not_in_rz() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1919,11 +3716,7 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
-        let reward_zone: Vec<Address> = vec![
-            &e,
-            Address::generate(&e),
-            to_remove.clone(), // index 7
-        ];
+        let reward_zone: Vec<Address> = vec![&e, Address::generate(&e)];
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
@@ -1932,8 +3725,8 @@ mod tests {
                 &e,
                 &to_remove,
                 &PoolBalance {
-                    shares: 80_000_0000000,
-                    tokens: 90_000_0000000,
+                    shares: 35_000_0000000,
+                    tokens: 40_000_0000000,
                     q4w: 1_000_0000000,
                 },
             );
@@ -1958,9 +3751,10 @@ mod tests {
         });
     }
 
+    /********** remove_from_reward_zone_batch **********/
+
     #[test]
-    #[should_panic(expected = "Error(Contract, #1000)")]
-    fn test_remove_from_rz_last_distribution_too_long_ago() {
+    fn test_remove_from_rz_batch() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -1976,7 +3770,9 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
-        let to_remove = Address::generate(&e);
+        let to_keep = Address::generate(&e);
+        let to_remove_1 = Address::generate(&e);
+        let to_remove_2 = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
         let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
@@ -1991,46 +3787,146 @@ mod tests {
         );
         let reward_zone: Vec<Address> = vec![
             &e,
-            Address::generate(&e),
-            to_remove.clone(), // index 7
+            to_remove_1.clone(),
+            to_keep.clone(),
+            to_remove_2.clone(),
         ];
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
-            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60 - 1));
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
+            for pool in [to_remove_1.clone(), to_remove_2.clone()] {
+                storage::set_pool_balance(
+                    &e,
+                    &pool,
+                    &PoolBalance {
+                        shares: 35_000_0000000,
+                        tokens: 40_000_0000000,
+                        q4w: 1_000_0000000,
+                    },
+                );
+                storage::set_rz_emis_data(
+                    &e,
+                    &pool,
+                    &RzEmissionData {
+                        index: 1234 * SCALAR_7,
+                        accrued: 0,
+                    },
+                );
+            }
+
+            remove_from_reward_zone_batch(&e, vec![&e, to_remove_1.clone(), to_remove_2.clone()]);
+
+            let actual_rz = storage::get_reward_zone(&e);
+            assert_eq!(actual_rz, vec![&e, to_keep.clone()]);
+            let to_remove_1_emis_data =
+                storage::get_rz_emis_data(&e, &to_remove_1).unwrap_optimized();
+            assert_eq!(to_remove_1_emis_data.index, i128::MAX);
+            let to_remove_2_emis_data =
+                storage::get_rz_emis_data(&e, &to_remove_2).unwrap_optimized();
+            assert_eq!(to_remove_2_emis_data.index, i128::MAX);
+        });
+    }
+
+    #[test]
+    fn test_prune_reward_zone() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_keep = Address::generate(&e);
+        let to_remove_1 = Address::generate(&e);
+        let to_remove_2 = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let reward_zone: Vec<Address> = vec![
+            &e,
+            to_remove_1.clone(),
+            to_keep.clone(),
+            to_remove_2.clone(),
+        ];
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
             storage::set_pool_balance(
                 &e,
-                &to_remove,
+                &to_keep,
                 &PoolBalance {
                     shares: 80_000_0000000,
                     tokens: 90_000_0000000,
                     q4w: 1_000_0000000,
                 },
             );
-            storage::set_backstop_emis_data(
-                &e,
-                &to_remove,
-                &BackstopEmissionData {
-                    eps: 0_10000000000000,
-                    expiration: 1713139200 + 1000,
-                    index: 0,
-                    last_time: 1713139200 - 12345,
-                },
-            );
-            storage::set_rz_emis_data(&e, &to_remove, {
-                &RzEmissionData {
-                    index: 1234 * SCALAR_7,
-                    accrued: 0,
-                }
-            });
-            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
-            remove_from_reward_zone(&e, to_remove.clone());
+            for pool in [to_remove_1.clone(), to_keep.clone(), to_remove_2.clone()] {
+                storage::set_rz_emis_data(
+                    &e,
+                    &pool,
+                    &RzEmissionData {
+                        index: 1234 * SCALAR_7,
+                        accrued: 0,
+                    },
+                );
+            }
+            for pool in [to_remove_1.clone(), to_remove_2.clone()] {
+                storage::set_pool_balance(
+                    &e,
+                    &pool,
+                    &PoolBalance {
+                        shares: 35_000_0000000,
+                        tokens: 40_000_0000000,
+                        q4w: 1_000_0000000,
+                    },
+                );
+            }
+
+            let removed = prune_reward_zone(&e);
+            assert_eq!(removed, vec![&e, to_remove_1.clone(), to_remove_2.clone()]);
+
+            let actual_rz = storage::get_reward_zone(&e);
+            assert_eq!(actual_rz, vec![&e, to_keep.clone()]);
+            let to_remove_1_emis_data =
+                storage::get_rz_emis_data(&e, &to_remove_1).unwrap_optimized();
+            assert_eq!(to_remove_1_emis_data.index, i128::MAX);
+            let to_remove_2_emis_data =
+                storage::get_rz_emis_data(&e, &to_remove_2).unwrap_optimized();
+            assert_eq!(to_remove_2_emis_data.index, i128::MAX);
+            let to_keep_emis_data = storage::get_rz_emis_data(&e, &to_keep).unwrap_optimized();
+            assert_eq!(to_keep_emis_data.index, 1234 * SCALAR_7);
+
+            // nothing left to prune
+            let removed = prune_reward_zone(&e);
+            assert_eq!(removed.len(), 0);
+            assert_eq!(storage::get_reward_zone(&e), vec![&e, to_keep.clone()]);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1002)")]
-    fn test_remove_from_rz_not_in_rz() {
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_remove_from_rz_batch_one_above_threshold() {
         let e = Env::default();
         e.ledger().set(LedgerInfo {
             timestamp: 1713139200,
@@ -2046,7 +3942,8 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let backstop_id = create_backstop(&e);
-        let to_remove = Address::generate(&e);
+        let to_remove_1 = Address::generate(&e);
+        let to_remove_2 = Address::generate(&e);
 
         let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
         let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
@@ -2059,38 +3956,104 @@ mod tests {
             &usdc_id,
             0_1000000,
         );
-        let reward_zone: Vec<Address> = vec![&e, Address::generate(&e)];
+        let reward_zone: Vec<Address> = vec![&e, to_remove_1.clone(), to_remove_2.clone()];
 
         e.as_contract(&backstop_id, || {
             storage::set_reward_zone(&e, &reward_zone);
             storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
             storage::set_pool_balance(
                 &e,
-                &to_remove,
+                &to_remove_1,
                 &PoolBalance {
                     shares: 35_000_0000000,
                     tokens: 40_000_0000000,
                     q4w: 1_000_0000000,
                 },
             );
-            storage::set_backstop_emis_data(
+            storage::set_pool_balance(
                 &e,
-                &to_remove,
-                &BackstopEmissionData {
-                    eps: 0_10000000000000,
-                    expiration: 1713139200 + 1000,
-                    index: 0,
-                    last_time: 1713139200 - 12345,
+                &to_remove_2,
+                &PoolBalance {
+                    shares: 80_000_0000000,
+                    tokens: 90_000_0000000,
+                    q4w: 1_000_0000000,
                 },
             );
-            storage::set_rz_emis_data(&e, &to_remove, {
+            for pool in [to_remove_1.clone(), to_remove_2.clone()] {
+                storage::set_rz_emis_data(
+                    &e,
+                    &pool,
+                    &RzEmissionData {
+                        index: 1234 * SCALAR_7,
+                        accrued: 0,
+                    },
+                );
+            }
+
+            remove_from_reward_zone_batch(&e, vec![&e, to_remove_1.clone(), to_remove_2.clone()]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1000)")]
+    fn test_remove_from_rz_batch_one_not_in_rz() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let backstop_id = create_backstop(&e);
+        let to_remove_1 = Address::generate(&e);
+        let to_remove_2 = Address::generate(&e);
+
+        let (blnd_id, _) = create_blnd_token(&e, &backstop_id, &bombadil);
+        let (usdc_id, _) = create_usdc_token(&e, &backstop_id, &bombadil);
+        create_comet_lp_pool_with_tokens_per_share(
+            &e,
+            &backstop_id,
+            &bombadil,
+            &blnd_id,
+            5_0000000,
+            &usdc_id,
+            0_1000000,
+        );
+        let reward_zone: Vec<Address> = vec![&e, Address::generate(&e), to_remove_1.clone()];
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_last_distribution_time(&e, &(1713139200 - 1 * 24 * 60 * 60));
+            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
+            for pool in [to_remove_1.clone(), to_remove_2.clone()] {
+                storage::set_pool_balance(
+                    &e,
+                    &pool,
+                    &PoolBalance {
+                        shares: 35_000_0000000,
+                        tokens: 40_000_0000000,
+                        q4w: 1_000_0000000,
+                    },
+                );
+            }
+            storage::set_rz_emis_data(
+                &e,
+                &to_remove_1,
                 &RzEmissionData {
                     index: 1234 * SCALAR_7,
                     accrued: 0,
-                }
-            });
-            storage::set_rz_emission_index(&e, &(5678 * SCALAR_7));
-            remove_from_reward_zone(&e, to_remove.clone());
+                },
+            );
+
+            remove_from_reward_zone_batch(&e, vec![&e, to_remove_1.clone(), to_remove_2.clone()]);
         });
     }
 
@@ -2389,4 +4352,84 @@ mod tests {
             assert!(actual_data.is_none());
         });
     }
+
+    /********** get_reward_zone_info **********/
+
+    #[test]
+    fn test_get_reward_zone_info() {
+        let e = Env::default();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1713139200,
+            protocol_version: 22,
+            sequence_number: 0,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let backstop_id = create_backstop(&e);
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone(), pool_2.clone()];
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+            storage::set_rz_emission_index(&e, &22_00000000000000);
+            storage::set_pool_balance(
+                &e,
+                &pool_1,
+                &PoolBalance {
+                    shares: 100_0000000,
+                    tokens: 150_0000000,
+                    q4w: 20_0000000,
+                },
+            );
+            storage::set_rz_emis_data(
+                &e,
+                &pool_1,
+                &RzEmissionData {
+                    index: 11_00000000000000,
+                    accrued: 3_0000000,
+                },
+            );
+            // pool 2 has no emission data
+            storage::set_pool_balance(
+                &e,
+                &pool_2,
+                &PoolBalance {
+                    shares: 50_0000000,
+                    tokens: 50_0000000,
+                    q4w: 0,
+                },
+            );
+
+            let rz_info = get_reward_zone_info(&e);
+            assert_eq!(rz_info.len(), 2);
+            assert_eq!(
+                rz_info.get_unchecked(0),
+                (pool_1.clone(), 120_0000000, 11_00000000000000)
+            );
+            assert_eq!(
+                rz_info.get_unchecked(1),
+                (pool_2.clone(), 50_0000000, 22_00000000000000)
+            );
+        });
+    }
+
+    #[test]
+    fn test_is_in_reward_zone() {
+        let e = Env::default();
+        let backstop_id = create_backstop(&e);
+        let pool_1 = Address::generate(&e);
+        let pool_2 = Address::generate(&e);
+        let reward_zone: Vec<Address> = vec![&e, pool_1.clone()];
+
+        e.as_contract(&backstop_id, || {
+            storage::set_reward_zone(&e, &reward_zone);
+
+            assert!(is_in_reward_zone(&e, &pool_1));
+            assert!(!is_in_reward_zone(&e, &pool_2));
+        });
+    }
 }
@@ -6,5 +6,9 @@ pub use distributor::update_emissions;
 
 mod manager;
 pub use manager::{
-    add_to_reward_zone, distribute, gulp_emissions, remove_from_reward_zone, update_rz_emis_data,
+    add_to_reward_zone, distribute, distribute_clamped, get_backfill_remaining, get_backstop_eps,
+    get_pool_emissions_allowance, get_reward_zone_info, gulp_emissions, is_in_reward_zone,
+    pending_emissions, prune_reward_zone, remove_from_reward_zone, remove_from_reward_zone_batch,
+    set_backstop_emission_share, set_max_gulp_emissions, set_pool_emissions_enabled,
+    update_rz_emis_data, DistributionResult,
 };
@@ -106,29 +106,54 @@ impl BackstopEvents {
             .publish(topics, (new_backstop_emissions, new_pool_emissions));
     }
 
-    /// Emitted when the reward zone is updated
+    /// Emitted when a pool is added to the reward zone, either into an open slot or by swapping
+    /// out another pool
     ///
     /// - topics - `["rw_zone_add"]`
-    /// - data - `[to_add: Address, to_remove: Address]`
+    /// - data - `[to_add: Address, to_remove: Option<Address>, to_add_tokens: i128, to_remove_tokens: i128]`
     ///
     /// ### Arguments
-    /// * `to_add` - The address to add to the reward zone
-    /// * `to_remove` - The address to remove from the reward zone
-    pub fn rw_zone_add(e: &Env, to_add: Address, to_remove: Option<Address>) {
+    /// * `to_add` - The address of the pool added to the reward zone
+    /// * `to_remove` - The address of the pool swapped out of the reward zone, if any
+    /// * `to_add_tokens` - The backstop tokens held by the added pool
+    /// * `to_remove_tokens` - The backstop tokens held by the removed pool, or 0 if no pool was removed
+    pub fn rw_zone_add(
+        e: &Env,
+        to_add: Address,
+        to_remove: Option<Address>,
+        to_add_tokens: i128,
+        to_remove_tokens: i128,
+    ) {
         let topics = (Symbol::new(e, "rw_zone_add"),);
-        e.events().publish(topics, (to_add, to_remove));
+        e.events()
+            .publish(topics, (to_add, to_remove, to_add_tokens, to_remove_tokens));
     }
 
     /// Emitted when a pool is removed from the reward zone
     ///
-    /// - topics - `["rw_zone_remove", pool_address: Address]`
-    /// - data - `[to_remove: Address]`
+    /// - topics - `["rw_zone_remove"]`
+    /// - data - `[to_remove: Address, tokens: i128]`
     ///
     /// ### Arguments
-    /// * `to_remove` - The address to remove from the reward zone
-    pub fn rw_zone_remove(e: &Env, to_remove: Address) {
+    /// * `to_remove` - The address of the pool removed from the reward zone
+    /// * `tokens` - The backstop tokens held by the removed pool
+    pub fn rw_zone_remove(e: &Env, to_remove: Address, tokens: i128) {
         let topics = (Symbol::new(e, "rw_zone_remove"),);
-        e.events().publish(topics, to_remove);
+        e.events().publish(topics, (to_remove, tokens));
+    }
+
+    /// Emitted when the backstop emissions rate for a pool is set
+    ///
+    /// - topics - `["backstop_eps_set", pool_address: Address]`
+    /// - data - `[eps: u64, expiration: u64]`
+    ///
+    /// ### Arguments
+    /// * `pool_address` - The address of the pool
+    /// * `eps` - The new emissions per second, scaled to 14 decimals
+    /// * `expiration` - The timestamp the emissions expire at
+    pub fn backstop_eps_set(e: &Env, pool_address: Address, eps: u64, expiration: u64) {
+        let topics = (Symbol::new(e, "backstop_eps_set"), pool_address);
+        e.events().publish(topics, (eps, expiration));
     }
 
     /// Emitted when emissions are claimed
@@ -13,7 +13,11 @@ mod events;
 mod storage;
 mod testutils;
 
-pub use backstop::{PoolBackstopData, PoolBalance, UserBalance, Q4W};
+pub use backstop::{BackstopDashboard, PoolBackstopData, PoolBalance, UserBalance, Q4W};
 pub use contract::*;
+pub use emissions::DistributionResult;
 pub use errors::BackstopError;
-pub use storage::{BackstopDataKey, BackstopEmissionData, PoolUserKey, UserEmissionData};
+pub use storage::{
+    BackstopConfig, BackstopDataKey, BackstopEmissionData, PoolUserKey, RzEmissionData,
+    UserEmissionData,
+};
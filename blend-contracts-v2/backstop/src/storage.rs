@@ -19,6 +19,17 @@ pub(crate) const LEDGER_BUMP_USER: u32 = LEDGER_THRESHOLD_USER + 20 * ONE_DAY_LE
 
 /********** Storage Types **********/
 
+// The addresses the backstop was configured with
+#[derive(Clone)]
+#[contracttype]
+pub struct BackstopConfig {
+    pub backstop_token: Address,
+    pub emitter: Address,
+    pub blnd_token: Address,
+    pub usdc_token: Address,
+    pub pool_factory: Address,
+}
+
 // The emission data for a reward zone pool
 #[derive(Clone)]
 #[contracttype]
@@ -80,6 +91,10 @@ pub enum BackstopDataKey {
     RzEmisData(Address),
     BEmisData(Address),
     UEmisData(PoolUserKey),
+    MaxGulp(Address),
+    EmisShare(Address),
+    RzEntryTime(Address),
+    EmisEnabled(Address),
 }
 
 /****************************
@@ -447,6 +462,134 @@ pub fn set_rz_emis_data(e: &Env, pool: &Address, emis_data: &RzEmissionData) {
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/// Get the maximum emissions the pool can gulp in a single `gulp_emissions` call, if any
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn get_max_gulp_emissions(e: &Env, pool: &Address) -> Option<i128> {
+    let key = BackstopDataKey::MaxGulp(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || None,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the maximum emissions the pool can gulp in a single `gulp_emissions` call
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `max_emissions` - The maximum emissions the pool can gulp
+pub fn set_max_gulp_emissions(e: &Env, pool: &Address, max_emissions: &i128) {
+    let key = BackstopDataKey::MaxGulp(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, i128>(&key, max_emissions);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Delete the maximum emissions the pool can gulp in a single `gulp_emissions` call
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn del_max_gulp_emissions(e: &Env, pool: &Address) {
+    let key = BackstopDataKey::MaxGulp(pool.clone());
+    e.storage().persistent().remove(&key);
+}
+
+/// Get the share of the pool's reward zone emissions that go to the backstop (7 decimals)
+///
+/// Defaults to 0_7000000 (70%)
+///
+/// ### Arguments
+/// * `pool` - The pool
+#[allow(clippy::zero_prefixed_literal)]
+pub fn get_backstop_emission_share(e: &Env, pool: &Address) -> u32 {
+    let key = BackstopDataKey::EmisShare(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || 0_7000000,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the share of the pool's reward zone emissions that go to the backstop (7 decimals)
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `share` - The backstop's share of the emissions
+pub fn set_backstop_emission_share(e: &Env, pool: &Address, share: &u32) {
+    let key = BackstopDataKey::EmisShare(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, u32>(&key, share);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Get the time the pool last entered the reward zone, or 0 if it is unknown
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn get_rz_entry_time(e: &Env, pool: &Address) -> u64 {
+    let key = BackstopDataKey::RzEntryTime(pool.clone());
+    get_persistent_default(e, &key, || 0, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED)
+}
+
+/// Set the time the pool entered the reward zone
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `entry_time` - The time the pool entered the reward zone
+pub fn set_rz_entry_time(e: &Env, pool: &Address, entry_time: &u64) {
+    let key = BackstopDataKey::RzEntryTime(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, u64>(&key, entry_time);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Get whether the pool accrues reward zone emissions
+///
+/// Defaults to true
+///
+/// ### Arguments
+/// * `pool` - The pool
+pub fn get_pool_emissions_enabled(e: &Env, pool: &Address) -> bool {
+    let key = BackstopDataKey::EmisEnabled(pool.clone());
+    get_persistent_default(
+        e,
+        &key,
+        || true,
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set whether the pool accrues reward zone emissions
+///
+/// ### Arguments
+/// * `pool` - The pool
+/// * `enabled` - Whether the pool accrues reward zone emissions
+pub fn set_pool_emissions_enabled(e: &Env, pool: &Address, enabled: &bool) {
+    let key = BackstopDataKey::EmisEnabled(pool.clone());
+    e.storage()
+        .persistent()
+        .set::<BackstopDataKey, bool>(&key, enabled);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
 /// Get the pool's backstop emissions data
 ///
 /// ### Arguments
@@ -1,8 +1,8 @@
 use crate::{
     constants::SCALAR_7,
     errors::PoolError,
-    pool::{Pool, User},
-    storage,
+    pool::{Pool, PositionData, RequestType, User},
+    storage::{self, AuctionConfig},
 };
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
@@ -11,7 +11,9 @@ use soroban_sdk::{contracttype, map, panic_with_error, Address, Env, Map, Vec};
 use super::{
     backstop_interest_auction::{create_interest_auction_data, fill_interest_auction},
     bad_debt_auction::{create_bad_debt_auction_data, fill_bad_debt_auction},
-    user_liquidation_auction::{create_user_liq_auction_data, fill_user_liq_auction},
+    user_liquidation_auction::{
+        calc_user_liq_percent_to_target, create_user_liq_auction_data, fill_user_liq_auction,
+    },
 };
 
 #[derive(Clone, PartialEq)]
@@ -71,6 +73,7 @@ pub struct AuctionData {
 /// ### Panics
 /// * If the max positions are exceeded
 /// * If the user and percent are invalid for the auction type
+/// * If a bid or lot asset that must be a reserve is not a reserve
 /// * If the auction is unable to be created
 pub fn create_auction(
     e: &Env,
@@ -82,6 +85,16 @@ pub fn create_auction(
 ) -> AuctionData {
     // panics if auction_type parameter is not valid
     let auction_type_enum = AuctionType::from_u32(e, auction_type);
+    // the backstop token is the only non-reserve asset an auction can hold, as the lot
+    // of a bad debt auction or the bid of an interest auction
+    match auction_type_enum {
+        AuctionType::UserLiquidation => {
+            require_reserves(e, bid);
+            require_reserves(e, lot);
+        }
+        AuctionType::BadDebtAuction => require_reserves(e, bid),
+        AuctionType::InterestAuction => require_reserves(e, lot),
+    }
     let auction_data = match auction_type_enum {
         AuctionType::UserLiquidation => create_user_liq_auction_data(e, user, bid, lot, percent),
         AuctionType::BadDebtAuction => create_bad_debt_auction_data(e, user, bid, lot, percent),
@@ -91,6 +104,86 @@ pub fn create_auction(
     auction_data
 }
 
+/// Require that every asset is a reserve in the pool
+///
+/// ### Panics
+/// If any asset is not a reserve
+fn require_reserves(e: &Env, assets: &Vec<Address>) {
+    let reserve_list = storage::get_res_list(e);
+    for asset in assets.iter() {
+        if !reserve_list.contains(&asset) {
+            panic_with_error!(e, PoolError::BadRequest);
+        }
+    }
+}
+
+/// Create a user liquidation auction that liquidates the minimum percent of the user's positions
+/// required to bring their health factor to at least `target_hf`
+///
+/// Returns the percent liquidated and the created auction
+///
+/// ### Arguments
+/// * `user` - The user being liquidated
+/// * `bid` - The set of liability assets to include in the auction bid
+/// * `lot` - The set of collateral assets to include in the auction lot
+/// * `target_hf` - The health factor the user should have after the liquidation (7 decimals). Must be
+///                 over 1_0000000 and under 1_1500000.
+///
+/// ### Panics
+/// If the liquidation auction cannot be created
+pub fn create_user_liq_auction_to_target(
+    e: &Env,
+    user: &Address,
+    bid: &Vec<Address>,
+    lot: &Vec<Address>,
+    target_hf: i128,
+) -> (u32, AuctionData) {
+    let percent = calc_user_liq_percent_to_target(e, user, bid, lot, target_hf);
+    let auction_data = create_auction(
+        e,
+        AuctionType::UserLiquidation as u32,
+        user,
+        bid,
+        lot,
+        percent,
+    );
+    (percent, auction_data)
+}
+
+/// Require that a newly created auction satisfies the creator's slippage bounds. Bounds are
+/// checked against the base auction amounts. Per-block scaling is still applied when the
+/// auction is filled.
+///
+/// ### Arguments
+/// * `auction_data` - The auction data created
+/// * `min_lot` - An optional map of the minimum amount of each asset required in the lot
+/// * `max_bid` - An optional map of the maximum amount of each asset allowed in the bid
+///
+/// ### Panics
+/// * If any lot asset amount is below its minimum
+/// * If any bid asset amount is above its maximum
+pub fn require_auction_bounds(
+    e: &Env,
+    auction_data: &AuctionData,
+    min_lot: &Option<Map<Address, i128>>,
+    max_bid: &Option<Map<Address, i128>>,
+) {
+    if let Some(min_lot) = min_lot {
+        for (asset, min_amount) in min_lot.iter() {
+            if auction_data.lot.get(asset).unwrap_or(0) < min_amount {
+                panic_with_error!(e, PoolError::BadRequest);
+            }
+        }
+    }
+    if let Some(max_bid) = max_bid {
+        for (asset, max_amount) in max_bid.iter() {
+            if auction_data.bid.get(asset).unwrap_or(0) > max_amount {
+                panic_with_error!(e, PoolError::BadRequest);
+            }
+        }
+    }
+}
+
 /// Delete a liquidation auction if the user being liquidated
 ///
 /// NOTE: Does not verify if the user's positions are healthy. This must be done before calling.
@@ -107,6 +200,34 @@ pub fn delete_liquidation(e: &Env, user: &Address) {
     storage::del_auction(e, &(AuctionType::UserLiquidation as u32), user);
 }
 
+/// Delete a liquidation auction for a user whose positions are healthy again. Can be called by anyone,
+/// as the user's health is verified before the auction is removed.
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction being deleted
+/// * `user` - The user being liquidated
+///
+/// ### Panics
+/// * If the auction is not a user liquidation auction, or no auction exists for the user
+/// * If the pool status does not allow liquidation auctions to be cancelled
+/// * If the user's health factor is not over 1
+pub fn delete_auction(e: &Env, auction_type: u32, user: &Address) {
+    if AuctionType::from_u32(e, auction_type) != AuctionType::UserLiquidation {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool = Pool::load(e);
+    pool.require_action_allowed(e, RequestType::DeleteLiquidationAuction as u32);
+
+    // min is 1.0000100 to prevent rounding errors, matching the check done on submit
+    let user_state = User::load(e, user);
+    let position_data = PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+    if position_data.is_hf_under(e, 1_0000100) {
+        panic_with_error!(e, PoolError::InvalidHf);
+    }
+
+    delete_liquidation(e, user);
+}
+
 /// Fills the auction from the invoker.
 ///
 /// ### Arguments
@@ -117,8 +238,9 @@ pub fn delete_liquidation(e: &Env, user: &Address) {
 /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
 ///
 /// ### Panics
-/// If the auction does not exist, or if the pool is unable to fulfill either side
-/// of the auction quote
+/// * If the auction does not exist, or if the pool is unable to fulfill either side
+///   of the auction quote
+/// * If a liquidation auction is fully filled before it has run for the minimum number of blocks
 pub fn fill(
     e: &Env,
     pool: &mut Pool,
@@ -131,7 +253,18 @@ pub fn fill(
         panic_with_error!(e, PoolError::InvalidLiquidation);
     }
     let auction_data = storage::get_auction(e, &auction_type, user);
-    let (to_fill_auction, remaining_auction) = scale_auction(e, &auction_data, percent_filled);
+    if auction_type == AuctionType::UserLiquidation as u32 && percent_filled == 100 {
+        require_min_liq_full_fill_blocks(e, &auction_data);
+    }
+    let lot_rates = load_lot_rates(e, &auction_data.lot);
+    let auction_config = storage::get_auction_config(e);
+    let (to_fill_auction, remaining_auction) = scale_auction(
+        e,
+        &auction_data,
+        percent_filled,
+        &lot_rates,
+        &auction_config,
+    );
     match AuctionType::from_u32(e, auction_type) {
         AuctionType::UserLiquidation => {
             fill_user_liq_auction(e, pool, &to_fill_auction, user, filler_state)
@@ -153,12 +286,171 @@ pub fn fill(
     to_fill_auction
 }
 
+/// Require that a liquidation auction has run for the minimum number of blocks before it is fully filled
+///
+/// ### Panics
+/// If the auction has not run for the minimum number of blocks
+fn require_min_liq_full_fill_blocks(e: &Env, auction_data: &AuctionData) {
+    let min_blocks = storage::get_min_liq_full_fill_blocks(e);
+    if min_blocks > 0 && e.ledger().sequence() < auction_data.block + min_blocks {
+        panic_with_error!(e, PoolError::AuctionFillTooEarly);
+    }
+}
+
+/// Preview the collateral seized and debt repaid by a user liquidation auction if it were created
+/// now and fully filled at `fill_block`. Does not write to storage.
+///
+/// Returns the AuctionData that would be filled, such that the bid is the dTokens repaid
+/// and the lot is the bTokens seized
+///
+/// ### Arguments
+/// * `user` - The user being liquidated
+/// * `bid` - The liability assets to include in the auction bid
+/// * `lot` - The collateral assets to include in the auction lot
+/// * `percent` - The percentage of the user's positions being liquidated
+/// * `fill_block` - The block the auction is expected to be filled on
+///
+/// ### Panics
+/// * If the liquidation auction is unable to be created
+/// * If the fill block is before the auction would begin
+pub fn preview_user_liquidation(
+    e: &Env,
+    user: &Address,
+    bid: &Vec<Address>,
+    lot: &Vec<Address>,
+    percent: u32,
+    fill_block: u32,
+) -> AuctionData {
+    let auction_data = create_user_liq_auction_data(e, user, bid, lot, percent);
+    if fill_block < auction_data.block {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let lot_rates = load_lot_rates(e, &auction_data.lot);
+    let auction_config = storage::get_auction_config(e);
+    let (to_fill_auction, _) = scale_auction_at_block(
+        e,
+        &auction_data,
+        100,
+        fill_block,
+        &lot_rates,
+        &auction_config,
+    );
+    to_fill_auction
+}
+
+/// Preview the bid and lot of an existing auction if `percent_filled` of it were filled at the
+/// current block. Does not write to storage or transfer any tokens.
+///
+/// Returns the (bid, lot) that would be filled
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The Address involved in the auction
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+///
+/// ### Panics
+/// * If the auction does not exist or the auction type is invalid
+/// * If the percent filled is invalid
+/// * If a liquidation auction would be fully filled before it has run for the minimum number of blocks
+pub fn preview_fill(
+    e: &Env,
+    auction_type: u32,
+    user: &Address,
+    percent_filled: u64,
+) -> (Map<Address, i128>, Map<Address, i128>) {
+    let auction_data = storage::get_auction(e, &auction_type, user);
+    if AuctionType::from_u32(e, auction_type) == AuctionType::UserLiquidation
+        && percent_filled == 100
+    {
+        require_min_liq_full_fill_blocks(e, &auction_data);
+    }
+    let lot_rates = load_lot_rates(e, &auction_data.lot);
+    let auction_config = storage::get_auction_config(e);
+    let (to_fill_auction, _) = scale_auction(
+        e,
+        &auction_data,
+        percent_filled,
+        &lot_rates,
+        &auction_config,
+    );
+    (to_fill_auction.bid, to_fill_auction.lot)
+}
+
+/// Require that filling `percent_filled` of an auction at the current block results in a lot of at
+/// least `min_lot_out` for each asset.
+///
+/// ### Arguments
+/// * `auction_type` - The type of auction
+/// * `user` - The Address involved in the auction
+/// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `min_lot_out` - A map of the minimum amount of each asset required to be received from the lot
+///
+/// ### Panics
+/// * If any lot asset amount is below its minimum
+/// * If the auction cannot be filled
+pub fn require_fill_min_lot(
+    e: &Env,
+    auction_type: u32,
+    user: &Address,
+    percent_filled: u64,
+    min_lot_out: &Map<Address, i128>,
+) {
+    let (_, lot) = preview_fill(e, auction_type, user, percent_filled);
+    for (asset, min_amount) in min_lot_out.iter() {
+        if lot.get(asset).unwrap_or(0) < min_amount {
+            panic_with_error!(e, PoolError::BadRequest);
+        }
+    }
+}
+
+/// Load all auctions currently stored for a user
+///
+/// Returns a vector of (auction_type, auction_data) tuples, ordered by auction type
+///
+/// ### Arguments
+/// * `user` - The Address involved in the auctions
+pub fn load_auctions(e: &Env, user: &Address) -> Vec<(u32, AuctionData)> {
+    let mut auctions = Vec::new(e);
+    for auction_type in [
+        AuctionType::UserLiquidation as u32,
+        AuctionType::BadDebtAuction as u32,
+        AuctionType::InterestAuction as u32,
+    ] {
+        if storage::has_auction(e, &auction_type, user) {
+            auctions.push_back((auction_type, storage::get_auction(e, &auction_type, user)));
+        }
+    }
+    auctions
+}
+
+/// Load the per block lot scaling rates for any lot assets that are reserves with an
+/// `auction_lot_rate` set. Lot assets without a rate scale at the default rate.
+///
+/// Returns a map of asset to per block lot scaling rate (7 decimals)
+///
+/// ### Arguments
+/// * `lot` - The auction lot
+fn load_lot_rates(e: &Env, lot: &Map<Address, i128>) -> Map<Address, i128> {
+    let mut lot_rates: Map<Address, i128> = map![e];
+    for asset in lot.keys() {
+        if storage::has_res(e, &asset) {
+            let lot_rate = storage::get_res_config(e, &asset).auction_lot_rate;
+            if lot_rate > 0 {
+                lot_rates.set(asset, i128(lot_rate));
+            }
+        }
+    }
+    lot_rates
+}
+
 /// Scale the auction based on the percent being filled and the amount of blocks that have passed
 /// since the auction began.
 ///
 /// ### Arguments
 /// * `auction_data` - The auction data to scale
 /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+/// * `lot_rates` - The per block lot scaling rates for lot assets that do not use the default rate
+/// * `auction_config` - The block based scaling parameters of the pool's auctions
 ///
 /// Returns the (Scaled Auction, Remaining Auction) such that:
 /// - Scaled Auction is the auction data scaled
@@ -166,11 +458,35 @@ pub fn fill(
 ///
 /// ### Panics
 /// If the percent filled is greater than 100 or less than 0
-#[allow(clippy::zero_prefixed_literal)]
 fn scale_auction(
     e: &Env,
     auction_data: &AuctionData,
     percent_filled: u64,
+    lot_rates: &Map<Address, i128>,
+    auction_config: &AuctionConfig,
+) -> (AuctionData, Option<AuctionData>) {
+    scale_auction_at_block(
+        e,
+        auction_data,
+        percent_filled,
+        e.ledger().sequence(),
+        lot_rates,
+        auction_config,
+    )
+}
+
+/// Scale the auction based on the percent being filled and the amount of blocks that will have passed
+/// since the auction began at `block`.
+///
+/// ### Panics
+/// If the percent filled is greater than 100 or less than 0
+fn scale_auction_at_block(
+    e: &Env,
+    auction_data: &AuctionData,
+    percent_filled: u64,
+    block: u32,
+    lot_rates: &Map<Address, i128>,
+    auction_config: &AuctionConfig,
 ) -> (AuctionData, Option<AuctionData>) {
     if percent_filled > 100 || percent_filled == 0 {
         panic_with_error!(e, PoolError::BadRequest);
@@ -190,19 +506,21 @@ fn scale_auction(
     // determine block based auction modifiers
     let bid_modifier: i128;
     let lot_modifier: i128;
-    let per_block_scalar: i128 = 0_0050000; // modifier moves 0.5% every block
-    let block_dif = i128(e.ledger().sequence() - auction_data.block);
-    if block_dif > 200 {
+    let per_block_scalar = auction_config.auction_per_block_scalar;
+    let lot_ramp_blocks = i128(auction_config.auction_lot_ramp_blocks);
+    let bid_ramp_blocks = i128(auction_config.auction_bid_ramp_blocks);
+    let block_dif = i128(block - auction_data.block);
+    if block_dif > lot_ramp_blocks {
         // lot 100%, bid scaling down from 100% to 0%
         lot_modifier = SCALAR_7;
-        if block_dif < 400 {
-            bid_modifier = SCALAR_7 - (block_dif - 200) * per_block_scalar;
+        if block_dif < lot_ramp_blocks + bid_ramp_blocks {
+            bid_modifier = (SCALAR_7 - (block_dif - lot_ramp_blocks) * per_block_scalar).max(0);
         } else {
             bid_modifier = 0;
         }
     } else {
         // lot scaling from 0% to 100%, bid 100%
-        lot_modifier = block_dif * per_block_scalar;
+        lot_modifier = (block_dif * per_block_scalar).min(SCALAR_7);
         bid_modifier = SCALAR_7;
     }
 
@@ -230,8 +548,13 @@ fn scale_auction(
         if remaining_base > 0 {
             remaining_auction.lot.set(asset.clone(), remaining_base);
         }
-        // apply block scalar to to_fill auction and don't store if 0
-        let to_fill_scaled = to_fill_base.fixed_mul_floor(e, &lot_modifier, &SCALAR_7);
+        // apply block scalar to to_fill auction and don't store if 0. Reserve lot rates are
+        // validated to fully ramp within the lot ramp blocks, so the lot does not jump at its end
+        let asset_lot_modifier = match lot_rates.get(asset.clone()) {
+            Some(lot_rate) if block_dif <= lot_ramp_blocks => (block_dif * lot_rate).min(SCALAR_7),
+            _ => lot_modifier,
+        };
+        let to_fill_scaled = to_fill_base.fixed_mul_floor(e, &asset_lot_modifier, &SCALAR_7);
         if to_fill_scaled > 0 {
             to_fill_auction.lot.set(asset, to_fill_scaled);
         }
@@ -253,6 +576,10 @@ mod tests {
     };
 
     use super::*;
+    use crate::constants::{
+        DEFAULT_AUCTION_BID_RAMP_BLOCKS, DEFAULT_AUCTION_LOT_RAMP_BLOCKS,
+        DEFAULT_AUCTION_PER_BLOCK_SCALAR,
+    };
     use sep_40_oracle::testutils::Asset;
     use soroban_sdk::{
         map,
@@ -261,6 +588,14 @@ mod tests {
         vec, Symbol,
     };
 
+    fn default_auction_config() -> AuctionConfig {
+        AuctionConfig {
+            auction_lot_ramp_blocks: DEFAULT_AUCTION_LOT_RAMP_BLOCKS,
+            auction_bid_ramp_blocks: DEFAULT_AUCTION_BID_RAMP_BLOCKS,
+            auction_per_block_scalar: DEFAULT_AUCTION_PER_BLOCK_SCALAR,
+        }
+    }
+
     #[test]
     fn test_create_bad_debt_auction() {
         let e = Env::default();
@@ -379,6 +714,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -489,6 +826,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -602,6 +941,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_backstop(&e, &Address::generate(&e));
@@ -621,6 +962,165 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_preview_user_liquidation_matches_fill() {
+        let e = Env::default();
+        let frodo = Address::generate(&e);
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let liq_pct = 45;
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_backstop(&e, &Address::generate(&e));
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            e.cost_estimate().budget().reset_unlimited();
+            // lot is fully scaled in, and 75% of the bid is required
+            let fill_block = 51 + 250;
+            let preview = preview_user_liquidation(
+                &e,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                liq_pct,
+                fill_block,
+            );
+            assert!(!storage::has_auction(&e, &0, &samwise));
+
+            let auction_data = create_auction(
+                &e,
+                0,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                liq_pct,
+            );
+            assert_eq!(
+                preview.lot.get_unchecked(underlying_0.clone()),
+                auction_data.lot.get_unchecked(underlying_0.clone())
+            );
+            assert_eq!(
+                preview.bid.get_unchecked(underlying_2.clone()),
+                auction_data
+                    .bid
+                    .get_unchecked(underlying_2.clone())
+                    .fixed_mul_ceil(&e, &0_7500000, &SCALAR_7)
+            );
+
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 250 * 5,
+                protocol_version: 22,
+                sequence_number: fill_block,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 172800,
+                min_persistent_entry_ttl: 172800,
+                max_entry_ttl: 9999999,
+            });
+            e.cost_estimate().budget().reset_unlimited();
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            let filled = fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
+            assert_eq!(filled.bid, preview.bid);
+            assert_eq!(filled.lot, preview.lot);
+            assert!(!storage::has_auction(&e, &0, &samwise));
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1211)")]
     fn test_create_liquidation_for_pool() {
@@ -716,6 +1216,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_backstop(&e, &Address::generate(&e));
@@ -829,6 +1331,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_backstop(&e, &backstop);
@@ -910,6 +1414,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -926,7 +1432,174 @@ mod tests {
     }
 
     #[test]
-    fn test_delete_user_liquidation() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_create_auction_non_reserve_asset() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let not_a_reserve = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        e.as_contract(&pool_address, || {
+            create_auction(
+                &e,
+                0,
+                &samwise,
+                &vec![&e, underlying_0],
+                &vec![&e, not_a_reserve],
+                100,
+            );
+        });
+    }
+
+    #[test]
+    fn test_load_auctions() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool_id = create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let underlying_0 = Address::generate(&e);
+
+        let liquidation_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_0.clone(), 12_0000000)],
+            block: 100,
+        };
+        let interest_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 5_0000000)],
+            lot: map![&e, (underlying_0.clone(), 6_0000000)],
+            block: 101,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &liquidation_data,
+            );
+            storage::set_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &samwise,
+                &interest_data,
+            );
+
+            let auctions = load_auctions(&e, &samwise);
+            assert_eq!(auctions.len(), 2);
+            let (auction_type, auction) = auctions.get_unchecked(0);
+            assert_eq!(auction_type, AuctionType::UserLiquidation as u32);
+            assert_eq!(auction.block, 100);
+            assert_eq!(auction.lot, liquidation_data.lot);
+            let (auction_type, auction) = auctions.get_unchecked(1);
+            assert_eq!(auction_type, AuctionType::InterestAuction as u32);
+            assert_eq!(auction.block, 101);
+            assert_eq!(auction.bid, interest_data.bid);
+
+            assert_eq!(load_auctions(&e, &frodo).len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_require_auction_bounds() {
+        let e = Env::default();
+
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 25_0000000)],
+            block: 51,
+        };
+
+        require_auction_bounds(&e, &auction_data, &None, &None);
+        require_auction_bounds(
+            &e,
+            &auction_data,
+            &Some(map![&e, (underlying_1.clone(), 25_0000000)]),
+            &Some(map![&e, (underlying_0.clone(), 10_0000000)]),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_require_auction_bounds_lot_too_small() {
+        let e = Env::default();
+
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 25_0000000)],
+            block: 51,
+        };
+
+        require_auction_bounds(
+            &e,
+            &auction_data,
+            &Some(map![&e, (underlying_1.clone(), 25_0000001)]),
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_require_auction_bounds_lot_missing_asset() {
+        let e = Env::default();
+
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 25_0000000)],
+            block: 51,
+        };
+
+        require_auction_bounds(
+            &e,
+            &auction_data,
+            &Some(map![&e, (underlying_0.clone(), 1)]),
+            &None,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_require_auction_bounds_bid_too_large() {
+        let e = Env::default();
+
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![&e, (underlying_1.clone(), 25_0000000)],
+            block: 51,
+        };
+
+        require_auction_bounds(
+            &e,
+            &auction_data,
+            &None,
+            &Some(map![&e, (underlying_0.clone(), 9_9999999)]),
+        );
+    }
+
+    #[test]
+    fn test_delete_user_liquidation() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -955,22 +1628,516 @@ mod tests {
         });
     }
 
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_delete_user_liquidation_does_not_exist() {
-        let e = Env::default();
-        e.mock_all_auths();
-        let pool_id = create_pool(&e);
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_delete_user_liquidation_does_not_exist() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool_id = create_pool(&e);
+
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool_id, || {
+            delete_liquidation(&e, &samwise);
+        });
+    }
+
+    #[test]
+    fn test_delete_auction_after_price_recovers() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_backstop(&e, &Address::generate(&e));
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            e.cost_estimate().budget().reset_unlimited();
+            create_auction(
+                &e,
+                0,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                45,
+            );
+            assert!(storage::has_auction(&e, &0, &samwise));
+        });
+
+        // collateral price recovers such that samwise is healthy again
+        oracle_client.set_price_stable(&vec![&e, 3_0000000, 4_0000000, 50_0000000]);
+
+        e.as_contract(&pool_address, || {
+            e.cost_estimate().budget().reset_unlimited();
+            delete_auction(&e, 0, &samwise);
+            assert!(!storage::has_auction(&e, &0, &samwise));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_delete_auction_user_unhealthy() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_backstop(&e, &Address::generate(&e));
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            e.cost_estimate().budget().reset_unlimited();
+            create_auction(
+                &e,
+                0,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                45,
+            );
+            assert!(storage::has_auction(&e, &0, &samwise));
+        });
+
+        e.as_contract(&pool_address, || {
+            e.cost_estimate().budget().reset_unlimited();
+            delete_auction(&e, 0, &samwise);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_delete_auction_not_liquidation() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool_id = create_pool(&e);
+
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool_id, || {
+            storage::set_auction(
+                &e,
+                &(AuctionType::BadDebtAuction as u32),
+                &samwise,
+                &AuctionData {
+                    bid: map![&e],
+                    lot: map![&e],
+                    block: 100,
+                },
+            );
+
+            delete_auction(&e, AuctionType::BadDebtAuction as u32, &samwise);
+        });
+    }
+
+    #[test]
+    fn test_fill() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 175,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+        e.cost_estimate().budget().reset_unlimited();
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_2.clone(), 1_2375000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 30_5595329),
+                (underlying_1.clone(), 1_5395739)
+            ],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(&e, &0, &samwise, &auction_data);
+
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 200 * 5,
+                protocol_version: 22,
+                sequence_number: 176 + 200,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 172800,
+                min_persistent_entry_ttl: 172800,
+                max_entry_ttl: 9999999,
+            });
+            e.cost_estimate().budget().reset_unlimited();
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
+            let has_auction = storage::has_auction(&e, &0, &samwise);
+            assert_eq!(has_auction, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1225)")]
+    fn test_fill_before_min_full_fill_blocks() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 175,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+        e.cost_estimate().budget().reset_unlimited();
 
-        let samwise = Address::generate(&e);
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_2.clone(), 1_2375000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 30_5595329),
+                (underlying_1.clone(), 1_5395739)
+            ],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(&e, &0, &samwise, &auction_data);
+            storage::set_min_liq_full_fill_blocks(&e, &201);
 
-        e.as_contract(&pool_id, || {
-            delete_liquidation(&e, &samwise);
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 200 * 5,
+                protocol_version: 22,
+                sequence_number: 176 + 200,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 172800,
+                min_persistent_entry_ttl: 172800,
+                max_entry_ttl: 9999999,
+            });
+            e.cost_estimate().budget().reset_unlimited();
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
         });
     }
 
     #[test]
-    fn test_fill() {
+    fn test_fill_after_min_full_fill_blocks() {
         let e = Env::default();
 
         e.mock_all_auths();
@@ -1044,6 +2211,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1058,6 +2227,7 @@ mod tests {
             storage::set_user_positions(&e, &samwise, &positions);
             storage::set_pool_config(&e, &pool_config);
             storage::set_auction(&e, &0, &samwise, &auction_data);
+            storage::set_min_liq_full_fill_blocks(&e, &200);
 
             e.ledger().set(LedgerInfo {
                 timestamp: 12345 + 200 * 5,
@@ -1153,6 +2323,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1275,6 +2447,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1339,54 +2513,299 @@ mod tests {
                 bid: map![&e, (underlying_2.clone(), 24_7500000)],
                 lot: map![
                     &e,
-                    (underlying_0.clone(), 2_4750000),
-                    (underlying_1.clone(), 0_2475000)
+                    (underlying_0.clone(), 2_4750000),
+                    (underlying_1.clone(), 0_2475000)
+                ],
+                block: 176,
+            };
+            let new_auction = storage::get_auction(&e, &0, &samwise);
+            assert_eq!(new_auction.bid, expected_new_auction_data.bid);
+            assert_eq!(new_auction.lot, expected_new_auction_data.lot);
+            assert_eq!(new_auction.block, expected_new_auction_data.block);
+
+            // full fill at 50% bid mod
+            e.ledger().set(LedgerInfo {
+                timestamp: 12345 + 300 * 5,
+                protocol_version: 22,
+                sequence_number: 176 + 300,
+                network_id: Default::default(),
+                base_reserve: 10,
+                min_temp_entry_ttl: 172800,
+                min_persistent_entry_ttl: 172800,
+                max_entry_ttl: 9999999,
+            });
+            let mut pool = Pool::load(&e);
+            let mut frodo_state = User::load(&e, &frodo);
+            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
+            let new_auction = storage::has_auction(&e, &0, &samwise);
+            assert_eq!(new_auction, false);
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(
+                samwise_positions
+                    .collateral
+                    .get(reserve_config_0.index)
+                    .unwrap_optimized(),
+                30_000_0000 - 1_250_0000 - 5_000_0002 - 2_499_9998
+            );
+            assert_eq!(
+                samwise_positions
+                    .collateral
+                    .get(reserve_config_1.index)
+                    .unwrap_optimized(),
+                3_000_0000 - 125_0000 - 500_0000 - 250_0000
+            );
+            assert_eq!(
+                samwise_positions
+                    .liabilities
+                    .get(reserve_config_2.index)
+                    .unwrap_optimized(),
+                200_000_0000 - 25_000_0000 - 50_000_0025 - 12_6249975
+            );
+        });
+    }
+
+    #[test]
+    fn test_preview_fill_matches_fill() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 175,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, reserve_data_0) = testutils::default_reserve_meta();
+
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
+
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_2.clone(), 100_000_0000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 10_000_0000),
+                (underlying_1.clone(), 1_000_0000)
+            ],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 30_000_0000),
+                (reserve_config_1.index, 3_000_0000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 200_000_0000),],
+            supply: map![&e],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(&e, &0, &samwise, &auction_data);
+
+            // (blocks since auction start, percent filled)
+            for (block_offset, percent_filled) in [(50, 25), (200, 67), (300, 100)] {
+                e.ledger().set(LedgerInfo {
+                    timestamp: 12345 + block_offset * 5,
+                    protocol_version: 22,
+                    sequence_number: 176 + block_offset as u32,
+                    network_id: Default::default(),
+                    base_reserve: 10,
+                    min_temp_entry_ttl: 172800,
+                    min_persistent_entry_ttl: 172800,
+                    max_entry_ttl: 9999999,
+                });
+                let pre_auction = storage::get_auction(&e, &0, &samwise);
+                let (preview_bid, preview_lot) = preview_fill(&e, 0, &samwise, percent_filled);
+
+                // preview does not modify the auction
+                let post_preview_auction = storage::get_auction(&e, &0, &samwise);
+                assert_eq!(post_preview_auction.bid, pre_auction.bid);
+                assert_eq!(post_preview_auction.lot, pre_auction.lot);
+
+                let mut pool = Pool::load(&e);
+                let mut frodo_state = User::load(&e, &frodo);
+                let filled = fill(&e, &mut pool, 0, &samwise, &mut frodo_state, percent_filled);
+                assert_eq!(filled.bid, preview_bid);
+                assert_eq!(filled.lot, preview_lot);
+            }
+            assert!(!storage::has_auction(&e, &0, &samwise));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_preview_fill_fails_pct_too_large() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let samwise = Address::generate(&e);
+        let pool_address = create_pool(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (Address::generate(&e), 100_0000000)],
+            lot: map![&e, (Address::generate(&e), 10_0000000)],
+            block: 176,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_auction(&e, &1, &samwise, &auction_data);
+
+            preview_fill(&e, 1, &samwise, 101);
+        });
+    }
+
+    #[test]
+    fn test_require_fill_min_lot() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 176 + 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let samwise = Address::generate(&e);
+        let pool_address = create_pool(&e);
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (Address::generate(&e), 100_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 10_0000000),
+                (underlying_1.clone(), 1_0000000)
+            ],
+            block: 176,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_auction(&e, &1, &samwise, &auction_data);
+
+            // 50% of the lot is scaled in after 100 blocks
+            require_fill_min_lot(
+                &e,
+                1,
+                &samwise,
+                50,
+                &map![
+                    &e,
+                    (underlying_0.clone(), 2_5000000),
+                    (underlying_1.clone(), 0_2500000)
                 ],
-                block: 176,
-            };
-            let new_auction = storage::get_auction(&e, &0, &samwise);
-            assert_eq!(new_auction.bid, expected_new_auction_data.bid);
-            assert_eq!(new_auction.lot, expected_new_auction_data.lot);
-            assert_eq!(new_auction.block, expected_new_auction_data.block);
-
-            // full fill at 50% bid mod
-            e.ledger().set(LedgerInfo {
-                timestamp: 12345 + 300 * 5,
-                protocol_version: 22,
-                sequence_number: 176 + 300,
-                network_id: Default::default(),
-                base_reserve: 10,
-                min_temp_entry_ttl: 172800,
-                min_persistent_entry_ttl: 172800,
-                max_entry_ttl: 9999999,
-            });
-            let mut pool = Pool::load(&e);
-            let mut frodo_state = User::load(&e, &frodo);
-            fill(&e, &mut pool, 0, &samwise, &mut frodo_state, 100);
-            let new_auction = storage::has_auction(&e, &0, &samwise);
-            assert_eq!(new_auction, false);
-            let samwise_positions = storage::get_user_positions(&e, &samwise);
-            assert_eq!(
-                samwise_positions
-                    .collateral
-                    .get(reserve_config_0.index)
-                    .unwrap_optimized(),
-                30_000_0000 - 1_250_0000 - 5_000_0002 - 2_499_9998
-            );
-            assert_eq!(
-                samwise_positions
-                    .collateral
-                    .get(reserve_config_1.index)
-                    .unwrap_optimized(),
-                3_000_0000 - 125_0000 - 500_0000 - 250_0000
             );
-            assert_eq!(
-                samwise_positions
-                    .liabilities
-                    .get(reserve_config_2.index)
-                    .unwrap_optimized(),
-                200_000_0000 - 25_000_0000 - 50_000_0025 - 12_6249975
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_require_fill_min_lot_under_min() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 176 + 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+
+        let samwise = Address::generate(&e);
+        let pool_address = create_pool(&e);
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (Address::generate(&e), 100_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 10_0000000),
+                (underlying_1.clone(), 1_0000000)
+            ],
+            block: 176,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_auction(&e, &1, &samwise, &auction_data);
+            let (_, quoted_lot) = preview_fill(&e, 1, &samwise, 50);
+
+            // another filler takes half of the auction before the quoted fill lands
+            storage::set_auction(
+                &e,
+                &1,
+                &samwise,
+                &AuctionData {
+                    bid: map![&e, (Address::generate(&e), 50_0000000)],
+                    lot: map![
+                        &e,
+                        (underlying_0.clone(), 5_0000000),
+                        (underlying_1.clone(), 0_5000000)
+                    ],
+                    block: 176,
+                },
             );
+
+            require_fill_min_lot(&e, 1, &samwise, 50, &quoted_lot);
         });
     }
 
@@ -1465,6 +2884,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1588,6 +3009,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1658,7 +3081,13 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &map![&e],
+            &default_auction_config(),
+        );
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             100_0000000
@@ -1677,7 +3106,13 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &map![&e],
+            &default_auction_config(),
+        );
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             100_0000000
@@ -1699,7 +3134,13 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &map![&e],
+            &default_auction_config(),
+        );
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             100_0000000
@@ -1721,7 +3162,13 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &map![&e],
+            &default_auction_config(),
+        );
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
             50_0000000
@@ -1743,7 +3190,13 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction) = scale_auction(&e, &base_auction_data, 100);
+        let (scaled_auction, remaining_auction) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &map![&e],
+            &default_auction_config(),
+        );
         assert_eq!(scaled_auction.bid.len(), 0);
         assert_eq!(
             scaled_auction.lot.get_unchecked(underlying_1.clone()),
@@ -1827,6 +3280,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1884,7 +3339,13 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 50);
+        let (scaled_auction, remaining_auction_option) = scale_auction(
+            &e,
+            &base_auction_data,
+            50,
+            &map![&e],
+            &default_auction_config(),
+        );
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
@@ -1912,7 +3373,13 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 60);
+        let (scaled_auction, remaining_auction_option) = scale_auction(
+            &e,
+            &base_auction_data,
+            60,
+            &map![&e],
+            &default_auction_config(),
+        );
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
@@ -1943,7 +3410,13 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 60);
+        let (scaled_auction, remaining_auction_option) = scale_auction(
+            &e,
+            &base_auction_data,
+            60,
+            &map![&e],
+            &default_auction_config(),
+        );
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(
             scaled_auction.bid.get_unchecked(underlying_0.clone()),
@@ -1973,7 +3446,13 @@ mod tests {
             min_persistent_entry_ttl: 172800,
             max_entry_ttl: 9999999,
         });
-        let (scaled_auction, remaining_auction_option) = scale_auction(&e, &base_auction_data, 50);
+        let (scaled_auction, remaining_auction_option) = scale_auction(
+            &e,
+            &base_auction_data,
+            50,
+            &map![&e],
+            &default_auction_config(),
+        );
         let remaining_auction = remaining_auction_option.unwrap();
         assert_eq!(scaled_auction.bid.len(), 0);
         assert_eq!(
@@ -2015,7 +3494,13 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (_, _) = scale_auction(&e, &base_auction_data, 0);
+        let (_, _) = scale_auction(
+            &e,
+            &base_auction_data,
+            0,
+            &map![&e],
+            &default_auction_config(),
+        );
     }
 
     #[test]
@@ -2043,6 +3528,256 @@ mod tests {
             max_entry_ttl: 9999999,
         });
 
-        let (_, _) = scale_auction(&e, &base_auction_data, 101);
+        let (_, _) = scale_auction(
+            &e,
+            &base_auction_data,
+            101,
+            &map![&e],
+            &default_auction_config(),
+        );
+    }
+
+    #[test]
+    fn test_scale_auction_short_ramp() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let base_auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 100_0000000)],
+            lot: map![&e, (underlying_1.clone(), 100_0000000)],
+            block: 1000,
+        };
+        // lot ramps over 50 blocks and bid over the next 50 blocks, moving 2% every block
+        let auction_config = AuctionConfig {
+            auction_lot_ramp_blocks: 50,
+            auction_bid_ramp_blocks: 50,
+            auction_per_block_scalar: 0_0200000,
+        };
+
+        // 25 blocks
+        let (scaled_auction, _) = scale_auction_at_block(
+            &e,
+            &base_auction_data,
+            100,
+            1025,
+            &map![&e],
+            &auction_config,
+        );
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            100_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            50_0000000
+        );
+
+        // 50 blocks - lot fully ramped
+        let (scaled_auction, _) = scale_auction_at_block(
+            &e,
+            &base_auction_data,
+            100,
+            1050,
+            &map![&e],
+            &auction_config,
+        );
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            100_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            100_0000000
+        );
+
+        // 75 blocks
+        let (scaled_auction, _) = scale_auction_at_block(
+            &e,
+            &base_auction_data,
+            100,
+            1075,
+            &map![&e],
+            &auction_config,
+        );
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            50_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            100_0000000
+        );
+
+        // 100 blocks - bid fully ramped
+        let (scaled_auction, _) = scale_auction_at_block(
+            &e,
+            &base_auction_data,
+            100,
+            1100,
+            &map![&e],
+            &auction_config,
+        );
+        assert_eq!(scaled_auction.bid.len(), 0);
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            100_0000000
+        );
+    }
+
+    #[test]
+    fn test_scale_auction_reserve_lot_rate() {
+        let e = Env::default();
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+        let underlying_2 = Address::generate(&e);
+
+        let base_auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 10_0000000)],
+            lot: map![
+                &e,
+                (underlying_1.clone(), 10_0000000),
+                (underlying_2.clone(), 10_0000000)
+            ],
+            block: 1000,
+        };
+        // underlying_2 lots scale at 1% per block
+        let lot_rates = map![&e, (underlying_2.clone(), 0_0100000)];
+
+        // 50 blocks
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1050,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+        let (scaled_auction, _) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &lot_rates,
+            &default_auction_config(),
+        );
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            10_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            2_5000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_2.clone()),
+            5_0000000
+        );
+
+        // 150 blocks - the high incentive lot is capped at 100%
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1150,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+        let (scaled_auction, _) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &lot_rates,
+            &default_auction_config(),
+        );
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            10_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            7_5000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_2.clone()),
+            10_0000000
+        );
+
+        // 300 blocks - both lots are at 100%
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 1300,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 172800,
+            min_persistent_entry_ttl: 172800,
+            max_entry_ttl: 9999999,
+        });
+        let (scaled_auction, _) = scale_auction(
+            &e,
+            &base_auction_data,
+            100,
+            &lot_rates,
+            &default_auction_config(),
+        );
+        assert_eq!(
+            scaled_auction.bid.get_unchecked(underlying_0.clone()),
+            5_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_1.clone()),
+            10_0000000
+        );
+        assert_eq!(
+            scaled_auction.lot.get_unchecked(underlying_2.clone()),
+            10_0000000
+        );
+    }
+
+    #[test]
+    fn test_load_lot_rates() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let pool_address = create_pool(&e);
+        let backstop_token = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config,
+            &reserve_data,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
+        reserve_config.auction_lot_rate = 0_0100000;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config,
+            &reserve_data,
+        );
+
+        let lot = map![
+            &e,
+            (underlying_0.clone(), 10_0000000),
+            (underlying_1.clone(), 10_0000000),
+            (backstop_token.clone(), 10_0000000)
+        ];
+        e.as_contract(&pool_address, || {
+            let lot_rates = load_lot_rates(&e, &lot);
+            assert_eq!(lot_rates.len(), 1);
+            assert_eq!(lot_rates.get_unchecked(underlying_1.clone()), 0_0100000);
+        });
     }
 }
@@ -3,7 +3,10 @@ use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{map, panic_with_error, Address, Env, Vec};
 
 use crate::auctions::auction::AuctionData;
-use crate::pool::{Pool, PositionData, User};
+use crate::constants::SCALAR_7;
+use crate::dependencies::BackstopClient;
+use crate::pool::{calc_pool_backstop_threshold, Pool, PositionData, User};
+use crate::storage::LiquidationBonusTier;
 use crate::Positions;
 use crate::{errors::PoolError, storage};
 
@@ -52,41 +55,8 @@ pub fn create_user_liq_auction_data(
     }
 
     // build position data from included assets
-    let mut positions_auctioned = Positions::env_default(e);
-    for bid_asset in bid {
-        // these will be cached if the bid is valid
-        let reserve = pool.load_reserve(e, &bid_asset, false);
-        match user_state.positions.liabilities.get(reserve.config.index) {
-            Some(amount) => {
-                positions_auctioned
-                    .liabilities
-                    .set(reserve.config.index, amount);
-            }
-            None => {
-                panic_with_error!(e, PoolError::InvalidBid);
-            }
-        }
-    }
-    if positions_auctioned.liabilities.len() == 0 {
-        panic_with_error!(e, PoolError::InvalidBid);
-    }
-    for lot_asset in lot {
-        // these will be cached if the lot is valid
-        let reserve = pool.load_reserve(e, &lot_asset, false);
-        match user_state.positions.collateral.get(reserve.config.index) {
-            Some(amount) => {
-                positions_auctioned
-                    .collateral
-                    .set(reserve.config.index, amount);
-            }
-            None => {
-                panic_with_error!(e, PoolError::InvalidLot);
-            }
-        }
-    }
-    if positions_auctioned.collateral.len() == 0 {
-        panic_with_error!(e, PoolError::InvalidLot);
-    }
+    let positions_auctioned =
+        load_auctioned_positions(e, &mut pool, &user_state.positions, bid, lot);
     let position_data_inc =
         PositionData::calculate_from_positions(e, &mut pool, &positions_auctioned);
     let is_all_collateral = position_data_inc.collateral_raw == position_data.collateral_raw;
@@ -105,24 +75,7 @@ pub fn create_user_liq_auction_data(
         i128(percent_liquidated_to_check) * position_data.scalar / 100; // scale to decimal form with scalar decimals
 
     // ensure liquidation size is fair and the collateral is large enough to allow for the auction to price the liquidation
-    let avg_cf = position_data_inc.collateral_base.fixed_div_floor(
-        e,
-        &position_data_inc.collateral_raw,
-        &position_data_inc.scalar,
-    );
-    // avg_lf is the inverse of the average liability factor
-    let avg_lf = position_data_inc.liability_base.fixed_div_floor(
-        e,
-        &position_data_inc.liability_raw,
-        &position_data_inc.scalar,
-    );
-    let est_incentive = (position_data_inc.scalar
-        - avg_cf.fixed_div_ceil(e, &avg_lf, &position_data_inc.scalar))
-    .fixed_div_ceil(
-        e,
-        &(2 * position_data_inc.scalar),
-        &position_data_inc.scalar,
-    ) + position_data_inc.scalar;
+    let est_incentive = calc_est_incentive(e, &position_data_inc);
 
     let est_withdrawn_collateral = position_data_inc
         .liability_raw
@@ -203,6 +156,211 @@ pub fn create_user_liq_auction_data(
     }
 }
 
+/// Calculate the minimum liquidation percent required to bring a user's health factor to at least
+/// `target_hf` based on current prices
+///
+/// Returns 100 if the target health factor cannot be reached by liquidating the included positions
+///
+/// ### Arguments
+/// * `user` - The user being liquidated
+/// * `bid` - The set of liability assets to include in the auction bid
+/// * `lot` - The set of collateral assets to include in the auction lot
+/// * `target_hf` - The health factor the user should have after the liquidation (7 decimals). Must be
+///                 over 1_0000000 and under 1_1500000, the maximum post-liquidation health factor.
+///
+/// ### Panics
+/// If the target health factor is out of range, the user is not underwater, or the bid or lot is invalid
+pub fn calc_user_liq_percent_to_target(
+    e: &Env,
+    user: &Address,
+    bid: &Vec<Address>,
+    lot: &Vec<Address>,
+    target_hf: i128,
+) -> u32 {
+    if target_hf <= SCALAR_7 || target_hf >= 1_1500000 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool = Pool::load(e);
+    let user_state = User::load(e, user);
+    let position_data = PositionData::calculate_from_positions(e, &mut pool, &user_state.positions);
+    if position_data.liability_base < position_data.collateral_base {
+        panic_with_error!(e, PoolError::InvalidLiquidation);
+    }
+
+    let positions_auctioned =
+        load_auctioned_positions(e, &mut pool, &user_state.positions, bid, lot);
+    let position_data_inc =
+        PositionData::calculate_from_positions(e, &mut pool, &positions_auctioned);
+    let est_incentive = calc_est_incentive(e, &position_data_inc);
+
+    // search for the smallest percent that brings the user to the target health factor
+    let mut low = 1u32;
+    let mut high = 100u32;
+    while low < high {
+        let percent = (low + high) / 2;
+        let new_data = calc_est_post_liq_position_data(
+            e,
+            &mut pool,
+            &user_state.positions,
+            &positions_auctioned,
+            &position_data_inc,
+            est_incentive,
+            percent,
+        );
+        if new_data.is_hf_under(e, target_hf) {
+            low = percent + 1;
+        } else {
+            high = percent;
+        }
+    }
+    low
+}
+
+/// Load the subset of the user's positions included in a liquidation
+///
+/// ### Panics
+/// If any bid or lot asset is not a position of the user, or if the bid or lot is empty
+fn load_auctioned_positions(
+    e: &Env,
+    pool: &mut Pool,
+    user_positions: &Positions,
+    bid: &Vec<Address>,
+    lot: &Vec<Address>,
+) -> Positions {
+    let mut positions_auctioned = Positions::env_default(e);
+    for bid_asset in bid {
+        // these will be cached if the bid is valid
+        let reserve = pool.load_reserve(e, &bid_asset, false);
+        match user_positions.liabilities.get(reserve.config.index) {
+            Some(amount) => {
+                positions_auctioned
+                    .liabilities
+                    .set(reserve.config.index, amount);
+            }
+            None => {
+                panic_with_error!(e, PoolError::InvalidBid);
+            }
+        }
+    }
+    if positions_auctioned.liabilities.len() == 0 {
+        panic_with_error!(e, PoolError::InvalidBid);
+    }
+    for lot_asset in lot {
+        // these will be cached if the lot is valid
+        let reserve = pool.load_reserve(e, &lot_asset, false);
+        match user_positions.collateral.get(reserve.config.index) {
+            Some(amount) => {
+                positions_auctioned
+                    .collateral
+                    .set(reserve.config.index, amount);
+            }
+            None => {
+                panic_with_error!(e, PoolError::InvalidLot);
+            }
+        }
+    }
+    if positions_auctioned.collateral.len() == 0 {
+        panic_with_error!(e, PoolError::InvalidLot);
+    }
+    positions_auctioned
+}
+
+/// Estimate the liquidation incentive for the included positions
+fn calc_est_incentive(e: &Env, position_data_inc: &PositionData) -> i128 {
+    let avg_cf = position_data_inc.collateral_base.fixed_div_floor(
+        e,
+        &position_data_inc.collateral_raw,
+        &position_data_inc.scalar,
+    );
+    // avg_lf is the inverse of the average liability factor
+    let avg_lf = position_data_inc.liability_base.fixed_div_floor(
+        e,
+        &position_data_inc.liability_raw,
+        &position_data_inc.scalar,
+    );
+    let mut est_incentive = (position_data_inc.scalar
+        - avg_cf.fixed_div_ceil(e, &avg_lf, &position_data_inc.scalar))
+    .fixed_div_ceil(
+        e,
+        &(2 * position_data_inc.scalar),
+        &position_data_inc.scalar,
+    ) + position_data_inc.scalar;
+    // tighten the incentive if the pool's backstop coverage has fallen below a bonus tier
+    if let Some(max_bonus) = load_max_liq_bonus(e) {
+        let max_incentive = max_bonus.fixed_mul_floor(e, &position_data_inc.scalar, &SCALAR_7);
+        est_incentive = est_incentive.min(max_incentive);
+    }
+    est_incentive
+}
+
+/// Estimate the user's position data after liquidating `percent` of the included positions
+fn calc_est_post_liq_position_data(
+    e: &Env,
+    pool: &mut Pool,
+    user_positions: &Positions,
+    positions_auctioned: &Positions,
+    position_data_inc: &PositionData,
+    est_incentive: i128,
+    percent: u32,
+) -> PositionData {
+    let scalar = position_data_inc.scalar;
+    let percent_scaled = i128(percent) * scalar / 100;
+    let est_withdrawn_collateral = position_data_inc
+        .liability_raw
+        .fixed_mul_floor(e, &percent_scaled, &scalar)
+        .fixed_mul_floor(e, &est_incentive, &scalar);
+    let est_withdrawn_collateral_pct = est_withdrawn_collateral
+        .fixed_div_ceil(e, &position_data_inc.collateral_raw, &scalar)
+        .min(scalar);
+
+    let mut new_positions = user_positions.clone();
+    for (index, amount) in positions_auctioned.collateral.iter() {
+        let removed = amount.fixed_mul_ceil(e, &est_withdrawn_collateral_pct, &scalar);
+        new_positions
+            .collateral
+            .set(index, (amount - removed).max(0));
+    }
+    for (index, amount) in positions_auctioned.liabilities.iter() {
+        let removed = amount.fixed_mul_ceil(e, &percent_scaled, &scalar);
+        new_positions
+            .liabilities
+            .set(index, (amount - removed).max(0));
+    }
+    PositionData::calculate_from_positions(e, pool, &new_positions)
+}
+
+/// Load the maximum liquidation incentive allowed based on the pool's current backstop coverage
+///
+/// Returns None if no liquidation bonus tiers are set, or if the coverage is above all tiers
+fn load_max_liq_bonus(e: &Env) -> Option<i128> {
+    let tiers = storage::get_liq_bonus_tiers(e);
+    if tiers.is_empty() {
+        return None;
+    }
+    let backstop_client = BackstopClient::new(e, &storage::get_backstop(e));
+    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+    calc_max_liq_bonus(&tiers, calc_pool_backstop_threshold(&pool_backstop_data))
+}
+
+/// Calculate the maximum liquidation incentive for a given backstop coverage. The tightest
+/// bonus of all tiers with a coverage above the current coverage is used.
+///
+/// ### Arguments
+/// * `tiers` - The liquidation bonus tiers
+/// * `coverage` - The pool's backstop coverage (7 decimals)
+fn calc_max_liq_bonus(tiers: &Vec<LiquidationBonusTier>, coverage: i128) -> Option<i128> {
+    let mut max_bonus: Option<i128> = None;
+    for tier in tiers.iter() {
+        if coverage < tier.coverage {
+            max_bonus = match max_bonus {
+                Some(bonus) => Some(bonus.min(tier.max_bonus)),
+                None => Some(tier.max_bonus),
+            };
+        }
+    }
+    max_bonus
+}
+
 pub fn fill_user_liq_auction(
     e: &Env,
     pool: &mut Pool,
@@ -269,6 +427,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -311,6 +471,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -347,6 +509,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -383,6 +547,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -419,6 +585,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_pool_config(&e, &pool_config);
@@ -510,6 +678,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -609,6 +779,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -708,6 +880,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -807,6 +981,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -922,6 +1098,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1036,6 +1214,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1058,6 +1238,563 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_calc_user_liq_percent_to_target() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+        let backstop_address = Address::generate(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+
+            let bid = vec![&e, underlying_2.clone()];
+            let lot = vec![&e, underlying_0.clone(), underlying_1.clone()];
+            let target_hf = 1_1000000;
+            let percent = calc_user_liq_percent_to_target(&e, &samwise, &bid, &lot, target_hf);
+            assert!(percent > 0 && percent < 100);
+
+            // simulate the liquidation and verify the target health factor is restored
+            let result = create_user_liq_auction_data(&e, &samwise, &bid, &lot, percent);
+            let mut pool = Pool::load(&e);
+            let mut user_state = User::load(&e, &samwise);
+            user_state.rm_positions(&e, &mut pool, result.lot, result.bid);
+            let new_data =
+                PositionData::calculate_from_positions(&e, &mut pool, &user_state.positions);
+            assert!(!new_data.is_hf_under(&e, target_hf));
+
+            // one percent less does not reach the target health factor
+            let mut pool = Pool::load(&e);
+            let user_state = User::load(&e, &samwise);
+            let positions_auctioned =
+                load_auctioned_positions(&e, &mut pool, &user_state.positions, &bid, &lot);
+            let position_data_inc =
+                PositionData::calculate_from_positions(&e, &mut pool, &positions_auctioned);
+            let est_incentive = calc_est_incentive(&e, &position_data_inc);
+            let prev_data = calc_est_post_liq_position_data(
+                &e,
+                &mut pool,
+                &user_state.positions,
+                &positions_auctioned,
+                &position_data_inc,
+                est_incentive,
+                percent - 1,
+            );
+            assert!(prev_data.is_hf_under(&e, target_hf));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_calc_user_liq_percent_to_target_under_one() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+        let backstop_address = Address::generate(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+
+            calc_user_liq_percent_to_target(
+                &e,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                1_0000000,
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_calc_user_liq_percent_to_target_over_max() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+        let backstop_address = Address::generate(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop_address);
+
+            calc_user_liq_percent_to_target(
+                &e,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                1_1500000,
+            );
+        });
+    }
+
+    #[test]
+    fn test_create_user_liquidation_auction_caps_bonus_below_coverage_tier() {
+        let e = Env::default();
+
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 50,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (oracle_address, oracle_client) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (blnd, _) = testutils::create_token_contract(&e, &bombadil);
+        let (usdc, _) = testutils::create_token_contract(&e, &bombadil);
+        let (lp_token, _) = testutils::create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        // no backstop deposits - the pool's backstop coverage is 0
+        testutils::create_backstop(&e, &pool_address, &lp_token, &usdc, &blnd);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+                Asset::Stellar(underlying_2.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 4_0000000, 50_0000000]);
+
+        let liq_pct = 35;
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_pool_config(&e, &pool_config);
+
+            // no tiers set - incentive is uncapped
+            let uncapped = create_user_liq_auction_data(
+                &e,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                liq_pct,
+            );
+
+            storage::set_liq_bonus_tiers(
+                &e,
+                &vec![
+                    &e,
+                    LiquidationBonusTier {
+                        coverage: 1_0000000,
+                        max_bonus: 1_1000000,
+                    },
+                ],
+            );
+            let capped = create_user_liq_auction_data(
+                &e,
+                &samwise,
+                &vec![&e, underlying_2.clone()],
+                &vec![&e, underlying_0.clone(), underlying_1.clone()],
+                liq_pct,
+            );
+
+            assert_eq!(
+                capped.bid.get_unchecked(underlying_2.clone()),
+                uncapped.bid.get_unchecked(underlying_2)
+            );
+            assert!(
+                capped.lot.get_unchecked(underlying_0.clone())
+                    < uncapped.lot.get_unchecked(underlying_0)
+            );
+            assert!(
+                capped.lot.get_unchecked(underlying_1.clone())
+                    < uncapped.lot.get_unchecked(underlying_1)
+            );
+        });
+    }
+
+    #[test]
+    fn test_calc_max_liq_bonus() {
+        let e = Env::default();
+
+        let tiers = vec![
+            &e,
+            LiquidationBonusTier {
+                coverage: 0_5000000,
+                max_bonus: 1_0500000,
+            },
+            LiquidationBonusTier {
+                coverage: 1_0000000,
+                max_bonus: 1_1000000,
+            },
+        ];
+
+        // coverage above all tiers
+        assert_eq!(calc_max_liq_bonus(&tiers, 1_0000000), None);
+        assert_eq!(calc_max_liq_bonus(&tiers, 5_0000000), None);
+
+        // coverage drops below the first tier
+        assert_eq!(calc_max_liq_bonus(&tiers, 0_9999999), Some(1_1000000));
+        assert_eq!(calc_max_liq_bonus(&tiers, 0_5000000), Some(1_1000000));
+
+        // coverage drops below the second tier, and the cap tightens
+        assert_eq!(calc_max_liq_bonus(&tiers, 0_4999999), Some(1_0500000));
+        assert_eq!(calc_max_liq_bonus(&tiers, 0), Some(1_0500000));
+
+        // no tiers
+        assert_eq!(calc_max_liq_bonus(&vec![&e], 0), None);
+    }
+
     #[test]
     fn test_create_user_liquidation_auction_weird_scalar() {
         let e = Env::default();
@@ -1137,6 +1874,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1238,6 +1977,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1345,6 +2086,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1456,6 +2199,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -1611,6 +2356,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1727,6 +2474,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1843,6 +2592,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -1957,6 +2708,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2068,6 +2821,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2177,6 +2932,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2288,6 +3045,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2392,6 +3151,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_address, || {
             storage::set_user_positions(&e, &samwise, &positions);
@@ -2509,6 +3270,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -2684,6 +3447,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -2820,6 +3585,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let positions: Positions = Positions {
             collateral: map![
@@ -6,11 +6,35 @@ pub const SCALAR_12: i128 = 1_000_000_000_000;
 /// Fixed-point scalar for 7 decimal numbers
 pub const SCALAR_7: i128 = 1_0000000;
 
+/// Fixed-point scalar for basis points
+pub const BPS_SCALAR: i128 = 10_000;
+
 /// Seconds per year
 pub const SECONDS_PER_YEAR: i128 = 31536000;
 
 /// Seconds per week
 pub const SECONDS_PER_WEEK: u64 = 604800;
 
+/// Estimated seconds per ledger
+pub const SECONDS_PER_LEDGER: i128 = 5;
+
 /// Max amount of reserves that can be added to a pool
 pub const MAX_RESERVES: u32 = 50;
+
+/// Max amount of emission index checkpoints stored for a reserve token
+pub const MAX_EMIS_HISTORY: u32 = 24;
+
+/// Max amount of requests that can be processed in a single submit
+pub const MAX_SUBMIT_REQUESTS: u32 = 32;
+
+/********** Auctions **********/
+
+/// Default number of blocks an auction lot takes to scale from 0% to 100%
+pub const DEFAULT_AUCTION_LOT_RAMP_BLOCKS: u32 = 200;
+
+/// Default number of blocks an auction bid takes to scale from 100% to 0% after the lot ramp
+pub const DEFAULT_AUCTION_BID_RAMP_BLOCKS: u32 = 200;
+
+/// Default rate auction modifiers move every block (7 decimals)
+#[allow(clippy::zero_prefixed_literal)]
+pub const DEFAULT_AUCTION_PER_BLOCK_SCALAR: i128 = 0_0050000;
@@ -1,12 +1,12 @@
 use crate::{
-    auctions::{self, AuctionData},
+    auctions::{self, AuctionData, AuctionType},
     emissions::{self, ReserveEmissionMetadata},
     events::PoolEvents,
-    pool::{self, FlashLoan, Positions, Request, Reserve},
-    storage::{self, ReserveConfig},
+    pool::{self, FlashLoan, HealthData, Positions, Request, Reserve},
+    storage::{self, AuctionConfig, LiquidationBonusTier, ReserveConfig},
     PoolConfig, ReserveEmissionData, UserEmissionData,
 };
-use soroban_sdk::{contract, contractclient, contractimpl, Address, Env, String, Vec};
+use soroban_sdk::{contract, contractclient, contractimpl, vec, Address, Env, Map, String, Vec};
 
 /// ### Pool
 ///
@@ -25,6 +25,22 @@ pub trait Pool {
     /// If the caller is not the admin
     fn set_admin(e: Env, new_admin: Address);
 
+    /// (Admin only) Propose a new address as the admin of this pool. The proposed admin must
+    /// call `accept_admin` to become the admin. Proposing a new address replaces any pending proposal.
+    ///
+    /// ### Arguments
+    /// * `new_admin` - The proposed admin address
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn propose_admin(e: Env, new_admin: Address);
+
+    /// (Pending admin only) Accept the admin role for this pool
+    ///
+    /// ### Panics
+    /// If no admin has been proposed or the caller is not the proposed admin
+    fn accept_admin(e: Env);
+
     /// (Admin only) Update the pool
     ///
     /// ### Arguments
@@ -32,10 +48,113 @@ pub trait Pool {
     /// * `max_positions` - The new maximum number of allowed positions for a single user's account
     /// * `min_collateral` - The new minimum collateral required to open a borrow position,
     ///                      in the oracles base asset decimals
+    /// * `flash_loan_fee` - The new fee charged on flash loaned amounts (7 decimals)
+    /// * `max_price_age` - The new maximum age of an oracle price in seconds. A value of 0 only
+    ///                     enforces the default limit of 24 hours.
     ///
     /// ### Panics
     /// If the caller is not the admin
-    fn update_pool(e: Env, backstop_take_rate: u32, max_positions: u32, min_collateral: i128);
+    fn update_pool(
+        e: Env,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+        flash_loan_fee: u32,
+        max_price_age: u64,
+    );
+
+    /// (Admin only) Set the liquidation bonus tiers for the pool. When the pool's backstop coverage
+    /// falls below a tier's coverage, the liquidation incentive of new user liquidation auctions
+    /// is capped at the tier's max bonus.
+    ///
+    /// ### Arguments
+    /// * `tiers` - The liquidation bonus tiers. An empty vector removes all caps.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If any tier has a non-positive coverage or a max bonus below 1_0000000
+    fn set_liquidation_bonus_tiers(e: Env, tiers: Vec<LiquidationBonusTier>);
+
+    /// (Admin only) Set the minimum number of blocks a liquidation auction must run before it
+    /// can be filled 100%. Partial fills are not restricted.
+    ///
+    /// ### Arguments
+    /// * `blocks` - The minimum number of blocks. A value of 0 removes the minimum.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `blocks` is greater than 400
+    fn set_min_liq_full_fill_blocks(e: Env, blocks: u32);
+
+    /// (Admin only) Set the block based scaling parameters of the pool's auctions. The lot scales
+    /// from 0% to 100% over `auction_lot_ramp_blocks`, then the bid scales from 100% to 0% over
+    /// `auction_bid_ramp_blocks`, with both modifiers moving `auction_per_block_scalar` every block.
+    ///
+    /// ### Arguments
+    /// * `config` - The auction config
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `auction_per_block_scalar` is not greater than 0 and at most 1_0000000
+    /// * If the lot or bid would not fully scale within their ramp blocks
+    /// * If a reserve's `auction_lot_rate` would not fully scale its lot within `auction_lot_ramp_blocks`
+    fn set_auction_config(e: Env, config: AuctionConfig);
+
+    /// (Admin only) Set the share of all accrued interest that is routed to the insurance fund
+    /// of each reserve. The insurance fund can only be used to cover bad debt.
+    ///
+    /// ### Arguments
+    /// * `bps` - The insurance share in basis points. A value of 0 disables the insurance fund.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the insurance share and backstop take rate combined are not less than 1
+    fn set_insurance_bps(e: Env, bps: u32);
+
+    /// (Admin only) Set the minimum value of the lot required to create an interest auction
+    ///
+    /// ### Arguments
+    /// * `value` - The minimum lot value, denominated in the oracle's base asset. A value of 0 removes the minimum.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `value` is negative
+    fn set_min_interest_auction_value(e: Env, value: i128);
+
+    /// (Admin only) Set the number of ledgers a status change must be observed for before
+    /// `update_status` applies it. Prevents the pool status from thrashing when the backstop
+    /// is close to a status threshold.
+    ///
+    /// ### Arguments
+    /// * `ledgers` - The number of ledgers. A value of 0 applies status changes immediately.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `ledgers` is greater than 17280
+    fn set_status_grace_ledgers(e: Env, ledgers: u32);
+
+    /// (Admin only) Set the minimum health factor a user must have after a submission that
+    /// borrows. Keeps new borrows from opening positions that are immediately liquidatable.
+    ///
+    /// ### Arguments
+    /// * `buffer` - The minimum health factor, with 7 decimals. A value of 0 removes the buffer.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `buffer` is not 0 and is under 1_0000000
+    fn set_min_borrow_health_buffer(e: Env, buffer: i128);
+
+    /// (Admin only) Set the maximum number of positions a user can hold. The user's effective
+    /// limit is the larger of the pool's `max_positions` and this limit.
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    /// * `limit` - The maximum number of positions. A value of 0 removes the override.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `limit` is over twice the maximum number of reserves
+    fn set_position_limit(e: Env, user: Address, limit: u32);
 
     /// (Admin only) Queues setting data for a reserve in the pool
     ///
@@ -44,17 +163,21 @@ pub trait Pool {
     /// * `config` - The ReserveConfig for the reserve
     ///
     /// ### Panics
-    /// If the caller is not the admin
+    /// * If the caller is not the admin
+    /// * If the config is invalid, e.g. `c_factor` or `l_factor` is over 1, or `c_factor` is over `l_factor`
+    /// * If a non-zero `auction_lot_rate` would not fully scale the lot within the auction lot ramp blocks
     fn queue_set_reserve(e: Env, asset: Address, metadata: ReserveConfig);
 
     /// (Admin only) Cancels the queued set of a reserve in the pool
     ///
+    /// Returns the ReserveConfig that was cancelled
+    ///
     /// ### Arguments
     /// * `asset` - The underlying asset to add as a reserve
     ///
     /// ### Panics
     /// If the caller is not the admin or the reserve is not queued for initialization
-    fn cancel_set_reserve(e: Env, asset: Address);
+    fn cancel_set_reserve(e: Env, asset: Address) -> ReserveConfig;
 
     /// (Admin only) Executes the queued set of a reserve in the pool
     ///
@@ -67,9 +190,91 @@ pub trait Pool {
     /// or has invalid metadata
     fn set_reserve(e: Env, asset: Address) -> u32;
 
+    /// Fetch a queued reserve set
+    ///
+    /// Returns the queued ReserveConfig and the timestamp at which `set_reserve` can be called,
+    /// or None if no reserve set is queued for the asset
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_queued_reserve(e: Env, asset: Address) -> Option<(ReserveConfig, u64)>;
+
+    /// (Admin only) Queues removing a reserve from the pool. Only the last reserve in the pool
+    /// can be removed, so the index of every other reserve is unchanged, and a reserve that has
+    /// ever had emission data for its b or d token can never be removed.
+    ///
+    /// Returns the timestamp at which `remove_reserve` can be called
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve to remove
+    ///
+    /// ### Panics
+    /// If the caller is not the admin, the asset is not a reserve, or a removal is already queued
+    fn queue_remove_reserve(e: Env, asset: Address) -> u64;
+
+    /// (Admin only) Cancels the queued removal of a reserve from the pool
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the reserve is not queued for removal
+    fn cancel_remove_reserve(e: Env, asset: Address);
+
+    /// Executes the queued removal of a reserve from the pool
+    ///
+    /// Returns the index of the removed reserve
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve to remove
+    ///
+    /// ### Panics
+    /// If the reserve is not queued for removal or the removal is not unlocked
+    /// or the reserve is not the last reserve in the pool
+    /// or the reserve has any outstanding supply, liabilities, or credit
+    /// or the reserve's b or d token has ever had emission data
+    fn remove_reserve(e: Env, asset: Address) -> u32;
+
+    /// Fetch the timestamp at which a queued reserve removal can be executed, or None if no
+    /// removal is queued for the asset
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    fn get_queued_reserve_removal(e: Env, asset: Address) -> Option<u64>;
+
     /// Fetch the pool configuration
     fn get_config(e: Env) -> PoolConfig;
 
+    /// Fetch the liquidation bonus tiers for the pool
+    fn get_liquidation_bonus_tiers(e: Env) -> Vec<LiquidationBonusTier>;
+
+    /// Fetch the minimum number of blocks a liquidation auction must run before it can be filled 100%
+    fn get_min_liq_full_fill_blocks(e: Env) -> u32;
+
+    /// Fetch the block based scaling parameters of the pool's auctions
+    fn get_auction_config(e: Env) -> AuctionConfig;
+
+    /// Fetch the share of accrued interest routed to the insurance fund, in basis points
+    fn get_insurance_bps(e: Env) -> u32;
+
+    /// Fetch the minimum value of the lot required to create an interest auction, in the oracle's base asset
+    fn get_min_interest_auction_value(e: Env) -> i128;
+
+    /// Fetch the number of ledgers a status change must be observed for before it is applied
+    fn get_status_grace_ledgers(e: Env) -> u32;
+
+    /// Fetch the minimum health factor a user must have after borrowing, with 7 decimals
+    fn get_min_borrow_health_buffer(e: Env) -> i128;
+
+    /// Fetch the pending status change as (candidate status, ledger first observed), if one exists
+    fn get_pending_status(e: Env) -> Option<(u32, u32)>;
+
+    /// Fetch the position limit override for a user, or 0 if the pool's `max_positions` applies
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn get_position_limit(e: Env, user: Address) -> u32;
+
     /// Fetch the admin address of the pool
     fn get_admin(e: Env) -> Address;
 
@@ -87,12 +292,94 @@ pub trait Pool {
     /// is updated to the current ledger.
     fn get_market(e: Env) -> (PoolConfig, Vec<Reserve>);
 
+    /// Fetch information about a set of reserves, updated to the current ledger
+    ///
+    /// Assets that are not reserves in the pool are skipped. Returns the reserves in the order
+    /// they were requested.
+    ///
+    /// ### Arguments
+    /// * `assets` - The addresses of the reserve assets
+    fn get_reserves(e: Env, assets: Vec<Address>) -> Vec<Reserve>;
+
+    /// Fetch the totals of a reserve in underlying tokens, updated to the current ledger
+    ///
+    /// Returns (total_supplied, total_borrowed, available_liquidity)
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve_totals(e: Env, asset: Address) -> (i128, i128, i128);
+
+    /// Fetch the bToken and dToken rates of a reserve, updated to the current ledger
+    ///
+    /// Returns (b_rate, d_rate)
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_reserve_rates(e: Env, asset: Address) -> (i128, i128);
+
+    /// Fetch the underlying asset addresses of the pool's reserves, ordered by reserve index
+    fn get_reserve_list(e: Env) -> Vec<Address>;
+
+    /// Fetch the number of reserves in the pool
+    fn get_reserve_count(e: Env) -> u32;
+
+    /// Fetch the backstop credit of every reserve in the pool, updated to the current ledger
+    ///
+    /// Returns a vector of (asset, backstop_credit) tuples, ordered by reserve index
+    fn get_all_backstop_credit(e: Env) -> Vec<(Address, i128)>;
+
     /// Fetch the positions for an address
     ///
     /// ### Arguments
     /// * `address` - The address to fetch positions for
     fn get_positions(e: Env, address: Address) -> Positions;
 
+    /// Fetch the current health of a user's positions, using the same valuation as `submit`
+    ///
+    /// Returns (collateral_base_value, liability_base_value, health_factor), where the values are
+    /// denominated in the oracle's base asset and the health factor is 7 decimals, or i128::MAX if
+    /// the user has no liabilities
+    ///
+    /// ### Arguments
+    /// * `user` - The address to fetch the health of
+    fn get_health(e: Env, user: Address) -> (i128, i128, i128);
+
+    /// Estimate the number of ledgers until a user's positions can be liquidated from interest alone,
+    /// assuming prices do not change. Interest is projected linearly from the current rates of each reserve.
+    ///
+    /// Returns the estimated number of ledgers, 0 if the positions can already be liquidated, or None if
+    /// the positions never become liquidatable from interest alone
+    ///
+    /// ### Arguments
+    /// * `user` - The address of the user
+    fn time_to_liquidation(e: Env, user: Address) -> Option<u64>;
+
+    /// Fetch a user's positions converted to underlying amounts and values at the current ledger
+    ///
+    /// Returns a Vec of (asset, supplied_underlying, liability_underlying, net_base_value) for each
+    /// reserve the user has a position in, where the supplied amount includes both collateral and
+    /// non-collateral supply and the net value is denominated in the oracle's base asset
+    ///
+    /// ### Arguments
+    /// * `user` - The address to fetch the positions of
+    fn get_positions_detailed(e: Env, user: Address) -> Vec<(Address, i128, i128, i128)>;
+
+    /// Simulate submitting a set of requests to the pool for 'from' without modifying any positions
+    /// or reserve data, or transferring any tokens. Auction requests are not supported.
+    ///
+    /// Emissions for the reserve tokens the requests touch are checkpointed for the reserve and
+    /// 'from', as they would be by any other interaction with those reserve tokens.
+    ///
+    /// Returns the new positions for 'from' and the health of those positions
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `requests` - A vec of requests to be processed
+    ///
+    /// ### Panics
+    /// If the requests would not be able to be completed by `submit`, excluding token transfers
+    fn simulate_submit(e: Env, from: Address, requests: Vec<Request>) -> (Positions, HealthData);
+
     /// Submit a set of requests to the pool where 'from' takes on the position, 'sender' sends any
     /// required tokens to the pool and 'to' receives any tokens sent from the pool.
     ///
@@ -102,7 +389,7 @@ pub trait Pool {
     /// * `from` - The address of the user whose positions are being modified
     /// * `spender` - The address of the user who is sending tokens to the pool
     /// * `to` - The address of the user who is receiving tokens from the pool
-    /// * `requests` - A vec of requests to be processed
+    /// * `requests` - A vec of requests to be processed, up to a maximum of 32 requests
     ///
     /// ### Panics
     /// If the request is not able to be completed for cases like insufficient funds or invalid health factor
@@ -114,6 +401,45 @@ pub trait Pool {
         requests: Vec<Request>,
     ) -> Positions;
 
+    /// Transfer part of a collateral or liability position from 'from' to 'to'. Requires
+    /// authorization from both 'from' and 'to', so positions cannot be pushed onto a user
+    /// that did not agree to take them.
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose position is being transferred
+    /// * `to` - The address of the user receiving the position
+    /// * `reserve_token_id` - The reserve token being transferred, `reserve_index * 2` for dTokens
+    ///                        and `reserve_index * 2 + 1` for collateralized bTokens
+    /// * `amount` - The amount of bTokens or dTokens to transfer
+    ///
+    /// ### Panics
+    /// If the transfer would leave either user with an invalid health factor
+    fn transfer_position(e: Env, from: Address, to: Address, reserve_token_id: u32, amount: i128);
+
+    /// Fill an auction as 'filler', reverting if the lot received at the current block is below
+    /// the filler's minimum for any asset. The filler takes on the positions, sends any required
+    /// tokens to the pool, and receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for 'filler'
+    ///
+    /// ### Arguments
+    /// * `filler` - The address filling the auction
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    /// * `min_lot_out` - A map of the minimum amount of each asset required to be received from the lot
+    ///
+    /// ### Panics
+    /// If any lot asset amount is below its minimum, or if the fill is not able to be completed
+    fn fill_with_min(
+        e: Env,
+        filler: Address,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+        min_lot_out: Map<Address, i128>,
+    ) -> Positions;
+
     /// Submit a set of requests to the pool where 'from' takes on the position, 'spender' sends any
     /// required tokens to the pool using transfer_from and 'to' receives any tokens sent from the pool.
     ///
@@ -135,9 +461,61 @@ pub trait Pool {
         requests: Vec<Request>,
     ) -> Positions;
 
+    /// Fill an auction as 'filler' and then process the filler's `requests` in the same transaction,
+    /// such that the lot received from the auction can be used by the follow-up requests (e.g. withdrawing
+    /// the received collateral to repay an existing liability). The filler takes on the positions, sends any
+    /// required tokens to the pool, and receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for 'filler'
+    ///
+    /// ### Arguments
+    /// * `filler` - The address filling the auction
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    /// * `requests` - A vec of requests to be processed after the fill
+    ///
+    /// ### Panics
+    /// If the fill or any request is not able to be completed for cases like insufficient funds or invalid health factor
+    fn fill_and_submit(
+        e: Env,
+        filler: Address,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+        requests: Vec<Request>,
+    ) -> Positions;
+
+    /// Submit a set of requests to the pool where 'from' takes on the position and 'to' receives any tokens
+    /// sent from the pool. Tokens required by a request named in 'funders' are sent to the pool by the named
+    /// funder using transfer_from, and any other required tokens are sent by 'spender' using transfer_from.
+    ///
+    /// Returns the new positions for 'from'
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified
+    /// * `spender` - The address of the user who is sending tokens to the pool for requests without a funder
+    /// * `to` - The address of the user who is receiving tokens from the pool
+    /// * `requests` - A vec of requests to be processed
+    /// * `funders` - A map of request index to the address sending the tokens for that request. Only supply,
+    /// supply collateral, and repay requests can be funded.
+    ///
+    /// ### Panics
+    /// If a funder is named for an invalid request, or the request is not able to be completed for cases like
+    /// insufficient funds, insufficient allowance, or invalid health factor
+    fn submit_with_funders(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+        funders: Map<u32, Address>,
+    ) -> Positions;
+
     /// Submit flash loan and a set of requests to the pool where 'from' takes on the position. The flash loan will be invoked using
     /// the 'flash_loan' arguments and 'from' as the caller. For the requests, 'from' sends any required tokens to the pool
-    /// using transfer_from and receives any tokens sent from the pool.
+    /// using transfer_from and receives any tokens sent from the pool. If the receiver contract returns a value
+    /// from `exec_op`, it is emitted in a `flash_loan_result` event.
     ///
     /// Returns the new positions for 'from'
     ///
@@ -156,6 +534,29 @@ pub trait Pool {
         requests: Vec<Request>,
     ) -> Positions;
 
+    /// Submit multiple flash loans and a set of requests to the pool where 'from' takes on the position. Every
+    /// flash loaned asset is sent to its receiver contract before any receiver is invoked, then each receiver is
+    /// invoked in order using the 'flash_loans' arguments and 'from' as the caller. For the requests, 'from' sends
+    /// any required tokens to the pool using transfer_from and receives any tokens sent from the pool.
+    ///
+    /// Returns the new positions for 'from'
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user whose positions are being modified and also the address of
+    /// the user who is sending and receiving the tokens to the pool.
+    /// * `flash_loans` - A vec of flash loans, each with a receiver contract, asset and borrowed amount.
+    /// * `requests` - A vec of requests to be processed
+    ///
+    /// ### Panics
+    /// If no flash loans are provided, or the request is not able to be completed for cases like insufficient
+    /// funds, insufficient allowance, or invalid health factor
+    fn flash_loan_multi(
+        e: Env,
+        from: Address,
+        flash_loans: Vec<FlashLoan>,
+        requests: Vec<Request>,
+    ) -> Positions;
+
     /// Manage bad debt. Debt is considered "bad" if there is no longer has any collateral posted.
     ///
     /// To manage a user's bad debt, all collateralized reserves for the user must be liquidated
@@ -171,6 +572,26 @@ pub trait Pool {
     /// If the user has collateral posted
     fn bad_debt(e: Env, user: Address);
 
+    /// Check if a user holds bad debt that can be transferred to the backstop with `bad_debt`
+    ///
+    /// Returns true if the user has liabilities but no collateral
+    ///
+    /// ### Arguments
+    /// * `user` - The user to check
+    fn has_bad_debt(e: Env, user: Address) -> bool;
+
+    /// (Admin only) Use a reserve's insurance fund to repay bad debt held by the backstop
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    ///
+    /// Returns the amount of insurance funds used
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the backstop holds no bad debt for the reserve or the reserve has no insurance funds
+    fn cover_bad_debt_with_insurance(e: Env, asset: Address) -> i128;
+
     /// Update the pool status based on the backstop state - backstop triggered status' are odd numbers
     /// * 1 = backstop active - if the minimum backstop deposit has been reached
     ///                and 30% of backstop deposits are not queued for withdrawal
@@ -183,11 +604,30 @@ pub trait Pool {
     ///                or 75% of backstop deposits are queued for withdrawal
     ///                then all borrowing, cancelling liquidations, and supplying are not permitted
     ///
+    /// If a status grace period is set, a status change is only applied once it has been observed
+    /// for at least `get_status_grace_ledgers` ledgers.
+    ///
+    /// Returns the pool's status after the update
+    ///
     /// ### Panics
     /// If the pool is currently on status 4, "admin-freeze", where only the admin
     /// can perform a status update via `set_status`
     fn update_status(e: Env) -> u32;
 
+    /// Fetch the status the pool would be set to by `update_status`, without updating it. Does
+    /// not account for the status grace period.
+    ///
+    /// ### Panics
+    /// If the pool is currently on status 4, "admin-freeze", or status 6, "setup"
+    fn preview_update_status(e: Env) -> u32;
+
+    /// Fetch the current pool status and the backstop metrics that drive `update_status`,
+    /// without updating anything
+    ///
+    /// Returns a tuple of (current status, percentage of backstop deposits queued for
+    /// withdrawal in 7 decimals, 1 if the minimum backstop deposit is met or 0 if not)
+    fn get_status(e: Env) -> (u32, i128, i128);
+
     /// (Admin only) Pool status is changed to "pool_status"
     /// * 0 = admin active - requires that the backstop threshold is met
     ///                 and less than 50% of backstop deposits are queued for withdrawal
@@ -202,6 +642,37 @@ pub trait Pool {
     /// If the specified conditions are not met for the status to be set
     fn set_status(e: Env, pool_status: u32);
 
+    /// (Admin only) Pause or unpause supplying and borrowing for a reserve. This takes effect
+    /// immediately and does not require queueing a reserve update. Withdrawing and repaying
+    /// are always allowed.
+    ///
+    /// ### Arguments
+    /// * `asset` - The underlying asset of the reserve
+    /// * `supply_enabled` - If supplying to the reserve is allowed
+    /// * `borrow_enabled` - If borrowing from the reserve is allowed
+    ///
+    /// ### Panics
+    /// If the caller is not the admin or the asset is not a reserve
+    fn set_reserve_status(e: Env, asset: Address, supply_enabled: bool, borrow_enabled: bool);
+
+    /// (Admin only) Pause or unpause the pool in an emergency. This takes effect immediately and
+    /// is independent of the pool status. While paused, `submit`, `flash_loan`, `new_auction`, and
+    /// `gulp_emissions` revert, except for submissions that only repay liabilities.
+    ///
+    /// ### Arguments
+    /// * `paused` - If the pool is paused
+    /// * `allow_withdrawals` - If submissions that only withdraw, supply collateral, or repay are
+    ///                         allowed while the pool is paused
+    ///
+    /// ### Panics
+    /// If the caller is not the admin
+    fn set_emergency(e: Env, paused: bool, allow_withdrawals: bool);
+
+    /// Fetch the emergency pause state of the pool
+    ///
+    /// Returns a tuple of (paused, allow_withdrawals)
+    fn get_emergency(e: Env) -> (bool, bool);
+
     /// Gulps unaccounted for tokens to the backstop credit so they aren't lost. This is most relevant
     /// for rebasing tokens where the token balance of the pool can increase without any corresponding
     /// transfer.
@@ -215,6 +686,32 @@ pub trait Pool {
     /// Returns the amount of tokens gulped
     fn gulp(e: Env, asset: Address) -> i128;
 
+    /// Gulps unaccounted for tokens to the backstop credit for each asset in `assets`. See `gulp`.
+    ///
+    /// ### Arguments
+    /// * `assets` - The addresses of the assets to gulp
+    ///
+    /// Returns a vector of (asset, token_delta) tuples with the amount of tokens gulped per asset
+    fn gulp_batch(e: Env, assets: Vec<Address>) -> Vec<(Address, i128)>;
+
+    /// (Admin only) Set the maximum amount of tokens a single `gulp` can credit to the backstop
+    /// for a reserve. Any excess is left to be gulped by subsequent calls.
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    /// * `max_gulp` - The maximum amount of tokens. A value of 0 removes the maximum.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the asset is not a reserve or the maximum is negative
+    fn set_max_gulp(e: Env, asset: Address, max_gulp: i128);
+
+    /// Fetch the maximum amount of tokens a single `gulp` can credit to the backstop for a reserve
+    ///
+    /// ### Arguments
+    /// * `asset` - The address of the reserve asset
+    fn get_max_gulp(e: Env, asset: Address) -> i128;
+
     /********* Emission Functions **********/
 
     /// Consume emissions from the backstop and distribute to the reserves based
@@ -223,6 +720,13 @@ pub trait Pool {
     /// Returns amount of new tokens emitted
     fn gulp_emissions(e: Env) -> i128;
 
+    /// Consume emissions from the backstop and distribute to the reserves based
+    /// on the reserve emission configuration.
+    ///
+    /// Returns a vector of (reserve_token_id, tokens allocated) for each reserve token
+    /// that received emissions
+    fn gulp_emissions_detailed(e: Env) -> Vec<(u32, i128)>;
+
     /// (Admin only) Set the emission configuration for the pool
     ///
     /// Changes will be applied in the next pool `update_emissions`, and affect the next emission cycle
@@ -235,13 +739,83 @@ pub trait Pool {
     /// * If the sum of ReserveEmissionMetadata shares is greater than 1
     fn set_emissions_config(e: Env, res_emission_metadata: Vec<ReserveEmissionMetadata>);
 
-    /// Claims outstanding emissions for the caller for the given reserve's.
+    /// Get the emission configuration for the pool
     ///
-    /// A reserve token id is a unique identifier for a position in a pool.
-    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
-    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    /// Returns a vector of ReserveEmissionMetadata for each reserve token with a configured share
+    fn get_emissions_config(e: Env) -> Vec<ReserveEmissionMetadata>;
+
+    /// (Admin only) Set the maximum emissions the pool can gulp from the backstop in a single
+    /// `gulp_emissions` call. Any emissions over the maximum are deferred to the next gulp.
     ///
-    /// Returns the number of tokens claimed
+    /// ### Arguments
+    /// * `max_emissions` - The maximum emissions the pool can gulp at once, or 0 to remove the cap
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `max_emissions` is negative
+    fn set_max_gulp_emissions(e: Env, max_emissions: i128);
+
+    /// (Admin only) Stop emissions for reserve tokens with no supply and return the emissions they had
+    /// left to emit to the backstop, reducing the pool's BLND allowance.
+    ///
+    /// Returns the amount of emissions reclaimed
+    ///
+    /// ### Arguments
+    /// * `reserve_token_ids` - The reserve token ids to sweep
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If any reserve token id is invalid, has no emission data, or has a non-zero supply
+    fn sweep_unclaimed_emissions(e: Env, reserve_token_ids: Vec<u32>) -> i128;
+
+    /// (Admin only) Set the share of the pool's reward zone emissions that go to the backstop.
+    /// The remainder is given to the pool to distribute to suppliers and borrowers.
+    ///
+    /// ### Arguments
+    /// * `share` - The backstop's share of the emissions (7 decimals, defaults to 0_7000000)
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If `share` is greater than 1
+    fn set_backstop_emission_share(e: Env, share: u32);
+
+    /// (Admin only) Enable or disable the pool's reward zone emissions on the backstop. While disabled,
+    /// the pool keeps its reward zone slot but does not accrue new emissions.
+    ///
+    /// ### Arguments
+    /// * `enabled` - Whether the pool accrues reward zone emissions
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    fn set_backstop_emissions_enabled(e: Env, enabled: bool);
+
+    /// (Admin only) Set the minimum position size required for a user to accrue emissions
+    /// on a reserve token. Positions below the minimum do not accrue emissions.
+    ///
+    /// The minimum only applies to emissions from the time it is set. Positions below the minimum
+    /// still count towards the reserve token's supply, so the emissions they would have earned
+    /// are forfeited and remain in the pool's BLND allowance.
+    ///
+    /// A reserve token id is a unique identifier for a position in a pool.
+    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
+    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    /// * `min_balance` - The minimum position size, in d/bTokens. A value of 0 removes the minimum.
+    ///
+    /// ### Panics
+    /// * If the caller is not the admin
+    /// * If the reserve token id is invalid or the minimum balance is negative
+    fn set_emissions_min_balance(e: Env, reserve_token_id: u32, min_balance: i128);
+
+    /// Claims outstanding emissions for the caller for the given reserve's.
+    ///
+    /// A reserve token id is a unique identifier for a position in a pool.
+    /// - For a reserve's dTokens (liabilities), reserve_token_id = reserve_index * 2
+    /// - For a reserve's bTokens (supply/collateral), reserve_token_id = reserve_index * 2 + 1
+    ///
+    /// Returns the number of tokens claimed
     ///
     /// ### Arguments
     /// * `from` - The address claiming
@@ -249,6 +823,34 @@ pub trait Pool {
     /// * `to` - The Address to send the claimed tokens to
     fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128;
 
+    /// Claims outstanding emissions for the caller for every reserve token they have emissions for.
+    ///
+    /// Returns the number of tokens claimed
+    ///
+    /// ### Arguments
+    /// * `from` - The address claiming
+    /// * `to` - The Address to send the claimed tokens to
+    fn claim_all(e: Env, from: Address, to: Address) -> i128;
+
+    /// Claims outstanding emissions for the caller for the given reserve's and supplies the
+    /// claimed BLND to the pool as collateral for the caller
+    ///
+    /// Returns the number of tokens claimed and supplied
+    ///
+    /// ### Arguments
+    /// * `from` - The address claiming
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    /// * `supply_reserve` - The reserve to supply the claimed tokens to. Must be the BLND token.
+    ///
+    /// ### Panics
+    /// If the pool does not have a BLND reserve or the supply is not able to be completed
+    fn claim_and_supply(
+        e: Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        supply_reserve: Address,
+    ) -> i128;
+
     /// Get the emissions data for a reserve token
     ///
     /// A reserve token id is a unique identifier for a position in a pool.
@@ -259,6 +861,11 @@ pub trait Pool {
     /// * `reserve_token_id` - The reserve token id
     fn get_reserve_emissions(e: Env, reserve_token_id: u32) -> Option<ReserveEmissionData>;
 
+    /// Get the emissions data for every reserve token in the pool
+    ///
+    /// Returns a vector of (reserve_token_id, emissions data) for each reserve token with emissions data
+    fn get_all_reserve_emissions(e: Env) -> Vec<(u32, ReserveEmissionData)>;
+
     /// Get the emissions data for a user
     ///
     /// A reserve token id is a unique identifier for a position in a pool.
@@ -271,6 +878,46 @@ pub trait Pool {
     fn get_user_emissions(e: Env, user: Address, reserve_token_id: u32)
         -> Option<UserEmissionData>;
 
+    /// Fetch the emissions "from" could claim for each of the given reserve tokens at the current
+    /// ledger timestamp, without claiming them
+    ///
+    /// Returns the claimable amount for each reserve token id, in the order given
+    ///
+    /// ### Arguments
+    /// * `from` - The address of the user
+    /// * `reserve_token_ids` - Vector of reserve token ids
+    ///
+    /// ### Panics
+    /// If any reserve token id is invalid
+    fn get_claimable(e: Env, from: Address, reserve_token_ids: Vec<u32>) -> Vec<i128>;
+
+    /// Recompute the expected emission index for a reserve token from its stored eps and timestamps
+    /// and compare it against the stored index. Useful for debugging emission accounting drift.
+    ///
+    /// Returns the expected index minus the stored index
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    ///
+    /// ### Panics
+    /// If the reserve token id is invalid
+    fn get_emissions_index_discrepancy(e: Env, reserve_token_id: u32) -> i128;
+
+    /// Get the minimum position size required for a user to accrue emissions on a reserve token
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    fn get_emissions_min_balance(e: Env, reserve_token_id: u32) -> i128;
+
+    /// Get the recent emission index checkpoints for a reserve token, recorded each time emissions
+    /// are gulped. Only the most recent 24 checkpoints are kept.
+    ///
+    /// Returns a vector of (timestamp, index) ordered from oldest to newest
+    ///
+    /// ### Arguments
+    /// * `reserve_token_id` - The reserve token id
+    fn get_emission_history(e: Env, reserve_token_id: u32) -> Vec<(u64, i128)>;
+
     /***** Auction / Liquidation Functions *****/
 
     /// Create a new auction. Auctions are used to process liquidations, bad debt, and interest.
@@ -283,6 +930,15 @@ pub trait Pool {
     /// * `lot` - The set of assets to include in the auction lot, or what the filler receives when filling the auction.
     /// * `percent` - The percent of the assets to be auctioned off as a percentage (15 => 15%). For bad debt and interest auctions.
     ///               this is expected to be 100.
+    /// * `min_lot` - (Optional) The minimum amount of each asset the created auction's lot must contain
+    /// * `max_bid` - (Optional) The maximum amount of each asset the created auction's bid may contain
+    ///
+    /// The bounds are checked against the auction as created. The auction is still scaled
+    /// based on the number of blocks that pass before it is filled.
+    ///
+    /// ### Panics
+    /// If the auction is unable to be created, or the created auction does not satisfy `min_lot` or `max_bid`
+    #[allow(clippy::too_many_arguments)]
     fn new_auction(
         e: Env,
         auction_type: u32,
@@ -290,6 +946,65 @@ pub trait Pool {
         bid: Vec<Address>,
         lot: Vec<Address>,
         percent: u32,
+        min_lot: Option<Map<Address, i128>>,
+        max_bid: Option<Map<Address, i128>>,
+    ) -> AuctionData;
+
+    /// Create a new user liquidation auction that liquidates the minimum percent of the user's positions
+    /// required to bring their health factor to at least `target_hf` based on current prices.
+    ///
+    /// ### Arguments
+    /// * `user` - The Address being liquidated
+    /// * `bid` - The set of liability assets to include in the auction bid
+    /// * `lot` - The set of collateral assets to include in the auction lot
+    /// * `target_hf` - The health factor the user should have after the liquidation (7 decimals). Must be
+    ///                 over 1_0000000 and under 1_1500000, the maximum post-liquidation health factor.
+    ///
+    /// ### Panics
+    /// If the target health factor is out of range, or the liquidation auction cannot be created
+    fn new_auction_to_target(
+        e: Env,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        target_hf: i128,
+    ) -> AuctionData;
+
+    /// Delete a liquidation auction for a user whose positions are healthy again. Anyone can
+    /// delete the auction, as the user's health factor is verified before it is removed.
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, only 0 for liquidation auction is supported
+    /// * `user` - The Address being liquidated
+    ///
+    /// ### Panics
+    /// * If the auction is not a liquidation auction or does not exist
+    /// * If the pool status does not allow liquidation auctions to be cancelled
+    /// * If the user's health factor is not over 1
+    fn delete_auction(e: Env, auction_type: u32, user: Address);
+
+    /// Preview the exact collateral seized and debt repaid by a user liquidation auction if it
+    /// were created now and fully filled at `fill_block`.
+    ///
+    /// Returns the AuctionData that would be filled, where the bid is the dTokens repaid and
+    /// the lot is the bTokens seized
+    ///
+    /// ### Arguments
+    /// * `user` - The Address being liquidated
+    /// * `bid` - The set of liability assets to include in the auction bid
+    /// * `lot` - The set of collateral assets to include in the auction lot
+    /// * `percent` - The percent of the user's positions to be liquidated as a percentage (15 => 15%)
+    /// * `fill_block` - The block the auction is expected to be filled on
+    ///
+    /// ### Panics
+    /// If the liquidation auction cannot be created or the fill block is before the auction begins
+    fn preview_liquidation(
+        e: Env,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+        fill_block: u32,
     ) -> AuctionData;
 
     /// Fetch an auction from the ledger. Returns a quote based on the current block.
@@ -301,6 +1016,40 @@ pub trait Pool {
     /// ### Panics
     /// If the auction does not exist
     fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData;
+
+    /// Preview the bid and lot of an auction if it were filled at the current block. Does not
+    /// modify the auction or transfer any tokens.
+    ///
+    /// Returns (bid, lot) as maps of asset address to amount
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    /// * `percent_filled` - The percentage being filled as a number (i.e. 15 => 15%)
+    ///
+    /// ### Panics
+    /// If the auction does not exist, or if `fill` would be rejected for the given percent
+    fn preview_fill(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+    ) -> (Map<Address, i128>, Map<Address, i128>);
+
+    /// Fetch all auctions from the ledger for a user
+    ///
+    /// Returns a vector of (auction_type, auction_data) tuples
+    ///
+    /// ### Arguments
+    /// * `user` - The Address involved in the auctions
+    fn get_auctions(e: Env, user: Address) -> Vec<(u32, AuctionData)>;
+
+    /// Check if an auction exists
+    ///
+    /// ### Arguments
+    /// * `auction_type` - The type of auction, 0 for liquidation auction, 1 for bad debt auction, and 2 for interest auction
+    /// * `user` - The Address involved in the auction
+    fn has_auction(e: Env, auction_type: u32, user: Address) -> bool;
 }
 
 #[contractimpl]
@@ -350,6 +1099,7 @@ impl PoolContract {
 impl Pool for PoolContract {
     fn set_admin(e: Env, new_admin: Address) {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
         new_admin.require_auth();
@@ -359,18 +1109,150 @@ impl Pool for PoolContract {
         PoolEvents::set_admin(&e, admin, new_admin);
     }
 
-    fn update_pool(e: Env, backstop_take_rate: u32, max_positions: u32, min_collateral: i128) {
+    fn propose_admin(e: Env, new_admin: Address) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        storage::set_pending_admin(&e, &new_admin);
+
+        PoolEvents::propose_admin(&e, admin, new_admin);
+    }
+
+    fn accept_admin(e: Env) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+
+        let (admin, new_admin) = pool::execute_accept_admin(&e);
+
+        PoolEvents::accept_admin(&e, admin, new_admin);
+    }
+
+    fn update_pool(
+        e: Env,
+        backstop_take_rate: u32,
+        max_positions: u32,
+        min_collateral: i128,
+        flash_loan_fee: u32,
+        max_price_age: u64,
+    ) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_update_pool(
+            &e,
+            backstop_take_rate,
+            max_positions,
+            min_collateral,
+            flash_loan_fee,
+            max_price_age,
+        );
+
+        PoolEvents::update_pool(
+            &e,
+            admin,
+            backstop_take_rate,
+            max_positions,
+            min_collateral,
+            flash_loan_fee,
+            max_price_age,
+        );
+    }
+
+    fn set_liquidation_bonus_tiers(e: Env, tiers: Vec<LiquidationBonusTier>) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_liq_bonus_tiers(&e, &tiers);
+
+        PoolEvents::set_liquidation_bonus_tiers(&e, admin, tiers);
+    }
+
+    fn set_min_liq_full_fill_blocks(e: Env, blocks: u32) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_min_liq_full_fill_blocks(&e, blocks);
+
+        PoolEvents::set_min_liq_full_fill_blocks(&e, admin, blocks);
+    }
+
+    fn set_auction_config(e: Env, config: AuctionConfig) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_auction_config(&e, &config);
+
+        PoolEvents::set_auction_config(&e, admin, config);
+    }
+
+    fn set_insurance_bps(e: Env, bps: u32) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_insurance_bps(&e, bps);
+
+        PoolEvents::set_insurance_bps(&e, admin, bps);
+    }
+
+    fn set_min_interest_auction_value(e: Env, value: i128) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_min_interest_auction_value(&e, value);
+
+        PoolEvents::set_min_interest_auction_value(&e, admin, value);
+    }
+
+    fn set_status_grace_ledgers(e: Env, ledgers: u32) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_status_grace_ledgers(&e, ledgers);
+
+        PoolEvents::set_status_grace_ledgers(&e, admin, ledgers);
+    }
+
+    fn set_min_borrow_health_buffer(e: Env, buffer: i128) {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
 
-        pool::execute_update_pool(&e, backstop_take_rate, max_positions, min_collateral);
+        pool::execute_set_min_borrow_health_buffer(&e, buffer);
 
-        PoolEvents::update_pool(&e, admin, backstop_take_rate, max_positions, min_collateral);
+        PoolEvents::set_min_borrow_health_buffer(&e, admin, buffer);
+    }
+
+    fn set_position_limit(e: Env, user: Address, limit: u32) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_position_limit(&e, &user, limit);
+
+        PoolEvents::set_position_limit(&e, admin, user, limit);
     }
 
     fn queue_set_reserve(e: Env, asset: Address, metadata: ReserveConfig) {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
 
@@ -379,27 +1261,106 @@ impl Pool for PoolContract {
         PoolEvents::queue_set_reserve(&e, admin, asset, metadata);
     }
 
-    fn cancel_set_reserve(e: Env, asset: Address) {
+    fn cancel_set_reserve(e: Env, asset: Address) -> ReserveConfig {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
 
-        pool::execute_cancel_queued_set_reserve(&e, &asset);
+        let metadata = pool::execute_cancel_queued_set_reserve(&e, &asset);
 
-        PoolEvents::cancel_set_reserve(&e, admin, asset);
+        PoolEvents::cancel_set_reserve(&e, admin, asset, metadata.clone());
+        metadata
     }
 
     fn set_reserve(e: Env, asset: Address) -> u32 {
+        pool::require_no_flash_loan(&e);
         let index = pool::execute_set_reserve(&e, &asset);
 
         PoolEvents::set_reserve(&e, asset, index);
         index
     }
 
+    fn get_queued_reserve(e: Env, asset: Address) -> Option<(ReserveConfig, u64)> {
+        pool::load_queued_reserve_set(&e, &asset)
+    }
+
+    fn queue_remove_reserve(e: Env, asset: Address) -> u64 {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let unlock_time = pool::execute_queue_remove_reserve(&e, &asset);
+
+        PoolEvents::queue_remove_reserve(&e, admin, asset, unlock_time);
+        unlock_time
+    }
+
+    fn cancel_remove_reserve(e: Env, asset: Address) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_cancel_queued_remove_reserve(&e, &asset);
+
+        PoolEvents::cancel_remove_reserve(&e, admin, asset);
+    }
+
+    fn remove_reserve(e: Env, asset: Address) -> u32 {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let index = pool::execute_remove_reserve(&e, &asset);
+
+        PoolEvents::remove_reserve(&e, asset, index);
+        index
+    }
+
+    fn get_queued_reserve_removal(e: Env, asset: Address) -> Option<u64> {
+        storage::get_queued_reserve_removal(&e, &asset)
+    }
+
     fn get_config(e: Env) -> PoolConfig {
         storage::get_pool_config(&e)
     }
 
+    fn get_liquidation_bonus_tiers(e: Env) -> Vec<LiquidationBonusTier> {
+        storage::get_liq_bonus_tiers(&e)
+    }
+
+    fn get_min_liq_full_fill_blocks(e: Env) -> u32 {
+        storage::get_min_liq_full_fill_blocks(&e)
+    }
+
+    fn get_auction_config(e: Env) -> AuctionConfig {
+        storage::get_auction_config(&e)
+    }
+
+    fn get_insurance_bps(e: Env) -> u32 {
+        storage::get_insurance_bps(&e)
+    }
+
+    fn get_min_interest_auction_value(e: Env) -> i128 {
+        storage::get_min_interest_auction_value(&e)
+    }
+
+    fn get_status_grace_ledgers(e: Env) -> u32 {
+        storage::get_status_grace_ledgers(&e)
+    }
+
+    fn get_min_borrow_health_buffer(e: Env) -> i128 {
+        storage::get_min_borrow_health_buffer(&e)
+    }
+
+    fn get_pending_status(e: Env) -> Option<(u32, u32)> {
+        storage::get_pending_status(&e)
+    }
+
+    fn get_position_limit(e: Env, user: Address) -> u32 {
+        storage::get_position_limit(&e, &user)
+    }
+
     fn get_admin(e: Env) -> Address {
         storage::get_admin(&e)
     }
@@ -420,10 +1381,51 @@ impl Pool for PoolContract {
         (pool_config, reserves)
     }
 
+    fn get_reserves(e: Env, assets: Vec<Address>) -> Vec<Reserve> {
+        pool::load_reserves(&e, &assets)
+    }
+
+    fn get_reserve_totals(e: Env, asset: Address) -> (i128, i128, i128) {
+        pool::load_reserve_totals(&e, &asset)
+    }
+
+    fn get_reserve_rates(e: Env, asset: Address) -> (i128, i128) {
+        pool::load_reserve_rates(&e, &asset)
+    }
+
+    fn get_reserve_list(e: Env) -> Vec<Address> {
+        storage::get_res_list(&e)
+    }
+
+    fn get_reserve_count(e: Env) -> u32 {
+        storage::get_res_list(&e).len()
+    }
+
+    fn get_all_backstop_credit(e: Env) -> Vec<(Address, i128)> {
+        pool::load_all_backstop_credit(&e)
+    }
+
     fn get_positions(e: Env, address: Address) -> Positions {
         storage::get_user_positions(&e, &address)
     }
 
+    fn get_health(e: Env, user: Address) -> (i128, i128, i128) {
+        let health = pool::load_health(&e, &user);
+        (health.collateral, health.liability, health.health_factor)
+    }
+
+    fn time_to_liquidation(e: Env, user: Address) -> Option<u64> {
+        pool::load_time_to_liquidation(&e, &user)
+    }
+
+    fn get_positions_detailed(e: Env, user: Address) -> Vec<(Address, i128, i128, i128)> {
+        pool::load_positions_detailed(&e, &user)
+    }
+
+    fn simulate_submit(e: Env, from: Address, requests: Vec<Request>) -> (Positions, HealthData) {
+        pool::simulate_submit(&e, &from, requests)
+    }
+
     fn submit(
         e: Env,
         from: Address,
@@ -440,6 +1442,29 @@ impl Pool for PoolContract {
         pool::execute_submit(&e, &from, &spender, &to, requests, false)
     }
 
+    fn transfer_position(e: Env, from: Address, to: Address, reserve_token_id: u32, amount: i128) {
+        storage::extend_instance(&e);
+        from.require_auth();
+        to.require_auth();
+
+        pool::execute_transfer_position(&e, &from, &to, reserve_token_id, amount);
+    }
+
+    fn fill_with_min(
+        e: Env,
+        filler: Address,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+        min_lot_out: Map<Address, i128>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        filler.require_auth();
+
+        auctions::require_fill_min_lot(&e, auction_type, &user, percent_filled, &min_lot_out);
+        pool::execute_fill_and_submit(&e, &filler, auction_type, &user, percent_filled, vec![&e])
+    }
+
     fn submit_with_allowance(
         e: Env,
         from: Address,
@@ -456,6 +1481,45 @@ impl Pool for PoolContract {
         pool::execute_submit(&e, &from, &spender, &to, requests, true)
     }
 
+    fn fill_and_submit(
+        e: Env,
+        filler: Address,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        filler.require_auth();
+
+        pool::execute_fill_and_submit(&e, &filler, auction_type, &user, percent_filled, requests)
+    }
+
+    fn submit_with_funders(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        requests: Vec<Request>,
+        funders: Map<u32, Address>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        spender.require_auth();
+        if from != spender {
+            from.require_auth();
+        }
+        // each funder authorizes once, regardless of how many requests it funds
+        let mut authorized = vec![&e, from.clone(), spender.clone()];
+        for funder in funders.values() {
+            if !authorized.contains(&funder) {
+                funder.require_auth();
+                authorized.push_back(funder);
+            }
+        }
+
+        pool::execute_submit_with_funders(&e, &from, &spender, &to, requests, funders)
+    }
+
     fn flash_loan(
         e: Env,
         from: Address,
@@ -468,20 +1532,59 @@ impl Pool for PoolContract {
         pool::execute_submit_with_flash_loan(&e, &from, flash_loan, requests)
     }
 
+    fn flash_loan_multi(
+        e: Env,
+        from: Address,
+        flash_loans: Vec<FlashLoan>,
+        requests: Vec<Request>,
+    ) -> Positions {
+        storage::extend_instance(&e);
+        from.require_auth();
+
+        pool::execute_submit_with_flash_loans(&e, &from, flash_loans, requests)
+    }
+
     fn bad_debt(e: Env, user: Address) {
+        pool::require_no_flash_loan(&e);
         pool::transfer_bad_debt_to_backstop(&e, &user);
     }
 
+    fn has_bad_debt(e: Env, user: Address) -> bool {
+        pool::has_bad_debt(&e, &user)
+    }
+
+    fn cover_bad_debt_with_insurance(e: Env, asset: Address) -> i128 {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let (d_tokens_burnt, insurance_used) = pool::cover_bad_debt_with_insurance(&e, &asset);
+
+        PoolEvents::insurance_cover(&e, asset, d_tokens_burnt, insurance_used);
+        insurance_used
+    }
+
     fn update_status(e: Env) -> u32 {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         let new_status = pool::execute_update_pool_status(&e);
 
         PoolEvents::set_status(&e, new_status);
         new_status
     }
 
+    fn preview_update_status(e: Env) -> u32 {
+        pool::preview_update_pool_status(&e)
+    }
+
+    fn get_status(e: Env) -> (u32, i128, i128) {
+        pool::get_pool_status(&e)
+    }
+
     fn set_status(e: Env, pool_status: u32) {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
         pool::execute_set_pool_status(&e, pool_status);
@@ -489,33 +1592,161 @@ impl Pool for PoolContract {
         PoolEvents::set_status_admin(&e, admin, pool_status);
     }
 
+    fn set_reserve_status(e: Env, asset: Address, supply_enabled: bool, borrow_enabled: bool) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::execute_set_reserve_status(&e, &asset, supply_enabled, borrow_enabled);
+
+        PoolEvents::set_reserve_status(&e, admin, asset, supply_enabled, borrow_enabled);
+    }
+
+    fn set_emergency(e: Env, paused: bool, allow_withdrawals: bool) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        pool::execute_set_emergency(&e, paused, allow_withdrawals);
+
+        PoolEvents::set_emergency(&e, admin, paused, allow_withdrawals);
+    }
+
+    fn get_emergency(e: Env) -> (bool, bool) {
+        (
+            storage::get_emergency_pause(&e),
+            storage::get_emergency_withdraw_enabled(&e),
+        )
+    }
+
     fn gulp(e: Env, asset: Address) -> i128 {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         let token_delta = pool::execute_gulp(&e, &asset);
 
         PoolEvents::gulp(&e, asset, token_delta);
         token_delta
     }
 
+    fn gulp_batch(e: Env, assets: Vec<Address>) -> Vec<(Address, i128)> {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let token_deltas = pool::execute_gulp_batch(&e, &assets);
+
+        for (asset, token_delta) in token_deltas.iter() {
+            PoolEvents::gulp(&e, asset, token_delta);
+        }
+        token_deltas
+    }
+
+    fn set_max_gulp(e: Env, asset: Address, max_gulp: i128) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        pool::execute_set_max_gulp(&e, &asset, max_gulp);
+
+        PoolEvents::set_max_gulp(&e, admin, asset, max_gulp);
+    }
+
+    fn get_max_gulp(e: Env, asset: Address) -> i128 {
+        storage::get_max_gulp(&e, &asset)
+    }
+
     /********* Emission Functions **********/
 
     fn gulp_emissions(e: Env) -> i128 {
         storage::extend_instance(&e);
-        let emissions = emissions::gulp_emissions(&e);
+        pool::require_no_flash_loan(&e);
+        pool::require_not_paused(&e);
+        let (emissions, _) = emissions::gulp_emissions(&e);
 
         PoolEvents::gulp_emissions(&e, emissions);
         emissions
     }
 
+    fn gulp_emissions_detailed(e: Env) -> Vec<(u32, i128)> {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        pool::require_not_paused(&e);
+        let (emissions, allocations) = emissions::gulp_emissions(&e);
+
+        PoolEvents::gulp_emissions(&e, emissions);
+        allocations
+    }
+
     fn set_emissions_config(e: Env, res_emission_metadata: Vec<ReserveEmissionMetadata>) {
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        emissions::set_pool_emissions(&e, res_emission_metadata.clone());
+
+        PoolEvents::set_emissions_config(&e, admin, res_emission_metadata);
+    }
+
+    fn get_emissions_config(e: Env) -> Vec<ReserveEmissionMetadata> {
+        emissions::get_pool_emissions(&e)
+    }
+
+    fn set_max_gulp_emissions(e: Env, max_emissions: i128) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        emissions::set_max_gulp_emissions(&e, max_emissions);
+
+        PoolEvents::set_max_gulp_emissions(&e, admin, max_emissions);
+    }
+
+    fn sweep_unclaimed_emissions(e: Env, reserve_token_ids: Vec<u32>) -> i128 {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        let amount_swept = emissions::sweep_unclaimed_emissions(&e, &reserve_token_ids);
+
+        PoolEvents::sweep_emissions(&e, admin, reserve_token_ids, amount_swept);
+        amount_swept
+    }
+
+    fn set_backstop_emission_share(e: Env, share: u32) {
+        pool::require_no_flash_loan(&e);
         let admin = storage::get_admin(&e);
         admin.require_auth();
 
-        emissions::set_pool_emissions(&e, res_emission_metadata);
+        emissions::set_backstop_emission_share(&e, share);
+
+        PoolEvents::set_backstop_emission_share(&e, admin, share);
+    }
+
+    fn set_backstop_emissions_enabled(e: Env, enabled: bool) {
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        emissions::set_backstop_emissions_enabled(&e, enabled);
+
+        PoolEvents::set_backstop_emissions_enabled(&e, admin, enabled);
+    }
+
+    fn set_emissions_min_balance(e: Env, reserve_token_id: u32, min_balance: i128) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+
+        emissions::set_emissions_min_balance(&e, reserve_token_id, min_balance);
+
+        PoolEvents::set_emissions_min_balance(&e, admin, reserve_token_id, min_balance);
     }
 
     fn claim(e: Env, from: Address, reserve_token_ids: Vec<u32>, to: Address) -> i128 {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
         from.require_auth();
 
         let amount_claimed = emissions::execute_claim(&e, &from, &reserve_token_ids, &to);
@@ -525,10 +1756,44 @@ impl Pool for PoolContract {
         amount_claimed
     }
 
+    fn claim_and_supply(
+        e: Env,
+        from: Address,
+        reserve_token_ids: Vec<u32>,
+        supply_reserve: Address,
+    ) -> i128 {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        from.require_auth();
+
+        let amount_claimed =
+            emissions::execute_claim_and_supply(&e, &from, &reserve_token_ids, &supply_reserve);
+
+        PoolEvents::claim(&e, from, reserve_token_ids, amount_claimed);
+
+        amount_claimed
+    }
+
+    fn claim_all(e: Env, from: Address, to: Address) -> i128 {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        from.require_auth();
+
+        let (reserve_token_ids, amount_claimed) = emissions::execute_claim_all(&e, &from, &to);
+
+        PoolEvents::claim(&e, from, reserve_token_ids, amount_claimed);
+
+        amount_claimed
+    }
+
     fn get_reserve_emissions(e: Env, reserve_token_index: u32) -> Option<ReserveEmissionData> {
         storage::get_res_emis_data(&e, &reserve_token_index)
     }
 
+    fn get_all_reserve_emissions(e: Env) -> Vec<(u32, ReserveEmissionData)> {
+        emissions::get_all_reserve_emissions(&e)
+    }
+
     fn get_user_emissions(
         e: Env,
         user: Address,
@@ -537,8 +1802,25 @@ impl Pool for PoolContract {
         storage::get_user_emissions(&e, &user, &reserve_token_index)
     }
 
+    fn get_claimable(e: Env, from: Address, reserve_token_ids: Vec<u32>) -> Vec<i128> {
+        emissions::calc_claimable(&e, &from, &reserve_token_ids)
+    }
+
+    fn get_emissions_index_discrepancy(e: Env, reserve_token_id: u32) -> i128 {
+        emissions::get_emission_index_discrepancy(&e, reserve_token_id)
+    }
+
+    fn get_emissions_min_balance(e: Env, reserve_token_id: u32) -> i128 {
+        storage::get_emis_min_balance(&e, &reserve_token_id).min_balance
+    }
+
+    fn get_emission_history(e: Env, reserve_token_id: u32) -> Vec<(u64, i128)> {
+        emissions::get_emission_history(&e, reserve_token_id)
+    }
+
     /***** Auction / Liquidation Functions *****/
 
+    #[allow(clippy::too_many_arguments)]
     fn new_auction(
         e: Env,
         auction_type: u32,
@@ -546,16 +1828,82 @@ impl Pool for PoolContract {
         bid: Vec<Address>,
         lot: Vec<Address>,
         percent: u32,
+        min_lot: Option<Map<Address, i128>>,
+        max_bid: Option<Map<Address, i128>>,
     ) -> AuctionData {
         storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        pool::require_not_paused(&e);
 
         let auction_data = auctions::create_auction(&e, auction_type, &user, &bid, &lot, percent);
+        auctions::require_auction_bounds(&e, &auction_data, &min_lot, &max_bid);
 
         PoolEvents::new_auction(&e, auction_type, user, percent, auction_data.clone());
         auction_data
     }
 
+    fn new_auction_to_target(
+        e: Env,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        target_hf: i128,
+    ) -> AuctionData {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+        pool::require_not_paused(&e);
+
+        let (percent, auction_data) =
+            auctions::create_user_liq_auction_to_target(&e, &user, &bid, &lot, target_hf);
+
+        PoolEvents::new_auction(
+            &e,
+            AuctionType::UserLiquidation as u32,
+            user,
+            percent,
+            auction_data.clone(),
+        );
+        auction_data
+    }
+
+    fn delete_auction(e: Env, auction_type: u32, user: Address) {
+        storage::extend_instance(&e);
+        pool::require_no_flash_loan(&e);
+
+        auctions::delete_auction(&e, auction_type, &user);
+
+        PoolEvents::delete_liquidation_auction(&e, user);
+    }
+
+    fn preview_liquidation(
+        e: Env,
+        user: Address,
+        bid: Vec<Address>,
+        lot: Vec<Address>,
+        percent: u32,
+        fill_block: u32,
+    ) -> AuctionData {
+        auctions::preview_user_liquidation(&e, &user, &bid, &lot, percent, fill_block)
+    }
+
     fn get_auction(e: Env, auction_type: u32, user: Address) -> AuctionData {
         storage::get_auction(&e, &auction_type, &user)
     }
+
+    fn preview_fill(
+        e: Env,
+        auction_type: u32,
+        user: Address,
+        percent_filled: u64,
+    ) -> (Map<Address, i128>, Map<Address, i128>) {
+        auctions::preview_fill(&e, auction_type, &user, percent_filled)
+    }
+
+    fn get_auctions(e: Env, user: Address) -> Vec<(u32, AuctionData)> {
+        auctions::load_auctions(&e, &user)
+    }
+
+    fn has_auction(e: Env, auction_type: u32, user: Address) -> bool {
+        storage::has_auction(&e, &auction_type, &user)
+    }
 }
@@ -1,12 +1,12 @@
 use cast::i128;
 use sep_41_token::TokenClient;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{panic_with_error, Address, Env, Vec};
+use soroban_sdk::{panic_with_error, vec, Address, Env, Vec};
 
 use crate::{
     constants::SCALAR_7,
     errors::PoolError,
-    pool::User,
+    pool::{self, Request, RequestType, User},
     storage::{self, ReserveEmissionData, UserEmissionData},
     validator::require_nonnegative,
 };
@@ -17,36 +17,16 @@ pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to:
     let reserve_list = storage::get_res_list(e);
     let mut to_claim = 0;
     for reserve_token_id in reserve_token_ids.clone() {
-        let reserve_index = reserve_token_id / 2;
-        let reserve_addr = reserve_list.get(reserve_index);
-        match reserve_addr {
-            Some(res_address) => {
-                let reserve_config = storage::get_res_config(e, &res_address);
-                let reserve_data = storage::get_res_data(e, &res_address);
-                let (user_balance, supply) = match reserve_token_id % 2 {
-                    0 => (
-                        from_state.get_liabilities(reserve_index),
-                        reserve_data.d_supply,
-                    ),
-                    1 => (
-                        from_state.get_total_supply(reserve_index),
-                        reserve_data.b_supply,
-                    ),
-                    _ => panic_with_error!(e, PoolError::BadRequest),
-                };
-                to_claim += claim_emissions(
-                    e,
-                    reserve_token_id,
-                    supply,
-                    10i128.pow(reserve_config.decimals),
-                    from,
-                    user_balance,
-                );
-            }
-            None => {
-                panic_with_error!(e, PoolError::BadRequest)
-            }
-        }
+        let (user_balance, supply, supply_scalar) =
+            load_reserve_token_balance(e, &from_state, &reserve_list, reserve_token_id);
+        to_claim += claim_emissions(
+            e,
+            reserve_token_id,
+            supply,
+            supply_scalar,
+            from,
+            user_balance,
+        );
     }
 
     if to_claim > 0 {
@@ -62,6 +42,147 @@ pub fn execute_claim(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>, to:
     to_claim
 }
 
+/// Performs a claim against the given "reserve_token_ids" for "from" and supplies the claimed
+/// tokens to the "supply_reserve" as collateral for "from"
+///
+/// Returns the amount of tokens claimed and supplied
+///
+/// ### Panics
+/// If "supply_reserve" is not the BLND token or the pool does not have a BLND reserve
+pub fn execute_claim_and_supply(
+    e: &Env,
+    from: &Address,
+    reserve_token_ids: &Vec<u32>,
+    supply_reserve: &Address,
+) -> i128 {
+    if *supply_reserve != storage::get_blnd_token(e) || !storage::has_res(e, supply_reserve) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let to_claim = execute_claim(e, from, reserve_token_ids, from);
+    if to_claim > 0 {
+        let requests = vec![
+            e,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: supply_reserve.clone(),
+                amount: to_claim,
+            },
+        ];
+        pool::execute_submit(e, from, from, from, requests, false);
+    }
+    to_claim
+}
+
+/// Calculate the emissions "from" can claim for each of the given "reserve_token_ids" at the
+/// current ledger timestamp. Does not write to storage or transfer any tokens.
+///
+/// Returns the claimable amount for each reserve token id, in the order given
+///
+/// ### Panics
+/// If any reserve token id is invalid
+pub fn calc_claimable(e: &Env, from: &Address, reserve_token_ids: &Vec<u32>) -> Vec<i128> {
+    let from_state = User::load(e, from);
+    let reserve_list = storage::get_res_list(e);
+    let mut claimable: Vec<i128> = Vec::new(e);
+    for reserve_token_id in reserve_token_ids.iter() {
+        let (user_balance, supply, supply_scalar) =
+            load_reserve_token_balance(e, &from_state, &reserve_list, reserve_token_id);
+        let amount = match storage::get_res_emis_data(e, &reserve_token_id) {
+            Some(res_emis_data) => {
+                let res_emis_data = calc_emission_data(e, &res_emis_data, supply, supply_scalar)
+                    .unwrap_or(res_emis_data);
+                calc_user_accrued(
+                    e,
+                    &res_emis_data,
+                    reserve_token_id,
+                    supply_scalar,
+                    &storage::get_user_emissions(e, from, &reserve_token_id),
+                    user_balance,
+                )
+            }
+            None => 0,
+        };
+        claimable.push_back(amount);
+    }
+    claimable
+}
+
+/// Load a user's balance of a reserve token, the reserve token's supply, and its scalar
+///
+/// Returns (user_balance, supply, supply_scalar)
+///
+/// ### Panics
+/// If the reserve token id is invalid
+fn load_reserve_token_balance(
+    e: &Env,
+    user_state: &User,
+    reserve_list: &Vec<Address>,
+    reserve_token_id: u32,
+) -> (i128, i128, i128) {
+    let reserve_index = reserve_token_id / 2;
+    match reserve_list.get(reserve_index) {
+        Some(res_address) => {
+            let reserve_config = storage::get_res_config(e, &res_address);
+            let reserve_data = storage::get_res_data(e, &res_address);
+            let (user_balance, supply) = match reserve_token_id % 2 {
+                0 => (
+                    user_state.get_liabilities(reserve_index),
+                    reserve_data.d_supply,
+                ),
+                1 => (
+                    user_state.get_total_supply(reserve_index),
+                    reserve_data.b_supply,
+                ),
+                _ => panic_with_error!(e, PoolError::BadRequest),
+            };
+            (user_balance, supply, 10i128.pow(reserve_config.decimals))
+        }
+        None => {
+            panic_with_error!(e, PoolError::BadRequest)
+        }
+    }
+}
+
+/// Performs a claim against every reserve token "from" has emissions data for. Reserve tokens
+/// where "from" has no accrued emissions and no balance are skipped.
+///
+/// Returns the reserve token ids claimed and the amount claimed
+pub fn execute_claim_all(e: &Env, from: &Address, to: &Address) -> (Vec<u32>, i128) {
+    let from_state = User::load(e, from);
+    let reserve_list = storage::get_res_list(e);
+    let mut reserve_token_ids: Vec<u32> = Vec::new(e);
+    for reserve_index in 0..reserve_list.len() {
+        let d_token_id = reserve_index * 2;
+        if has_claimable_emissions(
+            e,
+            from,
+            d_token_id,
+            from_state.get_liabilities(reserve_index),
+        ) {
+            reserve_token_ids.push_back(d_token_id);
+        }
+        let b_token_id = reserve_index * 2 + 1;
+        if has_claimable_emissions(
+            e,
+            from,
+            b_token_id,
+            from_state.get_total_supply(reserve_index),
+        ) {
+            reserve_token_ids.push_back(b_token_id);
+        }
+    }
+    let amount_claimed = execute_claim(e, from, &reserve_token_ids, to);
+    (reserve_token_ids, amount_claimed)
+}
+
+/// Check if a user could have emissions to claim for a reserve token
+fn has_claimable_emissions(e: &Env, user: &Address, res_token_id: u32, balance: i128) -> bool {
+    match storage::get_user_emissions(e, user, &res_token_id) {
+        Some(user_emis_data) => user_emis_data.accrued > 0 || balance > 0,
+        None => false,
+    }
+}
+
 /// Update the emissions information about a reserve token. Must be called before any update
 /// is made to the supply of debtTokens or blendTokens.
 ///
@@ -156,31 +277,96 @@ pub(super) fn update_emission_data(
     supply_scalar: i128,
 ) -> Option<ReserveEmissionData> {
     match storage::get_res_emis_data(e, &res_token_id) {
-        Some(mut res_emission_data) => {
-            if res_emission_data.last_time >= res_emission_data.expiration
-                || e.ledger().timestamp() == res_emission_data.last_time
-                || res_emission_data.eps == 0
-                || supply == 0
-            {
-                return Some(res_emission_data);
+        Some(res_emission_data) => {
+            match calc_emission_data(e, &res_emission_data, supply, supply_scalar) {
+                Some(new_emission_data) => {
+                    storage::set_res_emis_data(e, &res_token_id, &new_emission_data);
+                    Some(new_emission_data)
+                }
+                None => Some(res_emission_data),
             }
+        }
+        None => return None, // no emission exist, no update is required
+    }
+}
 
-            let ledger_timestamp = if e.ledger().timestamp() > res_emission_data.expiration {
-                res_emission_data.expiration
-            } else {
-                e.ledger().timestamp()
-            };
+/// Calculate the reserve token emission data at the current ledger timestamp. Does not write to storage.
+///
+/// Returns the new ReserveEmissionData, or None if the emission data does not need to be updated
+///
+/// ### Arguments
+/// * `res_emission_data` - The stored emission data for the reserve token
+/// * `supply` - The current supply of the reserve token
+/// * `supply_scalar` - The scalar of the reserve token
+fn calc_emission_data(
+    e: &Env,
+    res_emission_data: &ReserveEmissionData,
+    supply: i128,
+    supply_scalar: i128,
+) -> Option<ReserveEmissionData> {
+    if res_emission_data.last_time >= res_emission_data.expiration
+        || e.ledger().timestamp() == res_emission_data.last_time
+        || res_emission_data.eps == 0
+        || supply == 0
+    {
+        return None;
+    }
+
+    let ledger_timestamp = if e.ledger().timestamp() > res_emission_data.expiration {
+        res_emission_data.expiration
+    } else {
+        e.ledger().timestamp()
+    };
+
+    let additional_idx = (i128(ledger_timestamp - res_emission_data.last_time)
+        * i128(res_emission_data.eps))
+    .fixed_div_floor(&e, &supply, &supply_scalar);
 
-            let additional_idx = (i128(ledger_timestamp - res_emission_data.last_time)
-                * i128(res_emission_data.eps))
-            .fixed_div_floor(&e, &supply, &supply_scalar);
+    Some(ReserveEmissionData {
+        expiration: res_emission_data.expiration,
+        eps: res_emission_data.eps,
+        index: res_emission_data.index + additional_idx,
+        last_time: ledger_timestamp,
+    })
+}
 
-            res_emission_data.index += additional_idx;
-            res_emission_data.last_time = ledger_timestamp;
-            storage::set_res_emis_data(e, &res_token_id, &res_emission_data);
-            Some(res_emission_data)
+/// Recompute the expected emission index for a reserve token from the stored eps, last_time, and
+/// expiration at the current ledger timestamp, and compare it to the stored index.
+///
+/// Returns the expected index minus the stored index, or 0 if no emission data exists
+///
+/// ### Arguments
+/// * `res_token_id` - The reserve token id => (reserve index * 2 + (0 for debtToken or 1 for blendToken))
+///
+/// ### Panics
+/// If the reserve token id is invalid
+pub fn get_emission_index_discrepancy(e: &Env, res_token_id: u32) -> i128 {
+    let reserve_list = storage::get_res_list(e);
+    let res_address = match reserve_list.get(res_token_id / 2) {
+        Some(res_address) => res_address,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    match storage::get_res_emis_data(e, &res_token_id) {
+        Some(res_emission_data) => {
+            let reserve_config = storage::get_res_config(e, &res_address);
+            let reserve_data = storage::get_res_data(e, &res_address);
+            let supply = match res_token_id % 2 {
+                0 => reserve_data.d_supply,
+                _ => reserve_data.b_supply,
+            };
+            match calc_emission_data(
+                e,
+                &res_emission_data,
+                supply,
+                10i128.pow(reserve_config.decimals),
+            ) {
+                Some(expected_emission_data) => {
+                    expected_emission_data.index - res_emission_data.index
+                }
+                None => 0,
+            }
         }
-        None => return None, // no emission exist, no update is required
+        None => 0,
     }
 }
 
@@ -193,31 +379,68 @@ fn update_user_emissions(
     balance: i128,
     claim: bool,
 ) -> i128 {
-    if let Some(user_data) = storage::get_user_emissions(e, user, &res_token_id) {
-        if user_data.index != res_emis_data.index || claim {
-            let mut accrual = user_data.accrued;
-            if balance != 0 {
-                let delta_index = res_emis_data.index - user_data.index;
-                require_nonnegative(e, &delta_index);
-                let to_accrue = balance.fixed_mul_floor(
-                    e,
-                    &(res_emis_data.index - user_data.index),
-                    &(supply_scalar * SCALAR_7),
-                );
-                accrual += to_accrue;
-            }
-            return set_user_emissions(e, user, res_token_id, res_emis_data.index, accrual, claim);
+    let user_data = storage::get_user_emissions(e, user, &res_token_id);
+    if let Some(user_data) = &user_data {
+        if user_data.index == res_emis_data.index && !claim {
+            return 0;
         }
-        0
-    } else if balance == 0 {
-        // first time the user registered an action with the asset since emissions were added
-        return set_user_emissions(e, user, res_token_id, res_emis_data.index, 0, claim);
-    } else {
-        // user had tokens before emissions began, they are due any historical emissions
-        let to_accrue =
-            balance.fixed_mul_floor(e, &res_emis_data.index, &(supply_scalar * SCALAR_7));
-        return set_user_emissions(e, user, res_token_id, res_emis_data.index, to_accrue, claim);
     }
+    let accrued = calc_user_accrued(
+        e,
+        res_emis_data,
+        res_token_id,
+        supply_scalar,
+        &user_data,
+        balance,
+    );
+    set_user_emissions(e, user, res_token_id, res_emis_data.index, accrued, claim)
+}
+
+/// Calculate the total emissions a user has accrued for a reserve token up to the reserve
+/// token's current emission index. Does not write to storage.
+///
+/// ### Arguments
+/// * `res_emis_data` - The reserve token emission data
+/// * `res_token_id` - The reserve token id
+/// * `supply_scalar` - The scalar of the reserve token
+/// * `user_data` - The user's stored emission data for the reserve token, if any
+/// * `balance` - The current balance of the user
+fn calc_user_accrued(
+    e: &Env,
+    res_emis_data: &ReserveEmissionData,
+    res_token_id: u32,
+    supply_scalar: i128,
+    user_data: &Option<UserEmissionData>,
+    balance: i128,
+) -> i128 {
+    let (user_index, mut accrual) = match user_data {
+        Some(user_data) => (user_data.index, user_data.accrued),
+        // first time the user registered an action with the asset since emissions were added,
+        // if they had tokens before emissions began they are due any historical emissions
+        None => (0, 0),
+    };
+    if balance == 0 {
+        return accrual;
+    }
+    let delta_index = res_emis_data.index - user_index;
+    require_nonnegative(e, &delta_index);
+
+    // positions below the minimum size for the reserve token do not accrue emissions. The index
+    // accrued before the current minimum took effect is accrued under the previous minimum.
+    let min_balance = storage::get_emis_min_balance(e, &res_token_id);
+    let split_index = min_balance.index.clamp(user_index, res_emis_data.index);
+    if split_index > user_index && balance >= min_balance.prev_min_balance {
+        accrual +=
+            balance.fixed_mul_floor(e, &(split_index - user_index), &(supply_scalar * SCALAR_7));
+    }
+    if res_emis_data.index > split_index && balance >= min_balance.min_balance {
+        accrual += balance.fixed_mul_floor(
+            e,
+            &(res_emis_data.index - split_index),
+            &(supply_scalar * SCALAR_7),
+        );
+    }
+    accrual
 }
 
 fn set_user_emissions(
@@ -244,7 +467,7 @@ fn set_user_emissions(
 
 #[cfg(test)]
 mod tests {
-    use crate::{pool::Positions, testutils};
+    use crate::{pool::Positions, storage::EmissionMinBalance, testutils};
 
     use super::*;
     use soroban_sdk::{
@@ -897,6 +1120,74 @@ mod tests {
         });
     }
 
+    /********** get_emission_index_discrepancy **********/
+
+    #[test]
+    fn test_get_emission_index_discrepancy() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1501000000, // 10^6 seconds have passed
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            let reserve_emission_data = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01000000000000,
+                index: 23456780000000,
+                last_time: 1500000000,
+            };
+            let res_token_index = 0 * 2 + 1; // b_token for reserve 0
+            storage::set_res_emis_data(&e, &res_token_index, &reserve_emission_data);
+
+            // normal operation - index is updated to the current timestamp
+            update_emission_data(&e, res_token_index, 100_0000000, 1_0000000);
+            assert_eq!(get_emission_index_discrepancy(&e, res_token_index), 0);
+
+            // corrupt the last update time
+            let mut corrupted_emission_data =
+                storage::get_res_emis_data(&e, &res_token_index).unwrap_optimized();
+            corrupted_emission_data.last_time -= 1000;
+            storage::set_res_emis_data(&e, &res_token_index, &corrupted_emission_data);
+            assert_eq!(
+                get_emission_index_discrepancy(&e, res_token_index),
+                10000000000000
+            );
+
+            // reserve token with no emissions
+            assert_eq!(get_emission_index_discrepancy(&e, 0), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_get_emission_index_discrepancy_invalid_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            get_emission_index_discrepancy(&e, 2);
+        });
+    }
+
     /********** update_user_emissions **********/
 
     #[test]
@@ -1158,6 +1449,166 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_update_user_emissions_below_min_balance_skips_accrual() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let supply_scalar = 1_0000000;
+        e.as_contract(&pool, || {
+            let reserve_emission_data = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01000000000000,
+                index: 1234567890000000,
+                last_time: 1500000000,
+            };
+            let user_emission_data = UserEmissionData {
+                index: 567890000000,
+                accrued: 0_1000000,
+            };
+
+            let res_token_type = 1;
+            let res_token_index = 1 * 2 + res_token_type;
+            storage::set_emis_min_balance(
+                &e,
+                &res_token_index,
+                &EmissionMinBalance {
+                    min_balance: 1_0000000,
+                    prev_min_balance: 0,
+                    index: 0,
+                },
+            );
+            storage::set_user_emissions(&e, &samwise, &res_token_index, &user_emission_data);
+            storage::set_user_emissions(&e, &frodo, &res_token_index, &user_emission_data);
+
+            // dust position
+            update_user_emissions(
+                &e,
+                &reserve_emission_data,
+                res_token_index,
+                supply_scalar,
+                &samwise,
+                0_5000000,
+                false,
+            );
+            // normal position
+            update_user_emissions(
+                &e,
+                &reserve_emission_data,
+                res_token_index,
+                supply_scalar,
+                &frodo,
+                2_0000000,
+                false,
+            );
+
+            let samwise_emission_data =
+                storage::get_user_emissions(&e, &samwise, &res_token_index).unwrap_optimized();
+            assert_eq!(samwise_emission_data.index, reserve_emission_data.index);
+            assert_eq!(samwise_emission_data.accrued, 0_1000000);
+
+            let frodo_emission_data =
+                storage::get_user_emissions(&e, &frodo, &res_token_index).unwrap_optimized();
+            assert_eq!(frodo_emission_data.index, reserve_emission_data.index);
+            assert_eq!(frodo_emission_data.accrued, 24_7800000);
+        });
+    }
+
+    #[test]
+    fn test_update_user_emissions_min_balance_not_retroactive() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let supply_scalar = 1_0000000;
+        e.as_contract(&pool, || {
+            let reserve_emission_data = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01000000000000,
+                index: 1234567890000000,
+                last_time: 1500000000,
+            };
+            let user_emission_data = UserEmissionData {
+                index: 567890000000,
+                accrued: 0_1000000,
+            };
+
+            let res_token_type = 1;
+            let res_token_index = 1 * 2 + res_token_type;
+            storage::set_emis_min_balance(
+                &e,
+                &res_token_index,
+                &EmissionMinBalance {
+                    min_balance: 1_0000000,
+                    prev_min_balance: 0,
+                    index: 900000000000000,
+                },
+            );
+            storage::set_user_emissions(&e, &samwise, &res_token_index, &user_emission_data);
+            storage::set_user_emissions(&e, &frodo, &res_token_index, &user_emission_data);
+
+            // dust position, accrues up to the index the minimum took effect at
+            update_user_emissions(
+                &e,
+                &reserve_emission_data,
+                res_token_index,
+                supply_scalar,
+                &samwise,
+                0_5000000,
+                false,
+            );
+            // normal position
+            update_user_emissions(
+                &e,
+                &reserve_emission_data,
+                res_token_index,
+                supply_scalar,
+                &frodo,
+                2_0000000,
+                false,
+            );
+
+            let samwise_emission_data =
+                storage::get_user_emissions(&e, &samwise, &res_token_index).unwrap_optimized();
+            assert_eq!(samwise_emission_data.index, reserve_emission_data.index);
+            assert_eq!(samwise_emission_data.accrued, 4_5971605);
+
+            let frodo_emission_data =
+                storage::get_user_emissions(&e, &frodo, &res_token_index).unwrap_optimized();
+            assert_eq!(frodo_emission_data.index, reserve_emission_data.index);
+            assert_eq!(frodo_emission_data.accrued, 24_7800000);
+        });
+    }
+
     #[test]
     fn test_update_user_emissions_claim_returns_accrual() {
         let e = Env::default();
@@ -1442,6 +1893,265 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_calc_claimable() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let merry = Address::generate(&e);
+
+        let (blnd, blnd_token_client) = testutils::create_blnd_token(&e, &pool, &bombadil);
+        let (backstop, _) = testutils::create_backstop(
+            &e,
+            &pool,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            &blnd,
+        );
+        // mock backstop having emissions for pool
+        e.as_contract(&backstop, || {
+            blnd_token_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+        });
+        blnd_token_client.mint(&backstop, &100_000_0000000);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1501000000, // 10^6 seconds have passed
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 5;
+        reserve_data.b_supply = 100_00000;
+        reserve_data.d_supply = 50_00000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 9;
+        reserve_config.index = 1;
+        reserve_data.b_supply = 100_000_000_000;
+        reserve_data.d_supply = 50_000_000_000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 2_00000)],
+            collateral: map![&e, (1, 1_000_000_000)],
+            supply: map![&e, (1, 1_000_000_000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_backstop(&e, &backstop);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let reserve_emission_data_0 = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01000000000000,
+                index: 23456780000000,
+                last_time: 1500000000,
+            };
+            let user_emission_data_0 = UserEmissionData {
+                index: 12345670000000,
+                accrued: 0_1000000,
+            };
+            let res_token_index_0 = 0 * 2 + 0; // d_token for reserve 0
+
+            let reserve_emission_data_1 = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01500000000000,
+                index: 13456780000000,
+                last_time: 1500000000,
+            };
+            let user_emission_data_1 = UserEmissionData {
+                index: 12345670000000,
+                accrued: 1_0000000,
+            };
+            let res_token_index_1 = 1 * 2 + 1; // b_token for reserve 1
+
+            storage::set_res_emis_data(&e, &res_token_index_0, &reserve_emission_data_0);
+            storage::set_user_emissions(&e, &samwise, &res_token_index_0, &user_emission_data_0);
+
+            storage::set_res_emis_data(&e, &res_token_index_1, &reserve_emission_data_1);
+            storage::set_user_emissions(&e, &samwise, &res_token_index_1, &user_emission_data_1);
+
+            let reserve_token_ids: Vec<u32> = vec![&e, res_token_index_0, res_token_index_1];
+            let claimable = calc_claimable(&e, &samwise, &reserve_token_ids);
+            assert_eq!(claimable.len(), 2);
+
+            // nothing is written
+            let reserve_emission_data = storage::get_res_emis_data(&e, &res_token_index_0).unwrap();
+            assert_eq!(reserve_emission_data.last_time, 1500000000);
+            let user_emission_data =
+                storage::get_user_emissions(&e, &samwise, &res_token_index_0).unwrap();
+            assert_eq!(user_emission_data.accrued, 0_1000000);
+            assert_eq!(blnd_token_client.balance(&merry), 0);
+
+            // claimable amounts match what is claimed
+            let result_0 = execute_claim(&e, &samwise, &vec![&e, res_token_index_0], &merry);
+            assert_eq!(claimable.get_unchecked(0), result_0);
+            let result_1 = execute_claim(&e, &samwise, &vec![&e, res_token_index_1], &merry);
+            assert_eq!(claimable.get_unchecked(1), result_1);
+            assert_eq!(result_0 + result_1, 400_3222222 + 301_0222222);
+
+            // nothing is claimable after claiming
+            let claimable = calc_claimable(&e, &samwise, &reserve_token_ids);
+            assert_eq!(claimable.get_unchecked(0), 0);
+            assert_eq!(claimable.get_unchecked(1), 0);
+        });
+    }
+
+    #[test]
+    fn test_execute_claim_all() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.cost_estimate().budget().reset_unlimited();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let merry = Address::generate(&e);
+
+        let (blnd, blnd_token_client) = testutils::create_blnd_token(&e, &pool, &bombadil);
+        let (backstop, _) = testutils::create_backstop(
+            &e,
+            &pool,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            &blnd,
+        );
+        // mock backstop having emissions for pool
+        e.as_contract(&backstop, || {
+            blnd_token_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+        });
+        blnd_token_client.mint(&backstop, &100_000_0000000);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1501000000, // 10^6 seconds have passed
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 5;
+        reserve_data.b_supply = 100_00000;
+        reserve_data.d_supply = 50_00000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 9;
+        reserve_config.index = 1;
+        reserve_data.b_supply = 100_000_000_000;
+        reserve_data.d_supply = 50_000_000_000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 2_00000)],
+            collateral: map![&e, (1, 1_000_000_000)],
+            supply: map![&e, (1, 1_000_000_000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_backstop(&e, &backstop);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let reserve_emission_data_0 = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01000000000000,
+                index: 23456780000000,
+                last_time: 1500000000,
+            };
+            let user_emission_data_0 = UserEmissionData {
+                index: 12345670000000,
+                accrued: 0_1000000,
+            };
+            let res_token_index_0 = 0 * 2 + 0; // d_token for reserve 0
+
+            let reserve_emission_data_1 = ReserveEmissionData {
+                expiration: 1600000000,
+                eps: 0_01500000000000,
+                index: 13456780000000,
+                last_time: 1500000000,
+            };
+            let user_emission_data_1 = UserEmissionData {
+                index: 12345670000000,
+                accrued: 1_0000000,
+            };
+            let res_token_index_1 = 1 * 2 + 1; // b_token for reserve 1
+
+            storage::set_res_emis_data(&e, &res_token_index_0, &reserve_emission_data_0);
+            storage::set_user_emissions(&e, &samwise, &res_token_index_0, &user_emission_data_0);
+
+            storage::set_res_emis_data(&e, &res_token_index_1, &reserve_emission_data_1);
+            storage::set_user_emissions(&e, &samwise, &res_token_index_1, &user_emission_data_1);
+
+            // b_token for reserve 0 has emissions data, but samwise has no balance or accrued emissions
+            let res_token_index_skipped = 0 * 2 + 1;
+            storage::set_res_emis_data(&e, &res_token_index_skipped, &reserve_emission_data_0);
+            storage::set_user_emissions(
+                &e,
+                &samwise,
+                &res_token_index_skipped,
+                &UserEmissionData {
+                    index: 12345670000000,
+                    accrued: 0,
+                },
+            );
+
+            let (claimed_ids, result) = execute_claim_all(&e, &samwise, &merry);
+            assert_eq!(claimed_ids, vec![&e, res_token_index_0, res_token_index_1]);
+
+            let new_reserve_emission_data =
+                storage::get_res_emis_data(&e, &res_token_index_0).unwrap_optimized();
+            let new_user_emission_data =
+                storage::get_user_emissions(&e, &samwise, &res_token_index_0).unwrap_optimized();
+            assert_eq!(new_reserve_emission_data.last_time, 1501000000);
+            assert_eq!(
+                new_user_emission_data.index,
+                new_reserve_emission_data.index
+            );
+            assert_eq!(new_user_emission_data.accrued, 0);
+
+            let new_reserve_emission_data_1 =
+                storage::get_res_emis_data(&e, &res_token_index_1).unwrap_optimized();
+            let new_user_emission_data_1 =
+                storage::get_user_emissions(&e, &samwise, &res_token_index_1).unwrap_optimized();
+            assert_eq!(new_reserve_emission_data_1.last_time, 1501000000);
+            assert_eq!(
+                new_user_emission_data_1.index,
+                new_reserve_emission_data_1.index
+            );
+            assert_eq!(new_user_emission_data.accrued, 0);
+            assert_eq!(result, 400_3222222 + 301_0222222);
+
+            // verify the skipped reserve token was not updated
+            let skipped_reserve_emission_data =
+                storage::get_res_emis_data(&e, &res_token_index_skipped).unwrap_optimized();
+            assert_eq!(skipped_reserve_emission_data.last_time, 1500000000);
+
+            // verify tokens are sent
+            assert_eq!(blnd_token_client.balance(&merry), 400_3222222 + 301_0222222);
+            assert_eq!(
+                blnd_token_client.balance(&backstop),
+                100_000_0000000 - (400_3222222 + 301_0222222)
+            )
+        });
+    }
+
     #[test]
     fn test_execute_claim_with_already_claimed_reserve() {
         let e = Env::default();
@@ -1,11 +1,13 @@
 use crate::{
-    constants::SCALAR_7,
+    constants::{MAX_EMIS_HISTORY, SCALAR_7},
     dependencies::BackstopClient,
     errors::PoolError,
     events::PoolEvents,
-    storage::{self, ReserveConfig, ReserveEmissionData},
+    storage::{self, EmissionMinBalance, ReserveConfig, ReserveEmissionData},
+    validator::require_nonnegative,
 };
 use cast::{i128, u64};
+use sep_41_token::TokenClient;
 use soroban_fixed_point_math::SorobanFixedPoint;
 use soroban_sdk::{
     contracttype, map, panic_with_error, unwrap::UnwrapOptimized, Address, Env, Map, Vec,
@@ -16,6 +18,7 @@ use super::distributor;
 // Types
 
 /// Metadata for a pool's reserve emission configuration
+#[derive(Clone)]
 #[contracttype]
 pub struct ReserveEmissionMetadata {
     pub res_index: u32,
@@ -52,21 +55,209 @@ pub fn set_pool_emissions(e: &Env, res_emission_metadata: Vec<ReserveEmissionMet
     storage::set_pool_emissions(e, &pool_emissions);
 }
 
+/// Get the pool emissions configuration
+///
+/// Returns a vector of `ReserveEmissionMetadata` for each reserve token with a configured share,
+/// ordered by reserve token id
+pub fn get_pool_emissions(e: &Env) -> Vec<ReserveEmissionMetadata> {
+    let pool_emissions = storage::get_pool_emissions(e);
+    let mut res_emission_metadata: Vec<ReserveEmissionMetadata> = Vec::new(e);
+    for (res_token_id, share) in pool_emissions.iter() {
+        res_emission_metadata.push_back(ReserveEmissionMetadata {
+            res_index: res_token_id / 2,
+            res_type: res_token_id % 2,
+            share,
+        });
+    }
+    res_emission_metadata
+}
+
+/// Get the recent emission index checkpoints for a reserve token. A checkpoint is recorded each
+/// time emissions are gulped, and only the most recent `MAX_EMIS_HISTORY` are kept.
+///
+/// Returns a vector of (timestamp, index) ordered from oldest to newest
+///
+/// ### Arguments
+/// * `res_token_id` - The reserve token id
+pub fn get_emission_history(e: &Env, res_token_id: u32) -> Vec<(u64, i128)> {
+    storage::get_emis_history(e, &res_token_id)
+}
+
+/// Get the emissions data for every reserve token in the pool
+///
+/// Returns a vector of (reserve token id, emission data) for each reserve token with emission
+/// data, ordered by reserve token id
+pub fn get_all_reserve_emissions(e: &Env) -> Vec<(u32, ReserveEmissionData)> {
+    let reserve_count = storage::get_res_list(e).len();
+    let mut res_emissions: Vec<(u32, ReserveEmissionData)> = Vec::new(e);
+    for res_token_id in 0..reserve_count * 2 {
+        if let Some(emis_data) = storage::get_res_emis_data(e, &res_token_id) {
+            res_emissions.push_back((res_token_id, emis_data));
+        }
+    }
+    res_emissions
+}
+
+/// Set the minimum position size required for a user to accrue emissions on a reserve token
+///
+/// The reserve token's emissions are checkpointed first, so the new minimum only applies to
+/// emissions from this point on. Emissions a user had not yet checkpointed before the change
+/// accrue under the previous minimum.
+///
+/// Positions below the minimum still count towards the reserve token's supply, so the share of
+/// emissions they would have earned is not claimable by anyone and remains in the pool's BLND
+/// allowance.
+///
+/// ### Arguments
+/// * `res_token_id` - The reserve token id
+/// * `min_balance` - The minimum position size, in d/bTokens. A value of 0 removes the minimum.
+///
+/// ### Panics
+/// If the reserve token id is invalid or the minimum balance is negative
+pub fn set_emissions_min_balance(e: &Env, res_token_id: u32, min_balance: i128) {
+    let reserve_list = storage::get_res_list(e);
+    let asset = match reserve_list.get(res_token_id / 2) {
+        Some(asset) => asset,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    require_nonnegative(e, &min_balance);
+
+    let reserve_config = storage::get_res_config(e, &asset);
+    let reserve_data = storage::get_res_data(e, &asset);
+    let supply = match res_token_id % 2 {
+        0 => reserve_data.d_supply,
+        _ => reserve_data.b_supply,
+    };
+    let index = match distributor::update_emission_data(
+        e,
+        res_token_id,
+        supply,
+        10i128.pow(reserve_config.decimals),
+    ) {
+        Some(emission_data) => emission_data.index,
+        None => 0,
+    };
+
+    let prev_min_balance = storage::get_emis_min_balance(e, &res_token_id).min_balance;
+    storage::set_emis_min_balance(
+        e,
+        &res_token_id,
+        &EmissionMinBalance {
+            min_balance,
+            prev_min_balance,
+            index,
+        },
+    );
+}
+
 /// Consume emitted tokens from the backstop and distribute them to reserves
 ///
-/// Returns the number of new tokens distributed for emissions
+/// Returns a tuple of (the number of new tokens distributed for emissions,
+/// a vector of (reserve_token_id, tokens allocated) for each reserve token that received emissions)
 ///
 /// ### Panics
 /// If the pool is not in the backstop reward zone
-pub fn gulp_emissions(e: &Env) -> i128 {
+pub fn gulp_emissions(e: &Env) -> (i128, Vec<(u32, i128)>) {
     let backstop = storage::get_backstop(e);
     let new_emissions =
         BackstopClient::new(e, &backstop).gulp_emissions(&e.current_contract_address());
-    do_gulp_emissions(e, new_emissions);
-    new_emissions
+    let allocations = do_gulp_emissions(e, new_emissions);
+    (new_emissions, allocations)
 }
 
-fn do_gulp_emissions(e: &Env, new_emissions: i128) {
+/// Set the maximum emissions the pool can gulp from the backstop in a single `gulp_emissions` call
+///
+/// ### Arguments
+/// * `max_emissions` - The maximum emissions the pool can gulp at once, or 0 to remove the cap
+pub fn set_max_gulp_emissions(e: &Env, max_emissions: i128) {
+    let backstop = storage::get_backstop(e);
+    BackstopClient::new(e, &backstop)
+        .set_max_gulp_emissions(&e.current_contract_address(), &max_emissions);
+}
+
+/// Set the share of the pool's reward zone emissions that go to the backstop. The remainder
+/// is given to the pool.
+///
+/// ### Arguments
+/// * `share` - The backstop's share of the emissions (7 decimals)
+pub fn set_backstop_emission_share(e: &Env, share: u32) {
+    let backstop = storage::get_backstop(e);
+    BackstopClient::new(e, &backstop)
+        .set_backstop_emission_share(&e.current_contract_address(), &share);
+}
+
+/// Enable or disable the pool's reward zone emissions on the backstop
+///
+/// ### Arguments
+/// * `enabled` - Whether the pool accrues reward zone emissions
+pub fn set_backstop_emissions_enabled(e: &Env, enabled: bool) {
+    let backstop = storage::get_backstop(e);
+    BackstopClient::new(e, &backstop)
+        .set_pool_emissions_enabled(&e.current_contract_address(), &enabled);
+}
+
+/// Reclaim the emissions left to emit for reserve tokens with no supply. The reserve token's emissions
+/// are stopped, and the backstop's BLND allowance for the pool is reduced by the reclaimed amount.
+///
+/// Returns the amount of emissions reclaimed
+///
+/// ### Arguments
+/// * `reserve_token_ids` - The reserve token ids to sweep
+///
+/// ### Panics
+/// If any reserve token id is invalid, has no emission data, or has a non-zero supply
+pub fn sweep_unclaimed_emissions(e: &Env, reserve_token_ids: &Vec<u32>) -> i128 {
+    let reserve_list = storage::get_res_list(e);
+    let mut to_sweep: i128 = 0;
+    for res_token_id in reserve_token_ids.iter() {
+        let res_asset_address = match reserve_list.get(res_token_id / 2) {
+            Some(res_asset_address) => res_asset_address,
+            None => panic_with_error!(e, PoolError::BadRequest),
+        };
+        let reserve_data = storage::get_res_data(e, &res_asset_address);
+        let supply = match res_token_id % 2 {
+            0 => reserve_data.d_supply,
+            _ => reserve_data.b_supply,
+        };
+        let mut emission_data = match storage::get_res_emis_data(e, &res_token_id) {
+            Some(emission_data) => emission_data,
+            None => panic_with_error!(e, PoolError::BadRequest),
+        };
+        if supply != 0 {
+            panic_with_error!(e, PoolError::BadRequest);
+        }
+
+        // no supply has accrued emissions since the last update, so everything left to emit is unclaimable
+        if emission_data.expiration > emission_data.last_time {
+            let time_left = i128(emission_data.expiration - emission_data.last_time);
+            // Eps is scaled by 14 decimals
+            to_sweep += i128(emission_data.eps).fixed_mul_floor(e, &time_left, &SCALAR_7);
+        }
+        emission_data.eps = 0;
+        emission_data.last_time = e.ledger().timestamp();
+        storage::set_res_emis_data(e, &res_token_id, &emission_data);
+        PoolEvents::reserve_emission_update(e, res_token_id, 0, emission_data.expiration);
+    }
+
+    if to_sweep > 0 {
+        // return the swept emissions to the backstop to reduce the pool's allowance
+        let backstop = storage::get_backstop(e);
+        let blnd_token_client = TokenClient::new(e, &storage::get_blnd_token(e));
+        let allowance = blnd_token_client.allowance(&backstop, &e.current_contract_address());
+        to_sweep = to_sweep.min(allowance);
+        if to_sweep > 0 {
+            blnd_token_client.transfer_from(
+                &e.current_contract_address(),
+                &backstop,
+                &backstop,
+                &to_sweep,
+            );
+        }
+    }
+    to_sweep
+}
+
+fn do_gulp_emissions(e: &Env, new_emissions: i128) -> Vec<(u32, i128)> {
     // ensure enough tokens are being emitted to avoid rounding issues
     if new_emissions < SCALAR_7 {
         panic_with_error!(e, PoolError::BadRequest)
@@ -91,6 +282,7 @@ fn do_gulp_emissions(e: &Env, new_emissions: i128) {
             total_share += i128(res_eps_share);
         }
     }
+    let mut allocations: Vec<(u32, i128)> = Vec::new(e);
     for (res_config, res_asset_address, res_token_id, res_eps_share) in pool_emis_enabled {
         let new_reserve_emissions = i128(res_eps_share)
             .fixed_div_floor(e, &total_share, &SCALAR_7)
@@ -103,7 +295,9 @@ fn do_gulp_emissions(e: &Env, new_emissions: i128) {
             res_token_id,
             new_reserve_emissions,
         );
+        allocations.push_back((res_token_id, new_reserve_emissions));
     }
+    allocations
 }
 
 fn update_reserve_emission_eps(
@@ -152,6 +346,7 @@ fn update_reserve_emission_eps(
         emission_data.expiration = expiration;
         emission_data.eps = eps;
         storage::set_res_emis_data(e, &res_token_id, &emission_data);
+        record_emission_checkpoint(e, res_token_id, emission_data.index);
         PoolEvents::reserve_emission_update(e, res_token_id, eps, expiration);
     } else {
         // no config or data exists yet - first time this reserve token will get emission
@@ -166,10 +361,22 @@ fn update_reserve_emission_eps(
                 last_time: e.ledger().timestamp(),
             },
         );
+        record_emission_checkpoint(e, res_token_id, 0);
         PoolEvents::reserve_emission_update(e, res_token_id, eps, expiration);
     }
 }
 
+/// Record an emission index checkpoint for a reserve token at the current timestamp, dropping
+/// the oldest checkpoint if the history is full
+fn record_emission_checkpoint(e: &Env, res_token_id: u32, index: i128) {
+    let mut history = storage::get_emis_history(e, &res_token_id);
+    if history.len() >= MAX_EMIS_HISTORY {
+        history.pop_front();
+    }
+    history.push_back((e.ledger().timestamp(), index));
+    storage::set_emis_history(e, &res_token_id, &history);
+}
+
 #[cfg(test)]
 mod tests {
     use crate::testutils;
@@ -308,6 +515,53 @@ mod tests {
             assert_eq!(r_1_s_config.eps, 0_12500000000000);
             assert_eq!(r_1_s_data.index, 111110000000);
             assert_eq!(r_1_s_data.last_time, 1500000000);
+
+            // verify checkpoints were recorded for each updated reserve token
+            assert_eq!(
+                get_emission_history(&e, 0),
+                vec![&e, (1500000000, (99999 + 40 * SCALAR_7) * SCALAR_7)]
+            );
+            assert_eq!(get_emission_history(&e, 2), vec![&e, (1500000000, 0)]);
+            assert_eq!(
+                get_emission_history(&e, 3),
+                vec![&e, (1500000000, 111110000000)]
+            );
+            assert_eq!(get_emission_history(&e, 1).len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_emission_history_wraps_at_max() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            for i in 0..30u64 {
+                e.ledger().set_timestamp(1500000000 + i * 3600);
+                record_emission_checkpoint(&e, 1, i128(i) * SCALAR_7);
+            }
+
+            let history = get_emission_history(&e, 1);
+            assert_eq!(history.len(), MAX_EMIS_HISTORY);
+            // the oldest 6 checkpoints were dropped
+            for (i, (timestamp, index)) in history.iter().enumerate() {
+                let expected = i as u64 + 6;
+                assert_eq!(timestamp, 1500000000 + expected * 3600);
+                assert_eq!(index, i128(expected) * SCALAR_7);
+            }
+            assert_eq!(get_emission_history(&e, 3).len(), 0);
         });
     }
 
@@ -485,6 +739,51 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_gulp_emissions_returns_allocations() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500000000,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let new_emissions: i128 = 302_400_0000000;
+        let pool_emissions: Map<u32, u64> = map![
+            &e,
+            (0, 0_5000000), // reserve_0 liability
+            (3, 0_5000000)  // reserve_1 supply
+        ];
+
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 1499900000;
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_pool_emissions(&e, &pool_emissions);
+
+            let allocations = do_gulp_emissions(&e, new_emissions);
+
+            assert_eq!(allocations.len(), 2);
+            assert_eq!(allocations.get_unchecked(0), (0, 151_200_0000000));
+            assert_eq!(allocations.get_unchecked(1), (3, 151_200_0000000));
+            let total: i128 = allocations.iter().map(|(_, amount)| amount).sum();
+            assert_eq!(total, new_emissions);
+        });
+    }
+
     /********** set_pool_emissions **********/
 
     #[test]
@@ -544,6 +843,102 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_get_pool_emissions() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let res_emission_metadata: Vec<ReserveEmissionMetadata> = vec![
+            &e,
+            ReserveEmissionMetadata {
+                res_index: 0,
+                res_type: 1,
+                share: 0_3500000,
+            },
+            ReserveEmissionMetadata {
+                res_index: 1,
+                res_type: 0,
+                share: 0_6500000,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            assert_eq!(get_pool_emissions(&e).len(), 0);
+
+            set_pool_emissions(&e, res_emission_metadata.clone());
+
+            let result = get_pool_emissions(&e);
+            assert_eq!(result.len(), 2);
+            for (expected, actual) in res_emission_metadata.iter().zip(result.iter()) {
+                assert_eq!(actual.res_index, expected.res_index);
+                assert_eq!(actual.res_type, expected.res_type);
+                assert_eq!(actual.share, expected.share);
+            }
+        });
+    }
+
+    #[test]
+    fn test_get_all_reserve_emissions() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_2, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            assert_eq!(get_all_reserve_emissions(&e).len(), 0);
+
+            storage::set_res_emis_data(
+                &e,
+                &1,
+                &ReserveEmissionData {
+                    expiration: 1500000000,
+                    eps: 0_1000000,
+                    index: 1234,
+                    last_time: 1499990000,
+                },
+            );
+            storage::set_res_emis_data(
+                &e,
+                &4,
+                &ReserveEmissionData {
+                    expiration: 1600000000,
+                    eps: 0_2000000,
+                    index: 5678,
+                    last_time: 1499995000,
+                },
+            );
+
+            let result = get_all_reserve_emissions(&e);
+            assert_eq!(result.len(), 2);
+            let (res_token_id, emis_data) = result.get_unchecked(0);
+            assert_eq!(res_token_id, 1);
+            assert_eq!(emis_data.eps, 0_1000000);
+            assert_eq!(emis_data.index, 1234);
+            let (res_token_id, emis_data) = result.get_unchecked(1);
+            assert_eq!(res_token_id, 4);
+            assert_eq!(emis_data.eps, 0_2000000);
+            assert_eq!(emis_data.index, 5678);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1200)")]
     fn test_set_pool_emissions_panics_if_anyone_share_equal_0() {
@@ -656,4 +1051,229 @@ mod tests {
             assert_eq!(new_pool_emissions.get(6).unwrap_optimized(), 0_6500000);
         });
     }
+
+    /********** set_emissions_min_balance **********/
+
+    #[test]
+    fn test_set_emissions_min_balance() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_emis_min_balance(&e, &1).min_balance, 0);
+
+            set_emissions_min_balance(&e, 1, 0_0100000);
+            assert_eq!(storage::get_emis_min_balance(&e, &1).min_balance, 0_0100000);
+            assert_eq!(storage::get_emis_min_balance(&e, &0).min_balance, 0);
+        });
+    }
+
+    #[test]
+    fn test_set_emissions_min_balance_checkpoints_emissions() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500001000,
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_res_emis_data(
+                &e,
+                &1,
+                &ReserveEmissionData {
+                    expiration: 1600000000,
+                    eps: 1_0000000,
+                    index: 5000,
+                    last_time: 1500000000,
+                },
+            );
+
+            set_emissions_min_balance(&e, 1, 0_0100000);
+
+            let res_emis_data = storage::get_res_emis_data(&e, &1).unwrap_optimized();
+            assert_eq!(res_emis_data.index, 100005000);
+            assert_eq!(res_emis_data.last_time, 1500001000);
+            let min_balance = storage::get_emis_min_balance(&e, &1);
+            assert_eq!(min_balance.min_balance, 0_0100000);
+            assert_eq!(min_balance.prev_min_balance, 0);
+            assert_eq!(min_balance.index, 100005000);
+        });
+
+        e.ledger().set_timestamp(1500002000);
+        e.as_contract(&pool, || {
+            set_emissions_min_balance(&e, 1, 0);
+
+            let min_balance = storage::get_emis_min_balance(&e, &1);
+            assert_eq!(min_balance.min_balance, 0);
+            assert_eq!(min_balance.prev_min_balance, 0_0100000);
+            assert_eq!(min_balance.index, 200005000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_set_emissions_min_balance_invalid_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            set_emissions_min_balance(&e, 2, 0_0100000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #8)")]
+    fn test_set_emissions_min_balance_negative() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            set_emissions_min_balance(&e, 1, -1);
+        });
+    }
+
+    /********** sweep_unclaimed_emissions ********/
+
+    #[test]
+    fn test_sweep_unclaimed_emissions() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500100000,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (blnd, blnd_token_client) = testutils::create_blnd_token(&e, &pool, &bombadil);
+        let (backstop, _) = testutils::create_backstop(
+            &e,
+            &pool,
+            &Address::generate(&e),
+            &Address::generate(&e),
+            &blnd,
+        );
+        // mock backstop having emissions for pool
+        e.as_contract(&backstop, || {
+            blnd_token_client.approve(&backstop, &pool, &100_000_0000000_i128, &1000000);
+        });
+        blnd_token_client.mint(&backstop, &100_000_0000000);
+
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 0;
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_backstop(&e, &backstop);
+            storage::set_res_emis_data(
+                &e,
+                &1,
+                &ReserveEmissionData {
+                    expiration: 1500604800,
+                    eps: 0_01000000000000,
+                    index: 123456789,
+                    last_time: 1500000000,
+                },
+            );
+
+            let amount_swept = sweep_unclaimed_emissions(&e, &vec![&e, 1]);
+            assert_eq!(amount_swept, 6048_0000000);
+
+            let emission_data = storage::get_res_emis_data(&e, &1).unwrap_optimized();
+            assert_eq!(emission_data.eps, 0);
+            assert_eq!(emission_data.expiration, 1500604800);
+            assert_eq!(emission_data.index, 123456789);
+            assert_eq!(emission_data.last_time, 1500100000);
+        });
+        assert_eq!(
+            blnd_token_client.allowance(&backstop, &pool),
+            100_000_0000000 - 6048_0000000
+        );
+        assert_eq!(blnd_token_client.balance(&backstop), 100_000_0000000);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_sweep_unclaimed_emissions_with_supply() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+        e.ledger().set(LedgerInfo {
+            timestamp: 1500100000,
+            protocol_version: 22,
+            sequence_number: 20100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_res_emis_data(
+                &e,
+                &1,
+                &ReserveEmissionData {
+                    expiration: 1500604800,
+                    eps: 0_01000000000000,
+                    index: 123456789,
+                    last_time: 1500000000,
+                },
+            );
+
+            sweep_unclaimed_emissions(&e, &vec![&e, 1]);
+        });
+    }
 }
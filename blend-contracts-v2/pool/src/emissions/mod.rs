@@ -1,5 +1,12 @@
 mod manager;
-pub use manager::{gulp_emissions, set_pool_emissions, ReserveEmissionMetadata};
+pub use manager::{
+    get_all_reserve_emissions, get_emission_history, get_pool_emissions, gulp_emissions,
+    set_backstop_emission_share, set_backstop_emissions_enabled, set_emissions_min_balance,
+    set_max_gulp_emissions, set_pool_emissions, sweep_unclaimed_emissions, ReserveEmissionMetadata,
+};
 
 mod distributor;
-pub use distributor::{execute_claim, update_emissions};
+pub use distributor::{
+    calc_claimable, execute_claim, execute_claim_all, execute_claim_and_supply,
+    get_emission_index_discrepancy, update_emissions,
+};
@@ -52,4 +52,10 @@ pub enum PoolError {
     InvalidLot = 1222,
     ReserveDisabled = 1223,
     MinCollateralNotMet = 1224,
+    AuctionFillTooEarly = 1225,
+    SupplyCapExceeded = 1226,
+    BorrowCapExceeded = 1227,
+    StalePrice = 1228,
+    Reentrancy = 1229,
+    Paused = 1230,
 }
@@ -1,6 +1,8 @@
-use soroban_sdk::{Address, Env, Symbol, Vec};
+use soroban_sdk::{Address, Env, Symbol, Val, Vec};
 
-use crate::{AuctionData, ReserveConfig};
+use crate::{
+    AuctionConfig, AuctionData, LiquidationBonusTier, ReserveConfig, ReserveEmissionMetadata,
+};
 
 pub struct PoolEvents {}
 
@@ -18,25 +20,241 @@ impl PoolEvents {
         e.events().publish(topics, new_admin);
     }
 
+    /// Emitted when a new admin is proposed for a pool
+    ///
+    /// - topics - `["propose_admin", admin: Address]`
+    /// - data - `pending_admin: Address`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * pending_admin - The proposed admin of the pool
+    pub fn propose_admin(e: &Env, admin: Address, pending_admin: Address) {
+        let topics = (Symbol::new(&e, "propose_admin"), admin);
+        e.events().publish(topics, pending_admin);
+    }
+
+    /// Emitted when a proposed admin accepts the admin role for a pool
+    ///
+    /// - topics - `["accept_admin", admin: Address]`
+    /// - data - `new_admin: Address`
+    ///
+    /// ### Arguments
+    /// * admin - The previous admin of the pool
+    /// * new_admin - The new admin of the pool
+    pub fn accept_admin(e: &Env, admin: Address, new_admin: Address) {
+        let topics = (Symbol::new(&e, "accept_admin"), admin);
+        e.events().publish(topics, new_admin);
+    }
+
     /// Emitted when pool parameters are updated
     ///
     /// - topics - `["update_pool", admin: Address]`
-    /// - data - `[backstop_take_rate: u32, max_positions: u32, min_collateral: i128]`
+    /// - data - `[backstop_take_rate: u32, max_positions: u32, min_collateral: i128, flash_loan_fee: u32, max_price_age: u64]`
     ///
     /// ### Arguments
     /// * admin - The current admin of the pool
     /// * backstop_take_rate - The new backstop take rate
     /// * max_positions - The new maximum number of positions
+    /// * min_collateral - The new minimum collateral
+    /// * flash_loan_fee - The new flash loan fee
+    /// * max_price_age - The new maximum oracle price age
     pub fn update_pool(
         e: &Env,
         admin: Address,
         backstop_take_rate: u32,
         max_positions: u32,
         min_collateral: i128,
+        flash_loan_fee: u32,
+        max_price_age: u64,
     ) {
         let topics = (Symbol::new(&e, "update_pool"), admin);
-        e.events()
-            .publish(topics, (backstop_take_rate, max_positions, min_collateral));
+        e.events().publish(
+            topics,
+            (
+                backstop_take_rate,
+                max_positions,
+                min_collateral,
+                flash_loan_fee,
+                max_price_age,
+            ),
+        );
+    }
+
+    /// Emitted when the position limit of a user is updated
+    ///
+    /// - topics - `["set_position_limit", admin: Address]`
+    /// - data - `[user: Address, limit: u32]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * user - The user whose position limit was updated
+    /// * limit - The new position limit
+    pub fn set_position_limit(e: &Env, admin: Address, user: Address, limit: u32) {
+        let topics = (Symbol::new(&e, "set_position_limit"), admin);
+        e.events().publish(topics, (user, limit));
+    }
+
+    /// Emitted when the liquidation bonus tiers of the pool are updated
+    ///
+    /// - topics - `["set_liquidation_bonus_tiers", admin: Address]`
+    /// - data - `tiers: Vec<LiquidationBonusTier>`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * tiers - The new liquidation bonus tiers
+    pub fn set_liquidation_bonus_tiers(e: &Env, admin: Address, tiers: Vec<LiquidationBonusTier>) {
+        let topics = (Symbol::new(&e, "set_liquidation_bonus_tiers"), admin);
+        e.events().publish(topics, tiers);
+    }
+
+    /// Emitted when the minimum number of blocks before a liquidation can be fully filled is updated
+    ///
+    /// - topics - `["set_min_liq_full_fill_blocks", admin: Address]`
+    /// - data - `blocks: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * blocks - The new minimum number of blocks
+    pub fn set_min_liq_full_fill_blocks(e: &Env, admin: Address, blocks: u32) {
+        let topics = (Symbol::new(&e, "set_min_liq_full_fill_blocks"), admin);
+        e.events().publish(topics, blocks);
+    }
+
+    /// Emitted when the auction scaling parameters of the pool are updated
+    ///
+    /// - topics - `["set_auction_config", admin: Address]`
+    /// - data - `config: AuctionConfig`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * config - The new auction scaling parameters
+    pub fn set_auction_config(e: &Env, admin: Address, config: AuctionConfig) {
+        let topics = (Symbol::new(&e, "set_auction_config"), admin);
+        e.events().publish(topics, config);
+    }
+
+    /// Emitted when the insurance share of accrued interest is updated
+    ///
+    /// - topics - `["set_insurance_bps", admin: Address]`
+    /// - data - `bps: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * bps - The new insurance share, in basis points
+    pub fn set_insurance_bps(e: &Env, admin: Address, bps: u32) {
+        let topics = (Symbol::new(&e, "set_insurance_bps"), admin);
+        e.events().publish(topics, bps);
+    }
+
+    /// Emitted when the minimum value of an interest auction lot is updated
+    ///
+    /// - topics - `["set_min_interest_auction_value", admin: Address]`
+    /// - data - `value: i128`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * value - The new minimum value, in the oracle's base asset
+    pub fn set_min_interest_auction_value(e: &Env, admin: Address, value: i128) {
+        let topics = (Symbol::new(&e, "set_min_interest_auction_value"), admin);
+        e.events().publish(topics, value);
+    }
+
+    /// Emitted when the number of ledgers a status change must be observed for is updated
+    ///
+    /// - topics - `["set_status_grace_ledgers", admin: Address]`
+    /// - data - `ledgers: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * ledgers - The new number of ledgers
+    pub fn set_status_grace_ledgers(e: &Env, admin: Address, ledgers: u32) {
+        let topics = (Symbol::new(&e, "set_status_grace_ledgers"), admin);
+        e.events().publish(topics, ledgers);
+    }
+
+    /// Emitted when the minimum health factor required after borrowing is updated
+    ///
+    /// - topics - `["set_min_borrow_health_buffer", admin: Address]`
+    /// - data - `buffer: i128`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * buffer - The new minimum health factor
+    pub fn set_min_borrow_health_buffer(e: &Env, admin: Address, buffer: i128) {
+        let topics = (Symbol::new(&e, "set_min_borrow_health_buffer"), admin);
+        e.events().publish(topics, buffer);
+    }
+
+    /// Emitted when the maximum gulp amount of a reserve is updated
+    ///
+    /// - topics - `["set_max_gulp", admin: Address]`
+    /// - data - `[asset: Address, max_gulp: i128]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The address of the reserve asset
+    /// * max_gulp - The new maximum amount of tokens that can be gulped
+    pub fn set_max_gulp(e: &Env, admin: Address, asset: Address, max_gulp: i128) {
+        let topics = (Symbol::new(&e, "set_max_gulp"), admin);
+        e.events().publish(topics, (asset, max_gulp));
+    }
+
+    /// Emitted when the maximum emissions the pool can gulp from the backstop is updated
+    ///
+    /// - topics - `["set_max_gulp_emissions", admin: Address]`
+    /// - data - `max_emissions: i128`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * max_emissions - The new maximum emissions the pool can gulp at once
+    pub fn set_max_gulp_emissions(e: &Env, admin: Address, max_emissions: i128) {
+        let topics = (Symbol::new(&e, "set_max_gulp_emissions"), admin);
+        e.events().publish(topics, max_emissions);
+    }
+
+    /// Emitted when the backstop's share of the pool's reward zone emissions is updated
+    ///
+    /// - topics - `["set_backstop_emission_share", admin: Address]`
+    /// - data - `share: u32`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * share - The new backstop share of the emissions (7 decimals)
+    pub fn set_backstop_emission_share(e: &Env, admin: Address, share: u32) {
+        let topics = (Symbol::new(&e, "set_backstop_emission_share"), admin);
+        e.events().publish(topics, share);
+    }
+
+    /// Emitted when the pool's reward zone emissions are enabled or disabled
+    ///
+    /// - topics - `["set_backstop_emissions_enabled", admin: Address]`
+    /// - data - `enabled: bool`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * enabled - Whether the pool accrues reward zone emissions
+    pub fn set_backstop_emissions_enabled(e: &Env, admin: Address, enabled: bool) {
+        let topics = (Symbol::new(&e, "set_backstop_emissions_enabled"), admin);
+        e.events().publish(topics, enabled);
+    }
+
+    /// Emitted when the minimum position size to accrue emissions on a reserve token is updated
+    ///
+    /// - topics - `["set_emissions_min_balance", admin: Address]`
+    /// - data - `[reserve_token_id: u32, min_balance: i128]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * reserve_token_id - The reserve token id
+    /// * min_balance - The new minimum position size, in d/bTokens
+    pub fn set_emissions_min_balance(
+        e: &Env,
+        admin: Address,
+        reserve_token_id: u32,
+        min_balance: i128,
+    ) {
+        let topics = (Symbol::new(&e, "set_emissions_min_balance"), admin);
+        e.events().publish(topics, (reserve_token_id, min_balance));
     }
 
     /// Emitted when a new reserve configuration change is queued
@@ -56,14 +274,15 @@ impl PoolEvents {
     /// Emitted when a queued reserve configuration change is cancelled
     ///
     /// - topics - `["cancel_set_reserve", admin: Address]`
-    /// - data - `asset: Address`
+    /// - data - `[asset: Address, metadata: ReserveConfig]`
     ///
     /// ### Arguments
     /// * admin - The current admin of the pool
     /// * asset - The asset to cancel the reserve configuration change of
-    pub fn cancel_set_reserve(e: &Env, admin: Address, asset: Address) {
+    /// * metadata - The reserve configuration that was cancelled
+    pub fn cancel_set_reserve(e: &Env, admin: Address, asset: Address, metadata: ReserveConfig) {
         let topics = (Symbol::new(&e, "cancel_set_reserve"), admin);
-        e.events().publish(topics, asset);
+        e.events().publish(topics, (asset, metadata));
     }
 
     /// Emitted when a reserve configuration change is set
@@ -79,6 +298,46 @@ impl PoolEvents {
         e.events().publish(topics, (asset, index));
     }
 
+    /// Emitted when a reserve removal is queued
+    ///
+    /// - topics - `["queue_remove_reserve", admin: Address]`
+    /// - data - `[asset: Address, unlock_time: u64]`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The asset of the reserve to remove
+    /// * unlock_time - The timestamp the reserve can be removed at
+    pub fn queue_remove_reserve(e: &Env, admin: Address, asset: Address, unlock_time: u64) {
+        let topics = (Symbol::new(&e, "queue_remove_reserve"), admin);
+        e.events().publish(topics, (asset, unlock_time));
+    }
+
+    /// Emitted when a queued reserve removal is cancelled
+    ///
+    /// - topics - `["cancel_remove_reserve", admin: Address]`
+    /// - data - `asset: Address`
+    ///
+    /// ### Arguments
+    /// * admin - The current admin of the pool
+    /// * asset - The asset of the reserve that is no longer being removed
+    pub fn cancel_remove_reserve(e: &Env, admin: Address, asset: Address) {
+        let topics = (Symbol::new(&e, "cancel_remove_reserve"), admin);
+        e.events().publish(topics, asset);
+    }
+
+    /// Emitted when a reserve is removed from the pool
+    ///
+    /// - topics - `["remove_reserve"]`
+    /// - data - `[asset: Address, index: u32]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset of the removed reserve
+    /// * index - The index the reserve was removed from
+    pub fn remove_reserve(e: &Env, asset: Address, index: u32) {
+        let topics = (Symbol::new(&e, "remove_reserve"),);
+        e.events().publish(topics, (asset, index));
+    }
+
     /// Emitted when pool status is updated (non-admin)
     ///
     /// - topics - `["set_status"]`
@@ -104,6 +363,58 @@ impl PoolEvents {
         e.events().publish(topics, pool_status);
     }
 
+    /// Emitted when the supply and borrow flags of a reserve are updated by admin
+    ///
+    /// - topics - `["set_reserve_status", admin: Address, asset: Address]`
+    /// - data - `[supply_enabled: bool, borrow_enabled: bool]`
+    ///
+    /// ### Arguments
+    /// * admin - The admin setting the reserve status
+    /// * asset - The underlying asset of the reserve
+    /// * supply_enabled - If supplying to the reserve is allowed
+    /// * borrow_enabled - If borrowing from the reserve is allowed
+    pub fn set_reserve_status(
+        e: &Env,
+        admin: Address,
+        asset: Address,
+        supply_enabled: bool,
+        borrow_enabled: bool,
+    ) {
+        let topics = (Symbol::new(&e, "set_reserve_status"), admin, asset);
+        e.events().publish(topics, (supply_enabled, borrow_enabled));
+    }
+
+    /// Emitted when the emergency pause is updated by admin
+    ///
+    /// - topics - `["set_emergency", admin: Address]`
+    /// - data - `[paused: bool, allow_withdrawals: bool]`
+    ///
+    /// ### Arguments
+    /// * admin - The admin setting the emergency pause
+    /// * paused - If the pool is paused
+    /// * allow_withdrawals - If withdrawals are allowed while the pool is paused
+    pub fn set_emergency(e: &Env, admin: Address, paused: bool, allow_withdrawals: bool) {
+        let topics = (Symbol::new(&e, "set_emergency"), admin);
+        e.events().publish(topics, (paused, allow_withdrawals));
+    }
+
+    /// Emitted when the pool's emissions configuration is set by admin
+    ///
+    /// - topics - `["set_emissions_config", admin: Address]`
+    /// - data - `res_emission_metadata: Vec<ReserveEmissionMetadata>`
+    ///
+    /// ### Arguments
+    /// * admin - The admin setting the emissions configuration
+    /// * res_emission_metadata - The reserve token emission shares applied
+    pub fn set_emissions_config(
+        e: &Env,
+        admin: Address,
+        res_emission_metadata: Vec<ReserveEmissionMetadata>,
+    ) {
+        let topics = (Symbol::new(&e, "set_emissions_config"), admin);
+        e.events().publish(topics, res_emission_metadata);
+    }
+
     /// Emitted when reserve emissions are updated
     ///
     /// - topics - `["reserve_emission_update"]`
@@ -145,6 +456,26 @@ impl PoolEvents {
             .publish(topics, (reserve_token_ids, amount_claimed));
     }
 
+    /// Emitted when unclaimable emissions are swept back to the backstop
+    ///
+    /// - topics - `["sweep_emissions", admin: Address]`
+    /// - data - `[reserve_token_ids: Vec<u32>, amount_swept: i128]`
+    ///
+    /// ### Arguments
+    /// * admin - The admin sweeping the emissions
+    /// * reserve_token_ids - The reserve token IDs swept
+    /// * amount_swept - The amount of emissions returned to the backstop
+    pub fn sweep_emissions(
+        e: &Env,
+        admin: Address,
+        reserve_token_ids: Vec<u32>,
+        amount_swept: i128,
+    ) {
+        let topics = (Symbol::new(&e, "sweep_emissions"), admin);
+        e.events()
+            .publish(topics, (reserve_token_ids, amount_swept));
+    }
+
     /// Emitted when bad debt is recorded
     ///
     /// - topics - `["bad_debt", user: Address, asset: Address]`
@@ -172,6 +503,20 @@ impl PoolEvents {
         e.events().publish(topics, d_tokens_burnt);
     }
 
+    /// Emitted when the insurance fund is used to cover bad debt
+    ///
+    /// - topics - `["insurance_cover", asset: Address]`
+    /// - data - `[d_tokens_burnt: i128, insurance_used: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset with covered debt
+    /// * d_tokens_burnt - The amount of d_tokens repaid
+    /// * insurance_used - The amount of underlying tokens used from the insurance fund
+    pub fn insurance_cover(e: &Env, asset: Address, d_tokens_burnt: i128, insurance_used: i128) {
+        let topics = (Symbol::new(e, "insurance_cover"), asset);
+        e.events().publish(topics, (d_tokens_burnt, insurance_used));
+    }
+
     /// Emitted when tokens are supplied
     ///
     /// - topics - `["supply", asset: Address, from: Address]`
@@ -250,6 +595,29 @@ impl PoolEvents {
         e.events().publish(topics, (tokens_out, b_tokens_burnt));
     }
 
+    /// Emitted when a position is transferred between users
+    ///
+    /// - topics - `["transfer_position", asset: Address, from: Address, to: Address]`
+    /// - data - `[reserve_token_id: u32, amount: i128]`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position was transferred
+    /// * to - The address receiving the position
+    /// * reserve_token_id - The reserve token id of the position
+    /// * amount - The amount of b_tokens or d_tokens transferred
+    pub fn transfer_position(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        to: Address,
+        reserve_token_id: u32,
+        amount: i128,
+    ) {
+        let topics = (Symbol::new(e, "transfer_position"), asset, from, to);
+        e.events().publish(topics, (reserve_token_id, amount));
+    }
+
     /// Emitted when tokens are borrowed
     ///
     /// - topics - `["borrow", asset: Address, from: Address]`
@@ -303,6 +671,27 @@ impl PoolEvents {
         e.events().publish(topics, (tokens_out, d_tokens_minted));
     }
 
+    /// Emitted when a flash loan receiver returns data from `exec_op`
+    ///
+    /// - topics - `["flash_loan_result", asset: Address, from: Address, contract: Address]`
+    /// - data - `result: Val`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * from - The address whose position is being modified
+    /// * contract - The address of the flash loan contract
+    /// * result - The value returned by the flash loan contract
+    pub fn flash_loan_result(
+        e: &Env,
+        asset: Address,
+        from: Address,
+        contract: Address,
+        result: Val,
+    ) {
+        let topics = (Symbol::new(e, "flash_loan_result"), asset, from, contract);
+        e.events().publish(topics, result);
+    }
+
     /// Emitted when a reserve gulps excess tokens
     ///
     /// - topics - `["gulp", asset: Address]`
@@ -316,6 +705,19 @@ impl PoolEvents {
         e.events().publish(topics, token_delta);
     }
 
+    /// Emitted when interest accrued by a reserve is credited to the backstop
+    ///
+    /// - topics - `["backstop_credit", asset: Address]`
+    /// - data - `amount: i128`
+    ///
+    /// ### Arguments
+    /// * asset - The asset
+    /// * amount - The amount of underlying credited to the backstop
+    pub fn backstop_credit(e: &Env, asset: Address, amount: i128) {
+        let topics = (Symbol::new(e, "backstop_credit"), asset);
+        e.events().publish(topics, amount);
+    }
+
     /// Emitted when a new auction is created
     ///
     /// - topics - `["new_auction", user: Address, auction_type: u32]`
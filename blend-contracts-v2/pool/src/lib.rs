@@ -22,8 +22,8 @@ pub use auctions::{AuctionData, AuctionType};
 pub use contract::*;
 pub use emissions::ReserveEmissionMetadata;
 pub use errors::PoolError;
-pub use pool::{FlashLoan, Positions, Request, RequestType};
+pub use pool::{FlashLoan, HealthData, Positions, Request, RequestType};
 pub use storage::{
-    AuctionKey, PoolConfig, PoolDataKey, PoolEmissionConfig, ReserveConfig, ReserveData,
-    ReserveEmissionData, UserEmissionData, UserReserveKey,
+    AuctionConfig, AuctionKey, LiquidationBonusTier, PoolConfig, PoolDataKey, PoolEmissionConfig,
+    ReserveConfig, ReserveData, ReserveEmissionData, UserEmissionData, UserReserveKey,
 };
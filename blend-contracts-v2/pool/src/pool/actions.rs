@@ -1,3 +1,4 @@
+use sep_41_token::TokenClient;
 use soroban_sdk::Map;
 use soroban_sdk::{contracttype, panic_with_error, Address, Env, Vec};
 
@@ -30,6 +31,9 @@ pub enum RequestType {
     FillBadDebtAuction = 7,
     FillInterestAuction = 8,
     DeleteLiquidationAuction = 9,
+    WithdrawAll = 10,
+    WithdrawCollateralAll = 11,
+    RepayAll = 12,
 }
 
 impl RequestType {
@@ -49,6 +53,9 @@ impl RequestType {
             7 => RequestType::FillBadDebtAuction,
             8 => RequestType::FillInterestAuction,
             9 => RequestType::DeleteLiquidationAuction,
+            10 => RequestType::WithdrawAll,
+            11 => RequestType::WithdrawCollateralAll,
+            12 => RequestType::RepayAll,
             _ => panic_with_error!(e, PoolError::BadRequest),
         }
     }
@@ -66,6 +73,7 @@ pub struct Actions {
     pub spender_transfer: Map<Address, i128>,
     pub pool_transfer: Map<Address, i128>,
     pub check_health: bool,
+    pub check_borrow_health: bool,
 }
 
 impl Actions {
@@ -75,6 +83,7 @@ impl Actions {
             spender_transfer: Map::new(e),
             pool_transfer: Map::new(e),
             check_health: false,
+            check_borrow_health: false,
         }
     }
 
@@ -94,11 +103,29 @@ impl Actions {
         );
     }
 
+    // Add the transfers and health checks of another set of actions
+    pub fn append(&mut self, other: Actions) {
+        for (asset, amount) in other.spender_transfer.iter() {
+            self.add_for_spender_transfer(&asset, amount);
+        }
+        for (asset, amount) in other.pool_transfer.iter() {
+            self.add_for_pool_transfer(&asset, amount);
+        }
+        self.check_health = self.check_health || other.check_health;
+        self.check_borrow_health = self.check_borrow_health || other.check_borrow_health;
+    }
+
     // just a simple flag since we won't need
     // to switch it back to false once set to true.
     pub fn do_check_health(&mut self) {
         self.check_health = true
     }
+
+    // flag that the health factor must meet the borrow buffer
+    pub fn do_check_borrow_health(&mut self) {
+        self.check_health = true;
+        self.check_borrow_health = true
+    }
 }
 
 /// Build a set of pool actions and the new positions from the supplied requests. Validates that the requests
@@ -107,6 +134,9 @@ impl Actions {
 /// ### Arguments
 /// * pool - The pool
 /// * from - The sender of the requests
+/// * spender - The address sending tokens to the pool, if known. Used to cap "repay_all" requests
+///             at the tokens the spender can send
+/// * use_allowance - A bool indicating if the spender's tokens are sent via transfer_from
 /// * requests - The requests to be processed
 ///
 /// ### Returns
@@ -121,6 +151,8 @@ pub fn build_actions_from_request(
     e: &Env,
     pool: &mut Pool,
     from_state: &mut User,
+    spender: Option<&Address>,
+    use_allowance: bool,
     requests: Vec<Request>,
 ) -> Actions {
     let mut actions = Actions::new(e);
@@ -259,6 +291,46 @@ pub fn build_actions_from_request(
                 actions.do_check_health();
                 PoolEvents::delete_liquidation_auction(e, from_state.address.clone());
             }
+            RequestType::WithdrawAll => {
+                let (tokens_out, b_tokens_burnt) =
+                    apply_withdraw_all(e, &mut actions, pool, from_state, &request);
+                PoolEvents::withdraw(
+                    e,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    tokens_out,
+                    b_tokens_burnt,
+                );
+            }
+            RequestType::WithdrawCollateralAll => {
+                let (tokens_out, b_tokens_burnt) =
+                    apply_withdraw_collateral_all(e, &mut actions, pool, from_state, &request);
+                PoolEvents::withdraw_collateral(
+                    e,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    tokens_out,
+                    b_tokens_burnt,
+                );
+            }
+            RequestType::RepayAll => {
+                let (tokens_in, d_tokens_burnt) = apply_repay_all(
+                    e,
+                    &mut actions,
+                    pool,
+                    from_state,
+                    &request,
+                    spender,
+                    use_allowance,
+                );
+                PoolEvents::repay(
+                    e,
+                    request.address.clone(),
+                    from_state.address.clone(),
+                    tokens_in,
+                    d_tokens_burnt,
+                );
+            }
         }
     }
 
@@ -282,6 +354,7 @@ fn apply_supply(
     let b_tokens_minted = reserve.to_b_token_down(e, request.amount);
     user.add_supply(e, &mut reserve, b_tokens_minted);
     actions.add_for_spender_transfer(&reserve.asset, request.amount);
+    reserve.require_supply_below_cap(e);
     pool.cache_reserve(reserve);
     b_tokens_minted
 }
@@ -312,6 +385,37 @@ fn apply_withdraw(
     (tokens_out, to_burn)
 }
 
+/// Apply a "withdraw_all" request to the pool. An amount of 0 withdraws the user's entire
+/// supply position, otherwise the request is applied as a "withdraw" request.
+///
+/// Appends any necessary actions to the actions list, updates the user and pool's state
+///
+/// Returns the amount of tokens withdrawn and b_tokens burnt
+///
+/// ### Panics
+/// If the user has no supply position for the reserve
+fn apply_withdraw_all(
+    e: &Env,
+    actions: &mut Actions,
+    pool: &mut Pool,
+    user: &mut User,
+    request: &Request,
+) -> (i128, i128) {
+    if request.amount != 0 {
+        return apply_withdraw(e, actions, pool, user, request);
+    }
+    let mut reserve = pool.load_reserve(e, &request.address, true);
+    let to_burn = user.get_supply(reserve.config.index);
+    if to_burn == 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let tokens_out = reserve.to_asset_from_b_token(e, to_burn);
+    user.remove_supply(e, &mut reserve, to_burn);
+    actions.add_for_pool_transfer(&reserve.asset, tokens_out);
+    pool.cache_reserve(reserve);
+    (tokens_out, to_burn)
+}
+
 /// Apply a "supply_collateral" request to the pool
 ///
 /// Appends any necessary actions to the actions list, updates the user and pool's state
@@ -332,6 +436,7 @@ fn apply_supply_collateral(
     if reserve.total_supply(e) > reserve.config.collateral_cap {
         panic_with_error!(e, PoolError::ExceededCollateralCap);
     }
+    reserve.require_supply_below_cap(e);
     pool.cache_reserve(reserve);
     b_tokens_minted
 }
@@ -363,6 +468,38 @@ fn apply_withdraw_collateral(
     (tokens_out, to_burn)
 }
 
+/// Apply a "withdraw_collateral_all" request to the pool. An amount of 0 withdraws the user's
+/// entire collateral position, otherwise the request is applied as a "withdraw_collateral" request.
+///
+/// Appends any necessary actions to the actions list, updates the user and pool's state
+///
+/// Returns the amount of tokens withdrawn and b_tokens burnt
+///
+/// ### Panics
+/// If the user has no collateral position for the reserve
+fn apply_withdraw_collateral_all(
+    e: &Env,
+    actions: &mut Actions,
+    pool: &mut Pool,
+    user: &mut User,
+    request: &Request,
+) -> (i128, i128) {
+    if request.amount != 0 {
+        return apply_withdraw_collateral(e, actions, pool, user, request);
+    }
+    let mut reserve = pool.load_reserve(e, &request.address, true);
+    let to_burn = user.get_collateral(reserve.config.index);
+    if to_burn == 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let tokens_out = reserve.to_asset_from_b_token(e, to_burn);
+    user.remove_collateral(e, &mut reserve, to_burn);
+    actions.add_for_pool_transfer(&reserve.asset, tokens_out);
+    actions.do_check_health();
+    pool.cache_reserve(reserve);
+    (tokens_out, to_burn)
+}
+
 /// Apply a "borrow" request to the pool
 ///
 /// Appends any necessary actions to the actions list, updates the user and pool's state
@@ -379,9 +516,11 @@ fn apply_borrow(
     reserve.require_action_allowed(e, request.request_type);
     let d_tokens_minted = reserve.to_d_token_up(e, request.amount);
     user.add_liabilities(e, &mut reserve, d_tokens_minted);
+    reserve.require_liability_above_min_borrow(e, user.get_liabilities(reserve.config.index));
     reserve.require_utilization_below_max(e);
+    reserve.require_liabilities_below_cap(e);
     actions.add_for_pool_transfer(&reserve.asset, request.amount);
-    actions.do_check_health();
+    actions.do_check_borrow_health();
     pool.cache_reserve(reserve);
     d_tokens_minted
 }
@@ -419,6 +558,60 @@ fn apply_repay(
     }
 }
 
+/// Apply a "repay_all" request to the pool. An amount of 0 or i128::MAX repays the user's entire
+/// liability position at the current d_rate, otherwise the request is applied as a "repay" request.
+///
+/// If a spender is given, the repayment is capped at the tokens the spender can send, after any
+/// tokens of the asset already owed by earlier requests. This is the spender's balance, and its
+/// allowance to the pool if `use_allowance` is set. A capped repayment only burns the dTokens
+/// the capped amount covers.
+///
+/// Appends any necessary actions to the actions list, updates the user and pool's state
+///
+/// Returns the repayment amount and d_tokens burnt
+///
+/// ### Panics
+/// If the user has no liability for the reserve, or the spender cannot repay any of it
+fn apply_repay_all(
+    e: &Env,
+    actions: &mut Actions,
+    pool: &mut Pool,
+    user: &mut User,
+    request: &Request,
+    spender: Option<&Address>,
+    use_allowance: bool,
+) -> (i128, i128) {
+    if request.amount != 0 && request.amount != i128::MAX {
+        return apply_repay(e, actions, pool, user, request);
+    }
+    let mut reserve = pool.load_reserve(e, &request.address, true);
+    let cur_d_tokens = user.get_liabilities(reserve.config.index);
+    let mut tokens_in = reserve.to_asset_from_d_token(e, cur_d_tokens);
+    let mut to_burn = cur_d_tokens;
+    if let Some(spender) = spender {
+        let token = TokenClient::new(e, &reserve.asset);
+        let mut available = token.balance(spender);
+        if use_allowance {
+            available = available.min(token.allowance(spender, &e.current_contract_address()));
+        }
+        available -= actions
+            .spender_transfer
+            .get(reserve.asset.clone())
+            .unwrap_or(0);
+        if available < tokens_in {
+            tokens_in = available.max(0);
+            to_burn = reserve.to_d_token_down(e, tokens_in);
+        }
+    }
+    if to_burn == 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    user.remove_liabilities(e, &mut reserve, to_burn);
+    actions.add_for_spender_transfer(&reserve.asset, tokens_in);
+    pool.cache_reserve(reserve);
+    (tokens_in, to_burn)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -470,6 +663,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -486,7 +681,8 @@ mod tests {
             ];
 
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, false);
 
@@ -544,6 +740,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
         let user_positions = Positions {
@@ -566,7 +764,8 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, false);
 
@@ -619,6 +818,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e],
@@ -640,7 +841,81 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(actions.check_health, false);
+
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 0);
+            assert_eq!(pool_transfer.len(), 1);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 20_0000137);
+
+            let positions = user.positions.clone();
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 0);
+
+            let reserve = pool.load_reserve(&e, &underlying.clone(), false);
+            assert_eq!(reserve.data.b_supply, reserve_data.b_supply - 20_0000000);
+        });
+    }
+
+    #[test]
+    fn test_build_actions_from_request_withdraw_all() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e, (0, 20_0000000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::WithdrawAll as u32,
+                    address: underlying.clone(),
+                    amount: 0,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, false);
 
@@ -660,6 +935,63 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_build_actions_from_request_withdraw_all_no_supply() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 20_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::WithdrawAll as u32,
+                    address: underlying.clone(),
+                    amount: 0,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+        });
+    }
+
     #[test]
     fn test_build_actions_from_request_withdraw_allows_over_max_util() {
         let e = Env::default();
@@ -692,6 +1024,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
         let user_positions = Positions {
@@ -714,7 +1048,8 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, false);
 
@@ -763,6 +1098,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -778,7 +1115,8 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, false);
 
@@ -836,6 +1174,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e],
@@ -857,7 +1197,8 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, true);
 
@@ -910,6 +1251,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e],
@@ -931,7 +1274,8 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, true);
 
@@ -952,7 +1296,7 @@ mod tests {
     }
 
     #[test]
-    fn test_build_actions_from_request_withdraw_collateral_allows_over_max_util() {
+    fn test_build_actions_from_request_withdraw_collateral_all() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -961,10 +1305,7 @@ mod tests {
         let pool = testutils::create_pool(&e);
 
         let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
-        reserve_config.max_util = 0_9000000;
-        reserve_data.b_supply = 100_0000000;
-        reserve_data.d_supply = 89_0000000;
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
 
         e.ledger().set(LedgerInfo {
@@ -983,8 +1324,9 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
-
         let user_positions = Positions {
             liabilities: map![&e],
             collateral: map![&e, (0, 20_0000000)],
@@ -999,13 +1341,14 @@ mod tests {
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::WithdrawCollateral as u32,
+                    request_type: RequestType::WithdrawCollateralAll as u32,
                     address: underlying.clone(),
-                    amount: 2_0000000,
+                    amount: 0,
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, true);
 
@@ -1013,20 +1356,21 @@ mod tests {
             let pool_transfer = actions.pool_transfer;
             assert_eq!(spender_transfer.len(), 0);
             assert_eq!(pool_transfer.len(), 1);
-            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 2_0000000);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 20_0000137);
 
             let positions = user.positions.clone();
             assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.collateral.len(), 0);
             assert_eq!(positions.supply.len(), 0);
-            assert_eq!(user.get_collateral(0), 18_0000111);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.data.b_supply, reserve_data.b_supply - 20_0000000);
         });
     }
 
-    /***** borrow *****/
-
     #[test]
-    fn test_build_actions_from_request_borrow() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_build_actions_from_request_withdraw_collateral_all_no_collateral() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -1037,6 +1381,7 @@ mod tests {
         let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
         e.ledger().set(LedgerInfo {
             timestamp: 600,
             protocol_version: 22,
@@ -1053,26 +1398,161 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e, (0, 20_0000000)],
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
 
             let mut pool = Pool::load(&e);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Borrow as u32,
+                    request_type: RequestType::WithdrawCollateralAll as u32,
                     address: underlying.clone(),
-                    amount: 10_1234567,
+                    amount: 0,
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
-
-            assert_eq!(actions.check_health, true);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+        });
+    }
 
-            let spender_transfer = actions.spender_transfer;
+    #[test]
+    fn test_build_actions_from_request_withdraw_collateral_allows_over_max_util() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.max_util = 0_9000000;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 89_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 20_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::WithdrawCollateral as u32,
+                    address: underlying.clone(),
+                    amount: 2_0000000,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(actions.check_health, true);
+
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 0);
+            assert_eq!(pool_transfer.len(), 1);
+            assert_eq!(pool_transfer.get_unchecked(underlying.clone()), 2_0000000);
+
+            let positions = user.positions.clone();
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            assert_eq!(user.get_collateral(0), 18_0000111);
+        });
+    }
+
+    /***** borrow *****/
+
+    #[test]
+    fn test_build_actions_from_request_borrow() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 10_1234567,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(actions.check_health, true);
+
+            let spender_transfer = actions.spender_transfer;
             let pool_transfer = actions.pool_transfer;
             assert_eq!(spender_transfer.len(), 0);
             assert_eq!(pool_transfer.len(), 1);
@@ -1122,6 +1602,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
         let user_positions = Positions {
@@ -1144,7 +1626,7 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
         });
     }
 
@@ -1179,6 +1661,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 20_0000000)],
@@ -1200,7 +1684,8 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, false);
 
@@ -1257,6 +1742,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 20_0000000)],
@@ -1278,7 +1765,8 @@ mod tests {
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
             assert_eq!(actions.check_health, false);
 
@@ -1303,7 +1791,7 @@ mod tests {
     }
 
     #[test]
-    fn test_aggregating_actions() {
+    fn test_build_actions_from_request_repay_all() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -1312,15 +1800,8 @@ mod tests {
         let pool = testutils::create_pool(&e);
 
         let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
-        reserve_data.last_time = 600;
-        testutils::create_reserve(
-            &e,
-            &pool,
-            &underlying.clone(),
-            &reserve_config,
-            &reserve_data,
-        );
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
 
         e.ledger().set(LedgerInfo {
             timestamp: 600,
@@ -1338,8 +1819,14 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 20_0000000)],
+            collateral: map![&e],
+            supply: map![&e],
         };
-        let user_positions = Positions::env_default(&e);
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &samwise, &user_positions);
@@ -1349,552 +1836,1292 @@ mod tests {
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Supply as u32,
-                    address: underlying.clone(),
-                    amount: 10_0000000,
-                },
-                Request {
-                    request_type: RequestType::Withdraw as u32,
-                    address: underlying.clone(),
-                    amount: 5_0000000,
-                },
-                Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying.clone(),
-                    amount: 10_0000000,
-                },
-                Request {
-                    request_type: RequestType::WithdrawCollateral as u32,
-                    address: underlying.clone(),
-                    amount: 5_0000000,
-                },
-                Request {
-                    request_type: RequestType::Borrow as u32,
-                    address: underlying.clone(),
-                    amount: 20_0000000,
-                },
-                Request {
-                    request_type: RequestType::Repay as u32,
+                    request_type: RequestType::RepayAll as u32,
                     address: underlying.clone(),
-                    amount: 21_0000000,
+                    amount: 0,
                 },
             ];
             let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
-            assert_eq!(actions.check_health, true);
+            assert_eq!(actions.check_health, false);
 
             let spender_transfer = actions.spender_transfer;
             let pool_transfer = actions.pool_transfer;
             assert_eq!(spender_transfer.len(), 1);
             assert_eq!(
                 spender_transfer.get_unchecked(underlying.clone()),
-                10_0000000 + 10_0000000 + 21_0000000
-            );
-            assert_eq!(pool_transfer.len(), 1);
-            assert_eq!(
-                pool_transfer.get_unchecked(underlying.clone()),
-                5_0000000 + 5_0000000 + 20_0000000 + 1_0000000
+                20_0000229
             );
+            assert_eq!(pool_transfer.len(), 0);
 
             let positions = user.positions.clone();
             assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 1);
-            assert_eq!(positions.collateral.get_unchecked(0), 5_0000000);
-            assert_eq!(positions.supply.get_unchecked(0), 5_0000000);
+            assert_eq!(user.get_liabilities(0), 0);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 0);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.data.d_supply, reserve_data.d_supply - 20_0000000);
         });
     }
 
     #[test]
-    fn test_fill_user_liquidation() {
+    fn test_build_actions_from_request_repay_all_capped_at_spender_balance() {
         let e = Env::default();
-
         e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, underlying_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
         e.ledger().set(LedgerInfo {
-            timestamp: 12345,
+            timestamp: 600,
             protocol_version: 22,
-            sequence_number: 176 + 200,
+            sequence_number: 1234,
             network_id: Default::default(),
             base_reserve: 10,
             min_temp_entry_ttl: 10,
             min_persistent_entry_ttl: 10,
             max_entry_ttl: 3110400,
         });
-
-        let bombadil = Address::generate(&e);
-        let samwise = Address::generate(&e);
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 20_0000000)],
+            collateral: map![&e],
+            supply: map![&e],
+        };
         let frodo = Address::generate(&e);
+        underlying_client.mint(&frodo, &10_0000000);
+        underlying_client.approve(&frodo, &pool, &8_0000000, &1000000);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
 
-        let pool_address = create_pool(&e);
+            let mut pool = Pool::load(&e);
 
-        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::RepayAll as u32,
+                    address: underlying.clone(),
+                    amount: 0,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            // frodo can only send 8 tokens via their allowance, less than the 20 token liability
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, Some(&frodo), true, requests);
 
-        // creating reserves for a pool exhausts the budget
-        e.cost_estimate().budget().reset_unlimited();
-        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.last_time = 12345;
-        reserve_data_0.b_rate = 1_100_000_000_000;
-        reserve_config_0.c_factor = 0_8500000;
-        reserve_config_0.l_factor = 0_9000000;
-        reserve_config_0.index = 0;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_0,
-            &reserve_config_0,
-            &reserve_data_0,
-        );
+            assert_eq!(actions.check_health, false);
 
-        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.b_rate = 1_200_000_000_000;
-        reserve_config_1.c_factor = 0_7500000;
-        reserve_config_1.l_factor = 0_7500000;
-        reserve_data_1.last_time = 12345;
-        reserve_config_1.index = 1;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_1,
-            &reserve_config_1,
-            &reserve_data_1,
-        );
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 1);
+            assert_eq!(
+                spender_transfer.get_unchecked(underlying.clone()),
+                8_0000000
+            );
+            assert_eq!(pool_transfer.len(), 0);
 
-        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
-        reserve_config_2.c_factor = 0_0000000;
-        reserve_config_2.l_factor = 0_7000000;
-        reserve_config_2.index = 2;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_2,
-            &reserve_config_2,
-            &reserve_data_2,
-        );
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            let d_tokens_burnt = reserve.to_d_token_down(&e, 8_0000000);
+            assert!(d_tokens_burnt > 0 && d_tokens_burnt < 20_0000000);
+            assert_eq!(user.get_liabilities(0), 20_0000000 - d_tokens_burnt);
+            assert_eq!(
+                reserve.data.d_supply,
+                reserve_data.d_supply - d_tokens_burnt
+            );
+        });
+    }
 
-        let auction_data = AuctionData {
-            bid: map![&e, (underlying_2.clone(), 1_2375000)],
-            lot: map![
-                &e,
-                (underlying_0.clone(), 30_5595329),
-                (underlying_1.clone(), 1_5395739)
-            ],
-            block: 176,
-        };
+    #[test]
+    fn test_build_actions_from_request_repay_all_max() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
         let pool_config = PoolConfig {
-            oracle: oracle_address,
+            oracle: Address::generate(&e),
             min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
+            bstop_rate: 0_2000000,
             status: 0,
-            max_positions: 4,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
-        let positions: Positions = Positions {
-            collateral: map![
-                &e,
-                (reserve_config_0.index, 90_9100000),
-                (reserve_config_1.index, 04_5800000),
-            ],
-            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 20_0000000)],
+            collateral: map![&e],
             supply: map![&e],
         };
-        e.as_contract(&pool_address, || {
+        e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            storage::set_user_positions(&e, &samwise, &positions);
-            storage::set_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise,
-                &auction_data,
-            );
+            storage::set_user_positions(&e, &samwise, &user_positions);
 
             let mut pool = Pool::load(&e);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::FillUserLiquidationAuction as u32,
-                    address: samwise.clone(),
-                    amount: 50,
+                    request_type: RequestType::RepayAll as u32,
+                    address: underlying.clone(),
+                    amount: i128::MAX,
                 },
             ];
-            let mut user = User::load(&e, &frodo);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let mut user = User::load(&e, &samwise);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
 
-            assert_eq!(actions.check_health, true);
-            let exp_new_auction = AuctionData {
-                bid: map![&e, (underlying_2.clone(), 6187500)],
-                lot: map![
-                    &e,
-                    (underlying_0.clone(), 15_2797665),
-                    (underlying_1.clone(), 7697870)
-                ],
-                block: 176,
-            };
-            let new_auction =
-                storage::get_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise);
-            assert_eq!(exp_new_auction.bid, new_auction.bid);
-            assert_eq!(exp_new_auction.lot, new_auction.lot);
-            assert_eq!(exp_new_auction.block, new_auction.block);
-            assert_eq!(actions.pool_transfer.len(), 0);
-            assert_eq!(actions.spender_transfer.len(), 0);
+            assert_eq!(actions.check_health, false);
+
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 1);
+            assert_eq!(
+                spender_transfer.get_unchecked(underlying.clone()),
+                20_0000229
+            );
+            assert_eq!(pool_transfer.len(), 0);
+
+            let positions = user.positions.clone();
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(user.get_liabilities(0), 0);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 0);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.data.d_supply, reserve_data.d_supply - 20_0000000);
         });
     }
 
     #[test]
-    fn test_fill_bad_debt_auction() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_build_actions_from_request_repay_all_no_liability() {
         let e = Env::default();
-
         e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
         e.ledger().set(LedgerInfo {
-            timestamp: 12345,
+            timestamp: 600,
             protocol_version: 22,
-            sequence_number: 51 + 200,
+            sequence_number: 1234,
             network_id: Default::default(),
             base_reserve: 10,
             min_temp_entry_ttl: 10,
             min_persistent_entry_ttl: 10,
             max_entry_ttl: 3110400,
         });
-
-        let bombadil = Address::generate(&e);
-        let samwise = Address::generate(&e);
-        let frodo = Address::generate(&e);
-
-        let pool_address = create_pool(&e);
-
-        let (oracle_address, _) = testutils::create_mock_oracle(&e);
-
-        // creating reserves for a pool exhausts the budget
-        e.cost_estimate().budget().reset_unlimited();
-        let (backstop_token_id, backstop_token_client) =
-            testutils::create_token_contract(&e, &bombadil);
-        let (backstop_address, backstop_client) = testutils::create_backstop(
-            &e,
-            &pool_address,
-            &backstop_token_id,
-            &Address::generate(&e),
-            &Address::generate(&e),
-        );
-        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.last_time = 12345;
-        reserve_data_0.b_rate = 1_100_000_000_000;
-        reserve_config_0.c_factor = 0_8500000;
-        reserve_config_0.l_factor = 0_9000000;
-        reserve_config_0.index = 0;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_0,
-            &reserve_config_0,
-            &reserve_data_0,
-        );
-
-        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.b_rate = 1_200_000_000_000;
-        reserve_config_1.c_factor = 0_7500000;
-        reserve_config_1.l_factor = 0_7500000;
-        reserve_data_1.last_time = 12345;
-        reserve_config_1.index = 1;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_1,
-            &reserve_config_1,
-            &reserve_data_1,
-        );
         let pool_config = PoolConfig {
-            oracle: oracle_address,
+            oracle: Address::generate(&e),
             min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
+            bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
-        let auction_data = AuctionData {
-            bid: map![&e, (underlying_0, 10_0000000), (underlying_1, 2_5000000)],
-            lot: map![&e, (backstop_token_id, 95_2000000)],
-            block: 51,
-        };
-        let positions: Positions = Positions {
-            collateral: map![&e],
-            liabilities: map![
-                &e,
-                (reserve_config_0.index, 10_0000000),
-                (reserve_config_1.index, 2_5000000)
-            ],
-            supply: map![&e],
-        };
-        backstop_token_client.mint(&samwise, &95_2000000);
-        backstop_token_client.approve(&samwise, &backstop_address, &i128::MAX, &1000000);
-        backstop_client.deposit(&samwise, &pool_address, &95_2000000);
-        e.as_contract(&pool_address, || {
+        e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            storage::set_user_positions(&e, &backstop_address, &positions);
-            storage::set_auction(
-                &e,
-                &(AuctionType::BadDebtAuction as u32),
-                &backstop_address,
-                &auction_data,
-            );
 
             let mut pool = Pool::load(&e);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::FillBadDebtAuction as u32,
-                    address: backstop_address.clone(),
-                    amount: 100,
+                    request_type: RequestType::RepayAll as u32,
+                    address: underlying.clone(),
+                    amount: 0,
                 },
             ];
-            let mut user = User::load(&e, &frodo);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+        });
+    }
 
-            assert_eq!(actions.check_health, true);
-            assert_eq!(
-                storage::has_auction(&e, &(AuctionType::BadDebtAuction as u32), &backstop_address),
-                false
-            );
-            assert_eq!(actions.pool_transfer.len(), 0);
-            assert_eq!(actions.spender_transfer.len(), 0);
+    #[test]
+    fn test_aggregating_actions() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 600;
+        testutils::create_reserve(
+            &e,
+            &pool,
+            &underlying.clone(),
+            &reserve_config,
+            &reserve_data,
+        );
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions::env_default(&e);
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying.clone(),
+                    amount: 10_0000000,
+                },
+                Request {
+                    request_type: RequestType::Withdraw as u32,
+                    address: underlying.clone(),
+                    amount: 5_0000000,
+                },
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying.clone(),
+                    amount: 10_0000000,
+                },
+                Request {
+                    request_type: RequestType::WithdrawCollateral as u32,
+                    address: underlying.clone(),
+                    amount: 5_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 20_0000000,
+                },
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying.clone(),
+                    amount: 21_0000000,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(actions.check_health, true);
+
+            let spender_transfer = actions.spender_transfer;
+            let pool_transfer = actions.pool_transfer;
+            assert_eq!(spender_transfer.len(), 1);
+            assert_eq!(
+                spender_transfer.get_unchecked(underlying.clone()),
+                10_0000000 + 10_0000000 + 21_0000000
+            );
+            assert_eq!(pool_transfer.len(), 1);
+            assert_eq!(
+                pool_transfer.get_unchecked(underlying.clone()),
+                5_0000000 + 5_0000000 + 20_0000000 + 1_0000000
+            );
+
+            let positions = user.positions.clone();
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 1);
+            assert_eq!(positions.collateral.get_unchecked(0), 5_0000000);
+            assert_eq!(positions.supply.get_unchecked(0), 5_0000000);
+        });
+    }
+
+    #[test]
+    fn test_fill_user_liquidation() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 176 + 200,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, reserve_data_2) = testutils::default_reserve_meta();
+        reserve_config_2.c_factor = 0_0000000;
+        reserve_config_2.l_factor = 0_7000000;
+        reserve_config_2.index = 2;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_2.clone(), 1_2375000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 30_5595329),
+                (underlying_1.clone(), 1_5395739)
+            ],
+            block: 176,
+        };
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let positions: Positions = Positions {
+            collateral: map![
+                &e,
+                (reserve_config_0.index, 90_9100000),
+                (reserve_config_1.index, 04_5800000),
+            ],
+            liabilities: map![&e, (reserve_config_2.index, 02_7500000),],
+            supply: map![&e],
+        };
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &positions);
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &auction_data,
+            );
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::FillUserLiquidationAuction as u32,
+                    address: samwise.clone(),
+                    amount: 50,
+                },
+            ];
+            let mut user = User::load(&e, &frodo);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(actions.check_health, true);
+            let exp_new_auction = AuctionData {
+                bid: map![&e, (underlying_2.clone(), 6187500)],
+                lot: map![
+                    &e,
+                    (underlying_0.clone(), 15_2797665),
+                    (underlying_1.clone(), 7697870)
+                ],
+                block: 176,
+            };
+            let new_auction =
+                storage::get_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise);
+            assert_eq!(exp_new_auction.bid, new_auction.bid);
+            assert_eq!(exp_new_auction.lot, new_auction.lot);
+            assert_eq!(exp_new_auction.block, new_auction.block);
+            assert_eq!(actions.pool_transfer.len(), 0);
+            assert_eq!(actions.spender_transfer.len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_fill_bad_debt_auction() {
+        let e = Env::default();
+
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 51 + 200,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let (oracle_address, _) = testutils::create_mock_oracle(&e);
+
+        // creating reserves for a pool exhausts the budget
+        e.cost_estimate().budget().reset_unlimited();
+        let (backstop_token_id, backstop_token_client) =
+            testutils::create_token_contract(&e, &bombadil);
+        let (backstop_address, backstop_client) = testutils::create_backstop(
+            &e,
+            &pool_address,
+            &backstop_token_id,
+            &Address::generate(&e),
+            &Address::generate(&e),
+        );
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.last_time = 12345;
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_config_0.c_factor = 0_8500000;
+        reserve_config_0.l_factor = 0_9000000;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_200_000_000_000;
+        reserve_config_1.c_factor = 0_7500000;
+        reserve_config_1.l_factor = 0_7500000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        let pool_config = PoolConfig {
+            oracle: oracle_address,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0, 10_0000000), (underlying_1, 2_5000000)],
+            lot: map![&e, (backstop_token_id, 95_2000000)],
+            block: 51,
+        };
+        let positions: Positions = Positions {
+            collateral: map![&e],
+            liabilities: map![
+                &e,
+                (reserve_config_0.index, 10_0000000),
+                (reserve_config_1.index, 2_5000000)
+            ],
+            supply: map![&e],
+        };
+        backstop_token_client.mint(&samwise, &95_2000000);
+        backstop_token_client.approve(&samwise, &backstop_address, &i128::MAX, &1000000);
+        backstop_client.deposit(&samwise, &pool_address, &95_2000000);
+        e.as_contract(&pool_address, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &backstop_address, &positions);
+            storage::set_auction(
+                &e,
+                &(AuctionType::BadDebtAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::FillBadDebtAuction as u32,
+                    address: backstop_address.clone(),
+                    amount: 100,
+                },
+            ];
+            let mut user = User::load(&e, &frodo);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(actions.check_health, true);
+            assert_eq!(
+                storage::has_auction(&e, &(AuctionType::BadDebtAuction as u32), &backstop_address),
+                false
+            );
+            assert_eq!(actions.pool_transfer.len(), 0);
+            assert_eq!(actions.spender_transfer.len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_fill_interest_auction() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 51 + 250,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
+        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
+
+        let (backstop_token_id, backstop_token_client) =
+            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
+        let (backstop_address, backstop_client) =
+            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
+        blnd_client.mint(&samwise, &10_000_0000000);
+        usdc_client.mint(&samwise, &250_0000000);
+        let exp_ledger = e.ledger().sequence() + 100;
+        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
+        backstop_token_client.join_pool(
+            &(100 * SCALAR_7),
+            &vec![&e, 10_000_0000000, 250_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
+        reserve_data_0.b_rate = 1_100_000_000_000;
+        reserve_data_0.last_time = 12345;
+        reserve_config_0.index = 0;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_0,
+            &reserve_config_0,
+            &reserve_data_0,
+        );
+        underlying_0_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
+        reserve_data_1.b_rate = 1_100_000_000_000;
+        reserve_data_1.last_time = 12345;
+        reserve_config_1.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_1,
+            &reserve_config_1,
+            &reserve_data_1,
+        );
+        underlying_1_client.mint(&pool_address, &1_000_0000000);
+
+        let (underlying_2, underlying_2_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
+        reserve_data_2.b_rate = 1_100_000_000_000;
+        reserve_data_2.last_time = 12345;
+        reserve_config_2.index = 1;
+        testutils::create_reserve(
+            &e,
+            &pool_address,
+            &underlying_2,
+            &reserve_config_2,
+            &reserve_data_2,
+        );
+        underlying_2_client.mint(&pool_address, &1_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let auction_data = AuctionData {
+            bid: map![&e, (backstop_token_id.clone(), 100_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 100_0000000),
+                (underlying_1.clone(), 25_0000000)
+            ],
+            block: 51,
+        };
+
+        backstop_token_client.approve(
+            &samwise,
+            &backstop_address,
+            &100_0000000,
+            &e.ledger().sequence(),
+        );
+        e.as_contract(&pool_address, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(
+                &e,
+                &(AuctionType::InterestAuction as u32),
+                &backstop_address,
+                &auction_data,
+            );
+            storage::set_backstop(&e, &backstop_address);
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::FillInterestAuction as u32,
+                    address: backstop_address.clone(),
+                    amount: 100,
+                },
+            ];
+            let pre_fill_backstop_token_balance = backstop_token_client.balance(&backstop_address);
+            let mut user = User::load(&e, &samwise);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
+            assert_eq!(
+                backstop_token_client.balance(&backstop_address),
+                pre_fill_backstop_token_balance + 75_0000000
+            );
+            assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
+            assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
+            assert_eq!(actions.check_health, false);
+            assert_eq!(
+                storage::has_auction(
+                    &e,
+                    &(AuctionType::InterestAuction as u32),
+                    &backstop_address
+                ),
+                false
+            );
+            assert_eq!(actions.pool_transfer.len(), 0);
+            assert_eq!(actions.spender_transfer.len(), 0);
+        });
+    }
+
+    /***** delete liquidation auction *****/
+
+    #[test]
+    fn test_delete_liquidation_auction() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 12345,
+            protocol_version: 22,
+            sequence_number: 51 + 200,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let samwise = Address::generate(&e);
+        let underlying_0 = Address::generate(&e);
+        let underlying_1 = Address::generate(&e);
+
+        let pool_address = create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 952_0000000)],
+            lot: map![
+                &e,
+                (underlying_0.clone(), 100_0000000),
+                (underlying_1.clone(), 25_0000000)
+            ],
+            block: 51,
+        };
+
+        e.as_contract(&pool_address, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &auction_data,
+            );
+
+            let mut pool = Pool::load(&e);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::DeleteLiquidationAuction as u32,
+                    address: Address::generate(&e),
+                    amount: 0,
+                },
+            ];
+            let mut user = User::load(&e, &samwise);
+            let actions =
+                build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            assert_eq!(actions.check_health, true);
+            assert_eq!(
+                storage::has_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise),
+                false
+            );
+            assert_eq!(actions.pool_transfer.len(), 0);
+            assert_eq!(actions.spender_transfer.len(), 0);
+        });
+    }
+
+    /********** reserve conifg **********/
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1220)")]
+    fn test_exceed_collateral_cap() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
+        reserve_config.collateral_cap = 10_0000000; // Set low collateral cap
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 1,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: underlying.clone(),
+                amount: 20_0000000, // Try to supply more than cap
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+        });
+    }
+
+    #[test]
+    fn test_supply_up_to_supply_cap() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 80_0000000;
+        reserve_config.supply_cap = 100_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 20_0000000,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.total_supply(&e), 100_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1226)")]
+    fn test_exceed_supply_cap() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 80_0000000;
+        reserve_config.supply_cap = 100_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Supply as u32,
+                address: underlying.clone(),
+                amount: 20_0000001,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1226)")]
+    fn test_exceed_supply_cap_with_collateral() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 80_0000000;
+        reserve_config.supply_cap = 100_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: underlying.clone(),
+                amount: 20_0000001,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            let mut user = User::load(&e, &samwise);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+        });
+    }
+
+    #[test]
+    fn test_borrow_up_to_borrow_cap() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 10_0000000;
+        reserve_config.borrow_cap = 30_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        let borrow_10 = vec![
+            &e,
+            Request {
+                request_type: RequestType::Borrow as u32,
+                address: underlying.clone(),
+                amount: 10_0000000,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            // samwise and frodo borrow up to the cap
+            let mut samwise_state = User::load(&e, &samwise);
+            build_actions_from_request(
+                &e,
+                &mut pool,
+                &mut samwise_state,
+                None,
+                false,
+                borrow_10.clone(),
+            );
+            let mut frodo_state = User::load(&e, &frodo);
+            build_actions_from_request(
+                &e,
+                &mut pool,
+                &mut frodo_state,
+                None,
+                false,
+                borrow_10.clone(),
+            );
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.total_liabilities(&e), 30_0000000);
+
+            // samwise repays, freeing up capacity for merry
+            let repay_5 = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying.clone(),
+                    amount: 5_0000000,
+                },
+            ];
+            build_actions_from_request(&e, &mut pool, &mut samwise_state, None, false, repay_5);
+            let borrow_5 = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 5_0000000,
+                },
+            ];
+            let mut merry_state = User::load(&e, &merry);
+            build_actions_from_request(&e, &mut pool, &mut merry_state, None, false, borrow_5);
+            let reserve = pool.load_reserve(&e, &underlying, false);
+            assert_eq!(reserve.total_liabilities(&e), 30_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1227)")]
+    fn test_exceed_borrow_cap() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 10_0000000;
+        reserve_config.borrow_cap = 30_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        let borrow_10 = vec![
+            &e,
+            Request {
+                request_type: RequestType::Borrow as u32,
+                address: underlying.clone(),
+                amount: 10_0000000,
+            },
+        ];
+
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            let mut samwise_state = User::load(&e, &samwise);
+            build_actions_from_request(
+                &e,
+                &mut pool,
+                &mut samwise_state,
+                None,
+                false,
+                borrow_10.clone(),
+            );
+            let mut frodo_state = User::load(&e, &frodo);
+            build_actions_from_request(&e, &mut pool, &mut frodo_state, None, false, borrow_10);
+
+            // the reserve is at the borrow cap
+            let borrow_1 = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 1,
+                },
+            ];
+            let mut merry_state = User::load(&e, &merry);
+            build_actions_from_request(&e, &mut pool, &mut merry_state, None, false, borrow_1);
         });
     }
 
     #[test]
-    fn test_fill_interest_auction() {
+    fn test_borrow_at_min_borrow() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
-        e.mock_all_auths_allowing_non_root_auth();
-
-        e.ledger().set(LedgerInfo {
-            timestamp: 12345,
-            protocol_version: 22,
-            sequence_number: 51 + 250,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
+        e.mock_all_auths();
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
 
-        let pool_address = create_pool(&e);
-        let (usdc_id, usdc_client) = testutils::create_token_contract(&e, &bombadil);
-        let (blnd_id, blnd_client) = testutils::create_blnd_token(&e, &pool_address, &bombadil);
-
-        let (backstop_token_id, backstop_token_client) =
-            create_comet_lp_pool(&e, &bombadil, &blnd_id, &usdc_id);
-        let (backstop_address, backstop_client) =
-            testutils::create_backstop(&e, &pool_address, &backstop_token_id, &usdc_id, &blnd_id);
-        blnd_client.mint(&samwise, &10_000_0000000);
-        usdc_client.mint(&samwise, &250_0000000);
-        let exp_ledger = e.ledger().sequence() + 100;
-        blnd_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
-        usdc_client.approve(&bombadil, &backstop_token_id, &2_000_0000000, &exp_ledger);
-        backstop_token_client.join_pool(
-            &(100 * SCALAR_7),
-            &vec![&e, 10_000_0000000, 250_0000000],
-            &samwise,
-        );
-        backstop_client.deposit(&bombadil, &pool_address, &(50 * SCALAR_7));
-
-        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_0, mut reserve_data_0) = testutils::default_reserve_meta();
-        reserve_data_0.b_rate = 1_100_000_000_000;
-        reserve_data_0.last_time = 12345;
-        reserve_config_0.index = 0;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_0,
-            &reserve_config_0,
-            &reserve_data_0,
-        );
-        underlying_0_client.mint(&pool_address, &1_000_0000000);
-
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_1, mut reserve_data_1) = testutils::default_reserve_meta();
-        reserve_data_1.b_rate = 1_100_000_000_000;
-        reserve_data_1.last_time = 12345;
-        reserve_config_1.index = 1;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_1,
-            &reserve_config_1,
-            &reserve_data_1,
-        );
-        underlying_1_client.mint(&pool_address, &1_000_0000000);
-
-        let (underlying_2, underlying_2_client) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config_2, mut reserve_data_2) = testutils::default_reserve_meta();
-        reserve_data_2.b_rate = 1_100_000_000_000;
-        reserve_data_2.last_time = 12345;
-        reserve_config_2.index = 1;
-        testutils::create_reserve(
-            &e,
-            &pool_address,
-            &underlying_2,
-            &reserve_config_2,
-            &reserve_data_2,
-        );
-        underlying_2_client.mint(&pool_address, &1_000_0000000);
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 10_0000000;
+        reserve_config.min_borrow = 5_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
 
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
+            bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
-        };
-        let auction_data = AuctionData {
-            bid: map![&e, (backstop_token_id.clone(), 100_0000000)],
-            lot: map![
-                &e,
-                (underlying_0.clone(), 100_0000000),
-                (underlying_1.clone(), 25_0000000)
-            ],
-            block: 51,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
-        backstop_token_client.approve(
-            &samwise,
-            &backstop_address,
-            &100_0000000,
-            &e.ledger().sequence(),
-        );
-        e.as_contract(&pool_address, || {
-            e.mock_all_auths_allowing_non_root_auth();
+        e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            storage::set_auction(
-                &e,
-                &(AuctionType::InterestAuction as u32),
-                &backstop_address,
-                &auction_data,
-            );
-            storage::set_backstop(&e, &backstop_address);
-
             let mut pool = Pool::load(&e);
 
-            let requests = vec![
+            // samwise borrows exactly the minimum
+            let mut samwise_state = User::load(&e, &samwise);
+            let borrow_5 = vec![
                 &e,
                 Request {
-                    request_type: RequestType::FillInterestAuction as u32,
-                    address: backstop_address.clone(),
-                    amount: 100,
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 5_0000000,
                 },
             ];
-            let pre_fill_backstop_token_balance = backstop_token_client.balance(&backstop_address);
-            let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut samwise_state, None, false, borrow_5);
 
-            assert_eq!(backstop_token_client.balance(&samwise), 25_0000000);
-            assert_eq!(
-                backstop_token_client.balance(&backstop_address),
-                pre_fill_backstop_token_balance + 75_0000000
-            );
-            assert_eq!(underlying_0_client.balance(&samwise), 100_0000000);
-            assert_eq!(underlying_1_client.balance(&samwise), 25_0000000);
-            assert_eq!(actions.check_health, false);
+            // samwise can borrow less than the minimum once the position is above it
+            let borrow_1 = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 1_0000000,
+                },
+            ];
+            build_actions_from_request(&e, &mut pool, &mut samwise_state, None, false, borrow_1);
+
+            let reserve = pool.load_reserve(&e, &underlying, false);
             assert_eq!(
-                storage::has_auction(
-                    &e,
-                    &(AuctionType::InterestAuction as u32),
-                    &backstop_address
-                ),
-                false
+                reserve.to_asset_from_d_token(&e, samwise_state.get_liabilities(0)),
+                6_0000000
             );
-            assert_eq!(actions.pool_transfer.len(), 0);
-            assert_eq!(actions.spender_transfer.len(), 0);
         });
     }
 
-    /***** delete liquidation auction *****/
-
     #[test]
-    fn test_delete_liquidation_auction() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_borrow_under_min_borrow() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
-        e.mock_all_auths_allowing_non_root_auth();
-
-        e.ledger().set(LedgerInfo {
-            timestamp: 12345,
-            protocol_version: 22,
-            sequence_number: 51 + 200,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
+        e.mock_all_auths();
 
+        let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
-        let underlying_0 = Address::generate(&e);
-        let underlying_1 = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
 
-        let pool_address = create_pool(&e);
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 10_0000000;
+        reserve_config.min_borrow = 5_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
 
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
+            bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
-        };
-        let auction_data = AuctionData {
-            bid: map![&e, (underlying_0.clone(), 952_0000000)],
-            lot: map![
-                &e,
-                (underlying_0.clone(), 100_0000000),
-                (underlying_1.clone(), 25_0000000)
-            ],
-            block: 51,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
-        e.as_contract(&pool_address, || {
-            e.mock_all_auths_allowing_non_root_auth();
+        e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-            storage::set_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise,
-                &auction_data,
-            );
-
             let mut pool = Pool::load(&e);
 
-            let requests = vec![
+            let mut samwise_state = User::load(&e, &samwise);
+            let borrow = vec![
                 &e,
                 Request {
-                    request_type: RequestType::DeleteLiquidationAuction as u32,
-                    address: Address::generate(&e),
-                    amount: 0,
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying.clone(),
+                    amount: 4_9999999,
                 },
             ];
-            let mut user = User::load(&e, &samwise);
-            let actions = build_actions_from_request(&e, &mut pool, &mut user, requests);
-
-            assert_eq!(actions.check_health, true);
-            assert_eq!(
-                storage::has_auction(&e, &(AuctionType::UserLiquidation as u32), &samwise),
-                false
-            );
-            assert_eq!(actions.pool_transfer.len(), 0);
-            assert_eq!(actions.spender_transfer.len(), 0);
+            build_actions_from_request(&e, &mut pool, &mut samwise_state, None, false, borrow);
         });
     }
 
-    /********** reserve conifg **********/
-
     #[test]
-    #[should_panic(expected = "Error(Contract, #1220)")]
-    fn test_exceed_collateral_cap() {
+    #[should_panic(expected = "Error(Contract, #1223)")]
+    fn test_build_actions_borrow_paused_on_one_reserve() {
         let e = Env::default();
         e.mock_all_auths();
 
@@ -1902,34 +3129,60 @@ mod tests {
         let samwise = Address::generate(&e);
         let pool = testutils::create_pool(&e);
 
-        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
-        reserve_config.collateral_cap = 10_0000000; // Set low collateral cap
-        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_config.borrow_enabled = false;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.b_supply = 100_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
         let pool_config = PoolConfig {
             oracle: Address::generate(&e),
             min_collateral: 1_0000000,
             bstop_rate: 0_2000000,
             status: 0,
-            max_positions: 1,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
-        let requests = vec![
-            &e,
-            Request {
-                request_type: RequestType::SupplyCollateral as u32,
-                address: underlying.clone(),
-                amount: 20_0000000, // Try to supply more than cap
-            },
-        ];
-
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
             let mut pool = Pool::load(&e);
-
             let mut user = User::load(&e, &samwise);
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+
+            // supplying to the paused reserve and borrowing from the other reserve are allowed
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 20_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 5_0000000,
+                },
+            ];
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
+            assert_eq!(user.get_collateral(0), 20_0000000);
+            assert_eq!(user.get_liabilities(1), 5_0000000);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_0.clone(),
+                    amount: 5_0000000,
+                },
+            ];
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
         });
     }
 
@@ -1954,6 +3207,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 1,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
         let requests = vec![
@@ -1970,7 +3225,7 @@ mod tests {
             let mut pool = Pool::load(&e);
             let mut user = User::load(&e, &samwise);
 
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
         });
     }
 
@@ -1995,6 +3250,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 1,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
         let requests = vec![
@@ -2011,7 +3268,7 @@ mod tests {
             let mut pool = Pool::load(&e);
             let mut user = User::load(&e, &samwise);
 
-            build_actions_from_request(&e, &mut pool, &mut user, requests);
+            build_actions_from_request(&e, &mut pool, &mut user, None, false, requests);
         });
     }
 }
@@ -8,6 +8,19 @@ use crate::{
 
 use super::{user::User, Pool};
 
+/// Check if a user holds bad debt that can be transferred to the backstop. A user holds bad debt
+/// if they have liabilities but no collateral.
+///
+/// ### Arguments
+/// * `user` - The user to check
+pub fn has_bad_debt(e: &Env, user: &Address) -> bool {
+    if user.clone() == storage::get_backstop(e) {
+        return false;
+    }
+    let positions = storage::get_user_positions(e, user);
+    positions.collateral.is_empty() && !positions.liabilities.is_empty()
+}
+
 /// Transfer bad debt from a user to the backstop. Validates that the user does hold bad debt
 /// and transfers all held d_tokens to the backstop.
 ///
@@ -49,6 +62,46 @@ pub fn transfer_bad_debt_to_backstop(e: &Env, user: &Address) {
     new_user_state.store(e);
 }
 
+/// Use the insurance fund of a reserve to repay bad debt held by the backstop for that reserve.
+///
+/// ### Arguments
+/// * `asset` - The asset of the reserve to cover bad debt for
+///
+/// Returns a tuple of (d_tokens_burnt, insurance_used)
+///
+/// ### Panics
+/// If the backstop does not hold bad debt for the reserve or the reserve has no insurance credit
+pub fn cover_bad_debt_with_insurance(e: &Env, asset: &Address) -> (i128, i128) {
+    let backstop_address = storage::get_backstop(e);
+    let mut pool = Pool::load(e);
+    let mut reserve = pool.load_reserve(e, asset, true);
+    let mut backstop_state = User::load(e, &backstop_address);
+
+    let d_tokens = backstop_state.get_liabilities(reserve.config.index);
+    if d_tokens == 0 || reserve.data.insurance_credit <= 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    let full_repay_amount = reserve.to_asset_from_d_token(e, d_tokens);
+    let (d_tokens_burnt, insurance_used) = if full_repay_amount <= reserve.data.insurance_credit {
+        (d_tokens, full_repay_amount)
+    } else {
+        let d_tokens_burnt = reserve.to_d_token_down(e, reserve.data.insurance_credit);
+        (d_tokens_burnt, reserve.data.insurance_credit)
+    };
+    if d_tokens_burnt == 0 {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    backstop_state.remove_liabilities(e, &mut reserve, d_tokens_burnt);
+    reserve.data.insurance_credit -= insurance_used;
+    pool.cache_reserve(reserve);
+
+    pool.store_cached_reserves(e);
+    backstop_state.store(e);
+    (d_tokens_burnt, insurance_used)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{pool::Positions, storage::PoolConfig, testutils};
@@ -59,6 +112,43 @@ mod tests {
         testutils::{Address as _, Ledger, LedgerInfo},
     };
 
+    /***** has_bad_debt ******/
+
+    #[test]
+    fn test_has_bad_debt() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let backstop = Address::generate(&e);
+
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+
+        let collateralized_positions = Positions {
+            liabilities: map![&e, (0, 24_0000000)],
+            collateral: map![&e, (1, 1)],
+            supply: map![&e],
+        };
+        let bad_debt_positions = Positions {
+            liabilities: map![&e, (0, 24_0000000), (1, 25_0000000)],
+            collateral: map![&e],
+            supply: map![&e, (1, 10_0000000)],
+        };
+        e.as_contract(&pool, || {
+            storage::set_backstop(&e, &backstop);
+            storage::set_user_positions(&e, &samwise, &collateralized_positions);
+            storage::set_user_positions(&e, &frodo, &bad_debt_positions);
+            storage::set_user_positions(&e, &backstop, &bad_debt_positions);
+
+            assert!(!has_bad_debt(&e, &samwise));
+            assert!(has_bad_debt(&e, &frodo));
+            assert!(!has_bad_debt(&e, &merry));
+            assert!(!has_bad_debt(&e, &backstop));
+        });
+    }
+
     /***** transfer_bad_debt_to_backstop ******/
 
     #[test]
@@ -98,6 +188,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 24_0000000), (1, 25_0000000)],
@@ -166,6 +258,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 24_0000000), (1, 25_0000000)],
@@ -220,6 +314,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions::env_default(&e);
         e.as_contract(&pool, || {
@@ -270,6 +366,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
             liabilities: map![&e, (0, 24_0000000), (1, 25_0000000)],
@@ -285,4 +383,135 @@ mod tests {
             transfer_bad_debt_to_backstop(&e, &backstop);
         });
     }
+
+    /***** cover_bad_debt_with_insurance ******/
+
+    #[test]
+    fn test_cover_bad_debt_with_insurance() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let backstop = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 200_0000000;
+        reserve_data.d_supply = 100_0000000;
+        reserve_data.insurance_credit = 10_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.b_supply = 200_0000000;
+        reserve_data.d_supply = 100_0000000;
+        reserve_data.insurance_credit = 4_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let backstop_positions = Positions {
+            liabilities: map![&e, (0, 6_0000000), (1, 6_0000000)],
+            collateral: map![&e],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop);
+            storage::set_user_positions(&e, &backstop, &backstop_positions);
+
+            e.cost_estimate().budget().reset_unlimited();
+            // insurance fully covers the bad debt
+            let (d_tokens_burnt, insurance_used) = cover_bad_debt_with_insurance(&e, &underlying_0);
+            assert_eq!(d_tokens_burnt, 6_0000000);
+            assert_eq!(insurance_used, 6_0000000);
+            let reserve_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_data.insurance_credit, 4_0000000);
+            assert_eq!(reserve_data.d_supply, 94_0000000);
+
+            // insurance partially covers the bad debt
+            let (d_tokens_burnt, insurance_used) = cover_bad_debt_with_insurance(&e, &underlying_1);
+            assert_eq!(d_tokens_burnt, 4_0000000);
+            assert_eq!(insurance_used, 4_0000000);
+            let reserve_data = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_data.insurance_credit, 0);
+            assert_eq!(reserve_data.d_supply, 96_0000000);
+
+            let new_backstop_positions = storage::get_user_positions(&e, &backstop);
+            assert_eq!(new_backstop_positions.liabilities.get(0), None);
+            assert_eq!(
+                new_backstop_positions.liabilities.get_unchecked(1),
+                2_0000000
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_cover_bad_debt_with_insurance_no_bad_debt_panics() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 123,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let pool = testutils::create_pool(&e);
+        let backstop = Address::generate(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 200_0000000;
+        reserve_data.d_supply = 100_0000000;
+        reserve_data.insurance_credit = 10_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_backstop(&e, &backstop);
+
+            e.cost_estimate().budget().reset_unlimited();
+            cover_bad_debt_with_insurance(&e, &underlying_0);
+        });
+    }
 }
@@ -1,11 +1,13 @@
 use crate::{
-    constants::{MAX_RESERVES, SCALAR_12, SCALAR_7, SECONDS_PER_WEEK},
+    constants::{BPS_SCALAR, MAX_RESERVES, SCALAR_12, SCALAR_7, SECONDS_PER_WEEK},
     errors::PoolError,
     storage::{
-        self, has_queued_reserve_set, PoolConfig, QueuedReserveInit, ReserveConfig, ReserveData,
+        self, has_queued_reserve_set, AuctionConfig, LiquidationBonusTier, PoolConfig,
+        QueuedReserveInit, ReserveConfig, ReserveData,
     },
 };
-use soroban_sdk::{panic_with_error, Address, Env, String};
+use cast::i128;
+use soroban_sdk::{panic_with_error, Address, Env, String, Vec};
 
 use super::pool::Pool;
 
@@ -30,6 +32,8 @@ pub fn execute_initialize(
         bstop_rate: *bstop_rate,
         status: 6,
         max_positions: *max_positions,
+        flash_loan_fee: 0,
+        max_price_age: 0,
     };
     require_valid_pool_config(e, &pool_config);
 
@@ -46,22 +50,159 @@ pub fn execute_update_pool(
     backstop_take_rate: u32,
     max_positions: u32,
     min_collateral: i128,
+    flash_loan_fee: u32,
+    max_price_age: u64,
 ) {
     let mut pool_config = storage::get_pool_config(e);
     pool_config.bstop_rate = backstop_take_rate;
     pool_config.max_positions = max_positions;
     pool_config.min_collateral = min_collateral;
+    pool_config.flash_loan_fee = flash_loan_fee;
+    pool_config.max_price_age = max_price_age;
 
     require_valid_pool_config(e, &pool_config);
     storage::set_pool_config(e, &pool_config);
 }
 
+/// Set the liquidation bonus tiers for the pool
+///
+/// Panics if any tier has a non-positive coverage or a max bonus below 1
+pub fn execute_set_liq_bonus_tiers(e: &Env, tiers: &Vec<LiquidationBonusTier>) {
+    if tiers.len() > MAX_RESERVES {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    for tier in tiers.iter() {
+        if tier.coverage <= 0 || tier.max_bonus < SCALAR_7 {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_liq_bonus_tiers(e, tiers);
+}
+
+/// Set the minimum number of blocks a liquidation auction must run before it can be fully filled
+///
+/// Panics if the number of blocks exceeds the length of an auction (400 blocks)
+pub fn execute_set_min_liq_full_fill_blocks(e: &Env, blocks: u32) {
+    if blocks > 400 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_min_liq_full_fill_blocks(e, &blocks);
+}
+
+/// Set the auction scaling parameters for the pool
+///
+/// Panics if the per block scalar is not in (0, 1], or if the lot or bid would not fully ramp
+/// within their configured number of blocks, including the lot of any reserve with an
+/// `auction_lot_rate` set
+pub fn execute_set_auction_config(e: &Env, config: &AuctionConfig) {
+    if config.auction_per_block_scalar <= 0
+        || config.auction_per_block_scalar > SCALAR_7
+        || i128(config.auction_lot_ramp_blocks) * config.auction_per_block_scalar < SCALAR_7
+        || i128(config.auction_bid_ramp_blocks) * config.auction_per_block_scalar < SCALAR_7
+    {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    for asset in storage::get_res_list(e) {
+        let lot_rate = storage::get_res_config(e, &asset).auction_lot_rate;
+        if lot_rate > 0 && i128(config.auction_lot_ramp_blocks) * i128(lot_rate) < SCALAR_7 {
+            panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+        }
+    }
+    storage::set_auction_config(e, config);
+}
+
+/// Set the share of accrued interest routed to the insurance fund, in basis points
+///
+/// Panics if the insurance share and backstop take rate combined are not less than 1
+pub fn execute_set_insurance_bps(e: &Env, bps: u32) {
+    let pool_config = storage::get_pool_config(e);
+    require_valid_take_rates(e, pool_config.bstop_rate, bps);
+    storage::set_insurance_bps(e, &bps);
+}
+
+/// Set the number of ledgers a status change must be observed for before `update_status` applies it
+///
+/// Panics if the number of ledgers exceeds one day (17280 ledgers)
+pub fn execute_set_status_grace_ledgers(e: &Env, ledgers: u32) {
+    if ledgers > 17280 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_status_grace_ledgers(e, &ledgers);
+}
+
+/// Set the minimum health factor a user must have after borrowing
+///
+/// Panics if the buffer is not 0 and is under 1 (1_0000000)
+pub fn execute_set_min_borrow_health_buffer(e: &Env, buffer: i128) {
+    if buffer != 0 && buffer < SCALAR_7 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_min_borrow_health_buffer(e, &buffer);
+}
+
+/// Set the maximum number of positions a user can hold
+///
+/// Panics if the limit exceeds twice the maximum number of reserves
+pub fn execute_set_position_limit(e: &Env, user: &Address, limit: u32) {
+    if limit > MAX_RESERVES * 2 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_position_limit(e, user, &limit);
+}
+
+/// Promote the pending admin to admin. Requires authorization from the pending admin.
+///
+/// Returns a tuple of (previous admin, new admin)
+///
+/// Panics if no admin has been proposed
+pub fn execute_accept_admin(e: &Env) -> (Address, Address) {
+    let new_admin = match storage::get_pending_admin(e) {
+        Some(pending_admin) => pending_admin,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    new_admin.require_auth();
+
+    let admin = storage::get_admin(e);
+    storage::set_admin(e, &new_admin);
+    storage::del_pending_admin(e);
+    (admin, new_admin)
+}
+
+/// Set the minimum value of an interest auction lot, in the oracle's base asset
+///
+/// Panics if the value is negative
+pub fn execute_set_min_interest_auction_value(e: &Env, value: i128) {
+    if value < 0 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+    storage::set_min_interest_auction_value(e, &value);
+}
+
+/// Set the supply and borrow flags of a reserve without the queued reserve update timelock
+///
+/// Panics if the asset is not a reserve
+pub fn execute_set_reserve_status(
+    e: &Env,
+    asset: &Address,
+    supply_enabled: bool,
+    borrow_enabled: bool,
+) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut reserve_config = storage::get_res_config(e, asset);
+    reserve_config.supply_enabled = supply_enabled;
+    reserve_config.borrow_enabled = borrow_enabled;
+    storage::set_res_config(e, asset, &reserve_config);
+}
+
 /// Execute a queueing a reserve initialization for the pool
 pub fn execute_queue_set_reserve(e: &Env, asset: &Address, metadata: &ReserveConfig) {
     if has_queued_reserve_set(e, asset) {
         panic_with_error!(&e, PoolError::BadRequest)
     }
     require_valid_reserve_metadata(e, metadata);
+    require_valid_lot_rate(e, metadata, &storage::get_auction_config(e));
     let mut unlock_time = e.ledger().timestamp();
     // require a timelock if pool status is not setup
     if storage::get_pool_config(e).status != 6 {
@@ -77,9 +218,27 @@ pub fn execute_queue_set_reserve(e: &Env, asset: &Address, metadata: &ReserveCon
     );
 }
 
+/// Load a queued reserve initialization for the pool
+///
+/// Returns the queued reserve config and the timestamp it unlocks at, or None if nothing is queued
+pub fn load_queued_reserve_set(e: &Env, asset: &Address) -> Option<(ReserveConfig, u64)> {
+    if has_queued_reserve_set(e, asset) {
+        let queued_init = storage::get_queued_reserve_set(e, asset);
+        Some((queued_init.new_config, queued_init.unlock_time))
+    } else {
+        None
+    }
+}
+
 /// Execute cancelling a queueing a reserve initialization for the pool
-pub fn execute_cancel_queued_set_reserve(e: &Env, asset: &Address) {
-    storage::del_queued_reserve_set(&e, &asset);
+///
+/// Returns the reserve config that was cancelled
+///
+/// Panics if no reserve set is queued for the asset
+pub fn execute_cancel_queued_set_reserve(e: &Env, asset: &Address) -> ReserveConfig {
+    let queued_init = storage::get_queued_reserve_set(e, asset);
+    storage::del_queued_reserve_set(e, asset);
+    queued_init.new_config
 }
 
 /// Execute a queued reserve initialization for the pool
@@ -90,6 +249,10 @@ pub fn execute_set_reserve(e: &Env, asset: &Address) -> u32 {
         panic_with_error!(e, PoolError::InitNotUnlocked);
     }
 
+    // re-validate the queued config, as the validation rules may have changed since it was queued
+    require_valid_reserve_metadata(e, &queued_init.new_config);
+    require_valid_lot_rate(e, &queued_init.new_config, &storage::get_auction_config(e));
+
     // remove queued reserve
     storage::del_queued_reserve_set(e, asset);
 
@@ -97,6 +260,90 @@ pub fn execute_set_reserve(e: &Env, asset: &Address) -> u32 {
     initialize_reserve(e, asset, &queued_init.new_config)
 }
 
+/// Execute queueing a reserve removal for the pool
+///
+/// Returns the timestamp the reserve can be removed at
+///
+/// Panics if the asset is not a reserve or a removal is already queued
+pub fn execute_queue_remove_reserve(e: &Env, asset: &Address) -> u64 {
+    if !storage::has_res(e, asset) || storage::get_queued_reserve_removal(e, asset).is_some() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut unlock_time = e.ledger().timestamp();
+    // require a timelock if pool status is not setup
+    if storage::get_pool_config(e).status != 6 {
+        unlock_time += SECONDS_PER_WEEK;
+    }
+    storage::set_queued_reserve_removal(e, asset, &unlock_time);
+    unlock_time
+}
+
+/// Execute cancelling a queued reserve removal for the pool
+///
+/// Panics if no reserve removal is queued for the asset
+pub fn execute_cancel_queued_remove_reserve(e: &Env, asset: &Address) {
+    if storage::get_queued_reserve_removal(e, asset).is_none() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    storage::del_queued_reserve_removal(e, asset);
+}
+
+/// Execute a queued reserve removal for the pool
+///
+/// Returns the index of the removed reserve
+///
+/// ### Panics
+/// * If the reserve removal is not queued or not unlocked
+/// * If the reserve is not the last reserve in the pool, as removing it would change the index of other reserves
+/// * If the reserve has any outstanding b or d tokens, or any backstop or insurance credit
+/// * If the reserve's b or d token has ever had emission data
+pub fn execute_remove_reserve(e: &Env, asset: &Address) -> u32 {
+    let unlock_time = match storage::get_queued_reserve_removal(e, asset) {
+        Some(unlock_time) => unlock_time,
+        None => panic_with_error!(e, PoolError::BadRequest),
+    };
+    if unlock_time > e.ledger().timestamp() {
+        panic_with_error!(e, PoolError::InitNotUnlocked);
+    }
+
+    let index = storage::get_res_config(e, asset).index;
+    if index + 1 != storage::get_res_list(e).len() {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let reserve_data = storage::get_res_data(e, asset);
+    if reserve_data.b_supply != 0
+        || reserve_data.d_supply != 0
+        || reserve_data.backstop_credit != 0
+        || reserve_data.insurance_credit != 0
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+
+    // user emission data is keyed by the reserve token index, so a reserve that has
+    // had emissions can't be removed without a later reserve inheriting it
+    let d_token_index = index * 2;
+    let b_token_index = index * 2 + 1;
+    if storage::get_res_emis_data(e, &d_token_index).is_some()
+        || storage::get_res_emis_data(e, &b_token_index).is_some()
+    {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    let mut pool_emissions = storage::get_pool_emissions(e);
+    if pool_emissions.contains_key(d_token_index) || pool_emissions.contains_key(b_token_index) {
+        pool_emissions.remove(d_token_index);
+        pool_emissions.remove(b_token_index);
+        storage::set_pool_emissions(e, &pool_emissions);
+    }
+    storage::del_emis_min_balance(e, &d_token_index);
+    storage::del_emis_min_balance(e, &b_token_index);
+
+    storage::del_queued_reserve_removal(e, asset);
+    storage::pop_res_list(e);
+    storage::del_res_config(e, asset);
+    storage::del_res_data(e, asset);
+    index
+}
+
 /// sets reserve data for the pool
 fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
     let index: u32;
@@ -132,6 +379,7 @@ fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
             b_supply: 0,
             last_time: e.ledger().timestamp(),
             backstop_credit: 0,
+            insurance_credit: 0,
         };
         storage::set_res_data(e, asset, &init_data);
     }
@@ -149,6 +397,12 @@ fn initialize_reserve(e: &Env, asset: &Address, config: &ReserveConfig) -> u32 {
         r_three: config.r_three,
         reactivity: config.reactivity,
         collateral_cap: config.collateral_cap,
+        supply_cap: config.supply_cap,
+        borrow_cap: config.borrow_cap,
+        min_borrow: config.min_borrow,
+        supply_enabled: config.supply_enabled,
+        borrow_enabled: config.borrow_enabled,
+        auction_lot_rate: config.auction_lot_rate,
         enabled: config.enabled,
     };
     storage::set_res_config(e, asset, &reserve_config);
@@ -162,22 +416,39 @@ fn require_valid_reserve_metadata(e: &Env, metadata: &ReserveConfig) {
     if metadata.decimals > 18
         || metadata.c_factor > SCALAR_7_U32
         || metadata.l_factor > SCALAR_7_U32
+        || metadata.c_factor > metadata.l_factor
+        || metadata.util == 0
         || metadata.util > 0_9000000
         || (metadata.max_util > SCALAR_7_U32 || metadata.max_util <= metadata.util)
         || metadata.r_base >= 1_0000000
         || metadata.r_base < 0_0001000
         || (metadata.r_one > metadata.r_two || metadata.r_two > metadata.r_three)
         || (metadata.reactivity > 0_0001000)
+        || metadata.supply_cap < 0
+        || metadata.borrow_cap < 0
+        || metadata.min_borrow < 0
+        || metadata.auction_lot_rate > 0_0500000
+    {
+        panic_with_error!(e, PoolError::InvalidReserveMetadata);
+    }
+}
+
+/// Require that a reserve's `auction_lot_rate`, if set, fully ramps the lot within the
+/// auction's lot ramp blocks. Otherwise the lot would jump to 100% once the lot ramp ends.
+fn require_valid_lot_rate(e: &Env, metadata: &ReserveConfig, auction_config: &AuctionConfig) {
+    if metadata.auction_lot_rate > 0
+        && i128(auction_config.auction_lot_ramp_blocks) * i128(metadata.auction_lot_rate) < SCALAR_7
     {
         panic_with_error!(e, PoolError::InvalidReserveMetadata);
     }
 }
 
 fn require_valid_pool_config(e: &Env, config: &PoolConfig) {
-    // ensure backstop is [0,1)
+    // ensure backstop is [0,1) and leaves room for the insurance fund
     if config.bstop_rate >= SCALAR_7 as u32 {
         panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
     }
+    require_valid_take_rates(e, config.bstop_rate, storage::get_insurance_bps(e));
 
     // verify max positions is at least 2 and less than 2 * max reserves
     if config.max_positions < 2 || config.max_positions > 2 * MAX_RESERVES {
@@ -188,11 +459,24 @@ fn require_valid_pool_config(e: &Env, config: &PoolConfig) {
     if config.min_collateral < 0 {
         panic_with_error!(&e, PoolError::InvalidPoolConfigArgs);
     }
+
+    // ensure flash loan fee is [0,1)
+    if config.flash_loan_fee >= SCALAR_7 as u32 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
+}
+
+/// Require the backstop take rate (7 decimals) and insurance share (basis points) to sum to less than 1
+fn require_valid_take_rates(e: &Env, bstop_rate: u32, insurance_bps: u32) {
+    let insurance_rate = i128(insurance_bps) * SCALAR_7 / BPS_SCALAR;
+    if insurance_bps > BPS_SCALAR as u32 || i128(bstop_rate) + insurance_rate >= SCALAR_7 {
+        panic_with_error!(e, PoolError::InvalidPoolConfigArgs);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::storage::QueuedReserveInit;
+    use crate::storage::{EmissionMinBalance, QueuedReserveInit};
     use crate::testutils;
 
     use super::*;
@@ -312,18 +596,358 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
             // happy path
-            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000);
+            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000, 0_0005000, 3600);
             let new_pool_config = storage::get_pool_config(&e);
             assert_eq!(new_pool_config.bstop_rate, 0_2000000);
             assert_eq!(new_pool_config.oracle, pool_config.oracle);
             assert_eq!(new_pool_config.status, pool_config.status);
             assert_eq!(new_pool_config.max_positions, 4u32);
             assert_eq!(new_pool_config.min_collateral, 2_0000000);
+            assert_eq!(new_pool_config.flash_loan_fee, 0_0005000);
+            assert_eq!(new_pool_config.max_price_age, 3600);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_liq_bonus_tiers() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let tiers = soroban_sdk::vec![
+            &e,
+            LiquidationBonusTier {
+                coverage: 1_0000000,
+                max_bonus: 1_1000000,
+            },
+            LiquidationBonusTier {
+                coverage: 0_5000000,
+                max_bonus: 1_0000000,
+            },
+        ];
+        e.as_contract(&pool, || {
+            execute_set_liq_bonus_tiers(&e, &tiers);
+            let new_tiers = storage::get_liq_bonus_tiers(&e);
+            assert_eq!(new_tiers.len(), 2);
+            assert_eq!(new_tiers.get_unchecked(0).coverage, 1_0000000);
+            assert_eq!(new_tiers.get_unchecked(0).max_bonus, 1_1000000);
+            assert_eq!(new_tiers.get_unchecked(1).coverage, 0_5000000);
+            assert_eq!(new_tiers.get_unchecked(1).max_bonus, 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_liq_bonus_tiers_validates_max_bonus() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let tiers = soroban_sdk::vec![
+            &e,
+            LiquidationBonusTier {
+                coverage: 1_0000000,
+                max_bonus: 0_9999999,
+            },
+        ];
+        e.as_contract(&pool, || {
+            execute_set_liq_bonus_tiers(&e, &tiers);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_min_liq_full_fill_blocks() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_min_liq_full_fill_blocks(&e), 0);
+            execute_set_min_liq_full_fill_blocks(&e, 400);
+            assert_eq!(storage::get_min_liq_full_fill_blocks(&e), 400);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_status_grace_ledgers() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_status_grace_ledgers(&e), 0);
+            execute_set_status_grace_ledgers(&e, 17280);
+            assert_eq!(storage::get_status_grace_ledgers(&e), 17280);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_status_grace_ledgers_too_long() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_status_grace_ledgers(&e, 17281);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_min_borrow_health_buffer() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_min_borrow_health_buffer(&e), 0);
+            execute_set_min_borrow_health_buffer(&e, 1_0500000);
+            assert_eq!(storage::get_min_borrow_health_buffer(&e), 1_0500000);
+            execute_set_min_borrow_health_buffer(&e, 0);
+            assert_eq!(storage::get_min_borrow_health_buffer(&e), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_min_borrow_health_buffer_under_one() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_min_borrow_health_buffer(&e, 0_9999999);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_position_limit() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_position_limit(&e, &samwise), 0);
+            execute_set_position_limit(&e, &samwise, 100);
+            assert_eq!(storage::get_position_limit(&e, &samwise), 100);
+            execute_set_position_limit(&e, &samwise, 0);
+            assert_eq!(storage::get_position_limit(&e, &samwise), 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_position_limit_too_high() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let samwise = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_position_limit(&e, &samwise, 101);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_insurance_bps() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            assert_eq!(storage::get_insurance_bps(&e), 0);
+            execute_set_insurance_bps(&e, 7999);
+            assert_eq!(storage::get_insurance_bps(&e), 7999);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_insurance_bps_exceeds_take_rate() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_set_insurance_bps(&e, 8000);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_auction_config() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            let default_config = storage::get_auction_config(&e);
+            assert_eq!(default_config.auction_lot_ramp_blocks, 200);
+            assert_eq!(default_config.auction_bid_ramp_blocks, 200);
+            assert_eq!(default_config.auction_per_block_scalar, 0_0050000);
+
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    auction_lot_ramp_blocks: 50,
+                    auction_bid_ramp_blocks: 100,
+                    auction_per_block_scalar: 0_0200000,
+                },
+            );
+            let new_config = storage::get_auction_config(&e);
+            assert_eq!(new_config.auction_lot_ramp_blocks, 50);
+            assert_eq!(new_config.auction_bid_ramp_blocks, 100);
+            assert_eq!(new_config.auction_per_block_scalar, 0_0200000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_lot_does_not_ramp() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            // lot only reaches 98% before the bid begins to scale down
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    auction_lot_ramp_blocks: 49,
+                    auction_bid_ramp_blocks: 50,
+                    auction_per_block_scalar: 0_0200000,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_reserve_lot_rate_does_not_ramp() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, reserve_data) = testutils::default_reserve_meta();
+        reserve_config.auction_lot_rate = 0_0050000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            // the reserve's lot only reaches 50% over 100 lot ramp blocks
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    auction_lot_ramp_blocks: 100,
+                    auction_bid_ramp_blocks: 100,
+                    auction_per_block_scalar: 0_0100000,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_auction_config_zero_scalar() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_auction_config(
+                &e,
+                &AuctionConfig {
+                    auction_lot_ramp_blocks: 200,
+                    auction_bid_ramp_blocks: 200,
+                    auction_per_block_scalar: 0,
+                },
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_min_liq_full_fill_blocks_too_long() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_min_liq_full_fill_blocks(&e, 401);
+        });
+    }
+
+    #[test]
+    fn test_execute_accept_admin() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        e.as_contract(&pool, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pending_admin(&e, &samwise);
+            // proposing a new admin overwrites the pending admin
+            storage::set_pending_admin(&e, &frodo);
+
+            let (admin, new_admin) = execute_accept_admin(&e);
+            assert_eq!(admin, bombadil);
+            assert_eq!(new_admin, frodo);
+            assert_eq!(storage::get_admin(&e), frodo);
+            assert!(storage::get_pending_admin(&e).is_none());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_accept_admin_no_pending_admin() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let bombadil = Address::generate(&e);
+        e.as_contract(&pool, || {
+            storage::set_admin(&e, &bombadil);
+
+            execute_accept_admin(&e);
+        });
+    }
+
+    #[test]
+    fn test_execute_set_min_interest_auction_value() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            assert_eq!(storage::get_min_interest_auction_value(&e), 0);
+            execute_set_min_interest_auction_value(&e, 500_0000000);
+            assert_eq!(storage::get_min_interest_auction_value(&e), 500_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_set_min_interest_auction_value_negative() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_min_interest_auction_value(&e, -1);
         });
     }
 
@@ -340,11 +964,13 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
-            execute_update_pool(&e, 1_0000000, 4u32, 1_0000000);
+            execute_update_pool(&e, 1_0000000, 4u32, 1_0000000, 0, 0);
         });
     }
 
@@ -361,11 +987,13 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
-            execute_update_pool(&e, 0_2000000, 4u32, -1);
+            execute_update_pool(&e, 0_2000000, 4u32, -1, 0, 0);
         });
     }
 
@@ -382,11 +1010,36 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
 
-            execute_update_pool(&e, 0_2000000, 1 + 2 * MAX_RESERVES, 2_0000000);
+            execute_update_pool(&e, 0_2000000, 1 + 2 * MAX_RESERVES, 2_0000000, 0, 0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1201)")]
+    fn test_execute_update_pool_validates_flash_loan_fee() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            execute_update_pool(&e, 0_2000000, 4u32, 2_0000000, 1_0000000, 0);
         });
     }
 
@@ -412,6 +1065,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         let pool_config = PoolConfig {
@@ -420,6 +1079,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 6,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -442,7 +1103,108 @@ mod tests {
     }
 
     #[test]
-    fn test_queue_set_reserve() {
+    fn test_execute_set_reserve_status() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_set_reserve_status(&e, &underlying, true, false);
+            let res_config = storage::get_res_config(&e, &underlying);
+            assert_eq!(res_config.supply_enabled, true);
+            assert_eq!(res_config.borrow_enabled, false);
+            assert_eq!(res_config.enabled, true);
+            assert_eq!(res_config.c_factor, reserve_config.c_factor);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_reserve_status_not_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_reserve_status(&e, &Address::generate(&e), false, false);
+        });
+    }
+
+    #[test]
+    fn test_queue_set_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 7,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            collateral_cap: 1000000000000000000,
+            supply_cap: 1_000_000_0000000,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
+            enabled: true,
+        };
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            execute_queue_set_reserve(&e, &asset_id_0, &metadata);
+            let queued_init = storage::get_queued_reserve_set(&e, &asset_id_0);
+            assert_eq!(queued_init.new_config.decimals, metadata.decimals);
+            assert_eq!(queued_init.new_config.c_factor, metadata.c_factor);
+            assert_eq!(queued_init.new_config.l_factor, metadata.l_factor);
+            assert_eq!(queued_init.new_config.util, metadata.util);
+            assert_eq!(queued_init.new_config.max_util, metadata.max_util);
+            assert_eq!(queued_init.new_config.r_base, metadata.r_base);
+            assert_eq!(queued_init.new_config.r_one, metadata.r_one);
+            assert_eq!(queued_init.new_config.r_two, metadata.r_two);
+            assert_eq!(queued_init.new_config.r_three, metadata.r_three);
+            assert_eq!(queued_init.new_config.reactivity, metadata.reactivity);
+            assert_eq!(queued_init.new_config.supply_cap, metadata.supply_cap);
+            assert_eq!(queued_init.new_config.index, 0);
+            assert_eq!(
+                queued_init.unlock_time,
+                e.ledger().timestamp() + SECONDS_PER_WEEK
+            );
+
+            let (queued_config, unlock_time) = load_queued_reserve_set(&e, &asset_id_0).unwrap();
+            assert_eq!(queued_config.c_factor, metadata.c_factor);
+            assert_eq!(queued_config.supply_cap, metadata.supply_cap);
+            assert_eq!(unlock_time, e.ledger().timestamp() + SECONDS_PER_WEEK);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_queue_set_reserve_lot_rate_does_not_ramp() {
         let e = Env::default();
         e.mock_all_auths();
         let pool = testutils::create_pool(&e);
@@ -463,6 +1225,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 1_000_000_0000000,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0_0010000,
             enabled: true,
         };
         let pool_config = PoolConfig {
@@ -471,26 +1239,13 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            // the lot would only reach 20% over the default 200 lot ramp blocks
             execute_queue_set_reserve(&e, &asset_id_0, &metadata);
-            let queued_init = storage::get_queued_reserve_set(&e, &asset_id_0);
-            assert_eq!(queued_init.new_config.decimals, metadata.decimals);
-            assert_eq!(queued_init.new_config.c_factor, metadata.c_factor);
-            assert_eq!(queued_init.new_config.l_factor, metadata.l_factor);
-            assert_eq!(queued_init.new_config.util, metadata.util);
-            assert_eq!(queued_init.new_config.max_util, metadata.max_util);
-            assert_eq!(queued_init.new_config.r_base, metadata.r_base);
-            assert_eq!(queued_init.new_config.r_one, metadata.r_one);
-            assert_eq!(queued_init.new_config.r_two, metadata.r_two);
-            assert_eq!(queued_init.new_config.r_three, metadata.r_three);
-            assert_eq!(queued_init.new_config.reactivity, metadata.reactivity);
-            assert_eq!(queued_init.new_config.index, 0);
-            assert_eq!(
-                queued_init.unlock_time,
-                e.ledger().timestamp() + SECONDS_PER_WEEK
-            );
         });
     }
 
@@ -517,6 +1272,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         let pool_config = PoolConfig {
@@ -525,6 +1286,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 6,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -560,6 +1323,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         let pool_config = PoolConfig {
@@ -568,6 +1337,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -597,6 +1368,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         e.as_contract(&pool, || {
@@ -608,10 +1385,27 @@ mod tests {
                 },
                 &asset_id_0,
             );
-            execute_cancel_queued_set_reserve(&e, &asset_id_0);
+            assert!(load_queued_reserve_set(&e, &asset_id_0).is_some());
+            let cancelled_config = execute_cancel_queued_set_reserve(&e, &asset_id_0);
             let result = storage::has_queued_reserve_set(&e, &asset_id_0);
 
             assert!(!result);
+            assert_eq!(cancelled_config.index, metadata.index);
+            assert_eq!(cancelled_config.decimals, metadata.decimals);
+            assert_eq!(cancelled_config.c_factor, metadata.c_factor);
+            assert_eq!(cancelled_config.l_factor, metadata.l_factor);
+            assert_eq!(cancelled_config.util, metadata.util);
+            assert_eq!(cancelled_config.max_util, metadata.max_util);
+            assert_eq!(cancelled_config.r_base, metadata.r_base);
+            assert_eq!(cancelled_config.r_one, metadata.r_one);
+            assert_eq!(cancelled_config.r_two, metadata.r_two);
+            assert_eq!(cancelled_config.r_three, metadata.r_three);
+            assert_eq!(cancelled_config.reactivity, metadata.reactivity);
+            assert_eq!(cancelled_config.collateral_cap, metadata.collateral_cap);
+            assert_eq!(cancelled_config.supply_cap, metadata.supply_cap);
+            assert_eq!(cancelled_config.borrow_cap, metadata.borrow_cap);
+            assert_eq!(cancelled_config.enabled, metadata.enabled);
+            assert!(load_queued_reserve_set(&e, &asset_id_0).is_none());
         });
     }
 
@@ -637,6 +1431,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 1_000_000_0000000,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         e.as_contract(&pool, || {
@@ -659,7 +1459,165 @@ mod tests {
             assert_eq!(res_config_0.r_two, metadata.r_two);
             assert_eq!(res_config_0.r_three, metadata.r_three);
             assert_eq!(res_config_0.reactivity, metadata.reactivity);
+            assert_eq!(res_config_0.supply_cap, metadata.supply_cap);
+            assert_eq!(res_config_0.index, 0);
+            assert!(load_queued_reserve_set(&e, &asset_id_0).is_none());
+        });
+    }
+
+    #[test]
+    fn test_execute_remove_reserve() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set_timestamp(1000);
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config_1, reserve_data_1) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config_1, &reserve_data_1);
+
+        let pool_config = PoolConfig {
+            oracle: Address::generate(&e),
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_emis_min_balance(
+                &e,
+                &3,
+                &EmissionMinBalance {
+                    min_balance: 1_0000000,
+                    prev_min_balance: 0,
+                    index: 0,
+                },
+            );
+
+            let unlock_time = execute_queue_remove_reserve(&e, &underlying_1);
+            assert_eq!(unlock_time, 1000 + SECONDS_PER_WEEK);
+            assert_eq!(
+                storage::get_queued_reserve_removal(&e, &underlying_1),
+                Some(unlock_time)
+            );
+        });
+
+        e.ledger().set_timestamp(1000 + SECONDS_PER_WEEK);
+        e.as_contract(&pool, || {
+            let index = execute_remove_reserve(&e, &underlying_1);
+            assert_eq!(index, 1);
+
+            let res_list = storage::get_res_list(&e);
+            assert_eq!(res_list.len(), 1);
+            assert_eq!(res_list.get_unchecked(0), underlying_0);
+            assert!(!storage::has_res(&e, &underlying_1));
+            assert_eq!(storage::get_queued_reserve_removal(&e, &underlying_1), None);
+            assert_eq!(storage::get_emis_min_balance(&e, &3).min_balance, 0);
+
+            // the remaining reserve is unchanged
+            let res_config_0 = storage::get_res_config(&e, &underlying_0);
             assert_eq!(res_config_0.index, 0);
+            assert_eq!(
+                storage::get_res_data(&e, &underlying_0).b_supply,
+                100_0000000
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_remove_reserve_outstanding_positions() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_queued_reserve_removal(&e, &underlying_0, &e.ledger().timestamp());
+            execute_remove_reserve(&e, &underlying_0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_remove_reserve_not_last() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_queued_reserve_removal(&e, &underlying_0, &e.ledger().timestamp());
+            execute_remove_reserve(&e, &underlying_0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1203)")]
+    fn test_execute_remove_reserve_requires_unlock() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_queued_reserve_removal(&e, &underlying_0, &(e.ledger().timestamp() + 1));
+            execute_remove_reserve(&e, &underlying_0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_remove_reserve_with_emissions() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            storage::set_res_emis_data(
+                &e,
+                &1,
+                &storage::ReserveEmissionData {
+                    expiration: 0,
+                    eps: 0,
+                    index: 0,
+                    last_time: 0,
+                },
+            );
+            storage::set_queued_reserve_removal(&e, &underlying_0, &e.ledger().timestamp());
+            execute_remove_reserve(&e, &underlying_0);
         });
     }
 
@@ -686,6 +1644,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         e.as_contract(&pool, || {
@@ -701,6 +1665,50 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_execute_set_reserve_validates_queued_metadata() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let pool = testutils::create_pool(&e);
+        let bombadil = Address::generate(&e);
+
+        let (asset_id_0, _) = testutils::create_token_contract(&e, &bombadil);
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 7,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
+            enabled: true,
+        };
+        e.as_contract(&pool, || {
+            storage::set_queued_reserve_set(
+                &e,
+                &QueuedReserveInit {
+                    new_config: metadata.clone(),
+                    unlock_time: e.ledger().timestamp(),
+                },
+                &asset_id_0,
+            );
+            execute_set_reserve(&e, &asset_id_0);
+        });
+    }
+
     #[test]
     fn test_execute_set_reserve_update() {
         let e = Env::default();
@@ -748,6 +1756,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -826,6 +1836,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -896,6 +1908,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 105,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
 
@@ -905,6 +1923,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -944,6 +1964,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         e.as_contract(&pool, || {
@@ -984,6 +2010,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1009,6 +2041,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1032,6 +2070,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1055,6 +2099,41 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
+            enabled: true,
+        };
+        require_valid_reserve_metadata(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_validate_reserve_metadata_validates_c_factor_over_l_factor() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500001,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1078,6 +2157,70 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
+            enabled: true,
+        };
+        require_valid_reserve_metadata(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_validate_reserve_metadata_validates_util_zero() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0,
+            max_util: 0_9500000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
+            enabled: true,
+        };
+        require_valid_reserve_metadata(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_validate_reserve_metadata_validates_max_util_over_util() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_9000000,
+            max_util: 0_9000000,
+            r_base: 0_0001000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 100,
+            collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1101,6 +2244,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1124,6 +2273,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1147,6 +2302,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1170,6 +2331,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 100,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
@@ -1193,6 +2360,70 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0_0001001,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
+            enabled: true,
+        };
+        require_valid_reserve_metadata(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_validate_reserve_metadata_validates_auction_lot_rate() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 0_0000020,
+            collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0_0500001,
+            enabled: true,
+        };
+        require_valid_reserve_metadata(&e, &metadata);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1202)")]
+    fn test_validate_reserve_metadata_validates_min_borrow() {
+        let e = Env::default();
+
+        let metadata = ReserveConfig {
+            index: 0,
+            decimals: 18,
+            c_factor: 0_7500000,
+            l_factor: 0_7500000,
+            util: 0_5000000,
+            max_util: 0_9500000,
+            r_base: 0_0100000,
+            r_one: 0_0500000,
+            r_two: 0_5000000,
+            r_three: 1_5000000,
+            reactivity: 0_0000020,
+            collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: -1,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         };
         require_valid_reserve_metadata(&e, &metadata);
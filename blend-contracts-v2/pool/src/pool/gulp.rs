@@ -1,44 +1,104 @@
 use sep_41_token::TokenClient;
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
-use crate::storage;
+use crate::{errors::PoolError, events::PoolEvents, storage, validator::require_nonnegative};
 
 use super::Reserve;
 
 /// Gulps the excess tokens in the pool, determined by the difference between the pool token balance
-/// and the reserve total supply, backstop credit, and liabiltiies.
+/// and the reserve total supply, backstop credit, insurance credit, and liabiltiies.
 ///
 /// ### Arguments
 /// * `asset` - The address of the asset to gulp
 ///
+/// If a maximum gulp is set for the reserve, at most that amount is credited in a single call.
+/// Any remaining delta can be gulped by subsequent calls.
+///
 /// ### Returns
 /// * The gulped token delta accrued to the backstop credit
 pub fn execute_gulp(e: &Env, asset: &Address) -> i128 {
     let pool_config = storage::get_pool_config(e);
-    let mut reserve = Reserve::load(e, &pool_config, asset);
+    let (mut reserve, new_backstop_credit) = Reserve::load_with_credit(e, &pool_config, asset);
     let pool_token_balance = TokenClient::new(e, asset).balance(&e.current_contract_address());
     let reserve_token_balance =
-        reserve.total_supply(e) + reserve.data.backstop_credit - reserve.total_liabilities(e);
-    let token_balance_delta = pool_token_balance - reserve_token_balance;
+        reserve.total_supply(e) + reserve.data.backstop_credit + reserve.data.insurance_credit
+            - reserve.total_liabilities(e);
+    let mut token_balance_delta = pool_token_balance - reserve_token_balance;
     if token_balance_delta <= 0 {
         return 0;
     }
+    let max_gulp = storage::get_max_gulp(e, asset);
+    if max_gulp > 0 && token_balance_delta > max_gulp {
+        token_balance_delta = max_gulp;
+    }
 
     reserve.data.backstop_credit += token_balance_delta;
 
+    if new_backstop_credit > 0 {
+        PoolEvents::backstop_credit(e, asset.clone(), new_backstop_credit);
+    }
     reserve.store(e);
     return token_balance_delta;
 }
 
+/// Gulps the excess tokens in the pool for each asset in `assets`
+///
+/// ### Arguments
+/// * `assets` - The addresses of the assets to gulp
+///
+/// ### Returns
+/// * A vector of (asset, token_delta) tuples, in the order of `assets`
+pub fn execute_gulp_batch(e: &Env, assets: &Vec<Address>) -> Vec<(Address, i128)> {
+    let mut token_deltas = Vec::new(e);
+    for asset in assets.iter() {
+        let token_delta = execute_gulp(e, &asset);
+        token_deltas.push_back((asset, token_delta));
+    }
+    token_deltas
+}
+
+/// Set the maximum amount of tokens a single gulp can credit to the backstop for a reserve
+///
+/// ### Arguments
+/// * `asset` - The address of the reserve asset
+/// * `max_gulp` - The maximum amount of tokens. A value of 0 removes the maximum.
+///
+/// ### Panics
+/// If the asset is not a reserve or the maximum is negative
+pub fn execute_set_max_gulp(e: &Env, asset: &Address, max_gulp: i128) {
+    if !storage::has_res(e, asset) {
+        panic_with_error!(e, PoolError::BadRequest);
+    }
+    require_nonnegative(e, &max_gulp);
+    storage::set_max_gulp(e, asset, &max_gulp);
+}
+
+/// Load the backstop credit of every reserve in the pool, updated to the current ledger
+///
+/// ### Returns
+/// * A vector of (asset, backstop_credit) tuples, ordered by reserve index
+pub fn load_all_backstop_credit(e: &Env) -> Vec<(Address, i128)> {
+    let pool_config = storage::get_pool_config(e);
+    let reserve_list = storage::get_res_list(e);
+    let mut backstop_credit = Vec::new(e);
+    for asset in reserve_list.iter() {
+        let reserve = Reserve::load(e, &pool_config, &asset);
+        backstop_credit.push_back((asset, reserve.data.backstop_credit));
+    }
+    backstop_credit
+}
+
 #[cfg(test)]
 mod tests {
     use crate::constants::SCALAR_7;
-    use crate::pool::execute_gulp;
+    use crate::pool::{
+        execute_gulp, execute_gulp_batch, execute_set_max_gulp, load_all_backstop_credit, Reserve,
+    };
     use crate::storage::{self, PoolConfig};
     use crate::testutils;
     use soroban_sdk::{
         testutils::{Address as _, Ledger, LedgerInfo},
-        Address, Env,
+        vec, Address, Env,
     };
 
     #[test]
@@ -79,6 +139,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 0,
                 max_positions: 4,
+                flash_loan_fee: 0,
+                max_price_age: 0,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -94,6 +156,105 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_execute_gulp_respects_max_gulp() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 100,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let initial_backstop_credit = 500;
+        let (underlying, underlying_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.d_supply = 500 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = initial_backstop_credit;
+        reserve_data.last_time = 100;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let additional_tokens = 10 * SCALAR_7;
+        underlying_client.mint(&pool, &additional_tokens);
+        e.as_contract(&pool, || {
+            let pool_config = PoolConfig {
+                oracle,
+                min_collateral: 1_0000000,
+                bstop_rate: 0_1000000,
+                status: 0,
+                max_positions: 4,
+                flash_loan_fee: 0,
+                max_price_age: 0,
+            };
+            storage::set_pool_config(&e, &pool_config);
+            let max_gulp = 4 * SCALAR_7;
+            execute_set_max_gulp(&e, &underlying, max_gulp);
+
+            // only the capped amount is credited
+            let token_delta_result = execute_gulp(&e, &underlying);
+            assert_eq!(token_delta_result, max_gulp);
+            let new_reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(
+                new_reserve_data.backstop_credit,
+                max_gulp + initial_backstop_credit
+            );
+
+            // the remaining delta is gulped by subsequent calls
+            let token_delta_result = execute_gulp(&e, &underlying);
+            assert_eq!(token_delta_result, max_gulp);
+            let token_delta_result = execute_gulp(&e, &underlying);
+            assert_eq!(token_delta_result, additional_tokens - 2 * max_gulp);
+            let token_delta_result = execute_gulp(&e, &underlying);
+            assert_eq!(token_delta_result, 0);
+
+            let new_reserve_data = storage::get_res_data(&e, &underlying);
+            assert_eq!(
+                new_reserve_data.backstop_credit,
+                additional_tokens + initial_backstop_credit
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_execute_set_max_gulp_not_reserve() {
+        let e = Env::default();
+        let pool = testutils::create_pool(&e);
+        let underlying = Address::generate(&e);
+
+        e.as_contract(&pool, || {
+            execute_set_max_gulp(&e, &underlying, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #8)")]
+    fn test_execute_set_max_gulp_negative() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            execute_set_max_gulp(&e, &underlying, -1);
+        });
+    }
+
     #[test]
     fn test_execute_gulp_accrues_interest_before_gulp() {
         let e = Env::default();
@@ -132,6 +293,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 0,
                 max_positions: 4,
+                flash_loan_fee: 0,
+                max_price_age: 0,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -184,6 +347,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 0,
                 max_positions: 4,
+                flash_loan_fee: 0,
+                max_price_age: 0,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -234,6 +399,8 @@ mod tests {
                 bstop_rate: 0_1000000,
                 status: 0,
                 max_positions: 4,
+                flash_loan_fee: 0,
+                max_price_age: 0,
             };
             storage::set_pool_config(&e, &pool_config);
 
@@ -247,4 +414,141 @@ mod tests {
             assert_eq!(new_reserve_data.backstop_credit, 0);
         });
     }
+
+    #[test]
+    fn test_execute_gulp_batch() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 100,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.d_supply = 500 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = 500;
+        reserve_data.last_time = 100;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.b_rate = 1_000_000_000_000;
+        reserve_data.d_rate = 1_000_000_000_000;
+        reserve_data.d_supply = 700 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = 0;
+        reserve_data.last_time = 100;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&pool, &(10 * SCALAR_7));
+        underlying_1_client.mint(&pool, &(3 * SCALAR_7));
+        e.as_contract(&pool, || {
+            let pool_config = PoolConfig {
+                oracle,
+                min_collateral: 1_0000000,
+                bstop_rate: 0_1000000,
+                status: 0,
+                max_positions: 4,
+                flash_loan_fee: 0,
+                max_price_age: 0,
+            };
+            storage::set_pool_config(&e, &pool_config);
+
+            let token_deltas =
+                execute_gulp_batch(&e, &vec![&e, underlying_0.clone(), underlying_1.clone()]);
+            assert_eq!(
+                token_deltas,
+                vec![
+                    &e,
+                    (underlying_0.clone(), 10 * SCALAR_7),
+                    (underlying_1.clone(), 3 * SCALAR_7)
+                ]
+            );
+
+            let new_reserve_data_0 = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(new_reserve_data_0.backstop_credit, 10 * SCALAR_7 + 500);
+            let new_reserve_data_1 = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(new_reserve_data_1.backstop_credit, 3 * SCALAR_7);
+        });
+    }
+
+    #[test]
+    fn test_load_all_backstop_credit() {
+        let e = Env::default();
+        e.mock_all_auths();
+        e.ledger().set(LedgerInfo {
+            timestamp: 100,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 500 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = 500;
+        reserve_data.last_time = 0;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.d_supply = 700 * SCALAR_7;
+        reserve_data.b_supply = 1000 * SCALAR_7;
+        reserve_data.backstop_credit = 0;
+        reserve_data.last_time = 0;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            let pool_config = PoolConfig {
+                oracle,
+                min_collateral: 1_0000000,
+                bstop_rate: 0_1000000,
+                status: 0,
+                max_positions: 4,
+                flash_loan_fee: 0,
+                max_price_age: 0,
+            };
+            storage::set_pool_config(&e, &pool_config);
+
+            let all_backstop_credit = load_all_backstop_credit(&e);
+            assert_eq!(all_backstop_credit.len(), 2);
+
+            let reserve_0 = Reserve::load(&e, &pool_config, &underlying_0);
+            let reserve_1 = Reserve::load(&e, &pool_config, &underlying_1);
+            // interest accrued on both reserves
+            assert!(reserve_0.data.backstop_credit > 500);
+            assert!(reserve_1.data.backstop_credit > 0);
+            assert_eq!(
+                all_backstop_credit.get_unchecked(0),
+                (underlying_0, reserve_0.data.backstop_credit)
+            );
+            assert_eq!(
+                all_backstop_credit.get_unchecked(1),
+                (underlying_1, reserve_1.data.backstop_credit)
+            );
+        });
+    }
 }
@@ -1,9 +1,22 @@
+use cast::{i128, u64};
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::Env;
-
-use crate::{constants::SCALAR_7, storage};
-
-use super::{pool::Pool, Positions};
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::{
+    constants::{SCALAR_7, SECONDS_PER_LEDGER, SECONDS_PER_YEAR},
+    storage,
+};
+
+use super::{interest::calc_interest_rate, pool::Pool, Positions};
+
+/// The health of a set of positions, denominated in the oracle's base asset
+#[derive(Clone)]
+#[contracttype]
+pub struct HealthData {
+    pub collateral: i128,    // the effective collateral value
+    pub liability: i128,     // the effective liability value
+    pub health_factor: i128, // the ratio of collateral to liabilities (7 decimals), or i128::MAX if there are no liabilities
+}
 
 pub struct PositionData {
     /// The effective collateral balance denominated in the base asset
@@ -77,6 +90,21 @@ impl PositionData {
         }
     }
 
+    /// Return the health data for the positions
+    pub fn as_health_data(&self, e: &Env) -> HealthData {
+        let health_factor = if self.liability_base == 0 {
+            i128::MAX
+        } else {
+            self.collateral_base
+                .fixed_div_floor(e, &self.liability_base, &SCALAR_7)
+        };
+        HealthData {
+            collateral: self.collateral_base,
+            liability: self.liability_base,
+            health_factor,
+        }
+    }
+
     /// Return the health factor as a ratio
     pub fn as_health_factor(&self, e: &Env) -> i128 {
         self.collateral_base
@@ -110,6 +138,118 @@ impl PositionData {
     }
 }
 
+/// Load the current health of a user's positions
+///
+/// Returns the health data for the user, using the same valuation as the health check in `submit`
+///
+/// ### Arguments
+/// * user - The address of the user
+pub fn load_health(e: &Env, user: &Address) -> HealthData {
+    let mut pool = Pool::load(e);
+    let positions = storage::get_user_positions(e, user);
+    PositionData::calculate_from_positions(e, &mut pool, &positions).as_health_data(e)
+}
+
+/// Estimate the number of ledgers until a user's positions can be liquidated from interest alone,
+/// assuming prices do not change. Interest is projected linearly from the current borrow rate of
+/// each liability and the current supply rate of each collateral.
+///
+/// Returns the estimated number of ledgers, 0 if the positions can already be liquidated, or None
+/// if the positions never become liquidatable from interest alone
+///
+/// ### Arguments
+/// * user - The address of the user
+pub fn load_time_to_liquidation(e: &Env, user: &Address) -> Option<u64> {
+    let mut pool = Pool::load(e);
+    let positions = storage::get_user_positions(e, user);
+    let reserve_list = storage::get_res_list(e);
+    let supplier_share = SCALAR_7 - i128(pool.config.bstop_rate);
+    let mut collateral_base = 0;
+    let mut liability_base = 0;
+    // the yearly increase in the base value of the collateral and liabilities from interest
+    let mut collateral_growth = 0;
+    let mut liability_growth = 0;
+    for i in 0..reserve_list.len() {
+        let b_token_balance = positions.collateral.get(i).unwrap_or(0);
+        let d_token_balance = positions.liabilities.get(i).unwrap_or(0);
+        if b_token_balance == 0 && d_token_balance == 0 {
+            continue;
+        }
+        let reserve = pool.load_reserve(e, &reserve_list.get_unchecked(i), false);
+        let asset_to_base = pool.load_price(e, &reserve.asset);
+        let util = reserve.utilization(e);
+        let borrow_rate = calc_interest_rate(e, &reserve.config, util, reserve.data.ir_mod);
+
+        if b_token_balance > 0 {
+            let asset_collateral = reserve.to_effective_asset_from_b_token(e, b_token_balance);
+            let base = asset_to_base.fixed_mul_floor(e, &asset_collateral, &reserve.scalar);
+            let supply_rate = borrow_rate
+                .fixed_mul_floor(e, &util, &SCALAR_7)
+                .fixed_mul_floor(e, &supplier_share, &SCALAR_7);
+            collateral_base += base;
+            collateral_growth += base.fixed_mul_floor(e, &supply_rate, &SCALAR_7);
+        }
+
+        if d_token_balance > 0 {
+            let asset_liability = reserve.to_effective_asset_from_d_token(e, d_token_balance);
+            let base = asset_to_base.fixed_mul_ceil(e, &asset_liability, &reserve.scalar);
+            liability_base += base;
+            liability_growth += base.fixed_mul_ceil(e, &borrow_rate, &SCALAR_7);
+        }
+
+        pool.cache_reserve(reserve);
+    }
+
+    if liability_base == 0 {
+        return None;
+    }
+    if collateral_base < liability_base {
+        return Some(0);
+    }
+    let net_growth = liability_growth - collateral_growth;
+    if net_growth <= 0 {
+        return None;
+    }
+    let seconds =
+        (collateral_base - liability_base).fixed_div_ceil(e, &net_growth, &SECONDS_PER_YEAR);
+    u64((seconds + SECONDS_PER_LEDGER - 1) / SECONDS_PER_LEDGER).ok()
+}
+
+/// Load a user's positions converted to underlying amounts and values at the current ledger
+///
+/// Returns a vec of (asset, supplied underlying, liability underlying, net base value) for each
+/// reserve the user has a position in. The supplied amount includes both collateral and
+/// non-collateral supply, and the net base value is the supplied value less the liability value,
+/// denominated in the oracle's base asset.
+///
+/// ### Arguments
+/// * user - The address of the user
+pub fn load_positions_detailed(e: &Env, user: &Address) -> Vec<(Address, i128, i128, i128)> {
+    let mut pool = Pool::load(e);
+    let positions = storage::get_user_positions(e, user);
+    let reserve_list = storage::get_res_list(e);
+    let mut detailed: Vec<(Address, i128, i128, i128)> = Vec::new(e);
+    for i in 0..reserve_list.len() {
+        let b_token_balance =
+            positions.collateral.get(i).unwrap_or(0) + positions.supply.get(i).unwrap_or(0);
+        let d_token_balance = positions.liabilities.get(i).unwrap_or(0);
+        if b_token_balance == 0 && d_token_balance == 0 {
+            continue;
+        }
+        let reserve = pool.load_reserve(e, &reserve_list.get_unchecked(i), false);
+        let asset_to_base = pool.load_price(e, &reserve.asset);
+
+        let supplied = reserve.to_asset_from_b_token(e, b_token_balance);
+        let liability = reserve.to_asset_from_d_token(e, d_token_balance);
+        let base_value = asset_to_base.fixed_mul_floor(e, &supplied, &reserve.scalar)
+            - asset_to_base.fixed_mul_ceil(e, &liability, &reserve.scalar);
+        detailed.push_back((reserve.asset.clone(), supplied, liability, base_value));
+
+        pool.cache_reserve(reserve);
+    }
+    detailed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -187,6 +327,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
 
         let positions = Positions {
@@ -206,6 +348,107 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_calculate_from_positions_mixed_decimals() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 6;
+        reserve_data.b_supply = 1_000_000_000;
+        reserve_data.d_supply = 500_000_000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.decimals = 18;
+        reserve_config.index = 1;
+        reserve_data.b_supply = 1_000_000_000_000_000_000;
+        reserve_data.d_supply = 500_000_000_000_000_000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0),
+                Asset::Stellar(underlying_1),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 2_0000000, 1000_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 0,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+
+        // 100 tokens of the 6 decimal reserve and 0.2 tokens of the 18 decimal reserve
+        // are both worth 200 USD
+        let positions_6 = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 100_000_000)],
+            supply: map![&e],
+        };
+        let positions_18 = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (1, 200_000_000_000_000_000)],
+            supply: map![&e],
+        };
+        // 0.15 tokens of the 18 decimal reserve are worth 150 USD
+        let positions_mixed = Positions {
+            liabilities: map![&e, (1, 150_000_000_000_000_000)],
+            collateral: map![&e, (0, 100_000_000), (1, 200_000_000_000_000_000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            let position_data_6 =
+                PositionData::calculate_from_positions(&e, &mut pool, &positions_6);
+            assert_eq!(position_data_6.collateral_raw, 200_0000000);
+            assert_eq!(position_data_6.collateral_base, 150_0000000);
+
+            let position_data_18 =
+                PositionData::calculate_from_positions(&e, &mut pool, &positions_18);
+            assert_eq!(position_data_18.collateral_raw, 200_0000000);
+            assert_eq!(position_data_18.collateral_base, 150_0000000);
+
+            let position_data =
+                PositionData::calculate_from_positions(&e, &mut pool, &positions_mixed);
+            assert_eq!(position_data.collateral_raw, 400_0000000);
+            assert_eq!(position_data.collateral_base, 300_0000000);
+            assert_eq!(position_data.liability_raw, 150_0000000);
+            assert_eq!(position_data.liability_base, 200_0000000);
+            assert_eq!(position_data.scalar, SCALAR_7);
+            assert_eq!(position_data.as_health_data(&e).health_factor, 1_5000000);
+        });
+    }
+
     #[test]
     fn test_as_health_factor_rounds_floor() {
         let e = Env::default();
@@ -356,4 +599,202 @@ mod tests {
         // panic
         assert!(result);
     }
+
+    /// Setup a pool with two reserves priced at 1 and store positions for samwise with
+    /// 100 bTokens of reserve 0 as collateral and `d_tokens` of reserve 1 as a liability
+    fn setup_load_health(e: &Env, d_tokens: i128) -> (Address, Address) {
+        let bombadil = Address::generate(e);
+        let samwise = Address::generate(e);
+        let pool = testutils::create_pool(e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(e);
+
+        let (underlying_0, _) = testutils::create_token_contract(e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 200_0000000;
+        reserve_data.d_supply = 100_0000000;
+        testutils::create_reserve(e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.b_supply = 200_0000000;
+        reserve_data.d_supply = 100_0000000;
+        testutils::create_reserve(e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(e, "USD")),
+            &vec![
+                e,
+                Asset::Stellar(underlying_0),
+                Asset::Stellar(underlying_1),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![e, 1_0000000, 1_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 0,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let positions = Positions {
+            liabilities: map![e, (1, d_tokens)],
+            collateral: map![e, (0, 100_0000000)],
+            supply: map![e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(e, &pool_config);
+            storage::set_user_positions(e, &samwise, &positions);
+        });
+        (pool, samwise)
+    }
+
+    #[test]
+    fn test_load_health_healthy() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let (pool, samwise) = setup_load_health(&e, 30_0000000);
+        let frodo = Address::generate(&e);
+        e.as_contract(&pool, || {
+            let health = load_health(&e, &samwise);
+            assert_eq!(health.collateral, 75_0000000);
+            assert_eq!(health.liability, 40_0000000);
+            assert_eq!(health.health_factor, 1_8750000);
+
+            // no positions
+            let health = load_health(&e, &frodo);
+            assert_eq!(health.collateral, 0);
+            assert_eq!(health.liability, 0);
+            assert_eq!(health.health_factor, i128::MAX);
+        });
+    }
+
+    #[test]
+    fn test_load_positions_detailed() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let (pool, samwise) = setup_load_health(&e, 30_0000000);
+        let frodo = Address::generate(&e);
+        e.ledger().set_timestamp(12345);
+        e.as_contract(&pool, || {
+            let mut positions = storage::get_user_positions(&e, &samwise);
+            positions.supply.set(1, 10_0000000);
+            storage::set_user_positions(&e, &samwise, &positions);
+
+            let detailed = load_positions_detailed(&e, &samwise);
+            assert_eq!(detailed.len(), 2);
+
+            // manually convert the positions
+            let reserve_list = storage::get_res_list(&e);
+            let mut pool = Pool::load(&e);
+            let reserve_0 = pool.load_reserve(&e, &reserve_list.get_unchecked(0), false);
+            let reserve_1 = pool.load_reserve(&e, &reserve_list.get_unchecked(1), false);
+            let collateral_0 = reserve_0.to_asset_from_b_token(&e, 100_0000000);
+            let supply_1 = reserve_1.to_asset_from_b_token(&e, 10_0000000);
+            let liability_1 = reserve_1.to_asset_from_d_token(&e, 30_0000000);
+            assert!(liability_1 > 30_0000000);
+
+            let (asset_0, supplied_0, debt_0, value_0) = detailed.get_unchecked(0);
+            assert_eq!(asset_0, reserve_list.get_unchecked(0));
+            assert_eq!(supplied_0, collateral_0);
+            assert_eq!(debt_0, 0);
+            assert_eq!(value_0, collateral_0);
+
+            let (asset_1, supplied_1, debt_1, value_1) = detailed.get_unchecked(1);
+            assert_eq!(asset_1, reserve_list.get_unchecked(1));
+            assert_eq!(supplied_1, supply_1);
+            assert_eq!(debt_1, liability_1);
+            assert_eq!(value_1, supply_1 - liability_1);
+
+            // no positions
+            let detailed = load_positions_detailed(&e, &frodo);
+            assert_eq!(detailed.len(), 0);
+        });
+    }
+
+    #[test]
+    fn test_load_time_to_liquidation() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let (pool, samwise) = setup_load_health(&e, 30_0000000);
+        let frodo = Address::generate(&e);
+        e.as_contract(&pool, || {
+            // collateral of 75 grows at ~1.73% and liabilities of 40 grow at ~4.33% a year
+            // -> 35 / (1.7333360 - 1.2999975) years ~= 2547108093 seconds
+            assert_eq!(load_time_to_liquidation(&e, &samwise), Some(509421619));
+
+            // supply only
+            let positions = Positions {
+                liabilities: map![&e],
+                collateral: map![&e, (0, 100_0000000)],
+                supply: map![&e, (1, 10_0000000)],
+            };
+            storage::set_user_positions(&e, &frodo, &positions);
+            assert_eq!(load_time_to_liquidation(&e, &frodo), None);
+        });
+    }
+
+    #[test]
+    fn test_load_time_to_liquidation_underwater() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let (pool, samwise) = setup_load_health(&e, 60_0000000);
+        e.as_contract(&pool, || {
+            assert_eq!(load_time_to_liquidation(&e, &samwise), Some(0));
+        });
+    }
+
+    #[test]
+    fn test_load_health_at_threshold() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let (pool, samwise) = setup_load_health(&e, 56_2500000);
+        e.as_contract(&pool, || {
+            let health = load_health(&e, &samwise);
+            assert_eq!(health.collateral, 75_0000000);
+            assert_eq!(health.liability, 75_0000000);
+            assert_eq!(health.health_factor, SCALAR_7);
+        });
+    }
+
+    #[test]
+    fn test_load_health_underwater() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths();
+
+        let (pool, samwise) = setup_load_health(&e, 60_0000000);
+        e.as_contract(&pool, || {
+            let health = load_health(&e, &samwise);
+            assert_eq!(health.collateral, 75_0000000);
+            assert_eq!(health.liability, 80_0000000);
+            assert_eq!(health.health_factor, 0_9375000);
+        });
+    }
 }
@@ -8,25 +8,18 @@ use crate::{
     PoolError,
 };
 
-/// Calculates the loan accrual ratio for the Reserve based on the current utilization and
+/// Calculates the current interest rate for the Reserve based on the current utilization and
 /// rate modifier for the reserve.
 ///
 /// ### Arguments
-/// * `config` - The Reserve config to calculate an accrual for
+/// * `config` - The Reserve config to calculate the interest rate for
 /// * `cur_util` - The current utilization rate of the reserve (7 decimals)
-/// * `ir_mod` - The current interest rate modifier of the reserve (9 decimals)
-/// * `last_block` - The last block an accrual was performed
+/// * `ir_mod` - The current interest rate modifier of the reserve (7 decimals)
 ///
 /// ### Returns
-/// * (i128, i128) - (accrual amount scaled to 9 decimal places, new interest rate modifier scaled to 9 decimal places)
+/// * i128 - The yearly interest rate scaled to 7 decimal places
 #[allow(clippy::zero_prefixed_literal)]
-pub fn calc_accrual(
-    e: &Env,
-    config: &ReserveConfig,
-    cur_util: i128,
-    ir_mod: i128,
-    last_time: u64,
-) -> (i128, i128) {
+pub fn calc_interest_rate(e: &Env, config: &ReserveConfig, cur_util: i128, ir_mod: i128) -> i128 {
     let cur_ir: i128;
     let target_util: i128 = i128(config.util);
     if cur_util <= target_util {
@@ -54,6 +47,30 @@ pub fn calc_accrual(
         );
         cur_ir = extra_rate + intersection;
     }
+    cur_ir
+}
+
+/// Calculates the loan accrual ratio for the Reserve based on the current utilization and
+/// rate modifier for the reserve.
+///
+/// ### Arguments
+/// * `config` - The Reserve config to calculate an accrual for
+/// * `cur_util` - The current utilization rate of the reserve (7 decimals)
+/// * `ir_mod` - The current interest rate modifier of the reserve (9 decimals)
+/// * `last_block` - The last block an accrual was performed
+///
+/// ### Returns
+/// * (i128, i128) - (accrual amount scaled to 9 decimal places, new interest rate modifier scaled to 9 decimal places)
+#[allow(clippy::zero_prefixed_literal)]
+pub fn calc_accrual(
+    e: &Env,
+    config: &ReserveConfig,
+    cur_util: i128,
+    ir_mod: i128,
+    last_time: u64,
+) -> (i128, i128) {
+    let target_util: i128 = i128(config.util);
+    let cur_ir = calc_interest_rate(e, config, cur_util, ir_mod);
 
     // update rate_modifier
     let delta_time = i128(e.ledger().timestamp() - last_time);
@@ -120,6 +137,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0_0000020,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -158,6 +181,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0_0000020,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -196,6 +225,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0_0000020,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -234,6 +269,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0_0000020,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -271,6 +312,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0_0000020,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -308,6 +355,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -346,6 +399,12 @@ mod tests {
             r_three: 1_5000000,
             reactivity: 0_0000020,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -384,6 +443,12 @@ mod tests {
             r_three: 0,
             reactivity: 0_0000020,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             index: 0,
             enabled: true,
         };
@@ -2,37 +2,50 @@ mod actions;
 pub use actions::{FlashLoan, Request, RequestType};
 
 mod bad_debt;
-pub use bad_debt::transfer_bad_debt_to_backstop;
+pub use bad_debt::{cover_bad_debt_with_insurance, has_bad_debt, transfer_bad_debt_to_backstop};
 
 mod config;
 pub use config::{
-    execute_cancel_queued_set_reserve, execute_initialize, execute_queue_set_reserve,
-    execute_set_reserve, execute_update_pool,
+    execute_accept_admin, execute_cancel_queued_remove_reserve, execute_cancel_queued_set_reserve,
+    execute_initialize, execute_queue_remove_reserve, execute_queue_set_reserve,
+    execute_remove_reserve, execute_set_auction_config, execute_set_insurance_bps,
+    execute_set_liq_bonus_tiers, execute_set_min_borrow_health_buffer,
+    execute_set_min_interest_auction_value, execute_set_min_liq_full_fill_blocks,
+    execute_set_position_limit, execute_set_reserve, execute_set_reserve_status,
+    execute_set_status_grace_ledgers, execute_update_pool, load_queued_reserve_set,
 };
 
 mod health_factor;
-pub use health_factor::PositionData;
+pub use health_factor::{
+    load_health, load_positions_detailed, load_time_to_liquidation, HealthData, PositionData,
+};
 
 mod interest;
 
 mod submit;
 
-pub use submit::{execute_submit, execute_submit_with_flash_loan};
+pub use submit::{
+    execute_fill_and_submit, execute_submit, execute_submit_with_flash_loan,
+    execute_submit_with_flash_loans, execute_submit_with_funders, execute_transfer_position,
+    require_no_flash_loan, simulate_submit,
+};
 
 #[allow(clippy::module_inception)]
 mod pool;
 pub use pool::Pool;
 
 mod reserve;
-pub use reserve::Reserve;
+pub use reserve::{load_reserve_rates, load_reserve_totals, load_reserves, Reserve};
 
 mod user;
 pub use user::{Positions, User};
 
 mod status;
 pub use status::{
-    calc_pool_backstop_threshold, execute_set_pool_status, execute_update_pool_status,
+    calc_pool_backstop_threshold, execute_set_emergency, execute_set_pool_status,
+    execute_update_pool_status, get_pool_status, preview_update_pool_status, require_not_paused,
+    require_requests_not_paused,
 };
 
 mod gulp;
-pub use gulp::execute_gulp;
+pub use gulp::{execute_gulp, execute_gulp_batch, execute_set_max_gulp, load_all_backstop_credit};
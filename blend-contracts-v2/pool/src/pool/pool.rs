@@ -4,6 +4,7 @@ use sep_40_oracle::{Asset, PriceFeedClient};
 
 use crate::{
     errors::PoolError,
+    events::PoolEvents,
     storage::{self, PoolConfig},
     Positions,
 };
@@ -16,6 +17,7 @@ pub struct Pool {
     reserves_to_store: Vec<Address>,
     price_decimals: Option<u32>,
     prices: Map<Address, i128>,
+    backstop_credit: Map<Address, i128>,
 }
 
 impl Pool {
@@ -28,25 +30,37 @@ impl Pool {
             reserves_to_store: vec![e],
             price_decimals: None,
             prices: map![e],
+            backstop_credit: map![e],
         }
     }
 
     /// Load a Reserve from the ledger and update to the current ledger timestamp. Returns
     /// a cached version if it exists.
     ///
+    /// Emits a `backstop_credit` event the first time a reserve that accrued backstop credit
+    /// is expected to be stored.
+    ///
     /// ### Arguments
     /// * asset - The address of the underlying asset
     /// * store - If the reserve is expected to be stored to the ledger
     pub fn load_reserve(&mut self, e: &Env, asset: &Address, store: bool) -> Reserve {
+        let reserve = if let Some(reserve) = self.reserves.get(asset.clone()) {
+            reserve
+        } else {
+            let (reserve, new_backstop_credit) = Reserve::load_with_credit(e, &self.config, asset);
+            if new_backstop_credit > 0 {
+                self.backstop_credit.set(asset.clone(), new_backstop_credit);
+            }
+            reserve
+        };
+
         if store && !self.reserves_to_store.contains(asset) {
             self.reserves_to_store.push_back(asset.clone());
+            if let Some(new_backstop_credit) = self.backstop_credit.get(asset.clone()) {
+                PoolEvents::backstop_credit(e, asset.clone(), new_backstop_credit);
+            }
         }
-
-        if let Some(reserve) = self.reserves.get(asset.clone()) {
-            return reserve;
-        } else {
-            Reserve::load(e, &self.config, asset)
-        }
+        reserve
     }
 
     /// Cache the updated reserve in the pool.
@@ -83,16 +97,29 @@ impl Pool {
 
     /// Require that a position does not violate the maximum number of positions, or panic.
     ///
+    /// The maximum is the larger of the pool's max positions and the user's position limit override.
+    ///
     /// ### Arguments
+    /// * `user` - The address of the user
     /// * `positions` - The user's positions
     /// * `previous_num` - The number of positions the user previously had
     ///
     /// ### Panics
     /// If the user has more positions than the maximum allowed and they are not
     /// decreasing their number of positions
-    pub fn require_under_max(&self, e: &Env, positions: &Positions, previous_num: u32) {
+    pub fn require_under_max(
+        &self,
+        e: &Env,
+        user: &Address,
+        positions: &Positions,
+        previous_num: u32,
+    ) {
         let new_num = positions.effective_count();
-        if new_num > previous_num && self.config.max_positions < new_num {
+        let max_positions = self
+            .config
+            .max_positions
+            .max(storage::get_position_limit(e, user));
+        if new_num > previous_num && max_positions < new_num {
             panic_with_error!(e, PoolError::MaxPositionsExceeded)
         }
     }
@@ -115,7 +142,8 @@ impl Pool {
     /// * asset - The address of the underlying asset
     ///
     /// ### Panics
-    /// If the price is invalid due to being over a day old or being less than or equal to 0
+    /// If the price is invalid due to being over a day old or being less than or equal to 0,
+    /// or if the price is older than the pool's max price age
     pub fn load_price(&mut self, e: &Env, asset: &Address) -> i128 {
         if let Some(price) = self.prices.get(asset.clone()) {
             return price;
@@ -126,6 +154,11 @@ impl Pool {
         if price_data.timestamp + 24 * 60 * 60 < e.ledger().timestamp() || price_data.price <= 0 {
             panic_with_error!(e, PoolError::InvalidPrice);
         }
+        if self.config.max_price_age > 0
+            && price_data.timestamp + self.config.max_price_age < e.ledger().timestamp()
+        {
+            panic_with_error!(e, PoolError::StalePrice);
+        }
         self.prices.set(asset.clone(), price_data.price);
         price_data.price
     }
@@ -173,6 +206,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -192,6 +227,7 @@ mod tests {
                     d_supply: 0,
                     last_time: 0,
                     backstop_credit: 0,
+                    insurance_credit: 0,
                 },
             );
 
@@ -245,6 +281,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -276,6 +314,7 @@ mod tests {
                     d_supply: 0,
                     last_time: 0,
                     backstop_credit: 0,
+                    insurance_credit: 0,
                 },
             );
 
@@ -334,6 +373,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -364,6 +405,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 2,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -385,6 +428,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 1,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -407,6 +452,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 2,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -428,6 +475,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 1,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -450,6 +499,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 4,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -472,6 +523,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 4,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -493,6 +546,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 4,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -526,6 +581,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -566,6 +623,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -619,6 +678,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -629,6 +690,99 @@ mod tests {
         });
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1228)")]
+    fn test_load_price_panics_if_older_than_max_price_age() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1000 + 3600 + 1,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let asset = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(asset.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price(&vec![&e, 123], &1000);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 3600,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            pool.load_price(&e, &asset);
+            assert!(false);
+        });
+    }
+
+    #[test]
+    fn test_load_price_within_max_price_age() {
+        let e = Env::default();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 1000 + 3600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let asset = Address::generate(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(asset.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price(&vec![&e, 123], &1000);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 3600,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let mut pool = Pool::load(&e);
+
+            let price = pool.load_price(&e, &asset);
+            assert_eq!(price, 123);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "Error(Contract, #1210)")]
     fn test_load_price_panics_if_zero() {
@@ -664,6 +818,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -693,6 +849,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -701,7 +859,7 @@ mod tests {
             let pool = Pool::load(&e);
             user.add_collateral(&e, &mut reserve_0, 1);
 
-            pool.require_under_max(&e, &user.positions, prev_positions);
+            pool.require_under_max(&e, &samwise, &user.positions, prev_positions);
         });
     }
 
@@ -727,6 +885,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -738,7 +898,7 @@ mod tests {
             let pool = Pool::load(&e);
             user.add_liabilities(&e, &mut reserve_1, 2);
 
-            pool.require_under_max(&e, &user.positions, prev_positions);
+            pool.require_under_max(&e, &samwise, &user.positions, prev_positions);
         });
     }
 
@@ -764,6 +924,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -776,7 +938,7 @@ mod tests {
             let pool = Pool::load(&e);
             user.remove_collateral(&e, &mut reserve_1, 42);
 
-            pool.require_under_max(&e, &user.positions, prev_positions);
+            pool.require_under_max(&e, &samwise, &user.positions, prev_positions);
         });
     }
 
@@ -803,9 +965,49 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            user.add_collateral(&e, &mut reserve_0, 123);
+            user.add_liabilities(&e, &mut reserve_0, 789);
+            let prev_positions = user.positions.effective_count();
+
+            let pool = Pool::load(&e);
+            user.add_liabilities(&e, &mut reserve_1, 42);
+
+            pool.require_under_max(&e, &samwise, &user.positions, prev_positions);
+        });
+    }
+    #[test]
+    fn test_require_under_max_uses_position_limit() {
+        let e = Env::default();
+        e.mock_all_auths();
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let mut reserve_0 = testutils::default_reserve(&e);
+        let mut reserve_1 = testutils::default_reserve(&e);
+        reserve_1.config.index = 1;
+
+        let mut user = User {
+            address: samwise.clone(),
+            positions: Positions::env_default(&e),
+        };
+        let (oracle, _) = testutils::create_mock_oracle(&e);
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_position_limit(&e, &samwise, &3);
             user.add_collateral(&e, &mut reserve_0, 123);
             user.add_liabilities(&e, &mut reserve_0, 789);
             let prev_positions = user.positions.effective_count();
@@ -813,7 +1015,7 @@ mod tests {
             let pool = Pool::load(&e);
             user.add_liabilities(&e, &mut reserve_1, 42);
 
-            pool.require_under_max(&e, &user.positions, prev_positions);
+            pool.require_under_max(&e, &samwise, &user.positions, prev_positions);
         });
     }
 }
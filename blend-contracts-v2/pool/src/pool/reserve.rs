@@ -1,9 +1,9 @@
 use cast::i128;
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{contracttype, panic_with_error, Address, Env};
+use soroban_sdk::{contracttype, panic_with_error, Address, Env, Vec};
 
 use crate::{
-    constants::{SCALAR_12, SCALAR_7},
+    constants::{BPS_SCALAR, SCALAR_12, SCALAR_7},
     errors::PoolError,
     pool::actions::RequestType,
     storage::{self, PoolConfig, ReserveConfig, ReserveData},
@@ -33,6 +33,24 @@ impl Reserve {
     /// Panics if the asset is not supported, if emissions cannot be updated, or if the reserve
     /// cannot be updated to the current ledger timestamp.
     pub fn load(e: &Env, pool_config: &PoolConfig, asset: &Address) -> Reserve {
+        Reserve::load_with_credit(e, pool_config, asset).0
+    }
+
+    /// Load a Reserve from the ledger and update to the current ledger timestamp.
+    ///
+    /// Returns the reserve and the amount of accrued interest credited to the backstop
+    /// during the update
+    ///
+    /// **NOTE**: This function is not cached, and should be called from the Pool.
+    ///
+    /// ### Arguments
+    /// * pool_config - The pool configuration
+    /// * asset - The address of the underlying asset
+    ///
+    /// ### Panics
+    /// Panics if the asset is not supported, if emissions cannot be updated, or if the reserve
+    /// cannot be updated to the current ledger timestamp.
+    pub fn load_with_credit(e: &Env, pool_config: &PoolConfig, asset: &Address) -> (Reserve, i128) {
         let reserve_config = storage::get_res_config(e, asset);
         let reserve_data = storage::get_res_data(e, asset);
         let mut reserve = Reserve {
@@ -44,19 +62,19 @@ impl Reserve {
 
         // short circuit if the reserve has already been updated this ledger
         if e.ledger().timestamp() == reserve.data.last_time {
-            return reserve;
+            return (reserve, 0);
         }
 
         if reserve.data.b_supply == 0 {
             reserve.data.last_time = e.ledger().timestamp();
-            return reserve;
+            return (reserve, 0);
         }
 
         let cur_util = reserve.utilization(e);
         if cur_util == 0 {
             // if there are no assets borrowed, we don't need to update the reserve
             reserve.data.last_time = e.ledger().timestamp();
-            return reserve;
+            return (reserve, 0);
         }
 
         let (loan_accrual, new_ir_mod) = calc_accrual(
@@ -72,10 +90,13 @@ impl Reserve {
         reserve.data.d_rate = loan_accrual.fixed_mul_ceil(e, &reserve.data.d_rate, &SCALAR_12);
         let accrued_interest = reserve.total_liabilities(e) - pre_update_liabilities;
 
-        reserve.accrue(e, pool_config.bstop_rate, accrued_interest);
+        let insurance_bps = storage::get_insurance_bps(e);
+        let pre_update_backstop_credit = reserve.data.backstop_credit;
+        reserve.accrue(e, pool_config.bstop_rate, insurance_bps, accrued_interest);
+        let new_backstop_credit = reserve.data.backstop_credit - pre_update_backstop_credit;
 
         reserve.data.last_time = e.ledger().timestamp();
-        reserve
+        (reserve, new_backstop_credit)
     }
 
     /// Store the updated reserve to the ledger.
@@ -83,12 +104,13 @@ impl Reserve {
         storage::set_res_data(e, &self.asset, &self.data);
     }
 
-    /// Accrue tokens to the reserve supply. This issues any `backstop_credit` required and updates the reserve's bRate to account for the additional tokens.
+    /// Accrue tokens to the reserve supply. This issues any `backstop_credit` and `insurance_credit` required and updates the reserve's bRate to account for the additional tokens.
     ///
     /// ### Arguments
     /// * bstop_rate - The backstop take rate for the pool
+    /// * insurance_bps - The insurance fund take rate for the pool, in basis points
     /// * accrued - The amount of additional underlying tokens
-    fn accrue(&mut self, e: &Env, bstop_rate: u32, accrued: i128) {
+    fn accrue(&mut self, e: &Env, bstop_rate: u32, insurance_bps: u32, accrued: i128) {
         let pre_update_supply = self.total_supply(e);
 
         if accrued > 0 {
@@ -99,11 +121,15 @@ impl Reserve {
                 new_backstop_credit = accrued.fixed_mul_floor(e, &i128(bstop_rate), &SCALAR_7);
                 self.data.backstop_credit += new_backstop_credit;
             }
-            self.data.b_rate = (pre_update_supply + accrued - new_backstop_credit).fixed_div_floor(
-                e,
-                &self.data.b_supply,
-                &SCALAR_12,
-            );
+            let mut new_insurance_credit: i128 = 0;
+            if insurance_bps > 0 {
+                new_insurance_credit =
+                    accrued.fixed_mul_floor(e, &i128(insurance_bps), &BPS_SCALAR);
+                self.data.insurance_credit += new_insurance_credit;
+            }
+            self.data.b_rate =
+                (pre_update_supply + accrued - new_backstop_credit - new_insurance_credit)
+                    .fixed_div_floor(e, &self.data.b_supply, &SCALAR_12);
         }
     }
 
@@ -120,6 +146,34 @@ impl Reserve {
         }
     }
 
+    /// Require that the total supply is at or below the supply cap, or panic. A supply cap of 0 means no cap.
+    pub fn require_supply_below_cap(&self, e: &Env) {
+        if self.config.supply_cap > 0 && self.total_supply(e) > self.config.supply_cap {
+            panic_with_error!(e, PoolError::SupplyCapExceeded)
+        }
+    }
+
+    /// Require that the total liabilities are at or below the borrow cap, or panic. A borrow cap of 0 means no cap.
+    pub fn require_liabilities_below_cap(&self, e: &Env) {
+        if self.config.borrow_cap > 0 && self.total_liabilities(e) > self.config.borrow_cap {
+            panic_with_error!(e, PoolError::BorrowCapExceeded)
+        }
+    }
+
+    /// Require that a liability position is either empty or at least the reserve's minimum borrow amount,
+    /// or panic. A minimum borrow of 0 means no minimum.
+    ///
+    /// ### Arguments
+    /// * `d_tokens` - The number of dTokens the user holds for the reserve
+    pub fn require_liability_above_min_borrow(&self, e: &Env, d_tokens: i128) {
+        if self.config.min_borrow > 0
+            && d_tokens > 0
+            && self.to_asset_from_d_token(e, d_tokens) < self.config.min_borrow
+        {
+            panic_with_error!(e, PoolError::BadRequest)
+        }
+    }
+
     /// Check the action is allowed according to the reserve status, or panic.
     ///
     /// ### Arguments
@@ -134,6 +188,16 @@ impl Reserve {
                 panic_with_error!(e, PoolError::ReserveDisabled);
             }
         }
+        // reserve level pauses only restrict opening or increasing positions
+        if !self.config.supply_enabled
+            && (action_type == RequestType::Supply as u32
+                || action_type == RequestType::SupplyCollateral as u32)
+        {
+            panic_with_error!(e, PoolError::ReserveDisabled);
+        }
+        if !self.config.borrow_enabled && action_type == RequestType::Borrow as u32 {
+            panic_with_error!(e, PoolError::ReserveDisabled);
+        }
     }
 
     /// Fetch the total liabilities for the reserve in underlying tokens
@@ -217,11 +281,239 @@ impl Reserve {
     }
 }
 
+/// Load a set of reserves from the ledger and update them to the current ledger timestamp.
+///
+/// Assets that are not reserves in the pool are skipped, so a speculative list of assets
+/// can be passed.
+///
+/// ### Arguments
+/// * assets - The addresses of the underlying assets to load
+///
+/// ### Returns
+/// * A vector of the reserves that exist, in the order they were requested
+pub fn load_reserves(e: &Env, assets: &Vec<Address>) -> Vec<Reserve> {
+    let pool_config = storage::get_pool_config(e);
+    let mut reserves = Vec::new(e);
+    for asset in assets.iter() {
+        if storage::has_res(e, &asset) {
+            reserves.push_back(Reserve::load(e, &pool_config, &asset));
+        }
+    }
+    reserves
+}
+
+/// Load the totals of a reserve in underlying tokens, updated to the current ledger timestamp.
+///
+/// ### Arguments
+/// * asset - The address of the underlying asset
+///
+/// ### Returns
+/// * A tuple of (total_supplied, total_borrowed, available_liquidity)
+pub fn load_reserve_totals(e: &Env, asset: &Address) -> (i128, i128, i128) {
+    let pool_config = storage::get_pool_config(e);
+    let reserve = Reserve::load(e, &pool_config, asset);
+    let total_supplied = reserve.total_supply(e);
+    let total_borrowed = reserve.total_liabilities(e);
+    (
+        total_supplied,
+        total_borrowed,
+        (total_supplied - total_borrowed).max(0),
+    )
+}
+
+/// Load the bToken and dToken rates of a reserve, updated to the current ledger timestamp.
+///
+/// ### Arguments
+/// * asset - The address of the underlying asset
+///
+/// ### Returns
+/// * A tuple of (b_rate, d_rate)
+pub fn load_reserve_rates(e: &Env, asset: &Address) -> (i128, i128) {
+    let pool_config = storage::get_pool_config(e);
+    let reserve = Reserve::load(e, &pool_config, asset);
+    (reserve.data.b_rate, reserve.data.d_rate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::testutils;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger, LedgerInfo},
+        vec,
+    };
+
+    #[test]
+    fn test_load_reserves() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 20_0000000;
+        reserve_data.b_supply = 80_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        let (underlying_2, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_supply = 45_0000000;
+        reserve_data.b_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_2, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            // unknown assets are skipped
+            let reserves = load_reserves(
+                &e,
+                &vec![
+                    &e,
+                    underlying_0.clone(),
+                    Address::generate(&e),
+                    underlying_2.clone(),
+                ],
+            );
+            assert_eq!(reserves.len(), 2);
+
+            for (reserve, asset) in reserves.iter().zip([underlying_0, underlying_2]) {
+                let expected = Reserve::load(&e, &pool_config, &asset);
+                assert_eq!(reserve.asset, asset);
+                assert_eq!(reserve.config.index, expected.config.index);
+                assert_eq!(reserve.data.d_rate, expected.data.d_rate);
+                assert_eq!(reserve.data.b_rate, expected.data.b_rate);
+                assert_eq!(reserve.data.ir_mod, expected.data.ir_mod);
+                assert_eq!(reserve.data.backstop_credit, expected.data.backstop_credit);
+                assert_eq!(reserve.data.last_time, 617280);
+            }
+        });
+    }
+
+    #[test]
+    fn test_load_reserve_totals() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let (total_supplied, total_borrowed, available) = load_reserve_totals(&e, &underlying);
+
+            // rates accrue to d_rate: 1_349_657_798_173, b_rate: 1_125_547_124_242
+            assert_eq!(total_supplied, 111_4291652);
+            assert_eq!(total_borrowed, 87_7277569);
+            assert_eq!(available, 111_4291652 - 87_7277569);
+        });
+    }
+
+    #[test]
+    fn test_load_reserve_rates() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 123456 * 5,
+            protocol_version: 22,
+            sequence_number: 123456,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.d_rate = 1_345_678_123_000;
+        reserve_data.b_rate = 1_123_456_789_000;
+        reserve_data.d_supply = 65_0000000;
+        reserve_data.b_supply = 99_0000000;
+        testutils::create_reserve(&e, &pool, &underlying, &reserve_config, &reserve_data);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_2000000,
+            status: 0,
+            max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            let (b_rate, d_rate) = load_reserve_rates(&e, &underlying);
+
+            let reserve = Reserve::load(&e, &pool_config, &underlying);
+            assert_eq!(b_rate, reserve.data.b_rate);
+            assert_eq!(d_rate, reserve.data.d_rate);
+            assert_eq!(b_rate, 1_125_547_124_242);
+            assert_eq!(d_rate, 1_349_657_798_173);
+        });
+    }
 
     #[test]
     fn test_load_reserve() {
@@ -257,6 +549,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -313,6 +607,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -361,6 +657,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -408,6 +706,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -457,6 +757,8 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -507,6 +809,8 @@ mod tests {
             bstop_rate: 0_2000000,
             status: 0,
             max_positions: 5,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
@@ -744,6 +1048,41 @@ mod tests {
         reserve.require_action_allowed(&e, RequestType::Repay as u32);
     }
 
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1223)")]
+    fn test_require_action_allowed_panics_if_supply_paused() {
+        let e = Env::default();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.config.supply_enabled = false;
+
+        reserve.require_action_allowed(&e, RequestType::Supply as u32);
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1223)")]
+    fn test_require_action_allowed_panics_if_borrow_paused() {
+        let e = Env::default();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.config.borrow_enabled = false;
+
+        reserve.require_action_allowed(&e, RequestType::Borrow as u32);
+    }
+
+    #[test]
+    fn test_require_action_allowed_paused_allows_exit() {
+        let e = Env::default();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.config.supply_enabled = false;
+        reserve.config.borrow_enabled = false;
+
+        reserve.require_action_allowed(&e, RequestType::Withdraw as u32);
+        reserve.require_action_allowed(&e, RequestType::WithdrawCollateral as u32);
+        reserve.require_action_allowed(&e, RequestType::Repay as u32);
+    }
+
     #[test]
     fn test_accrue() {
         let e = Env::default();
@@ -763,7 +1102,7 @@ mod tests {
         let mut reserve = testutils::default_reserve(&e);
         reserve.data.backstop_credit = 0_1234567;
 
-        reserve.accrue(&e, 0_2000000, 100_0000000);
+        reserve.accrue(&e, 0_2000000, 0, 100_0000000);
         assert_eq!(reserve.data.backstop_credit, 20_0000000 + 0_1234567);
         assert_eq!(reserve.data.b_rate, 1_800_000_000_000);
         assert_eq!(reserve.data.last_time, 0);
@@ -788,9 +1127,28 @@ mod tests {
         let mut reserve = testutils::default_reserve(&e);
         reserve.data.backstop_credit = 0_1234567;
 
-        reserve.accrue(&e, 0_2000000, -10_0000000);
+        reserve.accrue(&e, 0_2000000, 0, -10_0000000);
         assert_eq!(reserve.data.backstop_credit, 0_1234567);
         assert_eq!(reserve.data.b_rate, 1_000_000_000_000);
         assert_eq!(reserve.data.last_time, 0);
     }
+
+    #[test]
+    fn test_accrue_with_insurance() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let mut reserve = testutils::default_reserve(&e);
+        reserve.data.b_supply = 100_0000000;
+        reserve.data.d_supply = 50_0000000;
+        reserve.data.backstop_credit = 0_1234567;
+        reserve.data.insurance_credit = 1_0000000;
+
+        // 20% to the backstop, 5% to the insurance fund
+        reserve.accrue(&e, 0_2000000, 500, 10_0000000);
+        assert_eq!(reserve.data.backstop_credit, 2_0000000 + 0_1234567);
+        assert_eq!(reserve.data.insurance_credit, 0_5000000 + 1_0000000);
+        // suppliers receive the remaining 7.5 tokens
+        assert_eq!(reserve.data.b_rate, 1_075_000_000_000);
+    }
 }
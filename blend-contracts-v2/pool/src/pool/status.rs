@@ -3,26 +3,96 @@ use crate::{
     dependencies::{BackstopClient, PoolBackstopData},
     storage, PoolError,
 };
-use soroban_sdk::{panic_with_error, Env};
+use soroban_sdk::{panic_with_error, Env, Vec};
+
+use super::{Request, RequestType};
 
 /// Update the pool status based on the backstop module
-#[allow(clippy::zero_prefixed_literal)]
-#[allow(clippy::inconsistent_digit_grouping)]
+///
+/// If a status grace period is set, a new status is only applied once it has been observed
+/// for at least the grace period number of ledgers. Until then, the candidate status and the
+/// ledger it was first observed are tracked as the pending status.
 pub fn execute_update_pool_status(e: &Env) -> u32 {
     let mut pool_config = storage::get_pool_config(e);
+    let new_status = preview_update_pool_status(e);
+    if new_status == pool_config.status {
+        storage::del_pending_status(e);
+        return pool_config.status;
+    }
+
+    let grace_ledgers = storage::get_status_grace_ledgers(e);
+    let cur_ledger = e.ledger().sequence();
+    let apply = if grace_ledgers == 0 {
+        true
+    } else {
+        match storage::get_pending_status(e) {
+            Some((pending_status, first_ledger)) if pending_status == new_status => {
+                cur_ledger - first_ledger >= grace_ledgers
+            }
+            _ => {
+                // a new candidate status restarts the grace period
+                storage::set_pending_status(e, &new_status, &cur_ledger);
+                false
+            }
+        }
+    };
+
+    if apply {
+        storage::del_pending_status(e);
+        pool_config.status = new_status;
+        storage::set_pool_config(e, &pool_config);
+    }
+    pool_config.status
+}
+
+/// Calculate the status the pool would be updated to based on the backstop module,
+/// without writing it. Does not account for the status grace period.
+///
+/// ### Panics
+/// If the pool is currently on status 4 (admin frozen) or 6 (setup)
+pub fn preview_update_pool_status(e: &Env) -> u32 {
+    let pool_config = storage::get_pool_config(e);
 
-    // check the pool has met minimum backstop deposits
     let backstop_id = storage::get_backstop(e);
     let backstop_client = BackstopClient::new(e, &backstop_id);
+    let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
+
+    calc_updated_pool_status(e, pool_config.status, &pool_backstop_data)
+}
+
+/// Fetch the current pool status and the backstop metrics that drive status updates,
+/// without writing anything
+///
+/// Returns a tuple of (current status, backstop queued for withdrawal percentage in SCALAR_7,
+/// 1 if the backstop threshold is met or 0 if not)
+pub fn get_pool_status(e: &Env) -> (u32, i128, i128) {
+    let pool_config = storage::get_pool_config(e);
 
+    let backstop_id = storage::get_backstop(e);
+    let backstop_client = BackstopClient::new(e, &backstop_id);
     let pool_backstop_data = backstop_client.pool_data(&e.current_contract_address());
-    let threshold = calc_pool_backstop_threshold(&pool_backstop_data);
+
+    let met_threshold = calc_pool_backstop_threshold(&pool_backstop_data) >= SCALAR_7;
+    (
+        pool_config.status,
+        pool_backstop_data.q4w_pct,
+        met_threshold as i128,
+    )
+}
+
+/// Calculate the pool status based on the current status and the pool's backstop data
+#[allow(clippy::zero_prefixed_literal)]
+#[allow(clippy::inconsistent_digit_grouping)]
+fn calc_updated_pool_status(e: &Env, status: u32, pool_backstop_data: &PoolBackstopData) -> u32 {
+    // check the pool has met minimum backstop deposits
+    let threshold = calc_pool_backstop_threshold(pool_backstop_data);
     let mut met_threshold = true;
     if threshold < SCALAR_7 {
         met_threshold = false;
     }
 
-    match pool_config.status {
+    let mut new_status = status;
+    match status {
         // Setup
         6 => {
             // Setup supersedes all other statuses
@@ -37,32 +107,31 @@ pub fn execute_update_pool_status(e: &Env) -> u32 {
         2 => {
             if pool_backstop_data.q4w_pct >= 0_7500000 {
                 // Q4W over 75% freezes the pool
-                pool_config.status = 5;
+                new_status = 5;
             }
         }
         // Admin active
         0 => {
             if !met_threshold || pool_backstop_data.q4w_pct >= 0_5000000 {
                 // Q4w over 50% or being under threshold puts the pool on-ice
-                pool_config.status = 3;
+                new_status = 3;
             }
         }
         // Admin status isn't set
         _ => {
             if pool_backstop_data.q4w_pct >= 0_6000000 {
                 // Q4w over 60% sets pool to Frozen
-                pool_config.status = 5;
+                new_status = 5;
             } else if pool_backstop_data.q4w_pct >= 0_3000000 || !met_threshold {
                 // Q4w over 30% sets pool to On-Ice
-                pool_config.status = 3;
+                new_status = 3;
             } else {
                 // Backstop is healthy and the pool is set to Active
-                pool_config.status = 1;
+                new_status = 1;
             }
         }
     }
-    storage::set_pool_config(e, &pool_config);
-    pool_config.status
+    new_status
 }
 
 /// Admin set the pool status
@@ -113,6 +182,7 @@ pub fn execute_set_pool_status(e: &Env, pool_status: u32) {
             panic_with_error!(e, PoolError::BadRequest);
         }
     }
+    storage::del_pending_status(e);
     storage::set_pool_config(e, &pool_config);
 }
 
@@ -147,6 +217,56 @@ pub fn calc_pool_backstop_threshold(pool_backstop_data: &PoolBackstopData) -> i1
     saturating_pool_pc / threshold_pc
 }
 
+/// Set the emergency pause for the pool. While paused, all state changing entry points
+/// revert, except for withdrawals if `allow_withdrawals` is set.
+///
+/// ### Arguments
+/// * `paused` - If the pool is paused
+/// * `allow_withdrawals` - If withdrawals are allowed while the pool is paused
+pub fn execute_set_emergency(e: &Env, paused: bool, allow_withdrawals: bool) {
+    storage::set_emergency_pause(e, &paused);
+    storage::set_emergency_withdraw_enabled(e, &allow_withdrawals);
+}
+
+/// Require that the pool is not emergency paused
+///
+/// ### Panics
+/// If the pool is emergency paused
+pub fn require_not_paused(e: &Env) {
+    if storage::get_emergency_pause(e) {
+        panic_with_error!(e, PoolError::Paused);
+    }
+}
+
+/// Require that a set of submitted requests is allowed given the pool's emergency pause. Repayments
+/// are always allowed. If withdrawals are allowed while paused, withdrawals and supplying collateral
+/// are also allowed.
+///
+/// ### Arguments
+/// * `requests` - The requests being submitted
+///
+/// ### Panics
+/// If the pool is emergency paused and the requests are not allowed
+pub fn require_requests_not_paused(e: &Env, requests: &Vec<Request>) {
+    if !storage::get_emergency_pause(e) {
+        return;
+    }
+    let allow_withdrawals = storage::get_emergency_withdraw_enabled(e);
+    let allowed = requests.iter().all(|request| {
+        let is_repay = request.request_type == RequestType::Repay as u32
+            || request.request_type == RequestType::RepayAll as u32;
+        let is_withdraw_or_collateral = request.request_type == RequestType::Withdraw as u32
+            || request.request_type == RequestType::WithdrawCollateral as u32
+            || request.request_type == RequestType::WithdrawAll as u32
+            || request.request_type == RequestType::WithdrawCollateralAll as u32
+            || request.request_type == RequestType::SupplyCollateral as u32;
+        is_repay || (allow_withdrawals && is_withdraw_or_collateral)
+    });
+    if !allowed {
+        panic_with_error!(e, PoolError::Paused);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -155,7 +275,10 @@ mod tests {
     };
 
     use super::*;
-    use soroban_sdk::{testutils::Address as _, vec, Address};
+    use soroban_sdk::{
+        testutils::{Address as _, Ledger},
+        vec, Address,
+    };
 
     #[test]
     fn test_set_pool_status_active() {
@@ -191,6 +314,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -238,6 +363,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -283,6 +410,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -325,6 +454,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -373,6 +504,8 @@ mod tests {
             bstop_rate: 0,
             status: 5,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -417,6 +550,8 @@ mod tests {
             bstop_rate: 0,
             status: 6,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -459,6 +594,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -505,6 +642,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -548,6 +687,8 @@ mod tests {
             bstop_rate: 0,
             status: 3,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -595,6 +736,8 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -642,6 +785,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -690,6 +835,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -703,6 +850,305 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_preview_update_pool_status_matches_update() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        let pool_id = create_pool(&e);
+        let oracle_id = Address::generate(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) = create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (_, backstop_client) = create_backstop(&e, &pool_id, &lp_token, &usdc, &blnd);
+
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_id, &50_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 3,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pool_config(&e, &pool_config);
+
+            // no Q4W - active
+            let preview = preview_update_pool_status(&e);
+            assert_eq!(storage::get_pool_config(&e).status, 3);
+            let status = execute_update_pool_status(&e);
+            assert_eq!(preview, status);
+            assert_eq!(status, 1);
+        });
+
+        // 30% Q4W - on-ice
+        backstop_client.queue_withdrawal(&samwise, &pool_id, &15_000_0000000);
+        e.as_contract(&pool_id, || {
+            let preview = preview_update_pool_status(&e);
+            assert_eq!(storage::get_pool_config(&e).status, 1);
+            let status = execute_update_pool_status(&e);
+            assert_eq!(preview, status);
+            assert_eq!(status, 3);
+        });
+
+        // 60% Q4W - frozen
+        backstop_client.queue_withdrawal(&samwise, &pool_id, &15_000_0000000);
+        e.as_contract(&pool_id, || {
+            let preview = preview_update_pool_status(&e);
+            assert_eq!(storage::get_pool_config(&e).status, 3);
+            let status = execute_update_pool_status(&e);
+            assert_eq!(preview, status);
+            assert_eq!(status, 5);
+        });
+
+        // 60% Q4W with admin on-ice - stays on-ice
+        e.as_contract(&pool_id, || {
+            let mut pool_config = storage::get_pool_config(&e);
+            pool_config.status = 2;
+            storage::set_pool_config(&e, &pool_config);
+
+            let preview = preview_update_pool_status(&e);
+            assert_eq!(storage::get_pool_config(&e).status, 2);
+            let status = execute_update_pool_status(&e);
+            assert_eq!(preview, status);
+            assert_eq!(status, 2);
+        });
+    }
+
+    #[test]
+    fn test_update_pool_status_grace_period() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        let pool_id = create_pool(&e);
+        let oracle_id = Address::generate(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) = create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (_, backstop_client) = create_backstop(&e, &pool_id, &lp_token, &usdc, &blnd);
+
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_id, &50_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 1,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.ledger().set_sequence_number(100);
+        e.as_contract(&pool_id, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_status_grace_ledgers(&e, &10);
+        });
+
+        // 30% Q4W - on-ice is pending
+        backstop_client.queue_withdrawal(&samwise, &pool_id, &15_000_0000000);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(storage::get_pool_config(&e).status, 1);
+            assert_eq!(storage::get_pending_status(&e), Some((3, 100)));
+        });
+
+        // Q4W drops back under the threshold before the grace period elapses - pending is cleared
+        e.ledger().set_sequence_number(105);
+        backstop_client.dequeue_withdrawal(&samwise, &pool_id, &15_000_0000000);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(storage::get_pending_status(&e), None);
+        });
+
+        // Q4W crosses the threshold again - grace period restarts
+        e.ledger().set_sequence_number(108);
+        backstop_client.queue_withdrawal(&samwise, &pool_id, &15_000_0000000);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(storage::get_pending_status(&e), Some((3, 108)));
+        });
+
+        // grace period not yet elapsed
+        e.ledger().set_sequence_number(117);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(storage::get_pool_config(&e).status, 1);
+            assert_eq!(storage::get_pending_status(&e), Some((3, 108)));
+        });
+
+        // grace period elapsed - status changes
+        e.ledger().set_sequence_number(118);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 3);
+            assert_eq!(storage::get_pool_config(&e).status, 3);
+            assert_eq!(storage::get_pending_status(&e), None);
+        });
+    }
+
+    #[test]
+    fn test_update_pool_status_grace_period_new_candidate_restarts() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        let pool_id = create_pool(&e);
+        let oracle_id = Address::generate(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) = create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (_, backstop_client) = create_backstop(&e, &pool_id, &lp_token, &usdc, &blnd);
+
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_id, &50_000_0000000);
+        backstop_client.queue_withdrawal(&samwise, &pool_id, &15_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 1,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.ledger().set_sequence_number(100);
+        e.as_contract(&pool_id, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_status_grace_ledgers(&e, &10);
+
+            // 30% Q4W - on-ice is pending
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(storage::get_pending_status(&e), Some((3, 100)));
+        });
+
+        // 60% Q4W - frozen replaces the pending on-ice status
+        e.ledger().set_sequence_number(105);
+        backstop_client.queue_withdrawal(&samwise, &pool_id, &15_000_0000000);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(storage::get_pending_status(&e), Some((5, 105)));
+        });
+
+        e.ledger().set_sequence_number(110);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 1);
+        });
+
+        e.ledger().set_sequence_number(115);
+        e.as_contract(&pool_id, || {
+            let status = execute_update_pool_status(&e);
+            assert_eq!(status, 5);
+            assert_eq!(storage::get_pending_status(&e), None);
+        });
+    }
+
+    #[test]
+    fn test_get_pool_status() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+        let pool_id = create_pool(&e);
+        let oracle_id = Address::generate(&e);
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+
+        let (blnd, blnd_client) = create_token_contract(&e, &bombadil);
+        let (usdc, usdc_client) = create_token_contract(&e, &bombadil);
+        let (lp_token, lp_token_client) = create_comet_lp_pool(&e, &bombadil, &blnd, &usdc);
+        let (_, backstop_client) = create_backstop(&e, &pool_id, &lp_token, &usdc, &blnd);
+
+        // mint lp tokens
+        blnd_client.mint(&samwise, &500_001_0000000);
+        blnd_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        usdc_client.mint(&samwise, &12_501_0000000);
+        usdc_client.approve(&samwise, &lp_token, &i128::MAX, &99999);
+        lp_token_client.join_pool(
+            &50_000_0000000,
+            &vec![&e, 500_001_0000000, 12_501_0000000],
+            &samwise,
+        );
+        backstop_client.deposit(&samwise, &pool_id, &50_000_0000000);
+        backstop_client.queue_withdrawal(&samwise, &pool_id, &35_000_0000000);
+
+        let pool_config = PoolConfig {
+            oracle: oracle_id,
+            min_collateral: 0,
+            bstop_rate: 0,
+            status: 1,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool_id, || {
+            storage::set_admin(&e, &bombadil);
+            storage::set_pool_config(&e, &pool_config);
+
+            let (status, q4w_pct, met_threshold) = get_pool_status(&e);
+            assert_eq!(status, 1);
+            assert_eq!(q4w_pct, 0_7000000);
+            assert_eq!(met_threshold, 1);
+
+            // the pool status is not updated
+            assert_eq!(storage::get_pool_config(&e).status, 1);
+            assert_eq!(preview_update_pool_status(&e), 5);
+        });
+    }
+
     #[test]
     fn test_update_pool_status_on_ice_30_q4w_admin_active() {
         let e = Env::default();
@@ -738,6 +1184,8 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -786,6 +1234,8 @@ mod tests {
             bstop_rate: 0,
             status: 0,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -834,6 +1284,8 @@ mod tests {
             bstop_rate: 0,
             status: 1,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -881,6 +1333,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -929,6 +1383,8 @@ mod tests {
             bstop_rate: 0,
             status: 2,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -977,6 +1433,8 @@ mod tests {
             bstop_rate: 0,
             status: 4,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -1021,6 +1479,8 @@ mod tests {
             bstop_rate: 0,
             status: 6,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -1066,6 +1526,8 @@ mod tests {
             bstop_rate: 0,
             status: 5,
             max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool_id, || {
             storage::set_admin(&e, &bombadil);
@@ -1173,4 +1635,165 @@ mod tests {
         let result = calc_pool_backstop_threshold(&pool_backstop_data);
         assert_eq!(result, 0_0000003);
     }
+
+    #[test]
+    fn test_require_requests_not_paused_allows_withdrawals() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Withdraw as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+            Request {
+                request_type: RequestType::WithdrawCollateralAll as u32,
+                address: asset.clone(),
+                amount: 0,
+            },
+        ];
+        e.as_contract(&pool_id, || {
+            // not paused
+            require_not_paused(&e);
+            require_requests_not_paused(&e, &requests);
+
+            execute_set_emergency(&e, true, true);
+            assert!(storage::get_emergency_pause(&e));
+            assert!(storage::get_emergency_withdraw_enabled(&e));
+            require_requests_not_paused(&e, &requests);
+        });
+    }
+
+    #[test]
+    fn test_require_requests_not_paused_allows_repay() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+            Request {
+                request_type: RequestType::RepayAll as u32,
+                address: asset.clone(),
+                amount: 0,
+            },
+        ];
+        e.as_contract(&pool_id, || {
+            execute_set_emergency(&e, true, false);
+            require_requests_not_paused(&e, &requests);
+        });
+    }
+
+    #[test]
+    fn test_require_requests_not_paused_allows_supply_collateral_with_withdrawals() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+            Request {
+                request_type: RequestType::Repay as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+        e.as_contract(&pool_id, || {
+            execute_set_emergency(&e, true, true);
+            require_requests_not_paused(&e, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1230)")]
+    fn test_require_requests_not_paused_blocks_supply_collateral() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+        e.as_contract(&pool_id, || {
+            execute_set_emergency(&e, true, false);
+            require_requests_not_paused(&e, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1230)")]
+    fn test_require_requests_not_paused_blocks_borrow() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Withdraw as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+            Request {
+                request_type: RequestType::Borrow as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+        e.as_contract(&pool_id, || {
+            execute_set_emergency(&e, true, true);
+            require_requests_not_paused(&e, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1230)")]
+    fn test_require_requests_not_paused_blocks_withdrawals() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+        let asset = Address::generate(&e);
+
+        let requests = vec![
+            &e,
+            Request {
+                request_type: RequestType::Withdraw as u32,
+                address: asset.clone(),
+                amount: 1_0000000,
+            },
+        ];
+        e.as_contract(&pool_id, || {
+            execute_set_emergency(&e, true, false);
+            require_requests_not_paused(&e, &requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1230)")]
+    fn test_require_not_paused() {
+        let e = Env::default();
+        let pool_id = create_pool(&e);
+
+        e.as_contract(&pool_id, || {
+            execute_set_emergency(&e, true, true);
+            require_not_paused(&e);
+        });
+    }
 }
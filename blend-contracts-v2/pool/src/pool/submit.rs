@@ -1,13 +1,19 @@
-use moderc3156::FlashLoanClient;
+use cast::i128;
 use sep_41_token::TokenClient;
-use soroban_sdk::{panic_with_error, Address, Env, Map, Vec};
+use soroban_fixed_point_math::SorobanFixedPoint;
+use soroban_sdk::{panic_with_error, vec, Address, Env, IntoVal, Map, Symbol, Val, Vec};
 
-use crate::{events::PoolEvents, storage, AuctionType, PoolError};
+use crate::{
+    constants::{MAX_SUBMIT_REQUESTS, SCALAR_7},
+    events::PoolEvents,
+    storage, AuctionType, PoolError,
+};
 
 use super::{
-    actions::{build_actions_from_request, Actions, Request},
-    health_factor::PositionData,
+    actions::{build_actions_from_request, Actions, Request, RequestType},
+    health_factor::{HealthData, PositionData},
     pool::Pool,
+    status::{require_not_paused, require_requests_not_paused},
     FlashLoan, Positions, User,
 };
 
@@ -21,7 +27,8 @@ use super::{
 /// * use_allowance - A bool indicating if transfer_from is to be used
 ///
 /// ### Panics
-/// If the request is unable to be fully executed
+/// If more than `MAX_SUBMIT_REQUESTS` requests are submitted or the request is unable to be
+/// fully executed
 pub fn execute_submit(
     e: &Env,
     from: &Address,
@@ -33,15 +40,25 @@ pub fn execute_submit(
     if from == &e.current_contract_address()
         || spender == &e.current_contract_address()
         || to == &e.current_contract_address()
+        || requests.len() > MAX_SUBMIT_REQUESTS
     {
         panic_with_error!(e, &PoolError::BadRequest);
     }
+    require_no_flash_loan(e);
+    require_requests_not_paused(e, &requests);
     let mut pool = Pool::load(e);
     let mut from_state = User::load(e, from);
 
     let prev_positions_count = from_state.positions.effective_count();
 
-    let actions = build_actions_from_request(e, &mut pool, &mut from_state, requests);
+    let actions = build_actions_from_request(
+        e,
+        &mut pool,
+        &mut from_state,
+        Some(spender),
+        use_allowance,
+        requests,
+    );
 
     validate_submit(
         e,
@@ -49,6 +66,7 @@ pub fn execute_submit(
         &from_state,
         prev_positions_count,
         actions.check_health,
+        actions.check_borrow_health,
     );
 
     if use_allowance {
@@ -64,6 +82,152 @@ pub fn execute_submit(
     from_state.positions
 }
 
+/// Same as `execute_submit` using allowances, but the tokens required by each request named in
+/// `funders` are pulled from the named funder instead of `spender`. A funder of a repay request
+/// only sends the amount used to repay the liability, so any excess is never pulled from the funder.
+///
+/// ### Arguments
+/// * from - The address of the user whose positions are being modified
+/// * spender - The address of the user who is sending tokens to the pool for unfunded requests
+/// * to - The address of the user who is receiving tokens from the pool
+/// * requests - A vec of requests to be processed
+/// * funders - A map of request index to the address funding the request
+///
+/// ### Panics
+/// If a funder is named for a request that does not exist or is not a supply, supply collateral
+/// or repay request, or the requests are unable to be fully executed
+pub fn execute_submit_with_funders(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+    requests: Vec<Request>,
+    funders: Map<u32, Address>,
+) -> Positions {
+    if from == &e.current_contract_address()
+        || spender == &e.current_contract_address()
+        || to == &e.current_contract_address()
+        || requests.len() > MAX_SUBMIT_REQUESTS
+    {
+        panic_with_error!(e, &PoolError::BadRequest);
+    }
+    for (index, funder) in funders.iter() {
+        let request = match requests.get(index) {
+            Some(request) => request,
+            None => panic_with_error!(e, &PoolError::BadRequest),
+        };
+        let fundable = matches!(
+            RequestType::from_u32(e, request.request_type),
+            RequestType::Supply | RequestType::SupplyCollateral | RequestType::Repay
+        );
+        if !fundable || funder == e.current_contract_address() {
+            panic_with_error!(e, &PoolError::BadRequest);
+        }
+    }
+    require_no_flash_loan(e);
+    require_requests_not_paused(e, &requests);
+    let mut pool = Pool::load(e);
+    let mut from_state = User::load(e, from);
+
+    let prev_positions_count = from_state.positions.effective_count();
+
+    // build the funded requests separately so their transfers are kept apart from the
+    // spender's and "to"'s transfers
+    let mut actions = Actions::new(e);
+    let mut funded_transfers: Vec<(Address, Address, i128)> = Vec::new(e);
+    for (index, request) in requests.iter().enumerate() {
+        let request_actions = build_actions_from_request(
+            e,
+            &mut pool,
+            &mut from_state,
+            Some(spender),
+            true,
+            vec![e, request.clone()],
+        );
+        match funders.get(index as u32) {
+            Some(funder) => {
+                // any excess repayment is netted against the amount pulled from the funder
+                let refund = request_actions
+                    .pool_transfer
+                    .get(request.address.clone())
+                    .unwrap_or(0);
+                funded_transfers.push_back((
+                    funder,
+                    request.address.clone(),
+                    request.amount - refund,
+                ));
+            }
+            None => actions.append(request_actions),
+        }
+    }
+
+    validate_submit(
+        e,
+        &mut pool,
+        &from_state,
+        prev_positions_count,
+        actions.check_health,
+        actions.check_borrow_health,
+    );
+
+    // pull the funded requests from their funders
+    for (funder, asset, amount) in funded_transfers.iter() {
+        if amount > 0 {
+            TokenClient::new(e, &asset).transfer_from(
+                &e.current_contract_address(),
+                &funder,
+                &e.current_contract_address(),
+                &amount,
+            );
+        }
+    }
+    handle_transfer_with_allowance(e, &actions, spender, to);
+
+    // store updated info to ledger
+    pool.store_cached_reserves(e);
+    from_state.store(e);
+
+    from_state.positions
+}
+
+/// Fill an auction as "filler" and process the filler's follow-up requests against the positions
+/// received from the auction. The filler is the sender and receiver of all tokens.
+///
+/// ### Arguments
+/// * filler - The address filling the auction
+/// * auction_type - The type of auction being filled
+/// * user - The address involved in the auction
+/// * percent_filled - The percentage of the auction being filled
+/// * requests - A vec of requests to be processed after the fill
+///
+/// ### Panics
+/// If the auction type is invalid, more than `MAX_SUBMIT_REQUESTS` requests are submitted
+/// including the fill, or the requests are unable to be fully executed
+pub fn execute_fill_and_submit(
+    e: &Env,
+    filler: &Address,
+    auction_type: u32,
+    user: &Address,
+    percent_filled: u64,
+    requests: Vec<Request>,
+) -> Positions {
+    let request_type = match AuctionType::from_u32(e, auction_type) {
+        AuctionType::UserLiquidation => RequestType::FillUserLiquidationAuction,
+        AuctionType::BadDebtAuction => RequestType::FillBadDebtAuction,
+        AuctionType::InterestAuction => RequestType::FillInterestAuction,
+    };
+    let mut fill_requests = vec![
+        e,
+        Request {
+            request_type: request_type as u32,
+            address: user.clone(),
+            amount: percent_filled as i128,
+        },
+    ];
+    fill_requests.append(&requests);
+    execute_submit(e, filler, filler, filler, fill_requests, false)
+}
+
 /// Same as `execute_submit` but specifically made for performing a flash loan borrow before
 /// the other submitted requests.
 pub fn execute_submit_with_flash_loan(
@@ -72,9 +236,34 @@ pub fn execute_submit_with_flash_loan(
     flash_loan: FlashLoan,
     requests: Vec<Request>,
 ) -> Positions {
-    if from == &e.current_contract_address() {
+    execute_submit_with_flash_loans(e, from, vec![e, flash_loan], requests)
+}
+
+/// Same as `execute_submit` but specifically made for performing one or more flash loan borrows
+/// before the other submitted requests.
+///
+/// All flash loaned assets are sent to their receivers before any receiver is invoked, so the
+/// first `exec_op` call has access to every borrowed asset. Any tokens returned to the pool beyond
+/// the principal and fees owed are credited to the reserve's backstop credit. If a receiver returns
+/// a value from `exec_op`, it is emitted in a `flash_loan_result` event.
+///
+/// ### Panics
+/// If no flash loans are provided, more than `MAX_SUBMIT_REQUESTS` requests are submitted, or
+/// the requests are unable to be fully executed
+pub fn execute_submit_with_flash_loans(
+    e: &Env,
+    from: &Address,
+    flash_loans: Vec<FlashLoan>,
+    requests: Vec<Request>,
+) -> Positions {
+    if from == &e.current_contract_address()
+        || flash_loans.is_empty()
+        || requests.len() > MAX_SUBMIT_REQUESTS
+    {
         panic_with_error!(e, &PoolError::BadRequest);
     }
+    require_no_flash_loan(e);
+    require_not_paused(e);
     let mut pool = Pool::load(e);
     let mut from_state = User::load(e, from);
 
@@ -82,11 +271,19 @@ pub fn execute_submit_with_flash_loan(
 
     // note: we add the flash loan liabilities before processing the other
     // requests.
-    {
+    let mut flash_loan_fees: Vec<i128> = Vec::new(e);
+    for flash_loan in flash_loans.iter() {
         let mut reserve = pool.load_reserve(e, &flash_loan.asset, true);
         let d_tokens_minted = reserve.to_d_token_up(e, flash_loan.amount);
         from_state.add_liabilities(e, &mut reserve, d_tokens_minted);
         reserve.require_utilization_below_max(e);
+        reserve.require_liabilities_below_cap(e);
+
+        // the flash loan fee is credited to the backstop
+        let fee = calc_flash_loan_fee(e, pool.config.flash_loan_fee, flash_loan.amount);
+        reserve.data.backstop_credit += fee;
+        flash_loan_fees.push_back(fee);
+        pool.cache_reserve(reserve);
 
         PoolEvents::flash_loan(
             e,
@@ -98,25 +295,71 @@ pub fn execute_submit_with_flash_loan(
         );
     }
 
-    let actions = build_actions_from_request(e, &mut pool, &mut from_state, requests);
+    // "from" may repay with the flash loaned tokens, so repayments are not capped at its balance
+    let mut actions =
+        build_actions_from_request(e, &mut pool, &mut from_state, None, true, requests);
+
+    // "from" owes the flash loan fees alongside the other request transfers
+    for (flash_loan, fee) in flash_loans.iter().zip(flash_loan_fees.iter()) {
+        if fee > 0 {
+            actions.add_for_spender_transfer(&flash_loan.asset, fee);
+        }
+    }
 
     // always check health since flash_borrow requires it
-    validate_submit(e, &mut pool, &from_state, prev_positions_count, true);
-
-    // we deal with the flashloan transfer before the others to allow the flash
-    // loan to yield the repaid or supplied amount in the transfers.
-    TokenClient::new(e, &flash_loan.asset).transfer(
-        &e.current_contract_address(),
-        &flash_loan.contract,
-        &flash_loan.amount,
-    );
-    // calls the receiver contract with "from" as the caller
-    FlashLoanClient::new(&e, &flash_loan.contract).exec_op(
-        &from,
-        &flash_loan.asset,
-        &flash_loan.amount,
-        &0,
-    );
+    validate_submit(e, &mut pool, &from_state, prev_positions_count, true, true);
+
+    // record the pool balance of each flash loaned asset before any tokens leave the pool
+    let mut pre_flash_balances: Map<Address, i128> = Map::new(e);
+    let mut flash_loaned: Map<Address, i128> = Map::new(e);
+    for flash_loan in flash_loans.iter() {
+        if !pre_flash_balances.contains_key(flash_loan.asset.clone()) {
+            let balance =
+                TokenClient::new(e, &flash_loan.asset).balance(&e.current_contract_address());
+            pre_flash_balances.set(flash_loan.asset.clone(), balance);
+        }
+        flash_loaned.set(
+            flash_loan.asset.clone(),
+            flash_loaned.get(flash_loan.asset.clone()).unwrap_or(0) + flash_loan.amount,
+        );
+    }
+
+    // we deal with the flashloan transfers before the others to allow the flash
+    // loans to yield the repaid or supplied amount in the transfers.
+    for flash_loan in flash_loans.iter() {
+        TokenClient::new(e, &flash_loan.asset).transfer(
+            &e.current_contract_address(),
+            &flash_loan.contract,
+            &flash_loan.amount,
+        );
+    }
+    // calls the receiver contracts with "from" as the caller. The pool is locked while
+    // the receivers execute to prevent them from re-entering the pool. Any data returned
+    // by a receiver is surfaced through a `flash_loan_result` event.
+    storage::set_flash_loan_lock(e);
+    for (flash_loan, fee) in flash_loans.iter().zip(flash_loan_fees.iter()) {
+        let result: Val = e.invoke_contract(
+            &flash_loan.contract,
+            &Symbol::new(e, "exec_op"),
+            vec![
+                e,
+                from.into_val(e),
+                flash_loan.asset.into_val(e),
+                flash_loan.amount.into_val(e),
+                fee.into_val(e),
+            ],
+        );
+        if !result.is_void() {
+            PoolEvents::flash_loan_result(
+                e,
+                flash_loan.asset.clone(),
+                from.clone(),
+                flash_loan.contract.clone(),
+                result,
+            );
+        }
+    }
+    storage::del_flash_loan_lock(e);
 
     // note: at this point, the pool has sum_by_asset(actions.flash_borrow.1) for each involed asset, but the user also has
     // increased liabilities. These will have to be either fully repaid by now in the requests following the flash borrow
@@ -125,6 +368,23 @@ pub fn execute_submit_with_flash_loan(
 
     handle_transfer_with_allowance(e, &actions, from, from);
 
+    // verify the pool holds at least the amount it expects for each flash loaned asset, including
+    // the flash loan fees collected from "from". Any surplus returned beyond what is owed, e.g. by a
+    // receiver over-repaying, is not refunded and is credited to the backstop.
+    for (asset, pre_balance) in pre_flash_balances.iter() {
+        let expected_balance = pre_balance - flash_loaned.get_unchecked(asset.clone())
+            + actions.spender_transfer.get(asset.clone()).unwrap_or(0)
+            - actions.pool_transfer.get(asset.clone()).unwrap_or(0);
+        let balance = TokenClient::new(e, &asset).balance(&e.current_contract_address());
+        if balance < expected_balance {
+            panic_with_error!(e, PoolError::BalanceError);
+        } else if balance > expected_balance {
+            let mut reserve = pool.load_reserve(e, &asset, true);
+            reserve.data.backstop_credit += balance - expected_balance;
+            pool.cache_reserve(reserve);
+        }
+    }
+
     // store updated info to ledger
     pool.store_cached_reserves(e);
     from_state.store(e);
@@ -132,6 +392,162 @@ pub fn execute_submit_with_flash_loan(
     from_state.positions
 }
 
+/// Transfer part of a collateral or liability position from one user to another.
+///
+/// ### Arguments
+/// * from - The address of the user whose position is being transferred
+/// * to - The address of the user receiving the position
+/// * reserve_token_id - The reserve token being transferred. A reserve's dTokens are
+///                      `reserve_index * 2` and its collateralized bTokens are `reserve_index * 2 + 1`
+/// * amount - The amount of bTokens or dTokens to transfer
+///
+/// ### Panics
+/// If the transfer is invalid, or if it would leave "from" (for collateral) or "to"
+/// (for liabilities) with an invalid health factor
+pub fn execute_transfer_position(
+    e: &Env,
+    from: &Address,
+    to: &Address,
+    reserve_token_id: u32,
+    amount: i128,
+) {
+    if from == to
+        || from == &e.current_contract_address()
+        || to == &e.current_contract_address()
+        || amount <= 0
+    {
+        panic_with_error!(e, &PoolError::BadRequest);
+    }
+    require_no_flash_loan(e);
+    require_not_paused(e);
+    let is_collateral = reserve_token_id % 2 == 1;
+    let mut pool = Pool::load(e);
+    if !is_collateral {
+        // "to" takes on new debt
+        pool.require_action_allowed(e, RequestType::Borrow as u32);
+    }
+    let asset = match storage::get_res_list(e).get(reserve_token_id / 2) {
+        Some(asset) => asset,
+        None => panic_with_error!(e, &PoolError::BadRequest),
+    };
+    let mut reserve = pool.load_reserve(e, &asset, true);
+    if !is_collateral {
+        reserve.require_action_allowed(e, RequestType::Borrow as u32);
+    }
+    let mut from_state = User::load(e, from);
+    let mut to_state = User::load(e, to);
+    let prev_from_count = from_state.positions.effective_count();
+    let prev_to_count = to_state.positions.effective_count();
+
+    if is_collateral {
+        from_state.remove_collateral(e, &mut reserve, amount);
+        to_state.add_collateral(e, &mut reserve, amount);
+    } else {
+        from_state.remove_liabilities(e, &mut reserve, amount);
+        to_state.add_liabilities(e, &mut reserve, amount);
+        reserve.require_liability_above_min_borrow(
+            e,
+            from_state.get_liabilities(reserve.config.index),
+        );
+        reserve
+            .require_liability_above_min_borrow(e, to_state.get_liabilities(reserve.config.index));
+    }
+    pool.cache_reserve(reserve);
+
+    validate_submit(
+        e,
+        &mut pool,
+        &from_state,
+        prev_from_count,
+        is_collateral,
+        false,
+    );
+    validate_submit(
+        e,
+        &mut pool,
+        &to_state,
+        prev_to_count,
+        !is_collateral,
+        !is_collateral,
+    );
+
+    pool.store_cached_reserves(e);
+    from_state.store(e);
+    to_state.store(e);
+
+    PoolEvents::transfer_position(e, asset, from.clone(), to.clone(), reserve_token_id, amount);
+}
+
+/// Require that no flash loan is currently in progress
+///
+/// ### Panics
+/// If a flash loan receiver is currently being executed
+pub fn require_no_flash_loan(e: &Env) {
+    if storage::has_flash_loan_lock(e) {
+        panic_with_error!(e, PoolError::Reentrancy);
+    }
+}
+
+/// Calculate the fee charged for a flash loan, rounded up
+///
+/// ### Arguments
+/// * flash_loan_fee - The pool's flash loan fee (7 decimals)
+/// * amount - The amount of tokens flash loaned
+fn calc_flash_loan_fee(e: &Env, flash_loan_fee: u32, amount: i128) -> i128 {
+    if flash_loan_fee == 0 {
+        return 0;
+    }
+    amount.fixed_mul_ceil(e, &i128(flash_loan_fee), &SCALAR_7)
+}
+
+/// Simulate a set of updates for a user against the pool, without storing the user's positions
+/// or reserve data, or transferring any tokens.
+///
+/// The emission data of each reserve token the requests touch, and "from"'s emission data for
+/// it, is still checkpointed to storage, the same as it would be by any other interaction with
+/// the reserve token. This does not change the emissions anyone can claim.
+///
+/// Returns the resulting positions and the health of the positions
+///
+/// ### Arguments
+/// * from - The address of the user whose positions are being modified
+/// * requests - A vec of requests to be processed. Auction requests are not supported, and
+///              "repay_all" requests repay the full liability regardless of any spender's balance.
+///
+/// ### Panics
+/// If the request is unable to be fully executed
+pub fn simulate_submit(e: &Env, from: &Address, requests: Vec<Request>) -> (Positions, HealthData) {
+    if from == &e.current_contract_address() || requests.len() > MAX_SUBMIT_REQUESTS {
+        panic_with_error!(e, &PoolError::BadRequest);
+    }
+    // auction requests modify the state of other users, so they cannot be simulated
+    for request in requests.iter() {
+        if request.request_type > RequestType::Repay as u32
+            && request.request_type < RequestType::WithdrawAll as u32
+        {
+            panic_with_error!(e, &PoolError::BadRequest);
+        }
+    }
+    let mut pool = Pool::load(e);
+    let mut from_state = User::load(e, from);
+
+    let prev_positions_count = from_state.positions.effective_count();
+
+    let actions = build_actions_from_request(e, &mut pool, &mut from_state, None, false, requests);
+
+    validate_submit(
+        e,
+        &mut pool,
+        &from_state,
+        prev_positions_count,
+        actions.check_health,
+        actions.check_borrow_health,
+    );
+
+    let position_data = PositionData::calculate_from_positions(e, &mut pool, &from_state.positions);
+    (from_state.positions, position_data.as_health_data(e))
+}
+
 /// Validate submit results in a valid state for the pool and user.
 ///
 /// ### Arguments
@@ -139,15 +555,22 @@ pub fn execute_submit_with_flash_loan(
 /// * from_state - The user state for "from"
 /// * prev_positions_count - The initial number of positions for "from"
 /// * check_health - A bool indicating if the health factor should be checked
+/// * check_borrow_health - A bool indicating if the health factor must meet the borrow buffer
 fn validate_submit(
     e: &Env,
     pool: &mut Pool,
     from_state: &User,
     prev_positions_count: u32,
     check_health: bool,
+    check_borrow_health: bool,
 ) {
     // Verify max positions haven't been exceeded
-    pool.require_under_max(e, &from_state.positions, prev_positions_count);
+    pool.require_under_max(
+        e,
+        &from_state.address,
+        &from_state.positions,
+        prev_positions_count,
+    );
 
     // Verify "from" does not have an active liquidation post requests
     if storage::has_auction(
@@ -159,10 +582,14 @@ fn validate_submit(
     }
 
     // panics if the new positions set does not meet the health factor requirement
-    // min is 1.0000100 to prevent rounding errors
+    // min is 1.0000100 to prevent rounding errors, or the borrow buffer if borrowing
     if check_health && from_state.has_liabilities() {
+        let mut min_health_factor = 1_0000100;
+        if check_borrow_health {
+            min_health_factor = min_health_factor.max(storage::get_min_borrow_health_buffer(e));
+        }
         let position_data = PositionData::calculate_from_positions(e, pool, &from_state.positions);
-        if position_data.is_hf_under(e, 1_0000100) {
+        if position_data.is_hf_under(e, min_health_factor) {
             panic_with_error!(e, PoolError::InvalidHf);
         } else if position_data.collateral_base < pool.config.min_collateral {
             panic_with_error!(e, PoolError::MinCollateralNotMet);
@@ -222,12 +649,14 @@ fn handle_transfers(e: &Env, actions: &Actions, spender: &Address, to: &Address)
 #[cfg(test)]
 mod tests {
     use crate::{
-        storage::{self, PoolConfig},
+        constants::SCALAR_7,
+        storage::{self, PoolConfig, ReserveEmissionData},
         testutils, AuctionData, RequestType,
     };
 
     use super::*;
     use sep_40_oracle::testutils::Asset;
+    use soroban_fixed_point_math::SorobanFixedPoint;
     use soroban_sdk::{
         map,
         testutils::{Address as _, Ledger, LedgerInfo},
@@ -287,6 +716,8 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -331,7 +762,7 @@ mod tests {
     }
 
     #[test]
-    fn test_submit_use_allowance() {
+    fn test_simulate_submit() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -350,7 +781,6 @@ mod tests {
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
         let frodo = Address::generate(&e);
-        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
@@ -362,7 +792,7 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&frodo, &15_0000000);
+        underlying_0_client.mint(&samwise, &16_0000000);
 
         oracle_client.set_data(
             &bombadil,
@@ -382,7 +812,9 @@ mod tests {
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 4,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
@@ -390,6 +822,7 @@ mod tests {
 
             let pre_pool_balance_0 = underlying_0_client.balance(&pool);
             let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+            let pre_reserve_data_1 = storage::get_res_data(&e, &underlying_1);
 
             let requests = vec![
                 &e,
@@ -400,81 +833,49 @@ mod tests {
                 },
                 Request {
                     request_type: RequestType::Borrow as u32,
-                    address: underlying_1,
+                    address: underlying_1.clone(),
                     amount: 1_5000000,
                 },
             ];
-            underlying_0_client.approve(&frodo, &pool, &15_0000000, &e.ledger().sequence());
-            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 15_0000000);
-
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
-
-            assert_eq!(positions.liabilities.len(), 1);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
-            assert_eq!(positions.liabilities.get_unchecked(1), 1_4999983);
-
-            assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 + 15_0000000
-            );
-            assert_eq!(underlying_1_client.allowance(&frodo, &pool), 0);
+            let (sim_positions, sim_health) = simulate_submit(&e, &samwise, requests.clone());
+
+            // nothing was written or transferred
+            let stored_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(stored_positions.collateral.len(), 0);
+            assert_eq!(stored_positions.liabilities.len(), 0);
+            assert_eq!(underlying_0_client.balance(&pool), pre_pool_balance_0);
+            assert_eq!(underlying_1_client.balance(&pool), pre_pool_balance_1);
+            assert_eq!(underlying_0_client.balance(&samwise), 16_0000000);
             assert_eq!(
-                underlying_1_client.balance(&pool),
-                pre_pool_balance_1 - 1_5000000
+                storage::get_res_data(&e, &underlying_1).d_supply,
+                pre_reserve_data_1.d_supply
             );
 
-            assert_eq!(underlying_0_client.balance(&frodo), 0);
-            assert_eq!(underlying_1_client.balance(&merry), 1_5000000);
-        });
-
-        underlying_0_client.mint(&frodo, &15_0000000);
-
-        e.as_contract(&pool, || {
-            e.mock_all_auths_allowing_non_root_auth();
-            storage::set_pool_config(&e, &pool_config);
-
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-
-            let requests = vec![
-                &e,
-                Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_0.clone(),
-                    amount: 15_0000000,
-                },
-                Request {
-                    request_type: RequestType::Borrow as u32,
-                    address: underlying_0,
-                    amount: 1_0000000,
-                },
-            ];
-            underlying_0_client.approve(&frodo, &pool, &14_0000000, &e.ledger().sequence());
-            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 14_0000000);
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
-
-            // new_allowance = old_allowance - (deposit - borrow)
-            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 0);
-
-            assert_eq!(positions.liabilities.len(), 2);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-
-            assert_eq!(positions.collateral.get_unchecked(0), 29_9999768);
-            assert_eq!(positions.liabilities.get_unchecked(1), 1_4999983);
-
+            // simulation matches the real submit
+            let positions = execute_submit(&e, &samwise, &samwise, &frodo, requests, false);
+            assert_eq!(sim_positions.collateral, positions.collateral);
+            assert_eq!(sim_positions.liabilities, positions.liabilities);
+            assert_eq!(sim_positions.supply, positions.supply);
+
+            let mut pool_state = Pool::load(&e);
+            let position_data =
+                PositionData::calculate_from_positions(&e, &mut pool_state, &positions);
+            assert_eq!(sim_health.collateral, position_data.collateral_base);
+            assert_eq!(sim_health.liability, position_data.liability_base);
             assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 + 14_0000000
+                sim_health.health_factor,
+                position_data.collateral_base.fixed_div_floor(
+                    &e,
+                    &position_data.liability_base,
+                    &SCALAR_7
+                )
             );
-
-            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
+            assert!(sim_health.health_factor > SCALAR_7);
         });
     }
 
     #[test]
-    fn test_submit_use_allowance_over_repay() {
+    fn test_simulate_submit_checkpoints_emissions() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -492,106 +893,77 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
-        let frodo = Address::generate(&e);
-        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.last_time = 600;
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
-        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
-
-        underlying_0_client.mint(&frodo, &15_0000000);
-
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
-            &vec![
-                &e,
-                Asset::Stellar(underlying_0.clone()),
-                Asset::Stellar(underlying_1.clone()),
-            ],
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
 
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 4,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
-            e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
-
-            let requests = vec![
+            storage::set_res_emis_data(
                 &e,
-                Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_0,
-                    amount: 15_0000000,
-                },
-                Request {
-                    request_type: RequestType::Borrow as u32,
-                    address: underlying_1.clone(),
-                    amount: 1_5000000,
+                &1,
+                &ReserveEmissionData {
+                    expiration: 1000,
+                    eps: 1_0000000,
+                    index: 0,
+                    last_time: 500,
                 },
-            ];
-            underlying_0_client.approve(&frodo, &pool, &15_0000000, &e.ledger().sequence());
-            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 15_0000000);
-
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
-
-            assert_eq!(positions.liabilities.len(), 1);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
-            assert_eq!(positions.liabilities.get_unchecked(1), 1_4999983);
-
-            underlying_1_client.mint(&frodo, &1_6000000);
-
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+            );
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Repay as u32,
-                    address: underlying_1,
-                    amount: 1_6000000,
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
                 },
             ];
-            underlying_1_client.approve(&frodo, &pool, &1_5000001, &e.ledger().sequence());
-            assert_eq!(underlying_1_client.allowance(&frodo, &pool), 1_5000001);
-            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
-
-            // new_allowance = old_allowance - repay
-            assert_eq!(underlying_1_client.allowance(&frodo, &pool), 0);
-
-            assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-
-            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
+            simulate_submit(&e, &samwise, requests);
 
+            // the positions and reserve data are not stored
+            let stored_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(stored_positions.collateral.len(), 0);
             assert_eq!(
-                underlying_1_client.balance(&pool),
-                pre_pool_balance_1 + 1_5000001
+                storage::get_res_data(&e, &underlying_0).b_supply,
+                100_0000000
             );
 
-            assert_eq!(underlying_1_client.balance(&frodo), 999999);
+            // the reserve token's and samwise's emissions are checkpointed
+            let res_emis_data = storage::get_res_emis_data(&e, &1).unwrap();
+            assert_eq!(res_emis_data.index, 1_0000000);
+            assert_eq!(res_emis_data.last_time, 600);
+            let user_emis_data = storage::get_user_emissions(&e, &samwise, &1).unwrap();
+            assert_eq!(user_emis_data.index, 1_0000000);
+            assert_eq!(user_emis_data.accrued, 0);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #9)")]
-    fn test_submit_use_allowance_no_allowance() {
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_simulate_submit_requires_healthy() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -609,12 +981,10 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
-        let frodo = Address::generate(&e);
-        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
@@ -622,8 +992,6 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&frodo, &16_0000000);
-
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
@@ -643,11 +1011,12 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
-
         e.as_contract(&pool, || {
-            e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
+
             let requests = vec![
                 &e,
                 Request {
@@ -658,21 +1027,22 @@ mod tests {
                 Request {
                     request_type: RequestType::Borrow as u32,
                     address: underlying_1,
-                    amount: 1_5000000,
+                    amount: 3_0000000,
                 },
             ];
-
-            execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+            simulate_submit(&e, &samwise, requests);
         });
     }
+
     #[test]
-    fn test_submit_no_liabilities_does_not_load_oracle() {
+    #[should_panic(expected = "Error(Contract, #1228)")]
+    fn test_submit_borrow_with_stale_price_panics() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
 
         e.ledger().set(LedgerInfo {
-            timestamp: 600,
+            timestamp: 10000,
             protocol_version: 22,
             sequence_number: 1234,
             network_id: Default::default(),
@@ -682,83 +1052,6 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
-        let bombadil = Address::generate(&e);
-        let samwise = Address::generate(&e);
-        let frodo = Address::generate(&e);
-        let pool = testutils::create_pool(&e);
-        let oracle = Address::generate(&e); // will fail if executed against
-
-        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
-        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
-
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
-        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
-
-        underlying_0_client.mint(&frodo, &16_0000000);
-        underlying_1_client.mint(&frodo, &10_0000000);
-
-        let pool_config = PoolConfig {
-            oracle,
-            min_collateral: 1_0000000,
-            bstop_rate: 0_1000000,
-            status: 0,
-            max_positions: 2,
-        };
-        e.as_contract(&pool, || {
-            e.mock_all_auths_allowing_non_root_auth();
-            storage::set_pool_config(&e, &pool_config);
-
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
-
-            let requests = vec![
-                &e,
-                Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_0,
-                    amount: 15_0000000,
-                },
-                // force check_health to true
-                Request {
-                    request_type: RequestType::Borrow as u32,
-                    address: underlying_1.clone(),
-                    amount: 1_5000000,
-                },
-                Request {
-                    request_type: RequestType::Repay as u32,
-                    address: underlying_1,
-                    amount: 1_5000001,
-                },
-            ];
-            let positions = execute_submit(&e, &samwise, &frodo, &frodo, requests, false);
-
-            assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
-
-            assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 + 15_0000000
-            );
-            assert_eq!(
-                underlying_1_client.balance(&pool),
-                pre_pool_balance_1 + 1 // repayment rounded against user
-            );
-
-            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
-            assert_eq!(underlying_1_client.balance(&frodo), 10_0000000 - 1);
-        });
-    }
-
-    #[test]
-    #[should_panic(expected = "Error(Contract, #1205)")]
-    fn test_submit_requires_healhty() {
-        let e = Env::default();
-        e.mock_all_auths();
-
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
         let frodo = Address::generate(&e);
@@ -766,16 +1059,19 @@ mod tests {
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.last_time = 10000;
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
         let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.last_time = 10000;
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&frodo, &16_0000000);
-
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
@@ -787,39 +1083,33 @@ mod tests {
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+        // prices are 2 hours old
+        oracle_client.set_price(&vec![&e, 1_0000000, 5_0000000], &(10000 - 7200));
 
-        e.ledger().set(LedgerInfo {
-            timestamp: 600,
-            protocol_version: 22,
-            sequence_number: 1234,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 2,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 3600,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 10_0000000)],
+            supply: map![&e],
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
 
             let requests = vec![
                 &e,
-                Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_0,
-                    amount: 15_0000000,
-                },
                 Request {
                     request_type: RequestType::Borrow as u32,
                     address: underlying_1,
-                    amount: 1_7500000,
+                    amount: 1_0000000,
                 },
             ];
             execute_submit(&e, &samwise, &frodo, &merry, requests, false);
@@ -827,14 +1117,13 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_submit_from_is_not_self() {
+    fn test_submit_withdraw_with_stale_price() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
 
         e.ledger().set(LedgerInfo {
-            timestamp: 600,
+            timestamp: 10000,
             protocol_version: 22,
             sequence_number: 1234,
             network_id: Default::default(),
@@ -846,50 +1135,73 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.last_time = 10000;
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&samwise, &16_0000000);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.last_time = 10000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
-            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+        // prices are 2 hours old
+        oracle_client.set_price(&vec![&e, 1_0000000, 5_0000000], &(10000 - 7200));
 
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 2,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 3600,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e],
+            supply: map![&e, (0, 10_0000000)],
         };
         e.as_contract(&pool, || {
-            e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::SupplyCollateral as u32,
+                    request_type: RequestType::Withdraw as u32,
                     address: underlying_0,
-                    amount: 15_0000000,
+                    amount: 5_0000000,
                 },
             ];
-            execute_submit(&e, &pool, &samwise, &samwise, requests, false);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+
+            assert_eq!(positions.supply.get_unchecked(0), 5_0000000);
+            assert_eq!(underlying_0_client.balance(&merry), 5_0000000);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_submit_spender_is_not_self() {
+    fn test_submit_use_allowance() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -907,6 +1219,8 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
@@ -914,43 +1228,125 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&samwise, &16_0000000);
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &15_0000000);
 
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
-            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
 
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 2,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
 
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
             let requests = vec![
                 &e,
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_0,
+                    address: underlying_0.clone(),
                     amount: 15_0000000,
                 },
-            ];
-            execute_submit(&e, &samwise, &pool, &samwise, requests, false);
-        });
-    }
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 1_5000000,
+                },
+            ];
+            underlying_0_client.approve(&frodo, &pool, &15_0000000, &e.ledger().sequence());
+            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 15_0000000);
+
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+
+            assert_eq!(positions.liabilities.len(), 1);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
+            assert_eq!(positions.liabilities.get_unchecked(1), 1_4999983);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 15_0000000
+            );
+            assert_eq!(underlying_1_client.allowance(&frodo, &pool), 0);
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 - 1_5000000
+            );
+
+            assert_eq!(underlying_0_client.balance(&frodo), 0);
+            assert_eq!(underlying_1_client.balance(&merry), 1_5000000);
+        });
+
+        underlying_0_client.mint(&frodo, &15_0000000);
+
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_0,
+                    amount: 1_0000000,
+                },
+            ];
+            underlying_0_client.approve(&frodo, &pool, &14_0000000, &e.ledger().sequence());
+            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 14_0000000);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+
+            // new_allowance = old_allowance - (deposit - borrow)
+            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 0);
+
+            assert_eq!(positions.liabilities.len(), 2);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+
+            assert_eq!(positions.collateral.get_unchecked(0), 29_9999768);
+            assert_eq!(positions.liabilities.get_unchecked(1), 1_4999983);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 14_0000000
+            );
+
+            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
+        });
+    }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1200)")]
-    fn test_submit_to_is_not_self() {
+    fn test_submit_with_funders() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -968,6 +1364,9 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pippin = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
@@ -975,43 +1374,93 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&samwise, &16_0000000);
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&merry, &10_0000000);
+        underlying_1_client.mint(&pippin, &5_0000000);
+        underlying_1_client.mint(&frodo, &2_0000000);
 
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
-            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
 
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 2,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
 
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
             let requests = vec![
                 &e,
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_0,
-                    amount: 15_0000000,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying_1.clone(),
+                    amount: 5_0000000,
+                },
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying_1.clone(),
+                    amount: 2_0000000,
                 },
             ];
-            execute_submit(&e, &samwise, &samwise, &pool, requests, false);
+            underlying_0_client.approve(&merry, &pool, &10_0000000, &e.ledger().sequence());
+            underlying_1_client.approve(&pippin, &pool, &5_0000000, &e.ledger().sequence());
+            underlying_1_client.approve(&frodo, &pool, &2_0000000, &e.ledger().sequence());
+
+            // merry funds the first request and pippin the second, frodo funds the rest
+            let funders = map![&e, (0, merry.clone()), (1, pippin.clone())];
+            let positions =
+                execute_submit_with_funders(&e, &samwise, &frodo, &samwise, requests, funders);
+
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 1);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 10_0000000
+            );
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 + 7_0000000
+            );
+            assert_eq!(underlying_0_client.balance(&merry), 0);
+            assert_eq!(underlying_0_client.allowance(&merry, &pool), 0);
+            assert_eq!(underlying_1_client.balance(&pippin), 0);
+            assert_eq!(underlying_1_client.allowance(&pippin, &pool), 0);
+            assert_eq!(underlying_1_client.balance(&frodo), 0);
+            assert_eq!(underlying_1_client.allowance(&frodo, &pool), 0);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1208)")]
-    fn test_submit_over_max_positions() {
+    fn test_submit_with_funders_over_repay() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -1029,6 +1478,7 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
@@ -1041,7 +1491,7 @@ mod tests {
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
         underlying_0_client.mint(&samwise, &10_0000000);
-        underlying_1_client.mint(&samwise, &10_0000000);
+        underlying_1_client.mint(&merry, &2_0000000);
 
         oracle_client.set_data(
             &bombadil,
@@ -1061,37 +1511,87 @@ mod tests {
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 3,
-        };
-        let user_positions = Positions {
-            liabilities: map![&e, (0, 1_0000000)],
-            collateral: map![&e, (0, 15_0000000)],
-            supply: map![&e],
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
-            storage::set_user_positions(&e, &samwise, &user_positions);
 
-            let requests = vec![
+            let setup_requests = vec![
                 &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
                 Request {
                     request_type: RequestType::Borrow as u32,
                     address: underlying_1.clone(),
-                    amount: 1_5000000,
+                    amount: 1_0000000,
                 },
+            ];
+            execute_submit(&e, &samwise, &samwise, &samwise, setup_requests, false);
+            assert_eq!(underlying_1_client.balance(&samwise), 1_0000000);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            // merry funds a repayment larger than samwise's liability
+            let requests = vec![
+                &e,
                 Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_1,
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1.clone(),
+                    amount: 2_0000000,
+                },
+            ];
+            underlying_1_client.approve(&merry, &pool, &2_0000000, &e.ledger().sequence());
+            let funders = map![&e, (0, merry.clone())];
+            let positions =
+                execute_submit_with_funders(&e, &samwise, &samwise, &samwise, requests, funders);
+
+            assert_eq!(positions.liabilities.len(), 0);
+            let repaid = underlying_1_client.balance(&pool) - pre_pool_balance_1;
+            assert!(repaid >= 1_0000000);
+            assert!(repaid < 2_0000000);
+            // merry only sent the repaid amount, and samwise did not receive the excess
+            assert_eq!(underlying_1_client.balance(&merry), 2_0000000 - repaid);
+            assert_eq!(underlying_1_client.balance(&samwise), 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_with_funders_borrow_request() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        e.as_contract(&pool, || {
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_0.clone(),
                     amount: 1_0000000,
                 },
             ];
-            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            let funders = map![&e, (0, merry.clone())];
+            execute_submit_with_funders(&e, &samwise, &samwise, &samwise, requests, funders);
         });
     }
 
     #[test]
-    fn test_submit_over_max_positions_decrease_allowed() {
+    fn test_submit_use_allowance_over_repay() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -1109,6 +1609,8 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
@@ -1120,8 +1622,7 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&samwise, &10_0000000);
-        underlying_1_client.mint(&samwise, &10_0000000);
+        underlying_0_client.mint(&frodo, &15_0000000);
 
         oracle_client.set_data(
             &bombadil,
@@ -1141,59 +1642,75 @@ mod tests {
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 2,
-        };
-        let user_positions = Positions {
-            liabilities: map![&e, (0, 1_0000000), (1, 1_0000000)],
-            collateral: map![&e, (0, 15_0000000), (1, 15_0000000)],
-            supply: map![&e],
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
-            storage::set_user_positions(&e, &samwise, &user_positions);
-
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
 
             let requests = vec![
                 &e,
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
                     address: underlying_0,
-                    amount: 1_0000000,
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
                 },
+            ];
+            underlying_0_client.approve(&frodo, &pool, &15_0000000, &e.ledger().sequence());
+            assert_eq!(underlying_0_client.allowance(&frodo, &pool), 15_0000000);
+
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
+
+            assert_eq!(positions.liabilities.len(), 1);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
+            assert_eq!(positions.liabilities.get_unchecked(1), 1_4999983);
+
+            underlying_1_client.mint(&frodo, &1_6000000);
+
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            let requests = vec![
+                &e,
                 Request {
                     request_type: RequestType::Repay as u32,
                     address: underlying_1,
-                    amount: 2_0000000,
+                    amount: 1_6000000,
                 },
             ];
-            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            underlying_1_client.approve(&frodo, &pool, &1_5000001, &e.ledger().sequence());
+            assert_eq!(underlying_1_client.allowance(&frodo, &pool), 1_5000001);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, true);
 
-            assert_eq!(result.liabilities.len(), 1);
-            assert_eq!(result.collateral.len(), 2);
+            // new_allowance = old_allowance - repay
+            assert_eq!(underlying_1_client.allowance(&frodo, &pool), 0);
+
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+
+            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
 
-            assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 + 1_0000000
-            );
             assert_eq!(
                 underlying_1_client.balance(&pool),
-                pre_pool_balance_1 + 1_0000012
+                pre_pool_balance_1 + 1_5000001
             );
 
-            assert_eq!(underlying_0_client.balance(&samwise), 9_0000000);
-            assert_eq!(
-                underlying_1_client.balance(&samwise),
-                10_0000000 - 1_0000012
-            );
+            assert_eq!(underlying_1_client.balance(&frodo), 999999);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1212)")]
-    fn test_submit_with_ongoing_liquidation_blocked() {
+    #[should_panic(expected = "Error(Contract, #9)")]
+    fn test_submit_use_allowance_no_allowance() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -1211,6 +1728,8 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
@@ -1218,12 +1737,11 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&samwise, &10_0000000);
-        underlying_1_client.mint(&samwise, &10_0000000);
+        underlying_0_client.mint(&frodo, &16_0000000);
 
         oracle_client.set_data(
             &bombadil,
@@ -1238,48 +1756,38 @@ mod tests {
         );
         oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
 
-        let auction_data = AuctionData {
-            bid: map![&e, (underlying_0.clone(), 2_0000000)],
-            lot: map![&e, (underlying_1.clone(), 2_0000000),],
-            block: 1200,
-        };
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
-        let user_positions = Positions {
-            liabilities: map![&e, (0, 5_0000000)],
-            collateral: map![&e, (1, 6_0000000)],
-            supply: map![&e],
-        };
+
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
-            storage::set_user_positions(&e, &samwise, &user_positions);
-            storage::set_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise,
-                &auction_data,
-            );
-
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Repay as u32,
+                    request_type: RequestType::SupplyCollateral as u32,
                     address: underlying_0,
-                    amount: 4_0000000,
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 1_5000000,
                 },
             ];
-            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+
+            execute_submit(&e, &samwise, &frodo, &merry, requests, true);
         });
     }
-
     #[test]
-    fn test_submit_with_ongoing_liquidation_works_if_canceled() {
+    fn test_submit_no_liabilities_does_not_load_oracle() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -1297,8 +1805,9 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
         let pool = testutils::create_pool(&e);
-        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+        let oracle = Address::generate(&e); // will fail if executed against
 
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
@@ -1308,93 +1817,67 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&samwise, &10_0000000);
-        underlying_1_client.mint(&samwise, &10_0000000);
-
-        oracle_client.set_data(
-            &bombadil,
-            &Asset::Other(Symbol::new(&e, "USD")),
-            &vec![
-                &e,
-                Asset::Stellar(underlying_0.clone()),
-                Asset::Stellar(underlying_1.clone()),
-            ],
-            &7,
-            &300,
-        );
-        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+        underlying_0_client.mint(&frodo, &16_0000000);
+        underlying_1_client.mint(&frodo, &10_0000000);
 
-        let auction_data = AuctionData {
-            bid: map![&e, (underlying_0.clone(), 2_0000000)],
-            lot: map![&e, (underlying_1.clone(), 2_0000000),],
-            block: 1200,
-        };
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
-        };
-        let user_positions = Positions {
-            liabilities: map![&e, (0, 5_0000000)],
-            collateral: map![&e, (1, 6_0000000)],
-            supply: map![&e],
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
-            storage::set_user_positions(&e, &samwise, &user_positions);
-            storage::set_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise,
-                &auction_data,
-            );
 
             let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Repay as u32,
+                    request_type: RequestType::SupplyCollateral as u32,
                     address: underlying_0,
-                    amount: 4_0000000,
+                    amount: 15_0000000,
+                },
+                // force check_health to true
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
                 },
                 Request {
-                    request_type: RequestType::DeleteLiquidationAuction as u32,
-                    address: samwise.clone(),
-                    amount: 0,
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1,
+                    amount: 1_5000001,
                 },
             ];
-            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
-
-            assert_eq!(result.liabilities.len(), 1);
-            assert_eq!(result.collateral.len(), 1);
+            let positions = execute_submit(&e, &samwise, &frodo, &frodo, requests, false);
 
-            assert_eq!(result.collateral.get_unchecked(1), 6_0000000);
-            assert_eq!(result.liabilities.get_unchecked(0), 1_0000046);
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            assert_eq!(positions.collateral.get_unchecked(0), 14_9999884);
 
             assert_eq!(
                 underlying_0_client.balance(&pool),
-                pre_pool_balance_0 + 4_0000000
+                pre_pool_balance_0 + 15_0000000
             );
             assert_eq!(
-                underlying_0_client.balance(&samwise),
-                10_0000000 - 4_0000000
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 + 1 // repayment rounded against user
             );
 
-            assert!(!storage::has_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise
-            ));
+            assert_eq!(underlying_0_client.balance(&frodo), 1_0000000);
+            assert_eq!(underlying_1_client.balance(&frodo), 10_0000000 - 1);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1224)")]
-    fn test_submit_under_min_collateral_fails() {
+    fn test_submit_at_max_requests() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -1410,6 +1893,99 @@ mod tests {
             max_entry_ttl: 3110400,
         });
 
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &100_0000000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+
+            let mut requests: Vec<Request> = Vec::new(&e);
+            for _ in 0..MAX_SUBMIT_REQUESTS {
+                requests.push_back(Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying_0.clone(),
+                    amount: 1_0000000,
+                });
+            }
+            let positions = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+
+            assert_eq!(positions.supply.len(), 1);
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 32_0000000
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_over_max_requests() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let oracle = Address::generate(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &100_0000000);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let mut requests: Vec<Request> = Vec::new(&e);
+            for _ in 0..MAX_SUBMIT_REQUESTS + 1 {
+                requests.push_back(Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying_0.clone(),
+                    amount: 1_0000000,
+                });
+            }
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_submit_requires_healhty() {
+        let e = Env::default();
+        e.mock_all_auths();
+
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
         let frodo = Address::generate(&e);
@@ -1440,15 +2016,26 @@ mod tests {
         );
         oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
 
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
-            e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
 
             let requests = vec![
@@ -1456,25 +2043,22 @@ mod tests {
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
                     address: underlying_0,
-                    amount: 0_9000000,
+                    amount: 15_0000000,
                 },
                 Request {
                     request_type: RequestType::Borrow as u32,
                     address: underlying_1,
-                    amount: 0_01000000,
+                    amount: 1_7500000,
                 },
             ];
             execute_submit(&e, &samwise, &frodo, &merry, requests, false);
         });
     }
 
-    /***** submit_with_flash_loan *****/
-
     #[test]
-    fn test_submit_with_flash_loan() {
+    fn test_submit_borrow_at_min_borrow_health_buffer() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
-        e.mock_all_auths_allowing_non_root_auth();
+        e.mock_all_auths();
 
         e.ledger().set(LedgerInfo {
             timestamp: 600,
@@ -1489,22 +2073,25 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
-
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
-        reserve_config.max_util = 9500000;
-        reserve_data.b_supply = 100_0000000;
-        reserve_data.d_supply = 50_0000000;
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 600;
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
+        underlying_0_client.mint(&frodo, &15_0000000);
+
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
@@ -1523,67 +2110,42 @@ mod tests {
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 4,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_min_borrow_health_buffer(&e, &1_2500000);
 
-            underlying_1_client.mint(&samwise, &25_0000000);
-            underlying_1_client.approve(&samwise, &pool, &100_0000000, &10000);
-
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
-
-            // pool has 100 supplied and 50 borrowed for asset_0
-            // -> max util is 95%
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_0,
-                amount: 25_0000000,
-            };
-
+            // 11.25 effective collateral and 9 effective liabilities
             let requests = vec![
                 &e,
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
                     address: underlying_1,
-                    amount: 25_0000000,
+                    amount: 1_3500000,
                 },
             ];
-            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+            assert_eq!(positions.liabilities.get_unchecked(1), 1_3500000);
 
-            assert_eq!(positions.liabilities.len(), 1);
-            assert_eq!(positions.collateral.len(), 1);
-            assert_eq!(positions.supply.len(), 0);
-            assert_eq!(positions.collateral.get_unchecked(1), 249999807);
-            // actual is 24.999979375 - rounds up
-            assert_eq!(positions.liabilities.get_unchecked(0), 249999794);
-
-            assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 - 25_0000000
-            );
-            assert_eq!(
-                underlying_1_client.balance(&pool),
-                pre_pool_balance_1 + 25_0000000
-            );
-
-            assert_eq!(underlying_0_client.balance(&samwise), 25_0000000);
-            assert_eq!(underlying_1_client.balance(&samwise), 0);
-
-            // check allowance is used
-            assert_eq!(
-                underlying_1_client.allowance(&samwise, &pool),
-                100_0000000 - 25_0000000
-            );
-        });
-    }
+            let position_data =
+                PositionData::calculate_from_positions(&e, &mut Pool::load(&e), &positions);
+            assert_eq!(position_data.as_health_factor(&e), 1_2500000);
+        });
+    }
 
     #[test]
-    fn test_submit_with_flash_loan_process_flash_loan_first() {
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_submit_borrow_under_min_borrow_health_buffer() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
-        e.mock_all_auths_allowing_non_root_auth();
+        e.mock_all_auths();
 
         e.ledger().set(LedgerInfo {
             timestamp: 600,
@@ -1598,22 +2160,25 @@ mod tests {
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
-
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
-        reserve_config.max_util = 9500000;
-        reserve_data.b_supply = 100_0000000;
-        reserve_data.d_supply = 50_0000000;
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.last_time = 600;
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
+        underlying_0_client.mint(&frodo, &15_0000000);
+
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
@@ -1632,90 +2197,54 @@ mod tests {
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 4,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_min_borrow_health_buffer(&e, &1_2500000);
 
-            underlying_0_client.mint(&samwise, &1_0000000);
-            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
-
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
-
-            // pool has 100 supplied and 50 borrowed for asset_0
-            // -> max util is 95%
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_0.clone(),
-                amount: 25_0000000,
-            };
-
+            // 11.25 effective collateral and 9 effective liabilities
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Repay as u32,
+                    request_type: RequestType::SupplyCollateral as u32,
                     address: underlying_0,
-                    amount: 25_0000010,
+                    amount: 15_0000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 1_3500001,
                 },
             ];
-            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
-
-            assert_eq!(positions.liabilities.len(), 0);
-            assert_eq!(positions.collateral.len(), 0);
-            assert_eq!(positions.supply.len(), 0);
-
-            assert_eq!(underlying_0_client.balance(&pool), pre_pool_balance_0 + 1,);
-            assert_eq!(underlying_1_client.balance(&pool), pre_pool_balance_1,);
-
-            // rounding causes 1 stroops to be lost
-            assert_eq!(underlying_0_client.balance(&samwise), 0_9999999);
-            assert_eq!(underlying_1_client.balance(&samwise), 0);
-
-            // check allowance is used
-            assert_eq!(
-                underlying_0_client.allowance(&samwise, &pool),
-                100_0000000 - 25_0000001
-            );
+            execute_submit(&e, &samwise, &frodo, &merry, requests, false);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1205)")]
-    fn test_submit_with_flash_loan_checks_health() {
+    fn test_submit_withdraw_collateral_all() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
-        e.mock_all_auths_allowing_non_root_auth();
-
-        e.ledger().set(LedgerInfo {
-            timestamp: 600,
-            protocol_version: 22,
-            sequence_number: 1234,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
+        e.mock_all_auths();
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
-
-        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
-        reserve_config.max_util = 9500000;
-        reserve_data.b_supply = 100_0000000;
-        reserve_data.d_supply = 50_0000000;
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
+        underlying_0_client.mint(&pool, &20_0000000);
+
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
@@ -1729,75 +2258,79 @@ mod tests {
         );
         oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
 
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 4,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
 
-            underlying_1_client.mint(&samwise, &25_0000000);
-            underlying_1_client.approve(&samwise, &pool, &100_0000000, &10000);
-
-            // pool has 100 supplied and 50 borrowed for asset_0
-            // -> max util is 95%
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_0,
-                amount: 25_0000000,
-            };
-
+            let pre_pool_balance = underlying_0_client.balance(&pool);
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_1,
-                    amount: 8_0000000,
+                    request_type: RequestType::WithdrawCollateralAll as u32,
+                    address: underlying_0.clone(),
+                    amount: 0,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let positions = execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(
+                storage::get_user_positions(&e, &samwise).collateral.len(),
+                0
+            );
+            let withdrawn = pre_pool_balance - underlying_0_client.balance(&pool);
+            assert!(withdrawn >= 15_0000000);
+            assert_eq!(underlying_0_client.balance(&merry), withdrawn);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1207)")]
-    fn test_submit_with_flash_loan_checks_max_util() {
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_submit_withdraw_collateral_all_requires_healthy() {
         let e = Env::default();
-        e.cost_estimate().budget().reset_unlimited();
-        e.mock_all_auths_allowing_non_root_auth();
-
-        e.ledger().set(LedgerInfo {
-            timestamp: 600,
-            protocol_version: 22,
-            sequence_number: 1234,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 10,
-            min_persistent_entry_ttl: 10,
-            max_entry_ttl: 3110400,
-        });
+        e.mock_all_auths();
 
         let bombadil = Address::generate(&e);
         let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
-
-        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
-        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
-        reserve_config.max_util = 9500000;
-        reserve_data.b_supply = 100_0000000;
-        reserve_data.d_supply = 50_0000000;
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
+        underlying_0_client.mint(&pool, &20_0000000);
+
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
@@ -1811,42 +2344,49 @@ mod tests {
         );
         oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
 
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 4,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
         };
         e.as_contract(&pool, || {
             storage::set_pool_config(&e, &pool_config);
-
-            underlying_1_client.mint(&samwise, &50_0000000);
-            underlying_1_client.approve(&samwise, &pool, &100_0000000, &10000);
-
-            // pool has 100 supplied and 50 borrowed for asset_0
-            // -> max util is 95%
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_0,
-                amount: 46_0000000,
-            };
+            storage::set_user_positions(&e, &samwise, &user_positions);
 
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_1,
-                    amount: 50_0000000,
+                    request_type: RequestType::WithdrawCollateralAll as u32,
+                    address: underlying_0,
+                    amount: 0,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            execute_submit(&e, &samwise, &frodo, &merry, requests, false);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1208)")]
-    fn test_submit_with_flash_loan_over_max_positions() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_from_is_not_self() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -1867,70 +2407,49 @@ mod tests {
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
-
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
 
-        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
-        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
-        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
-
-        underlying_0_client.mint(&samwise, &10_0000000);
-        underlying_0_client.approve(&samwise, &pool, &10_0000000, &100000);
-        underlying_1_client.mint(&samwise, &10_0000000);
-        underlying_1_client.approve(&samwise, &pool, &10_0000000, &100000);
+        underlying_0_client.mint(&samwise, &16_0000000);
 
         oracle_client.set_data(
             &bombadil,
             &Asset::Other(Symbol::new(&e, "USD")),
-            &vec![
-                &e,
-                Asset::Stellar(underlying_0.clone()),
-                Asset::Stellar(underlying_1.clone()),
-            ],
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
             &7,
             &300,
         );
-        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
 
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 3,
-        };
-        let user_positions = Positions {
-            liabilities: map![&e, (1, 1_0000000)],
-            collateral: map![&e, (0, 15_0000000)],
-            supply: map![&e],
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
-            storage::set_user_positions(&e, &samwise, &user_positions);
 
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_0,
-                amount: 1_0000000,
-            };
             let requests = vec![
                 &e,
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
-                    address: underlying_1,
-                    amount: 2_0000000,
-                },
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            execute_submit(&e, &pool, &samwise, &samwise, requests, false);
         });
     }
 
     #[test]
-    fn test_submit_with_flash_loan_over_max_positions_decrease_allowed() {
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_spender_is_not_self() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -1951,7 +2470,131 @@ mod tests {
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &pool, &samwise, requests, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_to_is_not_self() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 15_0000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &samwise, &pool, requests, false);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1208)")]
+    fn test_submit_over_max_positions() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
@@ -1962,9 +2605,7 @@ mod tests {
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
         underlying_0_client.mint(&samwise, &10_0000000);
-        underlying_0_client.approve(&samwise, &pool, &10_0000000, &100000);
         underlying_1_client.mint(&samwise, &10_0000000);
-        underlying_1_client.approve(&samwise, &pool, &10_0000000, &100000);
 
         oracle_client.set_data(
             &bombadil,
@@ -1984,11 +2625,13 @@ mod tests {
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 2,
+            max_positions: 3,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
-            liabilities: map![&e, (0, 1_0000000), (1, 1_0000000)],
-            collateral: map![&e, (0, 15_0000000), (1, 15_0000000)],
+            liabilities: map![&e, (0, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
             supply: map![&e],
         };
         e.as_contract(&pool, || {
@@ -1996,47 +2639,25 @@ mod tests {
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &samwise, &user_positions);
 
-            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
-            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
-
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_0,
-                amount: 1_0000000,
-            };
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Repay as u32,
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
+                },
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
                     address: underlying_1,
-                    amount: 2_0000000,
+                    amount: 1_0000000,
                 },
             ];
-            let result = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
-
-            assert_eq!(result.liabilities.len(), 1);
-            assert_eq!(result.collateral.len(), 2);
-
-            assert_eq!(
-                underlying_0_client.balance(&pool),
-                pre_pool_balance_0 - 1_0000000
-            );
-            assert_eq!(
-                underlying_1_client.balance(&pool),
-                pre_pool_balance_1 + 1_0000012
-            );
-
-            assert_eq!(underlying_0_client.balance(&samwise), 11_0000000);
-            assert_eq!(
-                underlying_1_client.balance(&samwise),
-                10_0000000 - 1_0000012
-            );
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1212)")]
-    fn test_submit_with_flash_loan_with_ongoing_liquidation_blocked() {
+    fn test_submit_over_max_positions_with_position_limit() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -2057,8 +2678,6 @@ mod tests {
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
-
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
@@ -2083,54 +2702,46 @@ mod tests {
         );
         oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
 
-        let auction_data = AuctionData {
-            bid: map![&e, (underlying_0.clone(), 2_0000000)],
-            lot: map![&e, (underlying_1.clone(), 2_0000000),],
-            block: 1200,
-        };
         let pool_config = PoolConfig {
             oracle,
             min_collateral: 1_0000000,
             bstop_rate: 0_1000000,
             status: 0,
-            max_positions: 2,
+            max_positions: 3,
+            flash_loan_fee: 0,
+            max_price_age: 0,
         };
         let user_positions = Positions {
-            liabilities: map![&e, (0, 5_0000000)],
-            collateral: map![&e, (1, 6_0000000)],
+            liabilities: map![&e, (0, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
             supply: map![&e],
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
             storage::set_user_positions(&e, &samwise, &user_positions);
-            storage::set_auction(
-                &e,
-                &(AuctionType::UserLiquidation as u32),
-                &samwise,
-                &auction_data,
-            );
+            storage::set_position_limit(&e, &samwise, &4);
 
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_0.clone(),
-                amount: 1_0000000,
-            };
             let requests = vec![
                 &e,
                 Request {
-                    request_type: RequestType::Repay as u32,
-                    address: underlying_0,
-                    amount: 4_5000000,
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1.clone(),
+                    amount: 1_5000000,
+                },
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_1,
+                    amount: 1_0000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+            let positions = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+            assert_eq!(positions.effective_count(), 4);
         });
     }
 
     #[test]
-    #[should_panic(expected = "Error(Contract, #1224)")]
-    fn test_submit_with_flash_loan_under_min_collateral_fails() {
+    fn test_submit_over_max_positions_decrease_allowed() {
         let e = Env::default();
         e.cost_estimate().budget().reset_unlimited();
         e.mock_all_auths_allowing_non_root_auth();
@@ -2151,8 +2762,6 @@ mod tests {
         let pool = testutils::create_pool(&e);
         let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
 
-        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
-
         let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
@@ -2161,10 +2770,8 @@ mod tests {
         let (reserve_config, reserve_data) = testutils::default_reserve_meta();
         testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
 
-        underlying_0_client.mint(&samwise, &20_0000000);
-        underlying_0_client.approve(&samwise, &pool, &20_0000000, &100000);
-        underlying_1_client.mint(&samwise, &20_0000000);
-        underlying_1_client.approve(&samwise, &pool, &20_0000000, &100000);
+        underlying_0_client.mint(&samwise, &10_0000000);
+        underlying_1_client.mint(&samwise, &10_0000000);
 
         oracle_client.set_data(
             &bombadil,
@@ -2185,30 +2792,2192 @@ mod tests {
             bstop_rate: 0_1000000,
             status: 0,
             max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 1_0000000), (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000), (1, 15_0000000)],
+            supply: map![&e],
         };
         e.as_contract(&pool, || {
             e.mock_all_auths_allowing_non_root_auth();
             storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
 
-            let flash_loan: FlashLoan = FlashLoan {
-                contract: flash_loan_receiver,
-                asset: underlying_1.clone(),
-                amount: 5_0000000,
-            };
             let requests = vec![
                 &e,
                 Request {
                     request_type: RequestType::SupplyCollateral as u32,
                     address: underlying_0,
-                    amount: 0_9000000,
+                    amount: 1_0000000,
                 },
                 Request {
                     request_type: RequestType::Repay as u32,
                     address: underlying_1,
-                    amount: 4_9900000,
+                    amount: 2_0000000,
                 },
             ];
-            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
-        });
+            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+
+            assert_eq!(result.liabilities.len(), 1);
+            assert_eq!(result.collateral.len(), 2);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 1_0000000
+            );
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 + 1_0000012
+            );
+
+            assert_eq!(underlying_0_client.balance(&samwise), 9_0000000);
+            assert_eq!(
+                underlying_1_client.balance(&samwise),
+                10_0000000 - 1_0000012
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1212)")]
+    fn test_submit_with_ongoing_liquidation_blocked() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &10_0000000);
+        underlying_1_client.mint(&samwise, &10_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 2_0000000)],
+            lot: map![&e, (underlying_1.clone(), 2_0000000),],
+            block: 1200,
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 5_0000000)],
+            collateral: map![&e, (1, 6_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &auction_data,
+            );
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0,
+                    amount: 4_0000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+        });
+    }
+
+    #[test]
+    fn test_submit_with_ongoing_liquidation_works_if_canceled() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &10_0000000);
+        underlying_1_client.mint(&samwise, &10_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 2_0000000)],
+            lot: map![&e, (underlying_1.clone(), 2_0000000),],
+            block: 1200,
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 5_0000000)],
+            collateral: map![&e, (1, 6_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &auction_data,
+            );
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0,
+                    amount: 4_0000000,
+                },
+                Request {
+                    request_type: RequestType::DeleteLiquidationAuction as u32,
+                    address: samwise.clone(),
+                    amount: 0,
+                },
+            ];
+            let result = execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+
+            assert_eq!(result.liabilities.len(), 1);
+            assert_eq!(result.collateral.len(), 1);
+
+            assert_eq!(result.collateral.get_unchecked(1), 6_0000000);
+            assert_eq!(result.liabilities.get_unchecked(0), 1_0000046);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 4_0000000
+            );
+            assert_eq!(
+                underlying_0_client.balance(&samwise),
+                10_0000000 - 4_0000000
+            );
+
+            assert!(!storage::has_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise
+            ));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1224)")]
+    fn test_submit_under_min_collateral_fails() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let merry = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&frodo, &16_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 0_9000000,
+                },
+                Request {
+                    request_type: RequestType::Borrow as u32,
+                    address: underlying_1,
+                    amount: 0_01000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &frodo, &merry, requests, false);
+        });
+    }
+
+    /***** submit_with_flash_loan *****/
+
+    #[test]
+    fn test_submit_with_flash_loan() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.max_util = 9500000;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_1_client.mint(&samwise, &25_0000000);
+            underlying_1_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            // pool has 100 supplied and 50 borrowed for asset_0
+            // -> max util is 95%
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0,
+                amount: 25_0000000,
+            };
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_1,
+                    amount: 25_0000000,
+                },
+            ];
+            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+
+            assert_eq!(positions.liabilities.len(), 1);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            assert_eq!(positions.collateral.get_unchecked(1), 249999807);
+            // actual is 24.999979375 - rounds up
+            assert_eq!(positions.liabilities.get_unchecked(0), 249999794);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 - 25_0000000
+            );
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 + 25_0000000
+            );
+
+            assert_eq!(underlying_0_client.balance(&samwise), 25_0000000);
+            assert_eq!(underlying_1_client.balance(&samwise), 0);
+
+            // check allowance is used
+            assert_eq!(
+                underlying_1_client.allowance(&samwise, &pool),
+                100_0000000 - 25_0000000
+            );
+        });
+    }
+
+    #[test]
+    fn test_submit_with_flash_loans_multi_asset() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.index = 1;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_1_client.mint(&samwise, &5_0000000);
+            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
+            underlying_1_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            let flash_loans = vec![
+                &e,
+                FlashLoan {
+                    contract: flash_loan_receiver.clone(),
+                    asset: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+                FlashLoan {
+                    contract: flash_loan_receiver,
+                    asset: underlying_1.clone(),
+                    amount: 2_0000000,
+                },
+            ];
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_1.clone(),
+                    amount: 5_0000000,
+                },
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1.clone(),
+                    amount: 2_0000000,
+                },
+            ];
+            let positions = execute_submit_with_flash_loans(&e, &samwise, flash_loans, requests);
+
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 1);
+            assert_eq!(positions.supply.len(), 0);
+            assert_eq!(positions.collateral.get_unchecked(1), 5_0000000);
+
+            // both flash loans were fully repaid
+            assert_eq!(underlying_0_client.balance(&pool), pre_pool_balance_0);
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 + 5_0000000
+            );
+            assert_eq!(underlying_0_client.balance(&samwise), 0);
+            assert_eq!(underlying_1_client.balance(&samwise), 0);
+
+            let reserve_data_0 = storage::get_res_data(&e, &underlying_0);
+            let reserve_data_1 = storage::get_res_data(&e, &underlying_1);
+            assert_eq!(reserve_data_0.d_supply, 50_0000000);
+            assert_eq!(reserve_data_1.d_supply, 50_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_submit_with_flash_loans_empty_panics() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+
+        e.as_contract(&pool, || {
+            execute_submit_with_flash_loans(&e, &samwise, vec![&e], vec![&e]);
+        });
+    }
+
+    #[test]
+    fn test_submit_with_flash_loan_charges_fee() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0_0005000,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_0_client.mint(&samwise, &0_0050000);
+            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0.clone(),
+                amount: 10_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+            ];
+            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+
+            // fee is collected from samwise and credited to the backstop
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 0_0050000
+            );
+            assert_eq!(underlying_0_client.balance(&samwise), 0);
+            let reserve_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_data.backstop_credit, 0_0050000);
+            assert_eq!(reserve_data.d_supply, 50_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #10)")]
+    fn test_submit_with_flash_loan_repays_principal_only_panics() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0_0005000,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0.clone(),
+                amount: 10_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+            ];
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+        });
+    }
+
+    #[test]
+    fn test_submit_with_flash_loan_surplus_credited_to_backstop() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let flash_loan_receiver =
+            testutils::create_over_repaying_flashloan_receiver(&e, &pool, 0_1000000);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0_0005000,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_0_client.mint(&samwise, &0_0050000);
+            underlying_0_client.mint(&flash_loan_receiver, &0_1000000);
+            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver.clone(),
+                asset: underlying_0.clone(),
+                amount: 10_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+            ];
+            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 + 0_0050000 + 0_1000000
+            );
+            assert_eq!(underlying_0_client.balance(&flash_loan_receiver), 0);
+            assert_eq!(underlying_0_client.balance(&samwise), 0);
+            // both the fee and the surplus are credited to the backstop
+            let reserve_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_data.backstop_credit, 0_0050000 + 0_1000000);
+            assert!(!storage::has_flash_loan_lock(&e));
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_submit_with_flash_loan_reentrant_receiver_panics() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let flash_loan_receiver = testutils::create_reentrant_flashloan_receiver(&e, &pool);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0_0005000,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_0_client.mint(&samwise, &0_0050000);
+            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver.clone(),
+                asset: underlying_0.clone(),
+                amount: 10_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0.clone(),
+                    amount: 10_0000000,
+                },
+            ];
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1229)")]
+    fn test_submit_during_flash_loan_panics() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        reserve_data.last_time = 600;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![&e, Asset::Stellar(underlying_0.clone())],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0_0005000,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_flash_loan_lock(&e);
+
+            underlying_0_client.mint(&samwise, &1_0000000);
+            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: underlying_0.clone(),
+                    amount: 1_0000000,
+                },
+            ];
+            execute_submit(&e, &samwise, &samwise, &samwise, requests, false);
+        });
+    }
+
+    #[test]
+    fn test_submit_with_flash_loan_process_flash_loan_first() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.max_util = 9500000;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_0_client.mint(&samwise, &1_0000000);
+            underlying_0_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            // pool has 100 supplied and 50 borrowed for asset_0
+            // -> max util is 95%
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0.clone(),
+                amount: 25_0000000,
+            };
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0,
+                    amount: 25_0000010,
+                },
+            ];
+            let positions = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+
+            assert_eq!(positions.liabilities.len(), 0);
+            assert_eq!(positions.collateral.len(), 0);
+            assert_eq!(positions.supply.len(), 0);
+
+            assert_eq!(underlying_0_client.balance(&pool), pre_pool_balance_0 + 1,);
+            assert_eq!(underlying_1_client.balance(&pool), pre_pool_balance_1,);
+
+            // rounding causes 1 stroops to be lost
+            assert_eq!(underlying_0_client.balance(&samwise), 0_9999999);
+            assert_eq!(underlying_1_client.balance(&samwise), 0);
+
+            // check allowance is used
+            assert_eq!(
+                underlying_0_client.allowance(&samwise, &pool),
+                100_0000000 - 25_0000001
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_submit_with_flash_loan_checks_health() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.max_util = 9500000;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_1_client.mint(&samwise, &25_0000000);
+            underlying_1_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            // pool has 100 supplied and 50 borrowed for asset_0
+            // -> max util is 95%
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0,
+                amount: 25_0000000,
+            };
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_1,
+                    amount: 8_0000000,
+                },
+            ];
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1207)")]
+    fn test_submit_with_flash_loan_checks_max_util() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.max_util = 9500000;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+
+            underlying_1_client.mint(&samwise, &50_0000000);
+            underlying_1_client.approve(&samwise, &pool, &100_0000000, &10000);
+
+            // pool has 100 supplied and 50 borrowed for asset_0
+            // -> max util is 95%
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0,
+                amount: 46_0000000,
+            };
+
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_1,
+                    amount: 50_0000000,
+                },
+            ];
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1208)")]
+    fn test_submit_with_flash_loan_over_max_positions() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &10_0000000);
+        underlying_0_client.approve(&samwise, &pool, &10_0000000, &100000);
+        underlying_1_client.mint(&samwise, &10_0000000);
+        underlying_1_client.approve(&samwise, &pool, &10_0000000, &100000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 3,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0,
+                amount: 1_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_1,
+                    amount: 2_0000000,
+                },
+            ];
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+        });
+    }
+
+    #[test]
+    fn test_submit_with_flash_loan_over_max_positions_decrease_allowed() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &10_0000000);
+        underlying_0_client.approve(&samwise, &pool, &10_0000000, &100000);
+        underlying_1_client.mint(&samwise, &10_0000000);
+        underlying_1_client.approve(&samwise, &pool, &10_0000000, &100000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 1_0000000), (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000), (1, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+
+            let pre_pool_balance_0 = underlying_0_client.balance(&pool);
+            let pre_pool_balance_1 = underlying_1_client.balance(&pool);
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0,
+                amount: 1_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1,
+                    amount: 2_0000000,
+                },
+            ];
+            let result = execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+
+            assert_eq!(result.liabilities.len(), 1);
+            assert_eq!(result.collateral.len(), 2);
+
+            assert_eq!(
+                underlying_0_client.balance(&pool),
+                pre_pool_balance_0 - 1_0000000
+            );
+            assert_eq!(
+                underlying_1_client.balance(&pool),
+                pre_pool_balance_1 + 1_0000012
+            );
+
+            assert_eq!(underlying_0_client.balance(&samwise), 11_0000000);
+            assert_eq!(
+                underlying_1_client.balance(&samwise),
+                10_0000000 - 1_0000012
+            );
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1212)")]
+    fn test_submit_with_flash_loan_with_ongoing_liquidation_blocked() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &10_0000000);
+        underlying_1_client.mint(&samwise, &10_0000000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let auction_data = AuctionData {
+            bid: map![&e, (underlying_0.clone(), 2_0000000)],
+            lot: map![&e, (underlying_1.clone(), 2_0000000),],
+            block: 1200,
+        };
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let user_positions = Positions {
+            liabilities: map![&e, (0, 5_0000000)],
+            collateral: map![&e, (1, 6_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &user_positions);
+            storage::set_auction(
+                &e,
+                &(AuctionType::UserLiquidation as u32),
+                &samwise,
+                &auction_data,
+            );
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_0.clone(),
+                amount: 1_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_0,
+                    amount: 4_5000000,
+                },
+            ];
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1224)")]
+    fn test_submit_with_flash_loan_under_min_collateral_fails() {
+        let e = Env::default();
+        e.cost_estimate().budget().reset_unlimited();
+        e.mock_all_auths_allowing_non_root_auth();
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (flash_loan_receiver, _) = testutils::create_flashloan_receiver(&e);
+
+        let (underlying_0, underlying_0_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, underlying_1_client) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, reserve_data) = testutils::default_reserve_meta();
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        underlying_0_client.mint(&samwise, &20_0000000);
+        underlying_0_client.approve(&samwise, &pool, &20_0000000, &100000);
+        underlying_1_client.mint(&samwise, &20_0000000);
+        underlying_1_client.approve(&samwise, &pool, &20_0000000, &100000);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 2,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        e.as_contract(&pool, || {
+            e.mock_all_auths_allowing_non_root_auth();
+            storage::set_pool_config(&e, &pool_config);
+
+            let flash_loan: FlashLoan = FlashLoan {
+                contract: flash_loan_receiver,
+                asset: underlying_1.clone(),
+                amount: 5_0000000,
+            };
+            let requests = vec![
+                &e,
+                Request {
+                    request_type: RequestType::SupplyCollateral as u32,
+                    address: underlying_0,
+                    amount: 0_9000000,
+                },
+                Request {
+                    request_type: RequestType::Repay as u32,
+                    address: underlying_1,
+                    amount: 4_9900000,
+                },
+            ];
+            execute_submit_with_flash_loan(&e, &samwise, flash_loan, requests);
+        });
+    }
+
+    #[test]
+    fn test_transfer_position_collateral() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let samwise_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &samwise_positions);
+
+            execute_transfer_position(&e, &samwise, &frodo, 1, 5_0000000);
+
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.collateral.get_unchecked(0), 10_0000000);
+            assert_eq!(samwise_positions.liabilities.get_unchecked(1), 1_0000000);
+            let frodo_positions = storage::get_user_positions(&e, &frodo);
+            assert_eq!(frodo_positions.collateral.get_unchecked(0), 5_0000000);
+            assert_eq!(frodo_positions.liabilities.len(), 0);
+
+            // b_supply is unchanged by the transfer
+            let reserve_data = storage::get_res_data(&e, &underlying_0);
+            assert_eq!(reserve_data.b_supply, 100_0000000);
+
+            let mut pool = Pool::load(&e);
+            let samwise_hf =
+                PositionData::calculate_from_positions(&e, &mut pool, &samwise_positions);
+            assert!(!samwise_hf.is_hf_under(&e, 1_0000100));
+            assert!(samwise_hf.is_hf_under(&e, 1_1300000));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_transfer_position_collateral_makes_from_unhealthy() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let samwise_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &samwise_positions);
+
+            execute_transfer_position(&e, &samwise, &frodo, 1, 8_0000000);
+        });
+    }
+
+    #[test]
+    fn test_transfer_position_liability() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let samwise_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        let frodo_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &samwise_positions);
+            storage::set_user_positions(&e, &frodo, &frodo_positions);
+
+            execute_transfer_position(&e, &samwise, &frodo, 2, 1_0000000);
+
+            let samwise_positions = storage::get_user_positions(&e, &samwise);
+            assert_eq!(samwise_positions.liabilities.len(), 0);
+            assert_eq!(samwise_positions.collateral.get_unchecked(0), 15_0000000);
+            let frodo_positions = storage::get_user_positions(&e, &frodo);
+            assert_eq!(frodo_positions.liabilities.get_unchecked(1), 1_0000000);
+            assert_eq!(frodo_positions.collateral.get_unchecked(0), 15_0000000);
+
+            let mut pool = Pool::load(&e);
+            let frodo_hf = PositionData::calculate_from_positions(&e, &mut pool, &frodo_positions);
+            assert!(!frodo_hf.is_hf_under(&e, 1_0000100));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_transfer_position_liability_makes_to_unhealthy() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let samwise_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &samwise_positions);
+
+            execute_transfer_position(&e, &samwise, &frodo, 2, 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1223)")]
+    fn test_transfer_position_liability_borrow_disabled() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.borrow_enabled = false;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let samwise_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        let frodo_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &samwise_positions);
+            storage::set_user_positions(&e, &frodo, &frodo_positions);
+
+            execute_transfer_position(&e, &samwise, &frodo, 2, 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1205)")]
+    fn test_transfer_position_liability_under_min_borrow_health_buffer() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let samwise_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        let frodo_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &samwise_positions);
+            storage::set_user_positions(&e, &frodo, &frodo_positions);
+            // frodo has a health factor of ~1.69 after the transfer
+            storage::set_min_borrow_health_buffer(&e, &1_7000000);
+
+            execute_transfer_position(&e, &samwise, &frodo, 2, 1_0000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1200)")]
+    fn test_transfer_position_liability_under_min_borrow() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let samwise = Address::generate(&e);
+        let frodo = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let (oracle, oracle_client) = testutils::create_mock_oracle(&e);
+
+        let (underlying_0, _) = testutils::create_token_contract(&e, &bombadil);
+        let (reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_0, &reserve_config, &reserve_data);
+
+        let (underlying_1, _) = testutils::create_token_contract(&e, &bombadil);
+        let (mut reserve_config, mut reserve_data) = testutils::default_reserve_meta();
+        reserve_config.min_borrow = 0_6000000;
+        reserve_data.b_supply = 100_0000000;
+        reserve_data.d_supply = 50_0000000;
+        testutils::create_reserve(&e, &pool, &underlying_1, &reserve_config, &reserve_data);
+
+        oracle_client.set_data(
+            &bombadil,
+            &Asset::Other(Symbol::new(&e, "USD")),
+            &vec![
+                &e,
+                Asset::Stellar(underlying_0.clone()),
+                Asset::Stellar(underlying_1.clone()),
+            ],
+            &7,
+            &300,
+        );
+        oracle_client.set_price_stable(&vec![&e, 1_0000000, 5_0000000]);
+
+        e.ledger().set(LedgerInfo {
+            timestamp: 600,
+            protocol_version: 22,
+            sequence_number: 1234,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 10,
+            min_persistent_entry_ttl: 10,
+            max_entry_ttl: 3110400,
+        });
+        let pool_config = PoolConfig {
+            oracle,
+            min_collateral: 1_0000000,
+            bstop_rate: 0_1000000,
+            status: 0,
+            max_positions: 4,
+            flash_loan_fee: 0,
+            max_price_age: 0,
+        };
+        let samwise_positions = Positions {
+            liabilities: map![&e, (1, 1_0000000)],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        let frodo_positions = Positions {
+            liabilities: map![&e],
+            collateral: map![&e, (0, 15_0000000)],
+            supply: map![&e],
+        };
+        e.as_contract(&pool, || {
+            storage::set_pool_config(&e, &pool_config);
+            storage::set_user_positions(&e, &samwise, &samwise_positions);
+            storage::set_user_positions(&e, &frodo, &frodo_positions);
+
+            execute_transfer_position(&e, &samwise, &frodo, 2, 0_5000000);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1229)")]
+    fn test_gulp_emissions_during_flash_loan_panics() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let pool = testutils::create_pool(&e);
+        let pool_client = crate::PoolContractClient::new(&e, &pool);
+        e.as_contract(&pool, || {
+            storage::set_flash_loan_lock(&e);
+        });
+
+        pool_client.gulp_emissions();
+    }
+
+    #[test]
+    #[should_panic(expected = "Error(Contract, #1229)")]
+    fn test_set_reserve_during_flash_loan_panics() {
+        let e = Env::default();
+        e.mock_all_auths();
+
+        let bombadil = Address::generate(&e);
+        let pool = testutils::create_pool(&e);
+        let pool_client = crate::PoolContractClient::new(&e, &pool);
+        let (underlying, _) = testutils::create_token_contract(&e, &bombadil);
+        e.as_contract(&pool, || {
+            storage::set_flash_loan_lock(&e);
+        });
+
+        pool_client.set_reserve(&underlying);
     }
 }
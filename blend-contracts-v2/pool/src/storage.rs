@@ -3,7 +3,15 @@ use soroban_sdk::{
     String, Symbol, TryFromVal, Val, Vec,
 };
 
-use crate::{auctions::AuctionData, constants::MAX_RESERVES, pool::Positions, PoolError};
+use crate::{
+    auctions::AuctionData,
+    constants::{
+        DEFAULT_AUCTION_BID_RAMP_BLOCKS, DEFAULT_AUCTION_LOT_RAMP_BLOCKS,
+        DEFAULT_AUCTION_PER_BLOCK_SCALAR, MAX_RESERVES,
+    },
+    pool::Positions,
+    PoolError,
+};
 
 /********** Ledger Thresholds **********/
 
@@ -29,6 +37,8 @@ pub struct PoolConfig {
     pub bstop_rate: u32, // the rate the backstop takes on accrued debt interest, expressed in 7 decimals
     pub status: u32,     // the status of the pool
     pub max_positions: u32, // the maximum number of effective positions (collateral + liabilities) a single user can hold
+    pub flash_loan_fee: u32, // the fee charged on flash loaned amounts, expressed in 7 decimals
+    pub max_price_age: u64, // the maximum age of an oracle price in seconds, or 0 to only use the default limit
 }
 
 /// The pool's emission config
@@ -43,8 +53,8 @@ pub struct PoolEmissionConfig {
 #[derive(Clone, Debug)]
 #[contracttype]
 pub struct ReserveConfig {
-    pub index: u32,           // the index of the reserve in the list
-    pub decimals: u32,        // the decimals used in both the bToken and underlying contract
+    pub index: u32,            // the index of the reserve in the list
+    pub decimals: u32,         // the decimals used in both the bToken and underlying contract
     pub c_factor: u32, // the collateral factor for the reserve scaled expressed in 7 decimals
     pub l_factor: u32, // the liability factor for the reserve scaled expressed in 7 decimals
     pub util: u32,     // the target utilization rate scaled expressed in 7 decimals
@@ -55,7 +65,13 @@ pub struct ReserveConfig {
     pub r_three: u32, // the R3 value in the interest rate formula scaled expressed in 7 decimals
     pub reactivity: u32, // the reactivity constant for the reserve scaled expressed in 7 decimals
     pub collateral_cap: i128, // the total amount of underlying tokens that can be used as collateral
-    pub enabled: bool,        // the enabled flag of the reserve
+    pub supply_cap: i128, // the total amount of underlying tokens that can be supplied, or 0 for no cap
+    pub borrow_cap: i128, // the total amount of underlying tokens that can be borrowed, or 0 for no cap
+    pub min_borrow: i128, // the minimum amount of underlying tokens a user can borrow from the reserve, or 0 for no minimum
+    pub enabled: bool,    // the enabled flag of the reserve
+    pub supply_enabled: bool, // the flag allowing supplying to the reserve
+    pub borrow_enabled: bool, // the flag allowing borrowing from the reserve
+    pub auction_lot_rate: u32, // the per block rate auction lots of the reserve scale at expressed in 7 decimals, or 0 for the default rate
 }
 
 #[derive(Clone)]
@@ -75,9 +91,27 @@ pub struct ReserveData {
     pub b_supply: i128, // the total supply of b tokens, in the underlying token's decimals
     pub d_supply: i128, // the total supply of d tokens, in the underlying token's decimals
     pub backstop_credit: i128, // the amount of underlying tokens currently owed to the backstop
+    pub insurance_credit: i128, // the amount of underlying tokens held in the insurance fund
     pub last_time: u64, // the last block the data was updated
 }
 
+/// A liquidation bonus tier based on the pool's backstop coverage
+#[derive(Clone)]
+#[contracttype]
+pub struct LiquidationBonusTier {
+    pub coverage: i128, // the backstop coverage (7 decimals, 1_0000000 = backstop threshold) below which the tier applies
+    pub max_bonus: i128, // the maximum liquidation incentive (7 decimals, 1_0500000 = 5% bonus) applied within the tier
+}
+
+/// The block based scaling parameters of the pool's auctions
+#[derive(Clone)]
+#[contracttype]
+pub struct AuctionConfig {
+    pub auction_lot_ramp_blocks: u32, // the number of blocks the lot takes to scale from 0% to 100%
+    pub auction_bid_ramp_blocks: u32, // the number of blocks the bid takes to scale from 100% to 0% after the lot ramp
+    pub auction_per_block_scalar: i128, // the rate the lot and bid modifiers move every block, expressed in 7 decimals
+}
+
 /// The emission data for the reserve b or d token
 #[derive(Clone)]
 #[contracttype]
@@ -88,6 +122,15 @@ pub struct ReserveEmissionData {
     pub last_time: u64,
 }
 
+/// The minimum position size required to accrue emissions on a reserve b or d token
+#[derive(Clone)]
+#[contracttype]
+pub struct EmissionMinBalance {
+    pub min_balance: i128,      // the minimum position size, in d/bTokens
+    pub prev_min_balance: i128, // the minimum position size before the current minimum took effect
+    pub index: i128, // the reserve token's emission index when the current minimum took effect
+}
+
 /// The user emission data for the reserve b or d token
 #[derive(Clone)]
 #[contracttype]
@@ -99,12 +142,24 @@ pub struct UserEmissionData {
 /********** Storage Key Types **********/
 
 const ADMIN_KEY: &str = "Admin";
+const PENDING_ADMIN_KEY: &str = "PendAdmin";
 const NAME_KEY: &str = "Name";
 const BACKSTOP_KEY: &str = "Backstop";
 const BLND_TOKEN_KEY: &str = "BLNDTkn";
 const POOL_CONFIG_KEY: &str = "Config";
 const RES_LIST_KEY: &str = "ResList";
 const POOL_EMIS_KEY: &str = "PoolEmis";
+const LIQ_BONUS_TIERS_KEY: &str = "LiqTiers";
+const MIN_LIQ_FILL_KEY: &str = "MinLiqFill";
+const AUCTION_CONFIG_KEY: &str = "AuctConfig";
+const INSURANCE_BPS_KEY: &str = "InsBps";
+const MIN_INT_AUCTION_KEY: &str = "MinIntAuct";
+const FLASH_LOAN_LOCK_KEY: &str = "FlashLock";
+const EMERGENCY_KEY: &str = "Emergency";
+const EMERGENCY_WITHDRAW_KEY: &str = "EmergWdrw";
+const STATUS_GRACE_KEY: &str = "StatGrace";
+const PENDING_STATUS_KEY: &str = "PendStatus";
+const MIN_BORROW_HF_KEY: &str = "MinBrwHf";
 
 #[derive(Clone)]
 #[contracttype]
@@ -127,6 +182,8 @@ pub enum PoolDataKey {
     ResConfig(Address),
     // A map of underlying asset's contract address to queued reserve init
     ResInit(Address),
+    // A map of underlying asset's contract address to the unlock time of a queued reserve removal
+    ResRemove(Address),
     // A map of underlying asset's contract address to reserve data
     ResData(Address),
     // The reserve's emission data
@@ -137,6 +194,14 @@ pub enum PoolDataKey {
     UserEmis(UserReserveKey),
     // The auction's data
     Auction(AuctionKey),
+    // The minimum position size for a reserve token to accrue emissions
+    EmisMinBal(u32),
+    // The maximum amount of tokens a single gulp can credit to a reserve's backstop credit
+    MaxGulp(Address),
+    // The maximum number of positions a user can hold, if above the pool's max positions
+    PositionLimit(Address),
+    // The recent emission index checkpoints for a reserve token
+    EmisHist(u32),
 }
 
 /********** Storage **********/
@@ -198,6 +263,32 @@ pub fn set_user_positions(e: &Env, user: &Address, positions: &Positions) {
         .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
 }
 
+/// Fetch the position limit override for a user
+///
+/// Defaults to 0 if no override is set
+///
+/// ### Arguments
+/// * `user` - The address of the user
+pub fn get_position_limit(e: &Env, user: &Address) -> u32 {
+    let key = PoolDataKey::PositionLimit(user.clone());
+    get_persistent_default(e, &key, || 0, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER)
+}
+
+/// Set the position limit override for a user
+///
+/// ### Arguments
+/// * `user` - The address of the user
+/// * `limit` - The maximum number of positions the user can hold
+pub fn set_position_limit(e: &Env, user: &Address, limit: &u32) {
+    let key = PoolDataKey::PositionLimit(user.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, u32>(&key, limit);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
 /********** Admin **********/
 
 // Fetch the current admin Address
@@ -221,6 +312,30 @@ pub fn set_admin(e: &Env, new_admin: &Address) {
         .set::<Symbol, Address>(&Symbol::new(e, ADMIN_KEY), new_admin);
 }
 
+/// Fetch the pending admin, if one has been proposed
+pub fn get_pending_admin(e: &Env) -> Option<Address> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, PENDING_ADMIN_KEY))
+}
+
+/// Set a pending admin
+///
+/// ### Arguments
+/// * `pending_admin` - The Address proposed as the next admin
+pub fn set_pending_admin(e: &Env, pending_admin: &Address) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Address>(&Symbol::new(e, PENDING_ADMIN_KEY), pending_admin);
+}
+
+/// Delete the pending admin
+pub fn del_pending_admin(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&Symbol::new(e, PENDING_ADMIN_KEY));
+}
+
 /********** Metadata **********/
 
 /// Set a pool name
@@ -299,6 +414,198 @@ pub fn set_pool_config(e: &Env, config: &PoolConfig) {
         .set::<Symbol, PoolConfig>(&Symbol::new(e, POOL_CONFIG_KEY), config);
 }
 
+/// Fetch the liquidation bonus tiers for the pool
+pub fn get_liq_bonus_tiers(e: &Env) -> Vec<LiquidationBonusTier> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, LIQ_BONUS_TIERS_KEY))
+        .unwrap_or(vec![e])
+}
+
+/// Set the liquidation bonus tiers for the pool
+///
+/// ### Arguments
+/// * `tiers` - The liquidation bonus tiers
+pub fn set_liq_bonus_tiers(e: &Env, tiers: &Vec<LiquidationBonusTier>) {
+    e.storage()
+        .instance()
+        .set::<Symbol, Vec<LiquidationBonusTier>>(&Symbol::new(e, LIQ_BONUS_TIERS_KEY), tiers);
+}
+
+/// Fetch the minimum number of blocks a liquidation auction must run before it can be fully filled
+pub fn get_min_liq_full_fill_blocks(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, MIN_LIQ_FILL_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the minimum number of blocks a liquidation auction must run before it can be fully filled
+///
+/// ### Arguments
+/// * `blocks` - The minimum number of blocks
+pub fn set_min_liq_full_fill_blocks(e: &Env, blocks: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, MIN_LIQ_FILL_KEY), blocks);
+}
+
+/// Fetch the auction scaling parameters for the pool
+pub fn get_auction_config(e: &Env) -> AuctionConfig {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, AUCTION_CONFIG_KEY))
+        .unwrap_or(AuctionConfig {
+            auction_lot_ramp_blocks: DEFAULT_AUCTION_LOT_RAMP_BLOCKS,
+            auction_bid_ramp_blocks: DEFAULT_AUCTION_BID_RAMP_BLOCKS,
+            auction_per_block_scalar: DEFAULT_AUCTION_PER_BLOCK_SCALAR,
+        })
+}
+
+/// Set the auction scaling parameters for the pool
+///
+/// ### Arguments
+/// * `config` - The auction config
+pub fn set_auction_config(e: &Env, config: &AuctionConfig) {
+    e.storage()
+        .instance()
+        .set::<Symbol, AuctionConfig>(&Symbol::new(e, AUCTION_CONFIG_KEY), config);
+}
+
+/// Fetch the share of accrued interest routed to the insurance fund, in basis points
+pub fn get_insurance_bps(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, INSURANCE_BPS_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the share of accrued interest routed to the insurance fund, in basis points
+///
+/// ### Arguments
+/// * `bps` - The insurance share in basis points
+pub fn set_insurance_bps(e: &Env, bps: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, INSURANCE_BPS_KEY), bps);
+}
+
+/// Fetch the minimum value of an interest auction lot, in the oracle's base asset
+pub fn get_min_interest_auction_value(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, MIN_INT_AUCTION_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the minimum value of an interest auction lot, in the oracle's base asset
+///
+/// ### Arguments
+/// * `value` - The minimum lot value
+pub fn set_min_interest_auction_value(e: &Env, value: &i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, MIN_INT_AUCTION_KEY), value);
+}
+
+/// Fetch if the pool is emergency paused
+pub fn get_emergency_pause(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, EMERGENCY_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if the pool is emergency paused
+///
+/// ### Arguments
+/// * `paused` - If the pool is paused
+pub fn set_emergency_pause(e: &Env, paused: &bool) {
+    e.storage()
+        .instance()
+        .set::<Symbol, bool>(&Symbol::new(e, EMERGENCY_KEY), paused);
+}
+
+/// Fetch if withdrawals are allowed while the pool is emergency paused
+pub fn get_emergency_withdraw_enabled(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, EMERGENCY_WITHDRAW_KEY))
+        .unwrap_or(false)
+}
+
+/// Set if withdrawals are allowed while the pool is emergency paused
+///
+/// ### Arguments
+/// * `enabled` - If withdrawals are allowed
+pub fn set_emergency_withdraw_enabled(e: &Env, enabled: &bool) {
+    e.storage()
+        .instance()
+        .set::<Symbol, bool>(&Symbol::new(e, EMERGENCY_WITHDRAW_KEY), enabled);
+}
+
+/// Fetch the number of ledgers a status change must be observed for before it is applied
+pub fn get_status_grace_ledgers(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, STATUS_GRACE_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the number of ledgers a status change must be observed for before it is applied
+///
+/// ### Arguments
+/// * `ledgers` - The number of ledgers
+pub fn set_status_grace_ledgers(e: &Env, ledgers: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, u32>(&Symbol::new(e, STATUS_GRACE_KEY), ledgers);
+}
+
+/// Fetch the minimum health factor a user must have after borrowing, with 7 decimals. A value of 0
+/// means no buffer is required above the standard health factor check.
+pub fn get_min_borrow_health_buffer(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, MIN_BORROW_HF_KEY))
+        .unwrap_or(0)
+}
+
+/// Set the minimum health factor a user must have after borrowing
+///
+/// ### Arguments
+/// * `buffer` - The minimum health factor, with 7 decimals
+pub fn set_min_borrow_health_buffer(e: &Env, buffer: &i128) {
+    e.storage()
+        .instance()
+        .set::<Symbol, i128>(&Symbol::new(e, MIN_BORROW_HF_KEY), buffer);
+}
+
+/// Fetch the pending status change as (candidate status, ledger first observed), if one exists
+pub fn get_pending_status(e: &Env) -> Option<(u32, u32)> {
+    e.storage()
+        .instance()
+        .get(&Symbol::new(e, PENDING_STATUS_KEY))
+}
+
+/// Set the pending status change
+///
+/// ### Arguments
+/// * `status` - The candidate status
+/// * `ledger` - The ledger the candidate status was first observed
+pub fn set_pending_status(e: &Env, status: &u32, ledger: &u32) {
+    e.storage()
+        .instance()
+        .set::<Symbol, (u32, u32)>(&Symbol::new(e, PENDING_STATUS_KEY), &(*status, *ledger));
+}
+
+/// Delete the pending status change
+pub fn del_pending_status(e: &Env) {
+    e.storage()
+        .instance()
+        .remove(&Symbol::new(e, PENDING_STATUS_KEY));
+}
+
 /********** Reserve Config (ResConfig) **********/
 
 /// Fetch the reserve data for an asset
@@ -334,6 +641,15 @@ pub fn set_res_config(e: &Env, asset: &Address, config: &ReserveConfig) {
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/// Delete the reserve configuration for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn del_res_config(e: &Env, asset: &Address) {
+    let key = PoolDataKey::ResConfig(asset.clone());
+    e.storage().persistent().remove(&key);
+}
+
 /// Checks if a reserve exists for an asset
 ///
 /// ### Arguments
@@ -394,6 +710,39 @@ pub fn del_queued_reserve_set(e: &Env, asset: &Address) {
     e.storage().temporary().remove(&key);
 }
 
+/// Fetch the unlock time of a queued reserve removal, if one is queued
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_queued_reserve_removal(e: &Env, asset: &Address) -> Option<u64> {
+    let key = PoolDataKey::ResRemove(asset.clone());
+    e.storage().temporary().get::<PoolDataKey, u64>(&key)
+}
+
+/// Set a new queued reserve removal
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `unlock_time` - The timestamp the reserve can be removed at
+pub fn set_queued_reserve_removal(e: &Env, asset: &Address, unlock_time: &u64) {
+    let key = PoolDataKey::ResRemove(asset.clone());
+    e.storage()
+        .temporary()
+        .set::<PoolDataKey, u64>(&key, unlock_time);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, LEDGER_THRESHOLD_USER, LEDGER_BUMP_USER);
+}
+
+/// Delete a queued reserve removal
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn del_queued_reserve_removal(e: &Env, asset: &Address) {
+    let key = PoolDataKey::ResRemove(asset.clone());
+    e.storage().temporary().remove(&key);
+}
+
 /********** Reserve Data (ResData) **********/
 
 /// Fetch the reserve data for an asset
@@ -429,6 +778,15 @@ pub fn set_res_data(e: &Env, asset: &Address, data: &ReserveData) {
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/// Delete the reserve data for an asset
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn del_res_data(e: &Env, asset: &Address) {
+    let key = PoolDataKey::ResData(asset.clone());
+    e.storage().persistent().remove(&key);
+}
+
 /********** Reserve List (ResList) **********/
 
 /// Fetch the list of reserves
@@ -450,7 +808,7 @@ pub fn get_res_list(e: &Env) -> Vec<Address> {
 /// ### Panics
 /// If the number of reserves in the list exceeds 50
 ///
-// @dev: Once added it can't be removed
+// @dev: Once added it can only be removed if it is the last reserve in the list
 pub fn push_res_list(e: &Env, asset: &Address) -> u32 {
     let mut res_list = get_res_list(e);
     if res_list.len() >= MAX_RESERVES {
@@ -469,6 +827,25 @@ pub fn push_res_list(e: &Env, asset: &Address) -> u32 {
     new_index
 }
 
+/// Remove the reserve at the back of the list
+///
+/// Returns the address of the removed reserve, or None if the list is empty
+//
+// @dev: Only the last reserve can be removed so the index of every other reserve is unchanged
+pub fn pop_res_list(e: &Env) -> Option<Address> {
+    let mut res_list = get_res_list(e);
+    let asset = res_list.pop_back();
+    e.storage()
+        .persistent()
+        .set::<Symbol, Vec<Address>>(&Symbol::new(e, RES_LIST_KEY), &res_list);
+    e.storage().persistent().extend_ttl(
+        &Symbol::new(e, RES_LIST_KEY),
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    );
+    asset
+}
+
 /********** Reserve Emissions **********/
 
 /// Fetch the emission data for the reserve b or d token
@@ -501,6 +878,111 @@ pub fn set_res_emis_data(e: &Env, res_token_index: &u32, res_emis_data: &Reserve
         .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
 }
 
+/// Fetch the recent (timestamp, index) emission checkpoints for the reserve b or d token
+///
+/// Defaults to an empty vector if no checkpoints exist
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_emis_history(e: &Env, res_token_index: &u32) -> Vec<(u64, i128)> {
+    let key = PoolDataKey::EmisHist(*res_token_index);
+    get_persistent_default(
+        e,
+        &key,
+        || vec![e],
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the recent (timestamp, index) emission checkpoints for the reserve b or d token
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `history` - The emission checkpoints, ordered from oldest to newest
+pub fn set_emis_history(e: &Env, res_token_index: &u32, history: &Vec<(u64, i128)>) {
+    let key = PoolDataKey::EmisHist(*res_token_index);
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, Vec<(u64, i128)>>(&key, history);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Fetch the minimum position size required for a user to accrue emissions on a reserve b or d token
+///
+/// Defaults to no minimum if none is set
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn get_emis_min_balance(e: &Env, res_token_index: &u32) -> EmissionMinBalance {
+    let key = PoolDataKey::EmisMinBal(*res_token_index);
+    get_persistent_default(
+        e,
+        &key,
+        || EmissionMinBalance {
+            min_balance: 0,
+            prev_min_balance: 0,
+            index: 0,
+        },
+        LEDGER_THRESHOLD_SHARED,
+        LEDGER_BUMP_SHARED,
+    )
+}
+
+/// Set the minimum position size required for a user to accrue emissions on a reserve b or d token
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+/// * `min_balance` - The minimum position size
+pub fn set_emis_min_balance(e: &Env, res_token_index: &u32, min_balance: &EmissionMinBalance) {
+    let key = PoolDataKey::EmisMinBal(*res_token_index);
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, EmissionMinBalance>(&key, min_balance);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
+/// Delete the minimum position size required for a user to accrue emissions on a reserve b or d token
+///
+/// ### Arguments
+/// * `res_token_index` - The d/bToken index for the reserve
+pub fn del_emis_min_balance(e: &Env, res_token_index: &u32) {
+    let key = PoolDataKey::EmisMinBal(*res_token_index);
+    e.storage().persistent().remove(&key);
+}
+
+/********** Gulp **********/
+
+/// Fetch the maximum amount of tokens a single gulp can credit to the backstop for a reserve
+///
+/// Defaults to 0 if no maximum is set
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+pub fn get_max_gulp(e: &Env, asset: &Address) -> i128 {
+    let key = PoolDataKey::MaxGulp(asset.clone());
+    get_persistent_default(e, &key, || 0, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED)
+}
+
+/// Set the maximum amount of tokens a single gulp can credit to the backstop for a reserve
+///
+/// ### Arguments
+/// * `asset` - The contract address of the asset
+/// * `max_gulp` - The maximum amount of tokens, in the underlying asset's decimals
+pub fn set_max_gulp(e: &Env, asset: &Address, max_gulp: &i128) {
+    let key = PoolDataKey::MaxGulp(asset.clone());
+    e.storage()
+        .persistent()
+        .set::<PoolDataKey, i128>(&key, max_gulp);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_THRESHOLD_SHARED, LEDGER_BUMP_SHARED);
+}
+
 /********** User Emissions **********/
 
 /// Fetch the users emission data for a reserve's b or d token
@@ -630,3 +1112,26 @@ pub fn del_auction(e: &Env, auction_type: &u32, user: &Address) {
     });
     e.storage().temporary().remove(&key);
 }
+
+/********** Flash Loan Lock **********/
+
+/// Check if a flash loan is currently in progress
+pub fn has_flash_loan_lock(e: &Env) -> bool {
+    e.storage()
+        .temporary()
+        .has(&Symbol::new(e, FLASH_LOAN_LOCK_KEY))
+}
+
+/// Set the flash loan lock while a flash loan receiver is being called
+pub fn set_flash_loan_lock(e: &Env) {
+    e.storage()
+        .temporary()
+        .set::<Symbol, bool>(&Symbol::new(e, FLASH_LOAN_LOCK_KEY), &true);
+}
+
+/// Remove the flash loan lock
+pub fn del_flash_loan_lock(e: &Env) {
+    e.storage()
+        .temporary()
+        .remove(&Symbol::new(e, FLASH_LOAN_LOCK_KEY));
+}
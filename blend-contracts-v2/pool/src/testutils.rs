@@ -4,13 +4,16 @@ use crate::{
     constants::{SCALAR_12, SCALAR_7},
     pool::Reserve,
     storage::{self, ReserveConfig, ReserveData},
-    PoolContract,
+    PoolClient, PoolContract, Request, RequestType,
 };
 use blend_contract_sdk::emitter::{Client as EmitterClient, WASM as EmitterWASM};
 use sep_40_oracle::testutils::{MockPriceOracleClient, MockPriceOracleWASM};
 use sep_41_token::testutils::{MockTokenClient, MockTokenWASM};
 use soroban_fixed_point_math::SorobanFixedPoint;
-use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, IntoVal, String};
+use soroban_sdk::{
+    contract, contractimpl, testutils::Address as _, token, vec, Address, BytesN, Env, IntoVal,
+    String, Symbol,
+};
 
 use backstop::{BackstopClient, BackstopContract};
 use mock_pool_factory::{MockPoolFactory, MockPoolFactoryClient, PoolInitMeta};
@@ -193,6 +196,100 @@ pub fn create_flashloan_receiver<'a>(
     )
 }
 
+/// Create a flash loan receiver contract that over-repays flash loans.
+///
+/// This returns the tokens received from the flash loan to the "caller" and
+/// sends an additional `surplus` of tokens it holds directly to the pool.
+pub(crate) fn create_over_repaying_flashloan_receiver(
+    e: &Env,
+    pool: &Address,
+    surplus: i128,
+) -> Address {
+    let contract_id = Address::generate(e);
+    e.register_at(
+        &contract_id,
+        OverRepayingFlashLoanReceiver {},
+        (pool.clone(), surplus),
+    );
+    contract_id
+}
+
+#[contract]
+pub struct OverRepayingFlashLoanReceiver;
+
+#[contractimpl]
+impl OverRepayingFlashLoanReceiver {
+    pub fn __constructor(e: Env, pool: Address, surplus: i128) {
+        e.storage().instance().set(&Symbol::new(&e, "pool"), &pool);
+        e.storage()
+            .instance()
+            .set(&Symbol::new(&e, "surplus"), &surplus);
+    }
+
+    pub fn exec_op(e: Env, caller: Address, token: Address, amount: i128, _fee: i128) {
+        caller.require_auth();
+
+        let pool: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "pool"))
+            .unwrap();
+        let surplus: i128 = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "surplus"))
+            .unwrap();
+        let token_client = token::Client::new(&e, &token);
+        token_client.transfer(&e.current_contract_address(), &caller, &amount);
+        token_client.transfer(&e.current_contract_address(), &pool, &surplus);
+    }
+}
+
+/// Create a flash loan receiver contract that attempts to re-enter the pool.
+///
+/// This calls `submit` on the pool during `exec_op` with a supply request of
+/// the flash loaned tokens.
+pub(crate) fn create_reentrant_flashloan_receiver(e: &Env, pool: &Address) -> Address {
+    let contract_id = Address::generate(e);
+    e.register_at(&contract_id, ReentrantFlashLoanReceiver {}, (pool.clone(),));
+    contract_id
+}
+
+#[contract]
+pub struct ReentrantFlashLoanReceiver;
+
+#[contractimpl]
+impl ReentrantFlashLoanReceiver {
+    pub fn __constructor(e: Env, pool: Address) {
+        e.storage().instance().set(&Symbol::new(&e, "pool"), &pool);
+    }
+
+    pub fn exec_op(e: Env, caller: Address, token: Address, amount: i128, _fee: i128) {
+        caller.require_auth();
+
+        let pool: Address = e
+            .storage()
+            .instance()
+            .get(&Symbol::new(&e, "pool"))
+            .unwrap();
+        let receiver = e.current_contract_address();
+        PoolClient::new(&e, &pool).submit(
+            &receiver,
+            &receiver,
+            &receiver,
+            &vec![
+                &e,
+                Request {
+                    request_type: RequestType::Supply as u32,
+                    address: token.clone(),
+                    amount,
+                },
+            ],
+        );
+        token::Client::new(&e, &token).transfer(&receiver, &caller, &amount);
+    }
+}
+
 //************************************************
 //            Object Creation Helpers
 //************************************************
@@ -215,6 +312,12 @@ pub(crate) fn default_reserve(e: &Env) -> Reserve {
             reactivity: 0_0000020, // 2e-6
             index: 0,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         },
         data: ReserveData {
@@ -225,6 +328,7 @@ pub(crate) fn default_reserve(e: &Env) -> Reserve {
             d_supply: 0,
             last_time: 0,
             backstop_credit: 0,
+            insurance_credit: 0,
         },
         scalar: SCALAR_7,
     }
@@ -245,6 +349,12 @@ pub(crate) fn default_reserve_meta() -> (ReserveConfig, ReserveData) {
             reactivity: 0_0000020, // 2e-6
             index: 0,
             collateral_cap: 1000000000000000000,
+            supply_cap: 0,
+            borrow_cap: 0,
+            min_borrow: 0,
+            supply_enabled: true,
+            borrow_enabled: true,
+            auction_lot_rate: 0,
             enabled: true,
         },
         ReserveData {
@@ -255,6 +365,7 @@ pub(crate) fn default_reserve_meta() -> (ReserveConfig, ReserveData) {
             d_supply: 0,
             last_time: 0,
             backstop_credit: 0,
+            insurance_credit: 0,
         },
     )
 }
@@ -289,7 +400,9 @@ pub(crate) fn create_reserve(
         reserve_data
             .d_supply
             .fixed_mul_floor(e, &reserve_data.d_rate, &SCALAR_12);
-    let to_mint_pool = total_supply - total_liabilities + reserve_data.backstop_credit;
+    let to_mint_pool = total_supply - total_liabilities
+        + reserve_data.backstop_credit
+        + reserve_data.insurance_credit;
     underlying_client
         .mock_all_auths()
         .mint(&pool_address, &to_mint_pool);
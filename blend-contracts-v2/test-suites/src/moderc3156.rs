@@ -1,7 +1,7 @@
 use moderc3156_example::{
     FlashLoanReceiverModifiedERC3156, FlashLoanReceiverModifiedERC3156Client,
 };
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{contract, contractimpl, testutils::Address as _, token, Address, Env};
 
 pub fn create_flashloan_receiver<'a>(
     e: &Env,
@@ -14,3 +14,24 @@ pub fn create_flashloan_receiver<'a>(
         FlashLoanReceiverModifiedERC3156Client::new(e, &contract_id),
     )
 }
+
+/// Create a flash loan receiver contract that reports the amount of tokens it returned
+/// to the caller from `exec_op`.
+pub fn create_reporting_flashloan_receiver(e: &Env) -> Address {
+    let contract_id = Address::generate(e);
+    e.register_at(&contract_id, ReportingFlashLoanReceiver {}, ());
+    contract_id
+}
+
+#[contract]
+pub struct ReportingFlashLoanReceiver;
+
+#[contractimpl]
+impl ReportingFlashLoanReceiver {
+    pub fn exec_op(e: Env, caller: Address, token: Address, amount: i128, _fee: i128) -> i128 {
+        caller.require_auth();
+
+        token::Client::new(&e, &token).transfer(&e.current_contract_address(), &caller, &amount);
+        amount
+    }
+}
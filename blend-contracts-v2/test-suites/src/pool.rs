@@ -19,6 +19,12 @@ pub fn default_reserve_metadata() -> ReserveConfig {
         reactivity: 0_0000020, // 2e-6
         index: 0,
         collateral_cap: 1000000000000000000,
+        supply_cap: 0,
+        borrow_cap: 0,
+        min_borrow: 0,
+        supply_enabled: true,
+        borrow_enabled: true,
+        auction_lot_rate: 0,
         enabled: true,
     }
 }
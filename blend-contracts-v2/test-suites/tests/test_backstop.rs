@@ -132,7 +132,7 @@ fn test_backstop() {
     fixture.jump(60 * 60 * 24 * 7 - 60 * 60);
     // Start the next emission cycle
     fixture.emitter.distribute();
-    fixture.backstop.distribute();
+    let result = fixture.backstop.distribute();
     let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
     assert_eq!(
         event,
@@ -145,6 +145,9 @@ fn test_backstop() {
             )
         ]
     );
+    assert_eq!(result.new_emissions, (60 * 60 * 24 * 7 + 60) * SCALAR_7);
+    assert_eq!(result.is_backfill, false);
+    assert_eq!(result.was_reset, false);
     pool.gulp_emissions();
     let amount = 2_000 * SCALAR_7;
     fixture.lp.approve(
@@ -562,6 +565,13 @@ fn test_backstop_constructor() {
 
     let backstop_client = BackstopClient::new(&e, &contract_id);
     assert_eq!(backstop_client.backstop_token(), backstop_token);
+
+    let config = backstop_client.get_config();
+    assert_eq!(config.backstop_token, backstop_token);
+    assert_eq!(config.emitter, emitter);
+    assert_eq!(config.blnd_token, blnd_token);
+    assert_eq!(config.usdc_token, usdc_token);
+    assert_eq!(config.pool_factory, pool_factory);
 }
 
 #[test]
@@ -593,3 +603,104 @@ fn test_backstop_constructor_over_max() {
         ),
     );
 }
+
+#[test]
+fn test_backstop_pool_emissions_allowance() {
+    let fixture = create_fixture_with_data(false);
+    let pool = &fixture.pools[0].pool;
+    let blnd = &fixture.tokens[TokenIndex::BLND];
+
+    let allowance_pre_gulp = fixture.backstop.pool_emissions_allowance(&pool.address);
+    assert_eq!(
+        allowance_pre_gulp,
+        blnd.allowance(&fixture.backstop.address, &pool.address)
+    );
+
+    fixture.jump(60 * 60 * 24 * 7);
+    fixture.emitter.distribute();
+    fixture.backstop.distribute();
+    pool.gulp_emissions();
+
+    let allowance = fixture.backstop.pool_emissions_allowance(&pool.address);
+    assert_eq!(
+        allowance,
+        blnd.allowance(&fixture.backstop.address, &pool.address)
+    );
+    assert!(allowance > allowance_pre_gulp);
+
+    // a pool that has never gulped has no allowance
+    let not_pool = Address::generate(&fixture.env);
+    assert_eq!(fixture.backstop.pool_emissions_allowance(&not_pool), 0);
+}
+
+#[test]
+fn test_backstop_top_up() {
+    let fixture = create_fixture_with_data(false);
+    let frodo = fixture.users.get(0).unwrap();
+    let samwise = Address::generate(&fixture.env);
+    let pool = &fixture.pools[0].pool;
+    let bstop_token = &fixture.lp;
+
+    let amount = 2_000 * SCALAR_7;
+    bstop_token.transfer(&frodo, &samwise, &amount);
+    bstop_token.approve(
+        &samwise,
+        &fixture.backstop.address,
+        &amount,
+        &fixture.env.ledger().sequence(),
+    );
+
+    let (pre_tokens, pre_shares, _, _) = fixture.backstop.get_pool_balance_detailed(&pool.address);
+    let (pre_threshold_tokens, _) = fixture.backstop.get_backstop_threshold(&pool.address);
+    let pre_share_value = pre_tokens.fixed_div_floor(pre_shares, SCALAR_7).unwrap();
+
+    fixture.backstop.top_up(&samwise, &pool.address, &amount);
+    assert_eq!(
+        fixture.env.auths()[0],
+        (
+            samwise.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    fixture.backstop.address.clone(),
+                    Symbol::new(&fixture.env, "top_up"),
+                    vec![
+                        &fixture.env,
+                        samwise.to_val(),
+                        pool.address.to_val(),
+                        amount.into_val(&fixture.env)
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(fixture.env.auths().len(), 1);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                fixture.backstop.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "donate"),
+                    pool.address.clone(),
+                    samwise.clone()
+                )
+                    .into_val(&fixture.env),
+                amount.into_val(&fixture.env)
+            )
+        ]
+    );
+
+    // no shares are minted, so the value of each share rises
+    let (tokens, shares, _, _) = fixture.backstop.get_pool_balance_detailed(&pool.address);
+    assert_eq!(tokens, pre_tokens + amount);
+    assert_eq!(shares, pre_shares);
+    assert!(tokens.fixed_div_floor(shares, SCALAR_7).unwrap() > pre_share_value);
+    assert_eq!(bstop_token.balance(&samwise), 0);
+
+    // the donation counts towards the pool's backstop threshold
+    let (threshold_tokens, _) = fixture.backstop.get_backstop_threshold(&pool.address);
+    assert_eq!(threshold_tokens, pre_threshold_tokens + amount);
+}
@@ -1,5 +1,8 @@
 #![cfg(test)]
-use soroban_sdk::{testutils::Address as _, vec, Address};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    vec, Address, IntoVal, Symbol,
+};
 use test_suites::{
     create_fixture_with_data,
     test_fixture::{TokenIndex, SCALAR_7},
@@ -46,6 +49,18 @@ fn test_backstop_rz_changes_handle_emissions() {
         .withdraw(frodo, &pool_fixture.pool.address, &(45000 * SCALAR_7));
 
     fixture.backstop.remove_reward(&pool_fixture.pool.address);
+    let pool_tokens = fixture
+        .backstop
+        .pool_data(&pool_fixture.pool.address)
+        .tokens;
+    assert_eq!(
+        fixture.env.events().all().last_unchecked(),
+        (
+            fixture.backstop.address.clone(),
+            (Symbol::new(&fixture.env, "rw_zone_remove"),).into_val(&fixture.env),
+            (pool_fixture.pool.address.clone(), pool_tokens).into_val(&fixture.env)
+        )
+    );
 
     let result = pool_fixture.pool.try_gulp_emissions();
     assert!(result.is_err());
@@ -82,6 +97,24 @@ fn test_backstop_rz_changes_handle_emissions() {
     fixture
         .backstop
         .add_reward(&pool_fixture.pool.address, &None);
+    let pool_tokens = fixture
+        .backstop
+        .pool_data(&pool_fixture.pool.address)
+        .tokens;
+    assert_eq!(
+        fixture.env.events().all().last_unchecked(),
+        (
+            fixture.backstop.address.clone(),
+            (Symbol::new(&fixture.env, "rw_zone_add"),).into_val(&fixture.env),
+            (
+                pool_fixture.pool.address.clone(),
+                None::<Address>,
+                pool_tokens,
+                0i128
+            )
+                .into_val(&fixture.env)
+        )
+    );
 
     fixture.emitter.distribute();
     fixture.backstop.distribute();
@@ -7,7 +7,7 @@ use soroban_sdk::{
 };
 use test_suites::{
     create_fixture_with_data,
-    moderc3156::create_flashloan_receiver,
+    moderc3156::{create_flashloan_receiver, create_reporting_flashloan_receiver},
     test_fixture::{TokenIndex, SCALAR_12, SCALAR_7},
 };
 
@@ -111,7 +111,8 @@ fn test_flashloan() {
     let xlm_res_data = pool_fixture.pool.get_reserve(&xlm_address);
     let stable_res_data = pool_fixture.pool.get_reserve(&stable_address);
 
-    let flash_loan_events = vec![&fixture.env, events.get_unchecked(0)];
+    // @dev: each reserve emits a backstop_credit event before its first pool event
+    let flash_loan_events = vec![&fixture.env, events.get_unchecked(1)];
     let flash_loan_d_tokens_minted = flash_loan
         .amount
         .fixed_div_ceil(xlm_res_data.data.d_rate, SCALAR_12)
@@ -139,7 +140,7 @@ fn test_flashloan() {
         ]
     );
 
-    let supply_event = vec![&fixture.env, events.get_unchecked(1)];
+    let supply_event = vec![&fixture.env, events.get_unchecked(3)];
     let supply_b_tokens_minted = supply_amount
         .fixed_div_floor(stable_res_data.data.b_rate, SCALAR_12)
         .unwrap();
@@ -165,7 +166,7 @@ fn test_flashloan() {
         ]
     );
 
-    let repay_event = vec![&fixture.env, events.get_unchecked(2)];
+    let repay_event = vec![&fixture.env, events.get_unchecked(4)];
     let repay_d_tokens_burned = repay_amount
         .fixed_div_floor(xlm_res_data.data.d_rate, SCALAR_12)
         .unwrap();
@@ -220,3 +221,59 @@ fn test_flashloan() {
         starting_stable_balance - supply_amount
     );
 }
+
+#[test]
+fn test_flashloan_receiver_result() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let xlm_address = xlm.address.clone();
+
+    let receiver_address = create_reporting_flashloan_receiver(&fixture.env);
+
+    let samwise = Address::generate(&fixture.env);
+    let approval_ledger = fixture.env.ledger().sequence() + 17280;
+    xlm.mint(&samwise, &(100 * SCALAR_7));
+    xlm.approve(
+        &samwise,
+        &pool_fixture.pool.address,
+        &i128::MAX,
+        &approval_ledger,
+    );
+
+    let flash_loan = FlashLoan {
+        contract: receiver_address.clone(),
+        asset: xlm_address.clone(),
+        amount: 1_000 * SCALAR_7,
+    };
+    let requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Repay as u32,
+            address: xlm_address.clone(),
+            amount: 1_001 * SCALAR_7,
+        },
+    ];
+    let result = pool_fixture
+        .pool
+        .flash_loan(&samwise, &flash_loan, &requests);
+    assert_eq!(result.liabilities.len(), 0);
+
+    // the value returned by the receiver is surfaced in a flash_loan_result event
+    let events = fixture.env.events().all();
+    let result_event = (
+        pool_fixture.pool.address.clone(),
+        (
+            Symbol::new(&fixture.env, "flash_loan_result"),
+            xlm_address.clone(),
+            samwise.clone(),
+            receiver_address.clone(),
+        )
+            .into_val(&fixture.env),
+        flash_loan.amount.into_val(&fixture.env),
+    );
+    assert!(events
+        .iter()
+        .any(|event| vec![&fixture.env, event] == vec![&fixture.env, result_event.clone()]));
+}
@@ -197,6 +197,8 @@ fn test_liquidations() {
             fixture.tokens[TokenIndex::XLM].address.clone(),
         ],
         &100u32,
+        &None,
+        &None,
     );
 
     let stable_interest_lot_amount = auction_data
@@ -249,6 +251,8 @@ fn test_liquidations() {
             fixture.tokens[TokenIndex::XLM].address.clone(),
         ],
         &liq_pct,
+        &None,
+        &None,
     );
     let usdc_bid_amount = auction_data
         .bid
@@ -463,6 +467,8 @@ fn test_liquidations() {
             fixture.tokens[TokenIndex::XLM].address.clone(),
         ],
         &liq_pct,
+        &None,
+        &None,
     );
 
     let usdc_bid_amount = auction_data_2
@@ -585,6 +591,8 @@ fn test_liquidations() {
         ],
         &vec![&fixture.env, fixture.lp.address.clone()],
         &100u32,
+        &None,
+        &None,
     );
 
     assert_eq!(bad_debt_auction_data.bid.len(), 2);
@@ -836,6 +844,8 @@ fn test_liquidations() {
             fixture.tokens[TokenIndex::WETH].address.clone(),
         ],
         &liq_pct,
+        &None,
+        &None,
     );
     let usdc_bid_amount = auction_data
         .bid
@@ -904,6 +914,8 @@ fn test_liquidations() {
         ],
         &vec![&fixture.env, fixture.lp.address.clone()],
         &100u32,
+        &None,
+        &None,
     );
 
     //fill bad debt auction
@@ -1043,6 +1055,8 @@ fn test_user_restore_position_and_delete_liquidation() {
             fixture.tokens[TokenIndex::STABLE].address.clone(),
         ],
         &50,
+        &None,
+        &None,
     );
     assert!(pool_fixture.pool.try_get_auction(&0, &samwise).is_ok());
 
@@ -1147,3 +1161,108 @@ fn test_user_restore_position_and_delete_liquidation() {
     );
     assert!(pool_fixture.pool.try_get_auction(&0, &samwise).is_err());
 }
+
+#[test]
+fn test_fill_and_submit_repays_own_debt() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let stable_pool_index = pool_fixture.reserves[&TokenIndex::STABLE];
+    let xlm_pool_index = pool_fixture.reserves[&TokenIndex::XLM];
+
+    // Create a liquidator that is supplying WETH and borrowing $100 STABLE
+    let merry = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::WETH].mint(&merry, &(10i128.pow(9)));
+    let merry_setup_request: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::WETH].address.clone(),
+            amount: 10i128.pow(9),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::STABLE].address.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture
+        .pool
+        .submit(&merry, &merry, &merry, &merry_setup_request);
+
+    // Create a user that is supply STABLE (cf = 90%, $1) and borrowing XLM (lf = 75%, $0.10)
+    let samwise = Address::generate(&fixture.env);
+    fixture.tokens[TokenIndex::STABLE].mint(&samwise, &(1100 * 10i128.pow(6)));
+    let setup_request: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: fixture.tokens[TokenIndex::STABLE].address.clone(),
+            amount: 1000 * 10i128.pow(6),
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: fixture.tokens[TokenIndex::XLM].address.clone(),
+            amount: 6075 * SCALAR_7,
+        },
+    ];
+    pool_fixture
+        .pool
+        .submit(&samwise, &samwise, &samwise, &setup_request);
+
+    // simulate 20% XLM price increase and create user liquidation
+    fixture.oracle.set_price_stable(&vec![
+        &fixture.env,
+        2000_0000000, // eth
+        1_0000000,    // usdc
+        0_1200000,    // xlm
+        1_0000000,    // stable
+    ]);
+    pool_fixture.pool.new_auction(
+        &0,
+        &samwise,
+        &vec![
+            &fixture.env,
+            fixture.tokens[TokenIndex::XLM].address.clone(),
+        ],
+        &vec![
+            &fixture.env,
+            fixture.tokens[TokenIndex::STABLE].address.clone(),
+        ],
+        &50,
+        &None,
+        &None,
+    );
+
+    // jump 200 blocks
+    fixture.jump_with_sequence(200 * 5);
+
+    // fill the auction and repay merry's STABLE debt with the received STABLE collateral. Merry must
+    // hold the tokens to send for the repayment, as transfers are not netted.
+    fixture.tokens[TokenIndex::STABLE].mint(&merry, &(10i128.pow(6)));
+    let merry_pre_stable_balance = fixture.tokens[TokenIndex::STABLE].balance(&merry);
+    let fill_requests: Vec<Request> = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::WithdrawCollateral as u32,
+            address: fixture.tokens[TokenIndex::STABLE].address.clone(),
+            amount: 101 * 10i128.pow(6),
+        },
+        Request {
+            request_type: RequestType::Repay as u32,
+            address: fixture.tokens[TokenIndex::STABLE].address.clone(),
+            amount: 101 * 10i128.pow(6),
+        },
+    ];
+    let merry_positions =
+        pool_fixture
+            .pool
+            .fill_and_submit(&merry, &0, &samwise, &100, &fill_requests);
+    assert!(pool_fixture.pool.try_get_auction(&0, &samwise).is_err());
+    assert!(merry_positions.liabilities.get(stable_pool_index).is_none());
+    assert!(merry_positions.liabilities.get_unchecked(xlm_pool_index) > 0);
+    assert!(merry_positions.collateral.get_unchecked(stable_pool_index) > 0);
+    // merry received the withdrawn STABLE and was refunded the excess repayment
+    let merry_stable_balance = fixture.tokens[TokenIndex::STABLE].balance(&merry);
+    assert!(merry_stable_balance > merry_pre_stable_balance);
+    assert!(merry_stable_balance < merry_pre_stable_balance + 10i128.pow(6));
+}
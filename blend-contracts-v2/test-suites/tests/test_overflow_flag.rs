@@ -139,6 +139,8 @@ fn test_auction_underflow_panics() {
             fixture.tokens[TokenIndex::XLM].address.clone(),
         ],
         &liq_pct,
+        &None,
+        &None,
     );
 
     let usdc_bid_amount = auction_data_2
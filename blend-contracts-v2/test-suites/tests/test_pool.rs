@@ -1,9 +1,11 @@
 #![cfg(test)]
 
-use pool::{Request, RequestType, ReserveEmissionMetadata};
+use pool::{AuctionConfig, LiquidationBonusTier, Request, RequestType, ReserveEmissionMetadata};
 use soroban_fixed_point_math::FixedPoint;
 use soroban_sdk::{
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, MockAuth, MockAuthInvoke,
+    },
     vec, Address, IntoVal, Symbol, Val,
 };
 use test_suites::{
@@ -409,8 +411,8 @@ fn test_pool_user() {
         )
     );
     let events = fixture.env.events().all();
-    // @dev: three transfer events follow the pool events, 1 pool event follows
-    let event = vec![&fixture.env, events.get_unchecked(events.len() - 5)];
+    // @dev: three transfer events follow the pool events, a backstop_credit event and 1 pool event follow
+    let event = vec![&fixture.env, events.get_unchecked(events.len() - 6)];
     let xlm_reserve_data = fixture.read_reserve_data(0, TokenIndex::XLM);
     let est_xlm = sam_xlm_btoken_balance
         .fixed_mul_floor(xlm_reserve_data.b_rate, SCALAR_12)
@@ -569,12 +571,14 @@ fn test_pool_config() {
     let backstop_take_rate: u32 = 0_0500000;
     pool_fixture
         .pool
-        .update_pool(&backstop_take_rate, &6, &0_5000000);
+        .update_pool(&backstop_take_rate, &6, &0_5000000, &0_0005000, &3600);
     let event_data: soroban_sdk::Vec<Val> = vec![
         &fixture.env,
         backstop_take_rate.into_val(&fixture.env),
         6u32.into_val(&fixture.env),
         0_5000000i128.into_val(&fixture.env),
+        0_0005000u32.into_val(&fixture.env),
+        3600u64.into_val(&fixture.env),
     ];
     assert_eq!(
         fixture.env.auths()[0],
@@ -608,6 +612,8 @@ fn test_pool_config() {
     );
     let new_pool_config = fixture.read_pool_config(0);
     assert_eq!(new_pool_config.bstop_rate, 0_0500000);
+    assert_eq!(new_pool_config.flash_loan_fee, 0_0005000);
+    assert_eq!(new_pool_config.max_price_age, 3600);
 
     // Initialize a reserve (admin only)
     let blnd = &fixture.tokens[TokenIndex::BLND];
@@ -908,9 +914,910 @@ fn test_pool_config() {
             }
         )
     );
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_emissions_config"),
+                    new_admin.clone()
+                )
+                    .into_val(&fixture.env),
+                reserve_emissions.into_val(&fixture.env)
+            )
+        ]
+    );
     let new_emissions_config = fixture.read_pool_emissions(0);
     assert_eq!(new_emissions_config.len(), 3);
     assert_eq!(new_emissions_config.get_unchecked(0), 0_400_0000);
     assert_eq!(new_emissions_config.get_unchecked(1 * 2 + 1), 0_400_0000);
     assert_eq!(new_emissions_config.get_unchecked(3 * 2 + 1), 0_200_0000);
 }
+
+/// Test that borrowers accrue emissions when a reserve's emissions are directed entirely to dTokens
+#[test]
+fn test_pool_d_token_emissions() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let stable_pool_index = pool_fixture.reserves[&TokenIndex::STABLE];
+    let xlm_pool_index = pool_fixture.reserves[&TokenIndex::XLM];
+    let stable = &fixture.tokens[TokenIndex::STABLE];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let blnd = &fixture.tokens[TokenIndex::BLND];
+
+    // Direct all emissions to STABLE dTokens
+    let reserve_emissions: soroban_sdk::Vec<ReserveEmissionMetadata> = soroban_sdk::vec![
+        &fixture.env,
+        ReserveEmissionMetadata {
+            res_index: stable_pool_index,
+            res_type: 0, // d_token
+            share: 1_000_0000
+        },
+    ];
+    pool_fixture.pool.set_emissions_config(&reserve_emissions);
+    let emissions_config = fixture.read_pool_emissions(0);
+    assert_eq!(emissions_config.len(), 1);
+    assert_eq!(
+        emissions_config.get_unchecked(stable_pool_index * 2),
+        1_000_0000
+    );
+
+    // Start a new emission cycle with the new config
+    fixture.jump(60 * 60 * 24 * 7);
+    fixture.emitter.distribute();
+    fixture.backstop.distribute();
+    pool_fixture.pool.gulp_emissions();
+    let stable_d_emis_data = fixture.read_reserve_emissions(0, TokenIndex::STABLE, 0);
+    assert!(stable_d_emis_data.eps > 0);
+    assert_eq!(
+        stable_d_emis_data.expiration,
+        fixture.env.ledger().timestamp() + 60 * 60 * 24 * 7
+    );
+
+    // Sam supplies XLM collateral and borrows STABLE
+    let sam = Address::generate(&fixture.env);
+    let amount = 10_000 * SCALAR_7;
+    xlm.mint(&sam, &amount);
+    xlm.approve(
+        &sam,
+        &pool_fixture.pool.address,
+        &amount,
+        &fixture.env.ledger().sequence(),
+    );
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.address.clone(),
+            amount,
+        },
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: stable.address.clone(),
+            amount: 100 * 10i128.pow(6),
+        },
+    ];
+    pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+
+    fixture.jump(60 * 60 * 24);
+
+    // Sam accrues emissions as a borrower, but not as a supplier
+    let sam_blnd_balance = blnd.balance(&sam);
+    let claim_amount =
+        pool_fixture
+            .pool
+            .claim(&sam, &vec![&fixture.env, xlm_pool_index * 2 + 1], &sam);
+    assert_eq!(claim_amount, 0);
+    let claim_amount =
+        pool_fixture
+            .pool
+            .claim(&sam, &vec![&fixture.env, stable_pool_index * 2], &sam);
+    assert!(claim_amount > 0);
+    assert_eq!(blnd.balance(&sam), sam_blnd_balance + claim_amount);
+
+    // Frodo's existing STABLE liability accrues a larger share of the same emissions
+    let frodo = fixture.users[0].clone();
+    let frodo_claim_amount =
+        pool_fixture
+            .pool
+            .claim(&frodo, &vec![&fixture.env, stable_pool_index * 2], &frodo);
+    assert!(frodo_claim_amount > claim_amount);
+}
+
+/// Test the two-step admin handoff
+#[test]
+fn test_pool_admin_handoff() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+
+    let samwise = Address::generate(&fixture.env);
+    let merry = Address::generate(&fixture.env);
+
+    // Propose samwise (admin only)
+    pool_fixture.pool.propose_admin(&samwise);
+    assert_eq!(
+        fixture.env.auths()[0],
+        (
+            fixture.bombadil.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    pool_fixture.pool.address.clone(),
+                    Symbol::new(&fixture.env, "propose_admin"),
+                    vec![&fixture.env, samwise.to_val()]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "propose_admin"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                samwise.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_admin(), fixture.bombadil);
+
+    // Propose merry, overwriting samwise
+    pool_fixture.pool.propose_admin(&merry);
+
+    // Samwise is no longer pending and cannot accept
+    let result = pool_fixture
+        .pool
+        .mock_auths(&[MockAuth {
+            address: &samwise,
+            invoke: &MockAuthInvoke {
+                contract: &pool_fixture.pool.address,
+                fn_name: "accept_admin",
+                args: ().into_val(&fixture.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_accept_admin();
+    assert!(result.is_err());
+    assert_eq!(pool_fixture.pool.get_admin(), fixture.bombadil);
+
+    // Merry accepts (pending admin only)
+    fixture.env.mock_all_auths();
+    pool_fixture.pool.accept_admin();
+    assert_eq!(
+        fixture.env.auths()[0],
+        (
+            merry.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    pool_fixture.pool.address.clone(),
+                    Symbol::new(&fixture.env, "accept_admin"),
+                    vec![&fixture.env]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "accept_admin"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                merry.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_admin(), merry);
+
+    // No admin is pending after accepting
+    let result = pool_fixture.pool.try_accept_admin();
+    assert!(result.is_err());
+}
+
+/// Test the reserve list views match the order reserves were added
+#[test]
+fn test_pool_reserve_list() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+
+    let reserve_list = pool_fixture.pool.get_reserve_list();
+    assert_eq!(
+        reserve_list,
+        vec![
+            &fixture.env,
+            fixture.tokens[TokenIndex::STABLE].address.clone(),
+            fixture.tokens[TokenIndex::XLM].address.clone(),
+            fixture.tokens[TokenIndex::WETH].address.clone(),
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_reserve_count(), 3);
+    for (index, asset) in reserve_list.iter().enumerate() {
+        assert_eq!(
+            pool_fixture.pool.get_reserve(&asset).config.index,
+            index as u32
+        );
+    }
+}
+
+/// Test the reserve totals reconcile with the pool's token balance after supply and borrow
+#[test]
+fn test_pool_reserve_totals() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    let samwise = Address::generate(&fixture.env);
+    xlm.mint(&samwise, &(10_000 * SCALAR_7));
+
+    let (supplied_0, borrowed_0, _) = pool_fixture.pool.get_reserve_totals(&xlm.address);
+    pool_fixture.pool.submit(
+        &samwise,
+        &samwise,
+        &samwise,
+        &vec![
+            &fixture.env,
+            Request {
+                request_type: RequestType::SupplyCollateral as u32,
+                address: xlm.address.clone(),
+                amount: 10_000 * SCALAR_7,
+            },
+            Request {
+                request_type: RequestType::Borrow as u32,
+                address: xlm.address.clone(),
+                amount: 2_000 * SCALAR_7,
+            },
+        ],
+    );
+    let (supplied, borrowed, available) = pool_fixture.pool.get_reserve_totals(&xlm.address);
+    assert_approx_eq_abs(supplied, supplied_0 + 10_000 * SCALAR_7, 10);
+    assert_approx_eq_abs(borrowed, borrowed_0 + 2_000 * SCALAR_7, 10);
+    assert_eq!(available, supplied - borrowed);
+
+    // the pool holds the available liquidity plus any backstop credit
+    fixture.jump(60 * 60 * 24 * 7);
+    let (supplied, borrowed, available) = pool_fixture.pool.get_reserve_totals(&xlm.address);
+    let reserve = pool_fixture.pool.get_reserve(&xlm.address);
+    assert_eq!(supplied, reserve.total_supply(&fixture.env));
+    assert_eq!(borrowed, reserve.total_liabilities(&fixture.env));
+    assert_approx_eq_abs(
+        xlm.balance(&pool_fixture.pool.address),
+        available + reserve.data.backstop_credit,
+        100,
+    );
+}
+
+/// Test the emergency pause blocks state changing entry points until lifted by the admin
+#[test]
+fn test_pool_emergency_pause() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    let samwise = Address::generate(&fixture.env);
+    xlm.mint(&samwise, &(10_000 * SCALAR_7));
+    let supply_request = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.address.clone(),
+            amount: 10_000 * SCALAR_7,
+        },
+    ];
+    pool_fixture
+        .pool
+        .submit(&samwise, &samwise, &samwise, &supply_request);
+    let borrow_request = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: xlm.address.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+    ];
+    let withdraw_request = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::WithdrawCollateral as u32,
+            address: xlm.address.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+    ];
+
+    // Pause the pool (admin only)
+    assert_eq!(pool_fixture.pool.get_emergency(), (false, false));
+    pool_fixture.pool.set_emergency(&true, &false);
+    assert_eq!(
+        fixture.env.auths()[0],
+        (
+            fixture.bombadil.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    pool_fixture.pool.address.clone(),
+                    Symbol::new(&fixture.env, "set_emergency"),
+                    vec![
+                        &fixture.env,
+                        true.into_val(&fixture.env),
+                        false.into_val(&fixture.env)
+                    ]
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_emergency"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                (true, false).into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_emergency(), (true, false));
+
+    // Borrows and withdrawals are blocked
+    let result = pool_fixture
+        .pool
+        .try_submit(&samwise, &samwise, &samwise, &borrow_request);
+    assert!(result.is_err());
+    let result = pool_fixture
+        .pool
+        .try_submit(&samwise, &samwise, &samwise, &withdraw_request);
+    assert!(result.is_err());
+    let result = pool_fixture.pool.try_gulp_emissions();
+    assert!(result.is_err());
+
+    // Allow withdrawals while paused
+    pool_fixture.pool.set_emergency(&true, &true);
+    pool_fixture
+        .pool
+        .submit(&samwise, &samwise, &samwise, &withdraw_request);
+    assert_eq!(xlm.balance(&samwise), 1_000 * SCALAR_7);
+    let result = pool_fixture
+        .pool
+        .try_submit(&samwise, &samwise, &samwise, &borrow_request);
+    assert!(result.is_err());
+
+    // Lift the pause
+    pool_fixture.pool.set_emergency(&false, &false);
+    assert_eq!(pool_fixture.pool.get_emergency(), (false, false));
+    pool_fixture
+        .pool
+        .submit(&samwise, &samwise, &samwise, &borrow_request);
+    assert_eq!(xlm.balance(&samwise), 2_000 * SCALAR_7);
+
+    // Repayments are allowed while paused
+    pool_fixture.pool.set_emergency(&true, &false);
+    let repay_request = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Repay as u32,
+            address: xlm.address.clone(),
+            amount: 500 * SCALAR_7,
+        },
+    ];
+    pool_fixture
+        .pool
+        .submit(&samwise, &samwise, &samwise, &repay_request);
+    assert_eq!(xlm.balance(&samwise), 1_500 * SCALAR_7);
+}
+
+#[test]
+fn test_pool_claim_and_supply() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let blnd = &fixture.tokens[TokenIndex::BLND];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let stable_pool_index = pool_fixture.reserves[&TokenIndex::STABLE];
+    let xlm_pool_index = pool_fixture.reserves[&TokenIndex::XLM];
+    let frodo = fixture.users[0].clone();
+    let reserve_token_ids = vec![&fixture.env, stable_pool_index * 2, xlm_pool_index * 2 + 1];
+
+    // the pool does not have a BLND reserve
+    let result = pool_fixture
+        .pool
+        .try_claim_and_supply(&frodo, &reserve_token_ids, &blnd.address);
+    assert!(result.is_err());
+
+    pool_fixture
+        .pool
+        .queue_set_reserve(&blnd.address, &default_reserve_metadata());
+    fixture.jump(7 * 24 * 60 * 60 + 1);
+    let blnd_pool_index = pool_fixture.pool.set_reserve(&blnd.address);
+
+    // only BLND can be supplied
+    let result = pool_fixture
+        .pool
+        .try_claim_and_supply(&frodo, &reserve_token_ids, &xlm.address);
+    assert!(result.is_err());
+
+    let frodo_blnd_balance = blnd.balance(&frodo);
+    let pool_blnd_balance = blnd.balance(&pool_fixture.pool.address);
+    let claimed = pool_fixture
+        .pool
+        .claim_and_supply(&frodo, &reserve_token_ids, &blnd.address);
+    assert!(claimed > 0);
+    assert_eq!(blnd.balance(&frodo), frodo_blnd_balance);
+    assert_eq!(
+        blnd.balance(&pool_fixture.pool.address),
+        pool_blnd_balance + claimed
+    );
+
+    // the new reserve has a b_rate of 1, so the collateral matches the claimed amount exactly
+    let positions = pool_fixture.pool.get_positions(&frodo);
+    assert_eq!(positions.collateral.get_unchecked(blnd_pool_index), claimed);
+    assert_eq!(
+        pool_fixture.pool.get_claimable(&frodo, &reserve_token_ids),
+        vec![&fixture.env, 0, 0]
+    );
+}
+
+#[test]
+fn test_pool_backstop_credit_event() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let frodo = fixture.users[0].clone();
+
+    // Frodo borrows XLM
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Borrow as u32,
+            address: xlm.address.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    let pre_backstop_credit = fixture
+        .read_reserve_data(0, TokenIndex::XLM)
+        .backstop_credit;
+
+    // Skip 1 day
+    fixture.jump(24 * 60 * 60);
+
+    // Frodo supplies XLM, accruing interest on the reserve
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::Supply as u32,
+            address: xlm.address.clone(),
+            amount: 10 * SCALAR_7,
+        },
+    ];
+    pool_fixture.pool.submit(&frodo, &frodo, &frodo, &requests);
+    let events = fixture.env.events().all();
+    // @dev: the backstop_credit event is emitted before the supply event and the transfer event
+    let event = vec![&fixture.env, events.get_unchecked(events.len() - 3)];
+    let backstop_credit = fixture
+        .read_reserve_data(0, TokenIndex::XLM)
+        .backstop_credit
+        - pre_backstop_credit;
+    assert!(backstop_credit > 0);
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "backstop_credit"),
+                    xlm.address.clone(),
+                )
+                    .into_val(&fixture.env),
+                backstop_credit.into_val(&fixture.env),
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_pool_admin_settings_events() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+    let samwise = Address::generate(&fixture.env);
+
+    // Set a position limit override for samwise (admin only)
+    pool_fixture.pool.set_position_limit(&samwise, &10);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_position_limit"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                (samwise.clone(), 10u32).into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_position_limit(&samwise), 10);
+
+    // Set the liquidation bonus tiers (admin only)
+    let tiers = vec![
+        &fixture.env,
+        LiquidationBonusTier {
+            coverage: 1_0000000,
+            max_bonus: 1_1000000,
+        },
+    ];
+    pool_fixture.pool.set_liquidation_bonus_tiers(&tiers);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_liquidation_bonus_tiers"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                tiers.into_val(&fixture.env)
+            )
+        ]
+    );
+    let new_tiers = pool_fixture.pool.get_liquidation_bonus_tiers();
+    assert_eq!(new_tiers.len(), 1);
+    assert_eq!(new_tiers.get_unchecked(0).max_bonus, 1_1000000);
+
+    // Set the minimum liquidation full fill blocks (admin only)
+    pool_fixture.pool.set_min_liq_full_fill_blocks(&100u32);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_min_liq_full_fill_blocks"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                100u32.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_min_liq_full_fill_blocks(), 100u32);
+
+    // Set the auction config (admin only)
+    let auction_config = AuctionConfig {
+        auction_lot_ramp_blocks: 200,
+        auction_bid_ramp_blocks: 200,
+        auction_per_block_scalar: 0_0050000,
+    };
+    pool_fixture.pool.set_auction_config(&auction_config);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_auction_config"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                auction_config.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(
+        pool_fixture
+            .pool
+            .get_auction_config()
+            .auction_per_block_scalar,
+        0_0050000
+    );
+
+    // Set the insurance share (admin only)
+    pool_fixture.pool.set_insurance_bps(&500u32);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_insurance_bps"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                500u32.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_insurance_bps(), 500u32);
+
+    // Set the minimum interest auction value (admin only)
+    pool_fixture
+        .pool
+        .set_min_interest_auction_value(&(100 * SCALAR_7));
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_min_interest_auction_value"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                (100 * SCALAR_7).into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(
+        pool_fixture.pool.get_min_interest_auction_value(),
+        (100 * SCALAR_7)
+    );
+
+    // Set the status grace ledgers (admin only)
+    pool_fixture.pool.set_status_grace_ledgers(&100u32);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_status_grace_ledgers"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                100u32.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(pool_fixture.pool.get_status_grace_ledgers(), 100u32);
+
+    // Set the minimum borrow health buffer (admin only)
+    pool_fixture
+        .pool
+        .set_min_borrow_health_buffer(&1_1000000i128);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_min_borrow_health_buffer"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                1_1000000i128.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(
+        pool_fixture.pool.get_min_borrow_health_buffer(),
+        1_1000000i128
+    );
+
+    // Set the maximum gulp of a reserve (admin only)
+    pool_fixture
+        .pool
+        .set_max_gulp(&xlm.address, &(1_000 * SCALAR_7));
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_max_gulp"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                (xlm.address.clone(), 1_000 * SCALAR_7).into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(
+        pool_fixture.pool.get_max_gulp(&xlm.address),
+        1_000 * SCALAR_7
+    );
+
+    // Set the maximum emissions the pool can gulp from the backstop (admin only)
+    pool_fixture
+        .pool
+        .set_max_gulp_emissions(&(100_000 * SCALAR_7));
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_max_gulp_emissions"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                (100_000 * SCALAR_7).into_val(&fixture.env)
+            )
+        ]
+    );
+
+    // Set the backstop emission share (admin only)
+    pool_fixture.pool.set_backstop_emission_share(&0_5000000);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_backstop_emission_share"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                0_5000000u32.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(
+        fixture
+            .backstop
+            .get_backstop_emission_share(&pool_fixture.pool.address),
+        0_5000000
+    );
+
+    // Disable the pool's reward zone emissions (admin only)
+    pool_fixture.pool.set_backstop_emissions_enabled(&false);
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_backstop_emissions_enabled"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                false.into_val(&fixture.env)
+            )
+        ]
+    );
+    assert!(!fixture
+        .backstop
+        .get_pool_emissions_enabled(&pool_fixture.pool.address));
+
+    // Set the minimum position size to accrue emissions (admin only)
+    pool_fixture
+        .pool
+        .set_emissions_min_balance(&1, &(10 * SCALAR_7));
+    let event = vec![&fixture.env, fixture.env.events().all().last_unchecked()];
+    assert_eq!(
+        event,
+        vec![
+            &fixture.env,
+            (
+                pool_fixture.pool.address.clone(),
+                (
+                    Symbol::new(&fixture.env, "set_emissions_min_balance"),
+                    fixture.bombadil.clone()
+                )
+                    .into_val(&fixture.env),
+                (1u32, 10 * SCALAR_7).into_val(&fixture.env)
+            )
+        ]
+    );
+    assert_eq!(
+        pool_fixture.pool.get_emissions_min_balance(&1),
+        10 * SCALAR_7
+    );
+}
+
+#[test]
+fn test_pool_transfer_position_auth() {
+    let fixture = create_fixture_with_data(false);
+    let pool_fixture = &fixture.pools[0];
+    let xlm_pool_index = pool_fixture.reserves[&TokenIndex::XLM];
+    let xlm = &fixture.tokens[TokenIndex::XLM];
+
+    let sam = Address::generate(&fixture.env);
+    let frodo = Address::generate(&fixture.env);
+    xlm.mint(&sam, &(1_000 * SCALAR_7));
+
+    let requests = vec![
+        &fixture.env,
+        Request {
+            request_type: RequestType::SupplyCollateral as u32,
+            address: xlm.address.clone(),
+            amount: 1_000 * SCALAR_7,
+        },
+    ];
+    let result = pool_fixture.pool.submit(&sam, &sam, &sam, &requests);
+    let b_tokens = result.collateral.get_unchecked(xlm_pool_index);
+
+    // collateral transfers also require authorization from the receiver
+    let reserve_token_id = xlm_pool_index * 2 + 1;
+    pool_fixture
+        .pool
+        .transfer_position(&sam, &frodo, &reserve_token_id, &b_tokens);
+    let args = vec![
+        &fixture.env,
+        sam.to_val(),
+        frodo.to_val(),
+        reserve_token_id.into_val(&fixture.env),
+        b_tokens.into_val(&fixture.env),
+    ];
+    assert_eq!(fixture.env.auths().len(), 2);
+    assert_eq!(
+        fixture.env.auths()[0],
+        (
+            sam.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    pool_fixture.pool.address.clone(),
+                    Symbol::new(&fixture.env, "transfer_position"),
+                    args.clone()
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(
+        fixture.env.auths()[1],
+        (
+            frodo.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    pool_fixture.pool.address.clone(),
+                    Symbol::new(&fixture.env, "transfer_position"),
+                    args
+                )),
+                sub_invocations: std::vec![]
+            }
+        )
+    );
+    assert_eq!(
+        pool_fixture
+            .pool
+            .get_positions(&frodo)
+            .collateral
+            .get_unchecked(xlm_pool_index),
+        b_tokens
+    );
+}
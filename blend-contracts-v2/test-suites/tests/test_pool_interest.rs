@@ -38,6 +38,12 @@ fn test_pool_interest() {
         r_three: 0,
         util: 50,
         collateral_cap: i64::MAX as i128,
+        supply_cap: 0,
+        borrow_cap: 0,
+        min_borrow: 0,
+        supply_enabled: true,
+        borrow_enabled: true,
+        auction_lot_rate: 0,
         enabled: true,
     };
     fixture.create_pool_reserve(0, TokenIndex::XLM, &xlm_config);
@@ -58,6 +64,12 @@ fn test_pool_interest() {
         r_three: 0,
         util: 50,
         collateral_cap: i64::MAX as i128,
+        supply_cap: 0,
+        borrow_cap: 0,
+        min_borrow: 0,
+        supply_enabled: true,
+        borrow_enabled: true,
+        auction_lot_rate: 0,
         enabled: true,
     };
     fixture.create_pool_reserve(0, TokenIndex::STABLE, &stable_config);